@@ -3,4 +3,16 @@ pub(crate) const WIN_VECTOR_EXTENSION_PATH: &str = "vector_extension/vector_exte
 pub(crate) const MAC_VECTOR_EXTENSION_PATH: &str = "vector_extension/vector_extension.dylib";
 pub(crate) const LINUX_VECTOR_EXTENSION_PATH: &str = "vector_extension/vector_extension.so";
 pub(crate) const CARGO_MANIFEST_DIR_ENV: &str = "CARGO_MANIFEST_DIR";
-pub(crate) const DEFAULT_SQLITE_TIMEOUT: u32 = 15000;
\ No newline at end of file
+pub(crate) const DEFAULT_SQLITE_TIMEOUT: u32 = 15000;
+pub(crate) const FLUSH_MARKER_TABLE: &str = "vx_flush_marker";
+pub(crate) const PERSIST_ATTACH_ALIAS: &str = "vx_persisted";
+pub(crate) const PAYLOAD_TABLE_PREFIX: &str = "pt";
+pub(crate) const DISTANCE_COLLISION_ALIAS: &str = "_vx_distance";
+pub(crate) const PAYLOAD_INDEX_PREFIX: &str = "vx_idx";
+pub(crate) const IDEMPOTENCY_KEY_TABLE: &str = "vx_idempotency_keys";
+pub(crate) const COLLECTION_OPTIONS_TABLE: &str = "vx_collection_options";
+pub(crate) const STRICT_IP_MAX_NORM_RATIO: f64 = 10.0;
+pub(crate) const COMPACT_TABLE_PREFIX: &str = "vx_compact";
+pub(crate) const SEARCH_STREAM_PAGE_SIZE: usize = 1024;
+pub(crate) const DEFAULT_IDEMPOTENCY_KEY_TTL_SECS: u64 = 24 * 60 * 60;
+pub(crate) const DEFAULT_MAX_ALLOWED_DIMENSION: u16 = 4096;
@@ -1,21 +1,49 @@
 use r2d2::CustomizeConnection;
-use rusqlite::Connection;
+use rusqlite::functions::{Context, FunctionFlags};
+use rusqlite::{Connection, ToSql};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use crate::{constant::DEFAULT_SQLITE_TIMEOUT, helper::load_sqlite_vector_extension};
+use crate::{
+    constant::DEFAULT_SQLITE_TIMEOUT,
+    error::VecXError,
+    helper::{load_sqlite_vector_extension, load_sqlite_vector_extension_from_path},
+    types::TempStore,
+};
 
 /// Connection customizer for SQLite that loads the vector extension and configures
 /// the connection for optimal concurrent access.
 #[derive(Debug)]
 pub struct SqliteConnectionCustomizer {
     busy_timeout_ms: u32,
+    extension_path: Option<PathBuf>,
+    scalar_functions: Vec<ScalarFunction>,
+    init_sql: Vec<String>,
+    temp_store: Option<TempStore>,
+}
+
+/// Registers a scalar function on one connection.
+type RegisterFunction = Box<dyn Fn(&Connection) -> rusqlite::Result<()> + Send + Sync>;
+
+/// A user-defined SQL function registered on every acquired connection.
+struct ScalarFunction {
+    name: String,
+    register: RegisterFunction,
+}
+
+impl fmt::Debug for ScalarFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScalarFunction")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
 }
 
 impl SqliteConnectionCustomizer {
     /// Creates a new customizer with default settings (5 second busy timeout).
     pub fn new() -> Box<Self> {
-        Box::new(SqliteConnectionCustomizer {
-            busy_timeout_ms: DEFAULT_SQLITE_TIMEOUT,
-        })
+        Box::new(SqliteConnectionCustomizer::default())
     }
 
     /// Creates a new customizer with a custom busy timeout.
@@ -24,7 +52,137 @@ impl SqliteConnectionCustomizer {
     /// * `busy_timeout_ms` - Timeout in milliseconds to wait when the database is locked.
     ///   Set to 0 to return immediately with SQLITE_BUSY.
     pub fn with_busy_timeout(busy_timeout_ms: u32) -> Box<Self> {
-        Box::new(SqliteConnectionCustomizer { busy_timeout_ms })
+        Box::new(SqliteConnectionCustomizer {
+            busy_timeout_ms,
+            ..SqliteConnectionCustomizer::default()
+        })
+    }
+
+    /// Creates a new customizer that loads the vector extension from a native library
+    /// on disk instead of the copy embedded in this crate.
+    ///
+    /// If the library cannot be loaded, acquiring a connection fails and operations
+    /// return `VecXError::ExtensionLoadError`. r2d2 retries the connection until the
+    /// pool's `connection_timeout` expires before reporting it.
+    pub fn with_extension_path(extension_path: impl Into<PathBuf>) -> Box<Self> {
+        Box::new(SqliteConnectionCustomizer {
+            extension_path: Some(extension_path.into()),
+            ..SqliteConnectionCustomizer::default()
+        })
+    }
+
+    /// Creates a new customizer with both a busy timeout and an optional extension path.
+    pub(crate) fn with_options(
+        busy_timeout_ms: u32,
+        extension_path: Option<PathBuf>,
+    ) -> Box<Self> {
+        Box::new(SqliteConnectionCustomizer {
+            busy_timeout_ms,
+            extension_path,
+            ..SqliteConnectionCustomizer::default()
+        })
+    }
+
+    /// Registers a scalar SQL function on every connection the pool hands out, so
+    /// payload queries can call it, e.g. a domain-specific score or a JSON helper.
+    ///
+    /// `arity` is the number of arguments the function takes, or -1 for any number.
+    /// Arguments are read from the `Context`, e.g. `ctx.get::<i64>(0)?`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vector_xlite::customizer::SqliteConnectionCustomizer;
+    ///
+    /// let customizer = SqliteConnectionCustomizer::new()
+    ///     .with_scalar_function("double", 1, |ctx| Ok(ctx.get::<i64>(0)? * 2));
+    /// ```
+    pub fn with_scalar_function<F, T>(
+        mut self: Box<Self>,
+        name: &str,
+        arity: i32,
+        function: F,
+    ) -> Box<Self>
+    where
+        F: Fn(&Context<'_>) -> rusqlite::Result<T> + Send + Sync + 'static,
+        T: ToSql,
+    {
+        let function = Arc::new(function);
+        let function_name = name.to_string();
+        let register: RegisterFunction = Box::new(move |conn| {
+            let function = Arc::clone(&function);
+            conn.create_scalar_function(
+                function_name.as_str(),
+                arity,
+                FunctionFlags::SQLITE_UTF8,
+                move |ctx| function(ctx),
+            )
+        });
+        self.scalar_functions.push(ScalarFunction {
+            name: name.to_string(),
+            register,
+        });
+        self
+    }
+
+    /// Runs `statements` in order on every connection the pool hands out, e.g.
+    /// `PRAGMA foreign_keys = ON` or an `ATTACH` of a read-only catalog database.
+    ///
+    /// They run after the built-in PRAGMAs, the extension load and the registration of
+    /// scalar functions, so they can use both. A failing statement fails the connection
+    /// with an error naming it; r2d2 retries the connection until the pool's
+    /// `connection_timeout` expires before reporting it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vector_xlite::customizer::SqliteConnectionCustomizer;
+    ///
+    /// let customizer = SqliteConnectionCustomizer::new()
+    ///     .with_init_sql(vec!["PRAGMA foreign_keys = ON".to_string()]);
+    /// ```
+    pub fn with_init_sql(mut self: Box<Self>, statements: Vec<String>) -> Box<Self> {
+        self.init_sql.extend(statements);
+        self
+    }
+
+    /// Sets where every connection keeps temporary tables and indexes, such as the
+    /// sorter of a large `ORDER BY` or the result of a payload JOIN, via
+    /// `PRAGMA temp_store`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vector_xlite::{customizer::SqliteConnectionCustomizer, types::TempStore};
+    ///
+    /// let customizer = SqliteConnectionCustomizer::new().with_temp_store(TempStore::Memory);
+    /// ```
+    pub fn with_temp_store(mut self: Box<Self>, temp_store: TempStore) -> Box<Self> {
+        self.temp_store = Some(temp_store);
+        self
+    }
+
+    /// Writes SQLite's temporary files to `path` instead of the system temp directory,
+    /// e.g. when `/tmp` is a small tmpfs, via `PRAGMA temp_store_directory`.
+    ///
+    /// SQLite keeps the temp directory for the whole process, so it applies to every
+    /// connection, including those of other pools, and changing it is not safe while
+    /// other threads use SQLite. Call this once at startup, before any pool is built.
+    /// The directory must exist and be writable; it is only used while temporary data
+    /// is stored in files, see `with_temp_store`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VecXError::InvalidQueryError` if `path` is not valid UTF-8, and
+    /// `VecXError::SqlError` if SQLite rejects the directory.
+    pub fn set_temp_dir(path: impl AsRef<Path>) -> Result<(), VecXError> {
+        let path = path.as_ref();
+        let temp_dir = path.to_str().ok_or_else(|| {
+            VecXError::InvalidQueryError(format!("temp dir {} is not valid UTF-8", path.display()))
+        })?;
+        let conn = Connection::open_in_memory()?;
+        conn.pragma_update(None, "temp_store_directory", temp_dir)?;
+        Ok(())
     }
 }
 
@@ -32,6 +190,10 @@ impl Default for SqliteConnectionCustomizer {
     fn default() -> Self {
         SqliteConnectionCustomizer {
             busy_timeout_ms: DEFAULT_SQLITE_TIMEOUT,
+            extension_path: None,
+            scalar_functions: Vec::new(),
+            init_sql: Vec::new(),
+            temp_store: None,
         }
     }
 }
@@ -46,11 +208,39 @@ impl CustomizeConnection<Connection, rusqlite::Error> for SqliteConnectionCustom
 
         // Recommended for WAL
         conn.pragma_update(None, "synchronous", "NORMAL")?;
-        
-        // Load the vector extension
-        load_sqlite_vector_extension(conn).map_err(|e| {
+
+        if let Some(temp_store) = self.temp_store {
+            conn.pragma_update(None, "temp_store", temp_store.as_str())?;
+        }
+
+        // Load the vector extension. Any failure is reported as an ExtensionLoadError,
+        // whose message prefix survives r2d2 and lets callers recognize it again.
+        let loaded = match &self.extension_path {
+            Some(path) => load_sqlite_vector_extension_from_path(conn, path),
+            None => load_sqlite_vector_extension(conn),
+        };
+        loaded.map_err(|e| {
+            let e = match e {
+                VecXError::ExtensionLoadError(_) => e,
+                other => VecXError::ExtensionLoadError(other.to_string()),
+            };
             rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(1), Some(e.to_string()))
-        })
+        })?;
+
+        for scalar_function in &self.scalar_functions {
+            (scalar_function.register)(conn)?;
+        }
+
+        for sql in &self.init_sql {
+            conn.execute_batch(sql).map_err(|e| {
+                let code = e.sqlite_error().copied().unwrap_or(rusqlite::ffi::Error::new(1));
+                rusqlite::Error::SqliteFailure(
+                    code,
+                    Some(format!("connection init SQL `{}` failed: {}", sql, e)),
+                )
+            })?;
+        }
+        Ok(())
     }
 
     fn on_release(&self, _conn: Connection) {}
@@ -1,6 +1,10 @@
 use std::fmt;
 use std::io;
 
+/// Display prefix of `ExtensionLoadError`. r2d2 and rusqlite keep errors raised while
+/// setting up a connection only as text, so the prefix is how they are recognized again.
+const EXTENSION_LOAD_ERROR_PREFIX: &str = "extension load error: ";
+
 #[derive(Debug)]
 pub enum VecXError {
     ExtensionLoadError(String),
@@ -14,7 +18,7 @@ pub enum VecXError {
 impl fmt::Display for VecXError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            VecXError::ExtensionLoadError(s) => write!(f, "extension load error: {}", s),
+            VecXError::ExtensionLoadError(s) => write!(f, "{}{}", EXTENSION_LOAD_ERROR_PREFIX, s),
             VecXError::SqlError(s) => write!(f, "sql error: {}", s),
             VecXError::InvalidQueryError(s) => write!(f, "invalid query error: {}", s),
             VecXError::DataParsingError(s) => write!(f, "data parsing error: {}", s),
@@ -26,8 +30,24 @@ impl fmt::Display for VecXError {
 
 impl std::error::Error for VecXError {}
 
+impl VecXError {
+    /// Recovers an `ExtensionLoadError` from the message of an error that wrapped it.
+    pub(crate) fn extension_load_error_in(message: &str) -> Option<VecXError> {
+        message.find(EXTENSION_LOAD_ERROR_PREFIX).map(|start| {
+            VecXError::ExtensionLoadError(
+                message[start + EXTENSION_LOAD_ERROR_PREFIX.len()..].to_string(),
+            )
+        })
+    }
+}
+
 impl From<rusqlite::Error> for VecXError {
     fn from(e: rusqlite::Error) -> Self {
+        if let rusqlite::Error::SqliteFailure(_, Some(message)) = &e {
+            if let Some(extension_load_error) = VecXError::extension_load_error_in(message) {
+                return extension_load_error;
+            }
+        }
         VecXError::SqlError(e.to_string())
     }
 }
@@ -46,6 +66,7 @@ impl From<io::Error> for VecXError {
 
 impl From<r2d2::Error> for VecXError {
     fn from(e: r2d2::Error) -> Self {
-        VecXError::Other(e.to_string())
+        let message = e.to_string();
+        VecXError::extension_load_error_in(&message).unwrap_or(VecXError::Other(message))
     }
 }
@@ -1,14 +1,92 @@
-use crate::{error::VecXError, types::QueryPlan};
+use crate::{
+    error::VecXError,
+    types::{
+        BatchResult, DeleteSummary, IdempotencyKeyPlan, InsertOutcome, PayloadIndex, QueryPlan,
+        SearchResult, SqlValue, VersionInfo,
+    },
+};
+use std::io::Write;
+use std::path::Path;
 
 pub(crate) trait QueryExecutor: Send + Sync {
     fn execute_create_collection_query(&self, query_plans: Vec<QueryPlan>)
-    -> Result<(), VecXError>;
+        -> Result<(), VecXError>;
+    fn execute_validate_collection_query(
+        &self,
+        query_plans: Vec<QueryPlan>,
+    ) -> Result<(), VecXError>;
     fn execute_insert_query(&self, query_plans: Vec<QueryPlan>) -> Result<(), VecXError>;
+    fn execute_idempotent_insert_query(
+        &self,
+        key_plan: IdempotencyKeyPlan,
+        query_plans: Vec<QueryPlan>,
+    ) -> Result<InsertOutcome, VecXError>;
+    fn execute_batch_insert_query(
+        &self,
+        query_plan_groups: Vec<(usize, Vec<QueryPlan>)>,
+        stop_on_error: bool,
+    ) -> Result<BatchResult, VecXError>;
     fn execute_delete_query(&self, query_plans: Vec<QueryPlan>) -> Result<(), VecXError>;
-    fn execute_delete_collection_query(&self, query_plans: Vec<QueryPlan>) -> Result<(), VecXError>;
+    fn execute_batch_delete_query(
+        &self,
+        query_plan_groups: Vec<Vec<QueryPlan>>,
+    ) -> Result<DeleteSummary, VecXError>;
+    fn execute_delete_collection_query(&self, query_plans: Vec<QueryPlan>)
+        -> Result<(), VecXError>;
+    fn execute_rename_collection_query(&self, query_plans: Vec<QueryPlan>)
+        -> Result<(), VecXError>;
     fn execute_search_query(
         &self,
         query_plan: QueryPlan,
     ) -> Result<Vec<std::collections::HashMap<String, String>>, VecXError>;
+    fn execute_search_many_query(
+        &self,
+        query_plans: Vec<QueryPlan>,
+    ) -> Result<Vec<Vec<std::collections::HashMap<String, String>>>, VecXError>;
+    fn execute_search_stream_query(
+        &self,
+        query_plan: QueryPlan,
+    ) -> Result<Box<dyn Iterator<Item = Result<SearchResult, VecXError>>>, VecXError>;
+    fn execute_typed_search_query(
+        &self,
+        query_plan: QueryPlan,
+    ) -> Result<Vec<std::collections::HashMap<String, SqlValue>>, VecXError>;
+    fn execute_ndjson_search_query(
+        &self,
+        query_plan: QueryPlan,
+        writer: &mut dyn Write,
+    ) -> Result<usize, VecXError>;
+    #[cfg(feature = "arrow")]
+    fn execute_arrow_search_query(
+        &self,
+        query_plan: QueryPlan,
+    ) -> Result<arrow_array::RecordBatch, VecXError>;
+    fn execute_rerank_query(&self, query_plan: QueryPlan) -> Result<Vec<(i64, f32)>, VecXError>;
     fn execute_collection_exists_query(&self, query_plan: QueryPlan) -> Result<bool, VecXError>;
+    fn execute_explain_query_plan_query(
+        &self,
+        query_plan: QueryPlan,
+    ) -> Result<Vec<String>, VecXError>;
+    fn execute_count_query(&self, query_plan: QueryPlan) -> Result<u64, VecXError>;
+    fn execute_update_query(&self, query_plan: QueryPlan) -> Result<u64, VecXError>;
+    fn execute_payload_index_query(&self, query_plan: QueryPlan) -> Result<(), VecXError>;
+    fn execute_list_payload_indexes_query(
+        &self,
+        query_plan: QueryPlan,
+    ) -> Result<Vec<PayloadIndex>, VecXError>;
+    fn execute_flush_query(&self, query_plan: QueryPlan) -> Result<(), VecXError>;
+    fn execute_persist_query(
+        &self,
+        db_path: &Path,
+        query_plans: Vec<QueryPlan>,
+    ) -> Result<(), VecXError>;
+    fn execute_version_info_query(&self, query_plan: QueryPlan) -> Result<VersionInfo, VecXError>;
+    fn execute_refresh_connections(&self) -> Result<(), VecXError>;
+    fn execute_shutdown_query(&self, query_plan: QueryPlan) -> Result<(), VecXError>;
+    fn execute_vacuum_query(
+        &self,
+        vacuum_query_plan: QueryPlan,
+        checkpoint_query_plan: QueryPlan,
+    ) -> Result<(), VecXError>;
+    fn execute_compact_payload_query(&self, query_plans: Vec<QueryPlan>) -> Result<(), VecXError>;
 }
@@ -1,16 +1,33 @@
-use crate::{error::VecXError, executor::query_executor::QueryExecutor, types::QueryPlan};
-use r2d2::Pool;
-use r2d2_sqlite::SqliteConnectionManager;
-use rusqlite::{DropBehavior, Result};
-use std::collections::HashMap;
+use crate::{
+    constant::{DEFAULT_SQLITE_TIMEOUT, PERSIST_ATTACH_ALIAS, SEARCH_STREAM_PAGE_SIZE},
+    customizer::SqliteConnectionCustomizer,
+    error::VecXError,
+    executor::query_executor::QueryExecutor,
+    helper::{parse_row_to_typed_map, write_ndjson_row, ConnectionSource, SourceConnection},
+    snapshot::{backup_connection, get_index_files_from},
+    types::{
+        BatchResult, DeleteSummary, IdempotencyKeyPlan, InsertOutcome, PayloadIndex, QueryPlan,
+        SearchResult, SqlValue, VersionInfo,
+    },
+};
+use r2d2::CustomizeConnection;
+use rusqlite::{Connection, DropBehavior, OptionalExtension, Result, TransactionBehavior};
+use std::collections::{HashMap, VecDeque};
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
 
 pub(crate) struct SqliteQueryExecutor {
-    conn_pool: Pool<SqliteConnectionManager>,
+    connections: ConnectionSource,
 }
 
 impl SqliteQueryExecutor {
-    pub fn new(conn_pool: Pool<SqliteConnectionManager>) -> Box<dyn QueryExecutor> {
-        Box::new(SqliteQueryExecutor { conn_pool })
+    pub fn new(connections: ConnectionSource) -> Box<dyn QueryExecutor> {
+        Box::new(SqliteQueryExecutor { connections })
+    }
+
+    fn connection(&self) -> Result<SourceConnection, VecXError> {
+        self.connections.get()
     }
 }
 
@@ -19,7 +36,7 @@ impl QueryExecutor for SqliteQueryExecutor {
         &self,
         query_plans: Vec<QueryPlan>,
     ) -> Result<(), VecXError> {
-        let mut conn = self.conn_pool.get()?;
+        let mut conn = self.connection()?;
         let trx = conn.transaction()?;
 
         for plan in &query_plans {
@@ -30,8 +47,25 @@ impl QueryExecutor for SqliteQueryExecutor {
         Ok(())
     }
 
+    /// Runs the create collection plans on a throwaway in-memory connection with the
+    /// vectorlite extension loaded, so SQL errors surface without touching the database.
+    fn execute_validate_collection_query(
+        &self,
+        query_plans: Vec<QueryPlan>,
+    ) -> Result<(), VecXError> {
+        let mut conn = Connection::open_in_memory()?;
+        SqliteConnectionCustomizer::default().on_acquire(&mut conn)?;
+        let trx = conn.transaction()?;
+
+        for plan in &query_plans {
+            trx.execute(&plan.sql, rusqlite::params_from_iter(plan.params.iter()))?;
+        }
+
+        Ok(())
+    }
+
     fn execute_insert_query(&self, query_plans: Vec<QueryPlan>) -> rusqlite::Result<(), VecXError> {
-        let mut conn = self.conn_pool.get()?;
+        let mut conn = self.connection()?;
         let trx = conn.transaction()?;
 
         for plan in &query_plans {
@@ -42,12 +76,86 @@ impl QueryExecutor for SqliteQueryExecutor {
         Ok(())
     }
 
+    /// Executes an insert unless its idempotency key was already recorded.
+    ///
+    /// The key lookup, the insert and recording the key share one immediate transaction,
+    /// so concurrent retries with the same key apply the insert at most once, and a
+    /// failed insert does not record its key.
+    fn execute_idempotent_insert_query(
+        &self,
+        key_plan: IdempotencyKeyPlan,
+        query_plans: Vec<QueryPlan>,
+    ) -> Result<InsertOutcome, VecXError> {
+        let mut conn = self.connection()?;
+        let trx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+
+        for plan in &key_plan.prepare {
+            trx.execute(&plan.sql, rusqlite::params_from_iter(&plan.params))?;
+        }
+
+        let seen = trx
+            .query_row(
+                &key_plan.lookup.sql,
+                rusqlite::params_from_iter(&key_plan.lookup.params),
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some();
+        if seen {
+            trx.commit()?;
+            return Ok(InsertOutcome::Duplicate);
+        }
+
+        for plan in query_plans.iter().chain([&key_plan.record]) {
+            trx.execute(&plan.sql, rusqlite::params_from_iter(&plan.params))?;
+        }
+
+        trx.commit()?;
+        Ok(InsertOutcome::Inserted)
+    }
+
+    /// Executes a batch insert in a single transaction.
+    ///
+    /// Every group of plans inserts the point at the given batch position inside its own
+    /// savepoint. With `stop_on_error` the first failure aborts and rolls back the whole
+    /// batch; otherwise only the failed point is rolled back and reported.
+    fn execute_batch_insert_query(
+        &self,
+        query_plan_groups: Vec<(usize, Vec<QueryPlan>)>,
+        stop_on_error: bool,
+    ) -> rusqlite::Result<BatchResult, VecXError> {
+        let mut conn = self.connection()?;
+        let mut trx = conn.transaction()?;
+
+        let mut result = BatchResult::default();
+        for (index, query_plans) in &query_plan_groups {
+            let savepoint = trx.savepoint()?;
+            let inserted = query_plans.iter().try_for_each(|plan| {
+                savepoint
+                    .execute(&plan.sql, rusqlite::params_from_iter(&plan.params))
+                    .map(|_| ())
+            });
+
+            match inserted {
+                Ok(()) => {
+                    savepoint.commit()?;
+                    result.succeeded += 1;
+                }
+                Err(e) if stop_on_error => return Err(e.into()),
+                Err(e) => result.failed.push((*index, e.into())),
+            }
+        }
+
+        trx.commit()?;
+        Ok(result)
+    }
+
     /// Executes a delete operation atomically.
     ///
     /// Removes the vector from both the payload table and the HNSW index
     /// within a single transaction, ensuring consistency.
     fn execute_delete_query(&self, query_plans: Vec<QueryPlan>) -> rusqlite::Result<(), VecXError> {
-        let mut conn = self.conn_pool.get()?;
+        let mut conn = self.connection()?;
         let trx = conn.transaction()?;
 
         for plan in &query_plans {
@@ -58,11 +166,56 @@ impl QueryExecutor for SqliteQueryExecutor {
         Ok(())
     }
 
+    /// Executes a batch delete atomically.
+    ///
+    /// Every group of plans removes one id. An id counts as deleted when any of its
+    /// statements removed a row, otherwise it is reported as missing.
+    fn execute_batch_delete_query(
+        &self,
+        query_plan_groups: Vec<Vec<QueryPlan>>,
+    ) -> rusqlite::Result<DeleteSummary, VecXError> {
+        let mut conn = self.connection()?;
+        let trx = conn.transaction()?;
+
+        let mut summary = DeleteSummary::default();
+        for query_plans in &query_plan_groups {
+            let mut affected_rows = 0;
+            for plan in query_plans {
+                affected_rows +=
+                    trx.execute(&plan.sql, rusqlite::params_from_iter(&plan.params))?;
+            }
+
+            if affected_rows > 0 {
+                summary.deleted_count += 1;
+            } else {
+                summary.missing_count += 1;
+            }
+        }
+
+        trx.commit()?;
+        Ok(summary)
+    }
+
     fn execute_delete_collection_query(
         &self,
         query_plans: Vec<QueryPlan>,
     ) -> rusqlite::Result<(), VecXError> {
-        let mut conn = self.conn_pool.get()?;
+        let mut conn = self.connection()?;
+        let trx = conn.transaction()?;
+
+        for plan in &query_plans {
+            trx.execute(&plan.sql, rusqlite::params_from_iter(&plan.params))?;
+        }
+
+        trx.commit()?;
+        Ok(())
+    }
+
+    fn execute_rename_collection_query(
+        &self,
+        query_plans: Vec<QueryPlan>,
+    ) -> Result<(), VecXError> {
+        let mut conn = self.connection()?;
         let trx = conn.transaction()?;
 
         for plan in &query_plans {
@@ -77,7 +230,7 @@ impl QueryExecutor for SqliteQueryExecutor {
         &self,
         query_plan: QueryPlan,
     ) -> rusqlite::Result<Vec<HashMap<String, String>>, VecXError> {
-        let conn = self.conn_pool.get()?;
+        let conn = self.connection()?;
 
         let mut stmt = conn.prepare(&query_plan.sql)?;
 
@@ -91,8 +244,108 @@ impl QueryExecutor for SqliteQueryExecutor {
         Ok(rows)
     }
 
+    /// Runs several search plans one after another on a single connection.
+    fn execute_search_many_query(
+        &self,
+        query_plans: Vec<QueryPlan>,
+    ) -> Result<Vec<Vec<HashMap<String, String>>>, VecXError> {
+        let conn = self.connection()?;
+
+        let mut results = Vec::with_capacity(query_plans.len());
+        for query_plan in query_plans {
+            let mut stmt = conn.prepare_cached(&query_plan.sql)?;
+            let rows = stmt
+                .query_map(
+                    rusqlite::params_from_iter(query_plan.params),
+                    query_plan.post_process.unwrap(),
+                )?
+                .collect::<Result<Vec<_>, _>>()?;
+            results.push(rows);
+        }
+
+        Ok(results)
+    }
+
+    fn execute_search_stream_query(
+        &self,
+        query_plan: QueryPlan,
+    ) -> Result<Box<dyn Iterator<Item = Result<SearchResult, VecXError>>>, VecXError> {
+        let conn = self.connection()?;
+
+        Ok(Box::new(SearchRowStream::new(conn, query_plan)?))
+    }
+
+    /// Runs a search plan, keeping each column's SQLite storage class instead of
+    /// applying the plan's string post-processing.
+    fn execute_typed_search_query(
+        &self,
+        query_plan: QueryPlan,
+    ) -> Result<Vec<HashMap<String, SqlValue>>, VecXError> {
+        let conn = self.connection()?;
+
+        let mut stmt = conn.prepare(&query_plan.sql)?;
+
+        let rows = stmt
+            .query_map(
+                rusqlite::params_from_iter(query_plan.params),
+                parse_row_to_typed_map,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows)
+    }
+
+    /// Runs a search plan, writing each row to `writer` as a line of JSON as soon as
+    /// it is read, and returns the number of rows written.
+    fn execute_ndjson_search_query(
+        &self,
+        query_plan: QueryPlan,
+        writer: &mut dyn Write,
+    ) -> Result<usize, VecXError> {
+        let conn = self.connection()?;
+
+        let mut stmt = conn.prepare(&query_plan.sql)?;
+        let column_names: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+
+        let mut rows = stmt.query(rusqlite::params_from_iter(query_plan.params))?;
+        let mut written = 0;
+        while let Some(row) = rows.next()? {
+            let values = (0..column_names.len())
+                .map(|i| row.get::<_, rusqlite::types::Value>(i).map(SqlValue::from))
+                .collect::<Result<Vec<_>>>()?;
+            write_ndjson_row(writer, &column_names, &values)?;
+            written += 1;
+        }
+        writer.flush()?;
+
+        Ok(written)
+    }
+
+    /// Runs a search plan into a columnar record batch, keeping the column order of
+    /// the result set.
+    #[cfg(feature = "arrow")]
+    fn execute_arrow_search_query(
+        &self,
+        query_plan: QueryPlan,
+    ) -> Result<arrow_array::RecordBatch, VecXError> {
+        let conn = self.connection()?;
+
+        let mut stmt = conn.prepare(&query_plan.sql)?;
+        let column_names: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(query_plan.params), |row| {
+                (0..column_names.len())
+                    .map(|i| row.get::<_, rusqlite::types::Value>(i).map(SqlValue::from))
+                    .collect::<Result<Vec<_>>>()
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        crate::helper::rows_to_record_batch(&column_names, &rows)
+    }
+
     fn execute_collection_exists_query(&self, query_plan: QueryPlan) -> Result<bool, VecXError> {
-        let conn = self.conn_pool.get()?;
+        let conn = self.connection()?;
 
         let count: i64 = conn.query_row(
             &query_plan.sql,
@@ -103,4 +356,393 @@ impl QueryExecutor for SqliteQueryExecutor {
         // If any table exists (count >= 1), the collection is considered to exist
         Ok(count >= 1)
     }
+
+    /// Returns the `detail` column of every `EXPLAIN QUERY PLAN` row, indented two
+    /// spaces per nesting level like the sqlite3 shell prints the plan tree.
+    fn execute_explain_query_plan_query(
+        &self,
+        query_plan: QueryPlan,
+    ) -> Result<Vec<String>, VecXError> {
+        let conn = self.connection()?;
+
+        let mut stmt = conn.prepare(&query_plan.sql)?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(query_plan.params), |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(3)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut depths: HashMap<i64, usize> = HashMap::new();
+        Ok(rows
+            .into_iter()
+            .map(|(id, parent, detail)| {
+                let depth = depths.get(&parent).map_or(0, |depth| depth + 1);
+                depths.insert(id, depth);
+                format!("{}{}", "  ".repeat(depth), detail)
+            })
+            .collect())
+    }
+
+    fn execute_rerank_query(&self, query_plan: QueryPlan) -> Result<Vec<(i64, f32)>, VecXError> {
+        let conn = self.connection()?;
+
+        let mut stmt = conn.prepare(&query_plan.sql)?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(query_plan.params), |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, f64>(1)? as f32))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    fn execute_count_query(&self, query_plan: QueryPlan) -> Result<u64, VecXError> {
+        let conn = self.connection()?;
+
+        let count: i64 = conn.query_row(
+            &query_plan.sql,
+            rusqlite::params_from_iter(query_plan.params),
+            |row| row.get(0),
+        )?;
+
+        u64::try_from(count).map_err(|e| VecXError::DataParsingError(e.to_string()))
+    }
+
+    fn execute_update_query(&self, query_plan: QueryPlan) -> Result<u64, VecXError> {
+        let mut conn = self.connection()?;
+        let trx = conn.transaction()?;
+
+        let affected_rows = trx.execute(
+            &query_plan.sql,
+            rusqlite::params_from_iter(&query_plan.params),
+        )?;
+
+        trx.commit()?;
+        Ok(affected_rows as u64)
+    }
+
+    fn execute_payload_index_query(&self, query_plan: QueryPlan) -> Result<(), VecXError> {
+        let conn = self.connection()?;
+
+        conn.execute(
+            &query_plan.sql,
+            rusqlite::params_from_iter(query_plan.params),
+        )?;
+
+        Ok(())
+    }
+
+    fn execute_list_payload_indexes_query(
+        &self,
+        query_plan: QueryPlan,
+    ) -> Result<Vec<PayloadIndex>, VecXError> {
+        let conn = self.connection()?;
+
+        let mut stmt = conn.prepare(&query_plan.sql)?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(query_plan.params), |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut indexes: Vec<PayloadIndex> = Vec::new();
+        for (name, column) in rows {
+            match indexes.last_mut() {
+                Some(index) if index.name == name => index.columns.push(column),
+                _ => indexes.push(PayloadIndex {
+                    name,
+                    columns: vec![column],
+                }),
+            }
+        }
+
+        Ok(indexes)
+    }
+
+    /// Forces vectorlite to write file-backed HNSW indexes to disk.
+    ///
+    /// The schema change is executed on a side connection so that every idle pooled
+    /// connection sees a stale schema on its next `sqlite_master` read, disconnecting
+    /// (and thereby saving) its virtual tables before reconnecting them from the file.
+    /// Connections checked out by other threads are flushed the next time they run a
+    /// statement. In-memory databases have no file to reopen and are left untouched.
+    fn execute_flush_query(&self, query_plan: QueryPlan) -> Result<(), VecXError> {
+        let conn = self.connection()?;
+
+        let db_path = match conn.path() {
+            Some(path) if !path.is_empty() => path.to_string(),
+            _ => return Ok(()),
+        };
+
+        let side_conn = Connection::open(&db_path)?;
+        side_conn.busy_timeout(Duration::from_millis(u64::from(DEFAULT_SQLITE_TIMEOUT)))?;
+        side_conn.execute_batch(&query_plan.sql)?;
+        drop(side_conn);
+
+        let mut idle_conns = vec![conn];
+        while let Some(idle_conn) = self.connections.try_get() {
+            idle_conns.push(idle_conn);
+        }
+
+        for idle_conn in &idle_conns {
+            idle_conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| {
+                row.get::<_, i64>(0)
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Backs up the database to `db_path`, then runs the persist plans on the same
+    /// connection, since only it holds the in-memory indexes being copied.
+    fn execute_persist_query(
+        &self,
+        db_path: &Path,
+        query_plans: Vec<QueryPlan>,
+    ) -> Result<(), VecXError> {
+        let conn = self.connection()?;
+
+        let mut dest_conn = Connection::open(db_path)?;
+        backup_connection(&conn, &mut dest_conn)?;
+        drop(dest_conn);
+
+        for query_plan in query_plans {
+            if let Err(e) = conn.execute(
+                &query_plan.sql,
+                rusqlite::params_from_iter(query_plan.params),
+            ) {
+                let _ = conn.execute(&format!("DETACH DATABASE {}", PERSIST_ATTACH_ALIAS), []);
+                return Err(e.into());
+            }
+        }
+
+        // Detached virtual tables are disconnected, writing their index files, once the
+        // connection runs its next statement.
+        conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| {
+            row.get::<_, i64>(0)
+        })?;
+
+        Ok(())
+    }
+
+    fn execute_version_info_query(&self, query_plan: QueryPlan) -> Result<VersionInfo, VecXError> {
+        let conn = self.connection()?;
+
+        let raw: String = conn.query_row(
+            &query_plan.sql,
+            rusqlite::params_from_iter(query_plan.params),
+            |row| row.get(0),
+        )?;
+
+        Ok(VersionInfo::parse(&raw))
+    }
+
+    /// Swaps every idle pooled connection for a freshly opened one on the same path.
+    ///
+    /// r2d2 cannot evict idle connections, so the connection inside each checked-out
+    /// `SourceConnection` is replaced in place and the stale one is closed. New
+    /// connections are set up like `SqliteConnectionCustomizer` sets up pooled ones.
+    ///
+    /// Closing a stale connection makes vectorlite save its in-memory indexes, so the
+    /// index files referenced by the database on disk are read beforehand and written
+    /// back once every stale connection is gone.
+    fn execute_refresh_connections(&self) -> Result<(), VecXError> {
+        let mut idle_conns = vec![self.connection()?];
+        while let Some(idle_conn) = self.connections.try_get() {
+            idle_conns.push(idle_conn);
+        }
+
+        let db_path = match idle_conns[0].path() {
+            Some(path) if !path.is_empty() => path.to_string(),
+            _ => return Ok(()),
+        };
+
+        let side_conn = Connection::open(&db_path)?;
+        side_conn.busy_timeout(Duration::from_millis(u64::from(DEFAULT_SQLITE_TIMEOUT)))?;
+        let index_files = get_index_files_from(&side_conn)?
+            .into_iter()
+            .map(|path| {
+                let contents = std::fs::read(&path).ok();
+                (path, contents)
+            })
+            .collect::<Vec<_>>();
+        drop(side_conn);
+
+        // Close every stale connection before opening a fresh one, so none of them can
+        // clean up WAL files that already belong to the replaced database.
+        for idle_conn in &mut idle_conns {
+            let stale_conn = std::mem::replace(&mut **idle_conn, Connection::open_in_memory()?);
+            stale_conn.close().map_err(|(_, e)| e)?;
+        }
+
+        for (path, contents) in index_files {
+            match contents {
+                Some(contents) => std::fs::write(&path, contents)?,
+                None => {
+                    let _ = std::fs::remove_file(&path);
+                }
+            }
+        }
+
+        for idle_conn in &mut idle_conns {
+            let mut fresh_conn = Connection::open(&db_path)?;
+            SqliteConnectionCustomizer::default().on_acquire(&mut fresh_conn)?;
+            **idle_conn = fresh_conn;
+        }
+
+        Ok(())
+    }
+
+    /// Checkpoints the WAL, then closes every idle connection, which makes vectorlite
+    /// write its indexes and lets the last close remove the `-wal` and `-shm` files.
+    ///
+    /// Pooled connections cannot be taken out of their pool, so each closed connection
+    /// is replaced with an in-memory one. All connections are closed even if the
+    /// checkpoint fails; the first error is returned.
+    fn execute_shutdown_query(&self, query_plan: QueryPlan) -> Result<(), VecXError> {
+        let mut idle_conns = vec![self.connection()?];
+        while let Some(idle_conn) = self.connections.try_get() {
+            idle_conns.push(idle_conn);
+        }
+
+        let checkpoint = idle_conns[0].query_row(&query_plan.sql, [], |row| row.get::<_, i64>(0));
+        let mut first_error = match checkpoint {
+            Ok(0) => None,
+            Ok(_) => Some(VecXError::Other(
+                "WAL checkpoint was blocked by another connection".to_string(),
+            )),
+            Err(e) => Some(e.into()),
+        };
+
+        for idle_conn in &mut idle_conns {
+            let conn = std::mem::replace(&mut **idle_conn, Connection::open_in_memory()?);
+            if let Err((_, e)) = conn.close() {
+                first_error.get_or_insert(e.into());
+            }
+        }
+
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// Runs `VACUUM` on a dedicated connection, which cannot be inside a transaction or
+    /// have statements in progress, then checkpoints the WAL the rebuilt database was
+    /// written to so the main file shrinks. In-memory databases have no other
+    /// connection to use, so they are vacuumed on the pooled one.
+    fn execute_vacuum_query(
+        &self,
+        vacuum_query_plan: QueryPlan,
+        checkpoint_query_plan: QueryPlan,
+    ) -> Result<(), VecXError> {
+        let conn = self.connection()?;
+
+        let db_path = match conn.path() {
+            Some(path) if !path.is_empty() => path.to_string(),
+            _ => {
+                conn.execute_batch(&vacuum_query_plan.sql)?;
+                return Ok(());
+            }
+        };
+        drop(conn);
+
+        let side_conn = Connection::open(&db_path)?;
+        side_conn.busy_timeout(Duration::from_millis(u64::from(DEFAULT_SQLITE_TIMEOUT)))?;
+        side_conn.execute_batch(&vacuum_query_plan.sql)?;
+
+        // A checkpoint blocked by a reader only delays the shrink until a later one
+        side_conn.query_row(&checkpoint_query_plan.sql, [], |row| row.get::<_, i64>(0))?;
+
+        Ok(())
+    }
+
+    fn execute_compact_payload_query(&self, query_plans: Vec<QueryPlan>) -> Result<(), VecXError> {
+        let mut conn = self.connection()?;
+        let trx = conn.transaction()?;
+
+        for plan in &query_plans {
+            trx.execute(&plan.sql, rusqlite::params_from_iter(&plan.params))?;
+        }
+
+        trx.commit()?;
+        Ok(())
+    }
+}
+
+/// Search rows read from SQLite a page at a time as the iterator advances.
+///
+/// Each page reruns the plan on the held connection and steps past the rows already
+/// returned, so at most one page is in memory. All pages are read in one read
+/// transaction and see the same snapshot. The transaction ends and the connection
+/// returns to the pool when the stream is dropped.
+struct SearchRowStream {
+    conn: SourceConnection,
+    query_plan: QueryPlan,
+    page: VecDeque<SearchResult>,
+    offset: usize,
+    exhausted: bool,
+}
+
+impl SearchRowStream {
+    fn new(conn: SourceConnection, query_plan: QueryPlan) -> Result<Self, VecXError> {
+        conn.execute_batch("BEGIN DEFERRED")?;
+        let mut stream = SearchRowStream {
+            conn,
+            query_plan,
+            page: VecDeque::new(),
+            offset: 0,
+            exhausted: false,
+        };
+        // The first page is read up front so a failing plan fails the call itself
+        stream.next_page()?;
+        Ok(stream)
+    }
+
+    fn next_page(&mut self) -> Result<(), VecXError> {
+        let post_process = self.query_plan.post_process.as_ref().unwrap();
+        let mut stmt = self.conn.prepare_cached(&self.query_plan.sql)?;
+        let mut rows = stmt.query(rusqlite::params_from_iter(&self.query_plan.params))?;
+
+        // Rows are skipped rather than cut with OFFSET, since wrapping the plan in a
+        // subquery would rename its duplicate columns.
+        for _ in 0..self.offset {
+            if rows.next()?.is_none() {
+                break;
+            }
+        }
+        let mut page = VecDeque::new();
+        while page.len() < SEARCH_STREAM_PAGE_SIZE {
+            match rows.next()? {
+                Some(row) => page.push_back(post_process(row)?),
+                None => break,
+            }
+        }
+
+        self.offset += page.len();
+        self.exhausted = page.len() < SEARCH_STREAM_PAGE_SIZE;
+        self.page = page;
+        Ok(())
+    }
+}
+
+impl Iterator for SearchRowStream {
+    type Item = Result<SearchResult, VecXError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.page.is_empty() && !self.exhausted {
+            if let Err(e) = self.next_page() {
+                self.exhausted = true;
+                return Some(Err(e));
+            }
+        }
+        self.page.pop_front().map(Ok)
+    }
+}
+
+impl Drop for SearchRowStream {
+    fn drop(&mut self) {
+        // The transaction only read, so rolling it back just releases its snapshot
+        let _ = self.conn.execute_batch("ROLLBACK");
+    }
 }
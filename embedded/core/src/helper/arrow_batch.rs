@@ -0,0 +1,169 @@
+use crate::{error::VecXError, types::SqlValue};
+use arrow_array::{
+    ArrayRef, BinaryArray, Float64Array, Int64Array, NullArray, RecordBatch, StringArray,
+};
+use arrow_schema::{DataType, Field, Schema};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Builds a record batch from result rows, one Arrow column per SQL column.
+///
+/// SQLite columns have no fixed type, so each column's type is inferred from its
+/// non-null values: integers become `Int64`, integers mixed with reals `Float64`,
+/// blobs `Binary`, and any other mix `Utf8` with numbers rendered as text. Columns
+/// holding only NULLs are `Null`. All fields are nullable. When a name repeats, as
+/// `rowid` does in a payload join, only its first column is kept.
+pub fn rows_to_record_batch(
+    column_names: &[String],
+    rows: &[Vec<SqlValue>],
+) -> Result<RecordBatch, VecXError> {
+    let mut seen = HashSet::new();
+    let mut fields = Vec::with_capacity(column_names.len());
+    let mut arrays: Vec<ArrayRef> = Vec::with_capacity(column_names.len());
+
+    for (i, name) in column_names.iter().enumerate() {
+        if !seen.insert(name.as_str()) {
+            continue;
+        }
+        let values = rows.iter().map(|row| &row[i]);
+        let data_type = infer_data_type(values.clone());
+        let array: ArrayRef = match data_type {
+            DataType::Int64 => Arc::new(values.map(SqlValue::as_i64).collect::<Int64Array>()),
+            DataType::Float64 => Arc::new(values.map(SqlValue::as_f64).collect::<Float64Array>()),
+            DataType::Binary => Arc::new(values.map(blob_value).collect::<BinaryArray>()),
+            DataType::Utf8 => Arc::new(values.map(text_value).collect::<StringArray>()),
+            _ => Arc::new(NullArray::new(rows.len())),
+        };
+        fields.push(Field::new(name, data_type, true));
+        arrays.push(array);
+    }
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)
+        .map_err(|e| VecXError::DataParsingError(e.to_string()))
+}
+
+fn infer_data_type<'a>(values: impl Iterator<Item = &'a SqlValue>) -> DataType {
+    let mut data_type = DataType::Null;
+    for value in values {
+        let value_type = match value {
+            SqlValue::Null => continue,
+            SqlValue::Integer(_) => DataType::Int64,
+            SqlValue::Real(_) => DataType::Float64,
+            SqlValue::Text(_) => DataType::Utf8,
+            SqlValue::Blob(_) => DataType::Binary,
+        };
+        data_type = match (data_type, value_type) {
+            (DataType::Null, value_type) => value_type,
+            (current, value_type) if current == value_type => current,
+            (DataType::Int64, DataType::Float64) | (DataType::Float64, DataType::Int64) => {
+                DataType::Float64
+            }
+            _ => DataType::Utf8,
+        };
+    }
+    data_type
+}
+
+fn blob_value(value: &SqlValue) -> Option<&[u8]> {
+    match value {
+        SqlValue::Blob(b) => Some(b),
+        _ => None,
+    }
+}
+
+fn text_value(value: &SqlValue) -> Option<String> {
+    match value {
+        SqlValue::Null => None,
+        SqlValue::Integer(v) => Some(v.to_string()),
+        SqlValue::Real(v) => Some(v.to_string()),
+        SqlValue::Text(s) => Some(s.clone()),
+        SqlValue::Blob(b) => Some(String::from_utf8_lossy(b).into_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::Array;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_column_types_are_inferred_from_values() {
+        let rows = vec![
+            vec![
+                SqlValue::Integer(1),
+                SqlValue::Integer(2),
+                SqlValue::Blob(vec![0xff]),
+                SqlValue::Null,
+            ],
+            vec![
+                SqlValue::Integer(2),
+                SqlValue::Real(0.5),
+                SqlValue::Null,
+                SqlValue::Null,
+            ],
+        ];
+        let batch = rows_to_record_batch(&names(&["id", "score", "data", "empty"]), &rows).unwrap();
+
+        let schema = batch.schema();
+        let types: Vec<_> = schema
+            .fields()
+            .iter()
+            .map(|f| f.data_type().clone())
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                DataType::Int64,
+                DataType::Float64,
+                DataType::Binary,
+                DataType::Null
+            ]
+        );
+        assert_eq!(batch.num_rows(), 2);
+        assert!(batch.column(2).is_null(1));
+    }
+
+    #[test]
+    fn test_mixed_text_column_becomes_utf8() {
+        let rows = vec![
+            vec![SqlValue::Text("a".into())],
+            vec![SqlValue::Integer(7)],
+            vec![SqlValue::Null],
+        ];
+        let batch = rows_to_record_batch(&names(&["label"]), &rows).unwrap();
+
+        let labels = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(labels.value(0), "a");
+        assert_eq!(labels.value(1), "7");
+        assert!(labels.is_null(2));
+    }
+
+    #[test]
+    fn test_repeated_column_names_keep_first() {
+        let rows = vec![vec![
+            SqlValue::Integer(1),
+            SqlValue::Real(0.1),
+            SqlValue::Integer(1),
+        ]];
+        let batch = rows_to_record_batch(&names(&["rowid", "distance", "rowid"]), &rows).unwrap();
+
+        assert_eq!(batch.num_columns(), 2);
+        assert_eq!(batch.schema().field(0).name(), "rowid");
+    }
+
+    #[test]
+    fn test_empty_result_keeps_columns() {
+        let batch = rows_to_record_batch(&names(&["rowid", "distance"]), &[]).unwrap();
+
+        assert_eq!(batch.num_rows(), 0);
+        assert_eq!(batch.num_columns(), 2);
+    }
+}
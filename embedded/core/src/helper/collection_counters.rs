@@ -0,0 +1,89 @@
+use crate::helper::canonical_collection_name;
+use crate::types::CollectionStats;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+#[derive(Default)]
+struct Counters {
+    searches: AtomicU64,
+    inserts: AtomicU64,
+    deletes: AtomicU64,
+}
+
+/// In-memory per-collection operation counters behind `VectorXLite::collection_stats`.
+///
+/// Counting takes a shared lock and an atomic add; the exclusive lock is only taken
+/// the first time a collection is counted.
+#[derive(Default)]
+pub(crate) struct CollectionCounters {
+    counters: RwLock<HashMap<String, Arc<Counters>>>,
+}
+
+impl CollectionCounters {
+    pub(crate) fn record_searches(&self, collection_name: &str, count: u64) {
+        self.counters(collection_name)
+            .searches
+            .fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_inserts(&self, collection_name: &str, count: u64) {
+        self.counters(collection_name)
+            .inserts
+            .fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_deletes(&self, collection_name: &str, count: u64) {
+        self.counters(collection_name)
+            .deletes
+            .fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub(crate) fn stats(&self, collection_name: &str) -> CollectionStats {
+        let counters = self.counters.read().unwrap_or_else(|e| e.into_inner());
+        match counters.get(&canonical_collection_name(collection_name)) {
+            Some(counters) => CollectionStats {
+                searches: counters.searches.load(Ordering::Relaxed),
+                inserts: counters.inserts.load(Ordering::Relaxed),
+                deletes: counters.deletes.load(Ordering::Relaxed),
+            },
+            None => CollectionStats::default(),
+        }
+    }
+
+    /// Drops the counts of a deleted collection.
+    pub(crate) fn remove(&self, collection_name: &str) {
+        self.counters
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&canonical_collection_name(collection_name));
+    }
+
+    /// Moves the counts of a renamed collection to its new name.
+    pub(crate) fn rename(&self, old_name: &str, new_name: &str) {
+        let mut counters = self.counters.write().unwrap_or_else(|e| e.into_inner());
+        if let Some(old) = counters.remove(&canonical_collection_name(old_name)) {
+            counters.insert(canonical_collection_name(new_name), old);
+        }
+    }
+
+    fn counters(&self, collection_name: &str) -> Arc<Counters> {
+        let name = canonical_collection_name(collection_name);
+        if let Some(counters) = self
+            .counters
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&name)
+        {
+            return Arc::clone(counters);
+        }
+
+        Arc::clone(
+            self.counters
+                .write()
+                .unwrap_or_else(|e| e.into_inner())
+                .entry(name)
+                .or_default(),
+        )
+    }
+}
@@ -0,0 +1,244 @@
+use crate::error::VecXError;
+use crate::helper::canonical_collection_name;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+/// What currently runs on one collection.
+#[derive(Default)]
+struct Usage {
+    operations: usize,
+    exclusive: bool,
+    waiting_exclusive: usize,
+}
+
+impl Usage {
+    fn is_idle(&self) -> bool {
+        self.operations == 0 && !self.exclusive && self.waiting_exclusive == 0
+    }
+}
+
+#[derive(Default)]
+struct InFlight {
+    usage: Mutex<HashMap<String, Usage>>,
+    released: Condvar,
+}
+
+/// Per-collection admission control behind `VectorXLite`: a read/write lock per
+/// collection, with an optional cap on concurrent readers.
+///
+/// Point operations (inserts, deletes, searches) are shared and run side by side;
+/// `VectorXLite::with_per_collection_limit` caps how many run at once, so one busy
+/// collection cannot hold every pooled connection while others wait for one.
+/// Structural operations such as `delete_collection` are exclusive: they wait for the
+/// collection's running operations to finish and block new ones until they are done.
+/// A waiting exclusive operation holds back new shared ones, so it is not starved.
+pub(crate) struct CollectionLimiter {
+    limit: Option<usize>,
+    timeout: Duration,
+    in_flight: Arc<InFlight>,
+}
+
+impl CollectionLimiter {
+    /// Creates a limiter without a limit; operations that cannot run wait at most
+    /// `timeout`.
+    pub(crate) fn new(timeout: Duration) -> Self {
+        CollectionLimiter {
+            limit: None,
+            timeout,
+            in_flight: Arc::default(),
+        }
+    }
+
+    pub(crate) fn set_limit(&mut self, limit: usize) {
+        self.limit = Some(limit.max(1));
+    }
+
+    /// Waits until every collection in `collection_names` runs fewer operations than
+    /// the limit and none is held exclusively, then counts one more operation on each
+    /// of them until the returned permit is dropped.
+    ///
+    /// All collections are taken at once, so operations spanning several collections
+    /// cannot deadlock on each other.
+    pub(crate) fn acquire<'a>(
+        &self,
+        collection_names: impl IntoIterator<Item = &'a str>,
+    ) -> Result<CollectionPermit, VecXError> {
+        let limit = self.limit.unwrap_or(usize::MAX);
+        let collection_names = canonical_names(collection_names);
+        let deadline = Instant::now() + self.timeout;
+        let mut usage = self.lock_usage();
+
+        loop {
+            let busy = collection_names.iter().find_map(|name| {
+                let usage = usage.get(name)?;
+                if usage.exclusive || usage.waiting_exclusive > 0 {
+                    Some(format!(
+                        "collection '{}' is locked by a running collection change",
+                        name
+                    ))
+                } else if usage.operations >= limit {
+                    Some(format!(
+                        "per-collection limit reached: collection '{}' already runs {} operations",
+                        name, limit
+                    ))
+                } else {
+                    None
+                }
+            });
+            let Some(busy) = busy else {
+                for name in &collection_names {
+                    usage.entry(name.clone()).or_default().operations += 1;
+                }
+                return Ok(self.permit(collection_names, false));
+            };
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(VecXError::Other(busy));
+            }
+            usage = self.wait(usage, remaining);
+        }
+    }
+
+    /// Waits until no operation runs on any collection in `collection_names`, then
+    /// holds them exclusively until the returned permit is dropped.
+    pub(crate) fn acquire_exclusive<'a>(
+        &self,
+        collection_names: impl IntoIterator<Item = &'a str>,
+    ) -> Result<CollectionPermit, VecXError> {
+        let collection_names = canonical_names(collection_names);
+        let deadline = Instant::now() + self.timeout;
+        let mut usage = self.lock_usage();
+        for name in &collection_names {
+            usage.entry(name.clone()).or_default().waiting_exclusive += 1;
+        }
+
+        let result = loop {
+            let busy = collection_names.iter().find(|name| {
+                let usage = &usage[*name];
+                usage.operations > 0 || usage.exclusive
+            });
+            let Some(busy) = busy else {
+                break Ok(());
+            };
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break Err(VecXError::Other(format!(
+                    "collection '{}' is busy: operations on it did not finish in time",
+                    busy
+                )));
+            }
+            usage = self.wait(usage, remaining);
+        };
+
+        for name in &collection_names {
+            let entry = usage.get_mut(name).unwrap();
+            entry.waiting_exclusive -= 1;
+            // A waiter that timed out must not clear the flag of the current holder.
+            if result.is_ok() {
+                entry.exclusive = true;
+            }
+        }
+        match result {
+            Ok(()) => Ok(self.permit(collection_names, true)),
+            Err(e) => {
+                usage.retain(|_, usage| !usage.is_idle());
+                self.in_flight.released.notify_all();
+                Err(e)
+            }
+        }
+    }
+
+    fn lock_usage(&self) -> std::sync::MutexGuard<'_, HashMap<String, Usage>> {
+        self.in_flight
+            .usage
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn wait<'g>(
+        &self,
+        usage: std::sync::MutexGuard<'g, HashMap<String, Usage>>,
+        timeout: Duration,
+    ) -> std::sync::MutexGuard<'g, HashMap<String, Usage>> {
+        self.in_flight
+            .released
+            .wait_timeout(usage, timeout)
+            .unwrap_or_else(|e| e.into_inner())
+            .0
+    }
+
+    fn permit(&self, collection_names: Vec<String>, exclusive: bool) -> CollectionPermit {
+        CollectionPermit {
+            in_flight: Arc::clone(&self.in_flight),
+            collection_names,
+            exclusive,
+        }
+    }
+}
+
+fn canonical_names<'a>(collection_names: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    collection_names
+        .into_iter()
+        .map(canonical_collection_name)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Holds an operation's place on its collections until dropped.
+pub(crate) struct CollectionPermit {
+    in_flight: Arc<InFlight>,
+    collection_names: Vec<String>,
+    exclusive: bool,
+}
+
+impl Drop for CollectionPermit {
+    fn drop(&mut self) {
+        let mut usage = self
+            .in_flight
+            .usage
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        for name in &self.collection_names {
+            if let Some(entry) = usage.get_mut(name) {
+                if self.exclusive {
+                    entry.exclusive = false;
+                } else {
+                    entry.operations -= 1;
+                }
+                if entry.is_idle() {
+                    usage.remove(name);
+                }
+            }
+        }
+        self.in_flight.released.notify_all();
+    }
+}
+
+/// An iterator that holds its operation's permit until it is dropped, for results read
+/// lazily from a pooled connection. Fields drop in order, so the connection behind
+/// `inner` is returned before the permit is released.
+pub(crate) struct PermitIter<I> {
+    inner: I,
+    _permit: CollectionPermit,
+}
+
+impl<I> PermitIter<I> {
+    pub(crate) fn new(inner: I, permit: CollectionPermit) -> Self {
+        PermitIter {
+            inner,
+            _permit: permit,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for PermitIter<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
@@ -0,0 +1,22 @@
+use r2d2::{Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
+use std::time::Duration;
+
+use crate::error::VecXError;
+
+/// Acquire a pooled connection, waiting at most `timeout`.
+///
+/// r2d2 reports both an exhausted pool and a failure to open a new connection as a
+/// timeout, so the original message is kept after the `connection pool exhausted`
+/// prefix to keep the latter diagnosable. A new connection that failed to load the
+/// vector extension is reported as `VecXError::ExtensionLoadError` instead.
+pub fn acquire_connection(
+    pool: &Pool<SqliteConnectionManager>,
+    timeout: Duration,
+) -> Result<PooledConnection<SqliteConnectionManager>, VecXError> {
+    pool.get_timeout(timeout).map_err(|e| {
+        let message = e.to_string();
+        VecXError::extension_load_error_in(&message)
+            .unwrap_or_else(|| VecXError::Other(format!("connection pool exhausted: {}", message)))
+    })
+}
@@ -0,0 +1,145 @@
+use r2d2::{Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::Connection;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::error::VecXError;
+use crate::helper::acquire_connection;
+
+/// Where planners and executors get their SQLite connections from: an r2d2 pool, or
+/// one owned connection that every operation takes turns on.
+#[derive(Clone)]
+pub enum ConnectionSource {
+    Pool {
+        pool: Pool<SqliteConnectionManager>,
+        timeout: Duration,
+    },
+    Single {
+        slot: Arc<SingleConnection>,
+        timeout: Duration,
+    },
+}
+
+impl ConnectionSource {
+    pub fn pool(pool: Pool<SqliteConnectionManager>, timeout: Duration) -> Self {
+        ConnectionSource::Pool { pool, timeout }
+    }
+
+    pub fn single(conn: Connection, timeout: Duration) -> Self {
+        ConnectionSource::Single {
+            slot: Arc::new(SingleConnection {
+                conn: Mutex::new(Some(conn)),
+                returned: Condvar::new(),
+            }),
+            timeout,
+        }
+    }
+
+    /// Acquire a connection, waiting at most the source's timeout.
+    pub fn get(&self) -> Result<SourceConnection, VecXError> {
+        match self {
+            ConnectionSource::Pool { pool, timeout } => {
+                acquire_connection(pool, *timeout).map(SourceConnection::Pooled)
+            }
+            ConnectionSource::Single { slot, timeout } => slot.take(*timeout),
+        }
+    }
+
+    /// How long `get` waits for a connection.
+    pub fn timeout(&self) -> Duration {
+        match self {
+            ConnectionSource::Pool { timeout, .. } | ConnectionSource::Single { timeout, .. } => {
+                *timeout
+            }
+        }
+    }
+
+    /// Acquire an idle connection without waiting.
+    pub fn try_get(&self) -> Option<SourceConnection> {
+        match self {
+            ConnectionSource::Pool { pool, .. } => pool.try_get().map(SourceConnection::Pooled),
+            ConnectionSource::Single { slot, .. } => slot.take(Duration::ZERO).ok(),
+        }
+    }
+}
+
+/// The owned connection of a single-connection source, lent out to one operation at
+/// a time.
+pub struct SingleConnection {
+    conn: Mutex<Option<Connection>>,
+    returned: Condvar,
+}
+
+impl SingleConnection {
+    fn take(self: &Arc<Self>, timeout: Duration) -> Result<SourceConnection, VecXError> {
+        let deadline = Instant::now() + timeout;
+        let mut conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+
+        loop {
+            if let Some(conn) = conn.take() {
+                return Ok(SourceConnection::Single {
+                    conn: Some(conn),
+                    slot: Arc::clone(self),
+                });
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(VecXError::Other(
+                    "connection pool exhausted: the single connection is in use".to_string(),
+                ));
+            }
+            conn = self
+                .returned
+                .wait_timeout(conn, remaining)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+
+    fn put_back(&self, conn: Connection) {
+        *self.conn.lock().unwrap_or_else(|e| e.into_inner()) = Some(conn);
+        self.returned.notify_one();
+    }
+}
+
+/// A connection acquired from a `ConnectionSource`, returned to it on drop.
+pub enum SourceConnection {
+    Pooled(PooledConnection<SqliteConnectionManager>),
+    Single {
+        conn: Option<Connection>,
+        slot: Arc<SingleConnection>,
+    },
+}
+
+impl Deref for SourceConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        match self {
+            SourceConnection::Pooled(conn) => conn,
+            SourceConnection::Single { conn, .. } => conn.as_ref().unwrap(),
+        }
+    }
+}
+
+impl DerefMut for SourceConnection {
+    fn deref_mut(&mut self) -> &mut Connection {
+        match self {
+            SourceConnection::Pooled(conn) => conn,
+            SourceConnection::Single { conn, .. } => conn.as_mut().unwrap(),
+        }
+    }
+}
+
+impl Drop for SourceConnection {
+    fn drop(&mut self) {
+        if let SourceConnection::Single { conn, slot } = self {
+            if let Some(conn) = conn.take() {
+                slot.put_back(conn);
+            }
+        }
+    }
+}
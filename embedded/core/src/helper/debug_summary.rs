@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+use std::fmt;
+
+/// Number of leading elements a summarized vector shows in `Debug` output.
+const SUMMARY_ELEMENTS: usize = 4;
+
+/// `Debug` wrapper that prints the first few elements of a vector and its length, so
+/// logging a point with a wide embedding stays readable.
+pub(crate) struct VectorSummary<'a>(pub(crate) &'a [f32]);
+
+impl fmt::Debug for VectorSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.len() <= SUMMARY_ELEMENTS {
+            return f.debug_list().entries(self.0).finish();
+        }
+        write!(f, "[")?;
+        for value in &self.0[..SUMMARY_ELEMENTS] {
+            write!(f, "{:?}, ", value)?;
+        }
+        write!(f, "... ({} values)]", self.0.len())
+    }
+}
+
+/// `Debug` wrapper that prints only the length of a byte buffer.
+pub(crate) struct BytesSummary<'a>(pub(crate) &'a [u8]);
+
+impl fmt::Debug for BytesSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{} bytes>", self.0.len())
+    }
+}
+
+/// `Debug` wrapper that prints how many entries of a sparse vector are set out of its
+/// dimension.
+pub(crate) struct SparseSummary<'a>(pub(crate) &'a HashMap<usize, f32>, pub(crate) usize);
+
+impl fmt::Debug for SparseSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{} of {} values>", self.0.len(), self.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_vectors_are_printed_in_full() {
+        assert_eq!(format!("{:?}", VectorSummary(&[1.0, 2.5])), "[1.0, 2.5]");
+    }
+
+    #[test]
+    fn long_vectors_are_truncated() {
+        let vector: Vec<f32> = (0..768).map(|i| i as f32).collect();
+
+        assert_eq!(
+            format!("{:?}", VectorSummary(&vector)),
+            "[0.0, 1.0, 2.0, 3.0, ... (768 values)]"
+        );
+    }
+}
@@ -2,7 +2,7 @@ use rusqlite::{Connection, LoadExtensionGuard};
 use std::env;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -18,6 +18,19 @@ pub fn load_sqlite_vector_extension(conn: &mut Connection) -> Result<(), VecXErr
     load_sqlite_vector_extension_with_bytes(conn, embedded_lib_bytes())
 }
 
+/// Loads the vector extension from a native library on disk instead of the embedded
+/// asset bytes.
+pub fn load_sqlite_vector_extension_from_path(
+    conn: &mut Connection,
+    path: &Path,
+) -> Result<(), VecXError> {
+    unsafe {
+        let _guard = LoadExtensionGuard::new(conn)?;
+        conn.load_extension(path, None::<&str>)
+            .map_err(|e| VecXError::ExtensionLoadError(format!("{}: {}", path.display(), e)))
+    }
+}
+
 fn create_unique_temp_filename(attempts: u8) -> PathBuf {
     // determine platform extension
     #[cfg(target_os = "linux")]
@@ -88,7 +101,8 @@ fn load_sqlite_vector_extension_with_bytes(
                 .to_str()
                 .ok_or_else(|| VecXError::ExtensionLoadError("invalid temporary path".into()))?,
             None::<&str>,
-        )?;
+        )
+        .map_err(|e| VecXError::ExtensionLoadError(e.to_string()))?;
     }
 
     // NOTE: On Unix systems it's safe to remove the file after loading (the loader keeps it open).
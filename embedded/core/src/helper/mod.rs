@@ -1,9 +1,29 @@
+pub mod collection_counters;
+pub mod collection_limiter;
+pub mod connection_pool;
+pub mod connection_source;
+pub mod debug_summary;
 pub mod extension_loader;
 pub mod sql_helper;
 pub mod row_parser;
 pub mod names;
+pub mod ndjson;
+pub mod sparse_vector;
+pub mod vector_json;
+#[cfg(feature = "arrow")]
+pub mod arrow_batch;
 
+pub(crate) use collection_counters::*;
+pub(crate) use collection_limiter::*;
+pub use connection_pool::*;
+pub use connection_source::*;
+pub(crate) use debug_summary::*;
 pub use extension_loader::*;
 pub use sql_helper::*;
 pub use row_parser::*;
-pub use names::*;
\ No newline at end of file
+pub use names::*;
+pub use ndjson::*;
+pub use sparse_vector::*;
+pub use vector_json::*;
+#[cfg(feature = "arrow")]
+pub use arrow_batch::*;
\ No newline at end of file
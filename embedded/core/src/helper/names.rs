@@ -1,5 +1,148 @@
 use crate::constant::*;
+use crate::error::VecXError;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+
+/// Canonical form of a collection name. SQLite resolves table names case-insensitively,
+/// so `MyColl` and `mycoll` name the same collection, and the tables backing it are
+/// created and looked up under the lowercase name.
+pub fn canonical_collection_name(collection_name: &str) -> String {
+    collection_name.to_ascii_lowercase()
+}
 
 pub fn get_vector_table_name(table_name: &str) -> String {
-    format!("{}_{}", VECTOR_TABLE_PREFIX, table_name)
+    format!(
+        "{}_{}",
+        VECTOR_TABLE_PREFIX,
+        canonical_collection_name(table_name)
+    )
+}
+
+/// Name of a collection's payload table: `pt_<collection>` when payload tables are
+/// prefixed, otherwise the collection name itself.
+pub fn get_payload_table_name(collection_name: &str, prefixed: bool) -> String {
+    let collection_name = canonical_collection_name(collection_name);
+    if prefixed {
+        format!("{}_{}", PAYLOAD_TABLE_PREFIX, collection_name)
+    } else {
+        collection_name
+    }
+}
+
+/// Name of the payload index over `columns`: `vx_idx_<payload table>_<column>...`.
+pub fn get_payload_index_name(payload_table_name: &str, columns: &[&str]) -> String {
+    format!(
+        "{}_{}_{}",
+        PAYLOAD_INDEX_PREFIX,
+        payload_table_name,
+        columns.join("_")
+    )
+}
+
+/// Whether `column` is a bare SQL identifier that can be used without quoting.
+pub fn is_plain_column_name(column: &str) -> bool {
+    column
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && column.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Quotes an identifier for interpolation into SQL, doubling embedded `"`, so columns
+/// of a free-form payload schema (reserved words, spaces) can be copied by name.
+pub fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// Checks that a collection name is a bare SQL identifier, since collection names are
+/// interpolated into SQL as table names.
+pub fn validate_collection_name(collection_name: &str) -> Result<(), String> {
+    if is_plain_column_name(collection_name) {
+        return Ok(());
+    }
+    Err(format!(
+        "collection name '{}' must contain only ASCII letters, digits and underscores and must not start with a digit.",
+        collection_name
+    ))
+}
+
+/// Derive the index file path of a renamed collection: the old collection name in the
+/// file name is replaced by the new one, otherwise the new name is prefixed.
+pub fn get_renamed_index_path(index_path: &str, old_name: &str, new_name: &str) -> PathBuf {
+    let path = Path::new(index_path);
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let renamed = if file_name.contains(old_name) {
+        file_name.replacen(old_name, new_name, 1)
+    } else {
+        format!("{}_{}", new_name, file_name)
+    };
+
+    path.with_file_name(renamed)
+}
+
+/// Check that vectorlite can write an index file at `index_path`: the path must not be
+/// a directory, and its parent directory must exist and accept new files. Writability
+/// is probed by creating and removing an empty file next to the index.
+pub fn check_index_file_path(index_path: &str) -> Result<(), VecXError> {
+    let path = Path::new(index_path);
+    if path.is_dir() {
+        return Err(VecXError::IoError(format!(
+            "index file path '{}' is a directory",
+            index_path
+        )));
+    }
+
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    if !parent.is_dir() {
+        return Err(VecXError::IoError(format!(
+            "directory '{}' of index file '{}' does not exist",
+            parent.display(),
+            index_path
+        )));
+    }
+
+    let probe = parent.join(format!(".vx_write_check_{}", std::process::id()));
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .map_err(|e| {
+            VecXError::IoError(format!(
+                "directory '{}' of index file '{}' is not writable: {}",
+                parent.display(),
+                index_path,
+                e
+            ))
+        })?;
+    let _ = fs::remove_file(&probe);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_names_use_canonical_collection_name() {
+        assert_eq!(canonical_collection_name("MyColl"), "mycoll");
+        assert_eq!(
+            get_vector_table_name("MyColl"),
+            get_vector_table_name("mycoll")
+        );
+        assert_eq!(get_payload_table_name("MyColl", false), "mycoll");
+        assert_eq!(get_payload_table_name("MyColl", true), "pt_mycoll");
+    }
+
+    #[test]
+    fn quote_identifier_doubles_embedded_quotes() {
+        assert_eq!(quote_identifier("order"), "\"order\"");
+        assert_eq!(quote_identifier("my \"col\""), "\"my \"\"col\"\"\"");
+    }
 }
@@ -0,0 +1,131 @@
+use crate::{error::VecXError, types::SqlValue};
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::io::Write;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Writes one result row as a JSON object on its own line.
+///
+/// Integers and finite reals become JSON numbers, text becomes a string, blobs a
+/// standard base64 string and NULL (or a non-finite real) `null`. Keys keep the column
+/// order of the result set; when a name repeats, as `rowid` does in a payload join, only
+/// its first column is written.
+pub fn write_ndjson_row(
+    writer: &mut dyn Write,
+    column_names: &[String],
+    values: &[SqlValue],
+) -> Result<(), VecXError> {
+    let mut seen = HashSet::new();
+    let mut line = String::from("{");
+
+    for (name, value) in column_names.iter().zip(values) {
+        if !seen.insert(name.as_str()) {
+            continue;
+        }
+        if line.len() > 1 {
+            line.push(',');
+        }
+        push_json_string(&mut line, name);
+        line.push(':');
+        match value {
+            SqlValue::Null => line.push_str("null"),
+            SqlValue::Integer(v) => line.push_str(&v.to_string()),
+            SqlValue::Real(v) if v.is_finite() => line.push_str(&v.to_string()),
+            SqlValue::Real(_) => line.push_str("null"),
+            SqlValue::Text(s) => push_json_string(&mut line, s),
+            SqlValue::Blob(b) => push_json_string(&mut line, &base64_encode(b)),
+        }
+    }
+
+    line.push_str("}\n");
+    writer.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+fn push_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ndjson(column_names: &[&str], values: Vec<SqlValue>) -> String {
+        let column_names: Vec<String> = column_names.iter().map(|s| s.to_string()).collect();
+        let mut out = Vec::new();
+        write_ndjson_row(&mut out, &column_names, &values).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn writes_typed_values() {
+        let line = ndjson(
+            &["rowid", "distance", "title", "note", "raw"],
+            vec![
+                SqlValue::Integer(7),
+                SqlValue::Real(0.5),
+                SqlValue::Text("say \"hi\"\n".into()),
+                SqlValue::Null,
+                SqlValue::Blob(vec![1, 2, 3]),
+            ],
+        );
+
+        assert_eq!(
+            line,
+            "{\"rowid\":7,\"distance\":0.5,\"title\":\"say \\\"hi\\\"\\n\",\"note\":null,\"raw\":\"AQID\"}\n"
+        );
+    }
+
+    #[test]
+    fn skips_repeated_columns_and_non_finite_reals() {
+        let line = ndjson(
+            &["rowid", "score", "rowid"],
+            vec![SqlValue::Integer(1), SqlValue::Real(f64::NAN), SqlValue::Integer(1)],
+        );
+
+        assert_eq!(line, "{\"rowid\":1,\"score\":null}\n");
+    }
+
+    #[test]
+    fn base64_pads_partial_chunks() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_encode(&[0xff, 0xfe]), "//4=");
+    }
+}
@@ -1,6 +1,8 @@
 use rusqlite::{types::Value, Row, Result};
 use std::collections::HashMap;
 
+use crate::types::SqlValue;
+
 /// Convert a single rusqlite Value to a readable string.
 pub fn get_value_as_string(row: &Row, i: usize) -> String {
     match row.get::<_, Value>(i) {
@@ -20,4 +22,12 @@ pub fn parse_row_to_map(row: &Row) -> Result<HashMap<String, String>> {
         map.insert((*col_name).to_string(), get_value_as_string(row, i));
     }
     Ok(map)
-}
\ No newline at end of file
+}
+/// Convert a rusqlite::Row into a HashMap<column_name, SqlValue>, keeping storage classes.
+pub fn parse_row_to_typed_map(row: &Row) -> Result<HashMap<String, SqlValue>> {
+    let mut map: HashMap<String, SqlValue> = HashMap::new();
+    for (i, col_name) in row.as_ref().column_names().iter().enumerate() {
+        map.insert((*col_name).to_string(), row.get::<_, Value>(i)?.into());
+    }
+    Ok(map)
+}
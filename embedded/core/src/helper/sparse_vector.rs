@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+/// Expand a sparse vector, given as `index -> value` entries, into a dense vector of
+/// `dimension` elements with every other element set to zero.
+pub fn dense_from_sparse(
+    entries: &HashMap<usize, f32>,
+    dimension: usize,
+) -> Result<Vec<f32>, String> {
+    if dimension == 0 {
+        return Err("sparse_vector dimension must be greater than 0.".into());
+    }
+    if let Some(index) = entries
+        .keys()
+        .copied()
+        .filter(|&index| index >= dimension)
+        .max()
+    {
+        return Err(format!(
+            "sparse_vector index {} is out of range for dimension {}.",
+            index, dimension
+        ));
+    }
+
+    let mut vector = vec![0.0; dimension];
+    for (&index, &value) in entries {
+        vector[index] = value;
+    }
+    Ok(vector)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_entries_are_zero() {
+        let entries = HashMap::from([(0, 1.5), (3, -2.0)]);
+
+        assert_eq!(
+            dense_from_sparse(&entries, 5).unwrap(),
+            vec![1.5, 0.0, 0.0, -2.0, 0.0]
+        );
+        assert_eq!(
+            dense_from_sparse(&HashMap::new(), 2).unwrap(),
+            vec![0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn out_of_range_index_is_rejected() {
+        let entries = HashMap::from([(1, 1.0), (4, 1.0), (7, 1.0)]);
+
+        assert_eq!(
+            dense_from_sparse(&entries, 4).unwrap_err(),
+            "sparse_vector index 7 is out of range for dimension 4."
+        );
+        assert_eq!(
+            dense_from_sparse(&HashMap::new(), 0).unwrap_err(),
+            "sparse_vector dimension must be greater than 0."
+        );
+    }
+}
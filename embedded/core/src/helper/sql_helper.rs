@@ -1,9 +1,9 @@
 use once_cell::sync::Lazy;
-use r2d2::Pool;
-use r2d2_sqlite::SqliteConnectionManager;
 use regex::Regex;
+use rusqlite::Connection;
 
 use crate::error::VecXError;
+use crate::types::{AggOp, AggSpec, Direction, DistanceFunction};
 
 /// Compile regexes once for performance and to avoid unwraps at runtime.
 static RE_WITH_COLS: Lazy<Regex> = Lazy::new(|| {
@@ -11,8 +11,32 @@ static RE_WITH_COLS: Lazy<Regex> = Lazy::new(|| {
 });
 static RE_NO_COLS: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?i)^insert\s+into\s+([^\s(]+)\s*values\s*\(([^)]*)\)").unwrap());
-static RE_SELECT_FROM_NONGREEDY: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"(?is)SELECT\s+.*?\s+FROM").unwrap());
+static RE_INSERT_VERB: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(\s*)insert\s+(?:or\s+(?:replace|ignore|abort|fail|rollback)\s+)?into\b")
+        .unwrap()
+});
+static RE_INSERT_TARGET: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?i)^\s*(?:insert(?:\s+or\s+[a-z]+)?|replace)\s+into\s+(?:["`\[]?([^\s."`\[\]]+)["`\]]?\s*\.\s*)?["`\[]?([^\s("`\[\]]+)["`\]]?"#,
+    )
+    .unwrap()
+});
+static RE_CREATE_TABLE_TARGET: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?i)^\s*create\s+(?:temp(?:orary)?\s+)?table\s+(?:if\s+not\s+exists\s+)?(?:["`\[]?([^\s."`\[\]]+)["`\]]?\s*\.\s*)?["`\[]?([^\s("`\[\]]+)["`\]]?"#,
+    )
+    .unwrap()
+});
+static RE_ROWID_ASSIGNMENT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?i)(?:^|,)\s*["`\[]?(?:rowid|_rowid_|oid)["`\]]?\s*="#).unwrap());
+static RE_VECTOR_DIMENSION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)float32\[(\d+)\]").unwrap());
+static RE_DISTANCE_TYPE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)float32\[\d+\]\s+(l2|cosine|ip)\b").unwrap());
+static RE_HNSW_MAX_ELEMENTS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)hnsw\([^)]*\bmax_elements\s*=\s*(\d+)").unwrap());
+static RE_HNSW_M: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)hnsw\([^)]*\bM\s*=\s*(\d+)").unwrap());
 static RE_COLLECTION_NAME: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?i)\b(?:table|into|from)\s+([a-zA-Z_][a-zA-Z0-9_]*)").unwrap());
 
@@ -64,18 +88,392 @@ pub fn inject_rowid(sql: &str, rowid: u64) -> String {
     }
 }
 
+/// Turn an INSERT statement into `INSERT OR REPLACE`, overriding any other conflict
+/// clause. Returns the original SQL if it does not start with an INSERT.
+pub fn as_insert_or_replace(sql: &str) -> String {
+    RE_INSERT_VERB
+        .replace(sql, "${1}INSERT OR REPLACE INTO")
+        .into_owned()
+}
+
+/// Extract the table an INSERT statement writes to, without quotes. A `main.` schema
+/// qualifier is dropped, any other schema is kept (`aux.docs`). Returns None if the
+/// statement does not start with an INSERT.
+pub fn insert_target_table(sql: &str) -> Option<String> {
+    let caps = RE_INSERT_TARGET.captures(sql)?;
+    let table = caps.get(2)?.as_str();
+
+    match caps.get(1).map(|m| m.as_str()) {
+        Some(schema) if !schema.eq_ignore_ascii_case("main") => {
+            Some(format!("{}.{}", schema, table))
+        }
+        _ => Some(table.to_string()),
+    }
+}
+
+/// Extract the table a CREATE TABLE statement creates, without quotes. Like
+/// `insert_target_table`, a `main.` schema qualifier is dropped and any other schema
+/// is kept. Returns None if the statement does not start with CREATE TABLE.
+pub fn created_table_name(sql: &str) -> Option<String> {
+    let caps = RE_CREATE_TABLE_TARGET.captures(sql)?;
+    let table = caps.get(2)?.as_str();
+
+    match caps.get(1).map(|m| m.as_str()) {
+        Some(schema) if !schema.eq_ignore_ascii_case("main") => {
+            Some(format!("{}.{}", schema, table))
+        }
+        _ => Some(table.to_string()),
+    }
+}
+
 /// Replace the SELECT clause with a COUNT(*) selection.
 pub fn replace_select_with_count(query: &str) -> String {
-    RE_SELECT_FROM_NONGREEDY
-        .replace(query, "SELECT count(*) FROM")
-        .to_string()
+    replace_outer_select_list(query, "SELECT count(*) FROM")
 }
 
 /// Replace the SELECT clause with `SELECT rowid FROM`.
 pub fn replace_select_with_row_ids(query: &str) -> String {
-    RE_SELECT_FROM_NONGREEDY
-        .replace(query, "SELECT rowid FROM")
-        .to_string()
+    replace_outer_select_list(query, "SELECT rowid FROM")
+}
+
+/// Replace the `SELECT ... FROM` of the outermost query, keeping any leading comments
+/// and `WITH` clause. Returns the original SQL if no outer `SELECT ... FROM` is found.
+fn replace_outer_select_list(query: &str, replacement: &str) -> String {
+    let Some(select_start) = find_outer_keyword(query, 0, "select") else {
+        return query.to_string();
+    };
+    let Some(from_start) = find_outer_keyword(query, select_start + "select".len(), "from") else {
+        return query.to_string();
+    };
+
+    format!(
+        "{}{}{}",
+        &query[..select_start],
+        replacement,
+        &query[from_start + "from".len()..]
+    )
+}
+
+/// Find the byte offset of `keyword` at parenthesis depth 0, skipping string literals,
+/// quoted identifiers and comments.
+fn find_outer_keyword(sql: &str, from: usize, keyword: &str) -> Option<usize> {
+    let bytes = sql.as_bytes();
+    let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || b == b'$';
+    let mut depth = 0usize;
+    let mut i = from;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = sql[i..].find('\n').map_or(bytes.len(), |end| i + end);
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = sql[i + 2..].find("*/").map_or(bytes.len(), |end| i + 2 + end + 2);
+                continue;
+            }
+            quote @ (b'\'' | b'"' | b'`' | b'[') => {
+                let close = if quote == b'[' { b']' } else { quote };
+                i = bytes[i + 1..]
+                    .iter()
+                    .position(|&b| b == close)
+                    .map_or(bytes.len(), |end| i + 1 + end);
+            }
+            b'(' => depth += 1,
+            b')' => depth = depth.saturating_sub(1),
+            b if depth == 0
+                && is_ident(b)
+                && (i == 0 || !is_ident(bytes[i - 1]))
+                && sql.len() >= i + keyword.len()
+                && sql.is_char_boundary(i + keyword.len())
+                && sql[i..i + keyword.len()].eq_ignore_ascii_case(keyword)
+                && !bytes.get(i + keyword.len()).copied().is_some_and(is_ident) =>
+            {
+                return Some(i);
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Render ids as a comma separated list suitable for a `rowid IN (...)` clause.
+pub fn join_ids(ids: &[i64]) -> String {
+    ids.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Whether the SET clause of an UPDATE assigns the rowid, which would detach payload
+/// rows from their vectors. `set_sql` is the part after `SET`, e.g. `a = 1, b = 'x'`.
+pub fn assigns_rowid(set_sql: &str) -> bool {
+    RE_ROWID_ASSIGNMENT.is_match(set_sql)
+}
+
+/// Re-sort the rows of a search query by a result column, keeping its LIMIT intact.
+/// Ties are broken by distance. Returns the query unchanged when no ordering is given.
+pub fn apply_order_by(
+    sql: String,
+    order_by: &Option<(String, Direction)>,
+    distance_column: &str,
+) -> String {
+    match order_by {
+        Some((column, direction)) => format!(
+            "SELECT * FROM ({}) ORDER BY \"{}\" {}, \"{}\"",
+            sql,
+            column,
+            direction.as_str(),
+            distance_column
+        ),
+        None => sql,
+    }
+}
+
+/// Drop the rows of a search query whose distance exceeds `max_distance`. Returns the
+/// query unchanged when no threshold is given.
+pub fn apply_max_distance(sql: String, max_distance: Option<f32>, distance_column: &str) -> String {
+    match max_distance {
+        Some(max_distance) => format!(
+            "SELECT * FROM ({}) WHERE \"{}\" <= {}",
+            sql, distance_column, max_distance
+        ),
+        None => sql,
+    }
+}
+
+/// Add a `rerank_score` column to the rows of a search query: the distance under
+/// `metric` between each row's vector in `vt_table_name` and the query vector bound to
+/// `?1`. Returns the query unchanged when no metric is given.
+pub fn apply_rerank_score(
+    sql: String,
+    rerank_with: Option<DistanceFunction>,
+    vt_table_name: &str,
+) -> String {
+    match rerank_with {
+        Some(metric) => format!(
+            "SELECT r.*, (SELECT vector_distance(rv.vector_embedding, vector_from_json(?1), '{metric}') \
+             FROM {vt_table_name} AS rv WHERE rv.rowid = r.rowid) AS rerank_score \
+             FROM ({sql}) AS r",
+            sql = sql,
+            metric = metric.as_str(),
+            vt_table_name = vt_table_name
+        ),
+        None => sql,
+    }
+}
+
+/// Add a `vector` column with each row's stored vector from `vt_table_name`, as a JSON
+/// array. Returns the query unchanged when `include_vector` is false.
+pub fn apply_include_vector(sql: String, include_vector: bool, vt_table_name: &str) -> String {
+    if !include_vector {
+        return sql;
+    }
+    format!(
+        "SELECT r.*, (SELECT vector_to_json(iv.vector_embedding) \
+         FROM {vt_table_name} AS iv WHERE iv.rowid = r.rowid) AS vector \
+         FROM ({sql}) AS r",
+        sql = sql,
+        vt_table_name = vt_table_name
+    )
+}
+
+/// Keep the nearest row per distinct value of `column` among the rows of a search
+/// query, then cut the result to `top_k` by distance. Ties on distance keep the
+/// lowest rowid. Returns the query unchanged when no column is given.
+pub fn apply_dedup_by(
+    sql: String,
+    dedup_by: &Option<String>,
+    top_k: i64,
+    distance_column: &str,
+) -> String {
+    match dedup_by {
+        Some(column) => format!(
+            "WITH vx_hits AS MATERIALIZED ({sql}) \
+             SELECT * FROM vx_hits AS h \
+             WHERE h.rowid = (SELECT d.rowid FROM vx_hits AS d \
+             WHERE d.\"{column}\" IS h.\"{column}\" ORDER BY d.\"{distance}\", d.rowid LIMIT 1) \
+             ORDER BY h.\"{distance}\" LIMIT {top_k}",
+            sql = sql,
+            column = column,
+            distance = distance_column,
+            top_k = top_k
+        ),
+        None => sql,
+    }
+}
+
+/// Keep the nearest `per_group` rows per distinct value of `column` among the rows of a
+/// search query, ranked with `ROW_NUMBER()` and ordered by that value, then distance.
+/// Ties on distance keep the lowest rowid. Returns the query unchanged when no grouping
+/// is given.
+pub fn apply_group_by(
+    sql: String,
+    group_by: &Option<(String, usize)>,
+    distance_column: &str,
+) -> String {
+    match group_by {
+        Some((column, per_group)) => format!(
+            "WITH vx_hits AS MATERIALIZED ({sql}), \
+             vx_ranked AS (SELECT rowid AS vx_rowid, ROW_NUMBER() OVER \
+             (PARTITION BY \"{column}\" ORDER BY \"{distance}\", rowid) AS vx_group_rank FROM vx_hits) \
+             SELECT h.* FROM vx_hits AS h INNER JOIN vx_ranked AS r ON r.vx_rowid = h.rowid \
+             WHERE r.vx_group_rank <= {per_group} \
+             ORDER BY h.\"{column}\", h.\"{distance}\", h.rowid",
+            sql = sql,
+            column = column,
+            distance = distance_column,
+            per_group = per_group
+        ),
+        None => sql,
+    }
+}
+
+/// Reduce the rows of a search query to a single `aggregate` column holding the sum
+/// or average of the aggregate's column. When weighted by similarity, each value is
+/// multiplied by `similarity_sql` and the average is divided by the sum of the weights.
+/// Returns the query unchanged when no aggregate is given.
+pub fn apply_aggregate(sql: String, aggregate: &Option<AggSpec>, similarity_sql: &str) -> String {
+    let Some(spec) = aggregate else {
+        return sql;
+    };
+
+    let column = format!("\"{}\"", spec.column);
+    let expression = match (spec.op, spec.weight_by_similarity) {
+        (AggOp::Sum, false) => format!("SUM({})", column),
+        (AggOp::Avg, false) => format!("AVG({})", column),
+        (AggOp::Sum, true) => format!("SUM({} * {})", column, similarity_sql),
+        (AggOp::Avg, true) => format!(
+            "SUM({column} * {weight}) / SUM({weight})",
+            column = column,
+            weight = similarity_sql
+        ),
+    };
+    format!("SELECT {} AS aggregate FROM ({})", expression, sql)
+}
+
+/// SQL expression for the similarity of a result to the query vector given its
+/// `distance_column`: `1 - distance` for cosine and ip, `1 / (1 + distance)` for l2.
+pub fn similarity_sql(distance_type: &str, distance_column: &str) -> String {
+    match distance_type {
+        "l2" => format!("(1.0 / (1.0 + \"{}\"))", distance_column),
+        _ => format!("(1.0 - \"{}\")", distance_column),
+    }
+}
+
+/// Extract the `vectorlite(...)` column and `hnsw(...)` arguments from a virtual table
+/// definition, dropping any trailing index file path.
+///
+/// `CREATE VIRTUAL TABLE t USING vectorlite(v float32[2] l2, hnsw(max_elements=10), /a.idx)`
+/// yields `v float32[2] l2, hnsw(max_elements=10)`.
+pub fn vectorlite_args_without_index_path(sql: &str) -> Option<String> {
+    let sql_lower = sql.to_lowercase();
+    let start = sql_lower.find("vectorlite(")? + "vectorlite(".len();
+    let hnsw_start = sql_lower[start..].find("hnsw(")? + start;
+    let end = sql[hnsw_start..].find(')')? + hnsw_start + 1;
+    Some(sql[start..end].trim().to_string())
+}
+
+/// Extract the vector dimension of a vectorlite virtual table definition.
+pub fn vectorlite_dimension(sql: &str) -> Option<usize> {
+    RE_VECTOR_DIMENSION
+        .captures(sql)
+        .and_then(|caps| caps.get(1))
+        .and_then(|m| m.as_str().parse().ok())
+}
+
+/// Extract the distance type (`l2`, `cosine` or `ip`) of a vectorlite virtual table
+/// definition. vectorlite uses `l2` when the column does not name one.
+pub fn vectorlite_distance_type(sql: &str) -> &'static str {
+    match RE_DISTANCE_TYPE
+        .captures(sql)
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str().to_lowercase())
+        .as_deref()
+    {
+        Some("cosine") => "cosine",
+        Some("ip") => "ip",
+        _ => "l2",
+    }
+}
+
+/// Extract the `M` (max neighbours per node) HNSW parameter of a vectorlite virtual
+/// table definition. vectorlite uses 16 when `hnsw(...)` does not set it.
+pub fn vectorlite_hnsw_m(sql: &str) -> usize {
+    RE_HNSW_M
+        .captures(sql)
+        .and_then(|caps| caps.get(1))
+        .and_then(|m| m.as_str().parse().ok())
+        .unwrap_or(16)
+}
+
+/// Extract the `max_elements` HNSW parameter of a vectorlite virtual table definition.
+pub fn vectorlite_max_elements(sql: &str) -> Option<i64> {
+    RE_HNSW_MAX_ELEMENTS
+        .captures(sql)
+        .and_then(|caps| caps.get(1))
+        .and_then(|m| m.as_str().parse().ok())
+}
+
+/// Estimated in-memory bytes of one element of an hnswlib index holding float32
+/// vectors of `dimension` with neighbour parameter `m`, see
+/// `VectorXLite::estimate_memory`.
+pub fn estimate_hnsw_element_bytes(dimension: usize, m: usize) -> f64 {
+    let m = m.max(2) as f64;
+    let vector_bytes = 4.0 * dimension as f64;
+    let graph_overhead = 4.0 * (2.0 * m + 1.0) + 16.0 + 4.0 * (m + 1.0) / (m - 1.0);
+    vector_bytes + graph_overhead
+}
+
+/// Extract the index file path following the `hnsw(...)` argument of a vectorlite
+/// virtual table definition. Returns None for in-memory indexes.
+pub fn vectorlite_index_path(sql: &str) -> Option<String> {
+    let sql_lower = sql.to_lowercase();
+    let start = sql_lower.find("vectorlite(")? + "vectorlite(".len();
+    let hnsw_start = sql_lower[start..].find("hnsw(")? + start;
+    let hnsw_end = sql[hnsw_start..].find(')')? + hnsw_start + 1;
+    let end = sql.rfind(')')?;
+    if end <= hnsw_end {
+        return None;
+    }
+
+    let path = sql[hnsw_end..end]
+        .trim()
+        .trim_start_matches(',')
+        .trim()
+        .trim_matches(|c| c == '\'' || c == '"');
+    (!path.is_empty()).then(|| path.to_string())
+}
+
+/// Point a `CREATE TABLE` or `CREATE INDEX ... ON` statement at a renamed table.
+/// Returns None if the statement does not reference `old_table` where expected.
+pub fn rename_table_in_create_sql(sql: &str, old_table: &str, new_table: &str) -> Option<String> {
+    let pattern = format!(
+        r#"(?is)^(\s*create\s+(?:table\s+(?:if\s+not\s+exists\s+)?|(?:unique\s+)?index\s+.*?\bon\s+))(["`\[]?){}(["`\]]?)(\s*\()"#,
+        regex::escape(old_table)
+    );
+    let re = Regex::new(&pattern).ok()?;
+    if !re.is_match(sql) {
+        return None;
+    }
+
+    let replacement = format!("${{1}}${{2}}{}${{3}}${{4}}", new_table);
+    Some(re.replace(sql, replacement.as_str()).to_string())
+}
+
+/// Point the `FROM` and `JOIN` clauses of a query that read `old_table` at `new_table`.
+pub fn rename_table_in_query(sql: &str, old_table: &str, new_table: &str) -> String {
+    let pattern = format!(
+        r#"(?i)\b(from|join)(\s+)(["`\[]?){}(["`\]]?)(\W|$)"#,
+        regex::escape(old_table)
+    );
+    match Regex::new(&pattern) {
+        Ok(re) => re
+            .replace_all(sql, format!("${{1}}${{2}}${{3}}{}${{4}}${{5}}", new_table).as_str())
+            .to_string(),
+        Err(_) => sql.to_string(),
+    }
 }
 
 /// Try to parse a collection/table name from SQL. Returns None if not found.
@@ -108,10 +506,9 @@ struct ColumnInfo {
 ///   INSERT INTO story(title, rating, created_at)
 ///   VALUES(?1, COALESCE(?2, 0.0), datetime('now'));
 pub fn generate_insert_with_defaults(
-    pool: Pool<SqliteConnectionManager>,
+    conn: &Connection,
     table: &str,
 ) -> rusqlite::Result<String, VecXError> {
-    let conn = pool.get()?;
     let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
     let rows = stmt.query_map([], |row| {
         let name: String = row.get(1)?;
@@ -182,3 +579,212 @@ pub fn generate_insert_with_defaults(
 
     Ok(query)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replace_select_with_count_simple_query() {
+        let got = replace_select_with_count("SELECT id, name FROM story WHERE rating > 4");
+        assert_eq!(got, "SELECT count(*) FROM story WHERE rating > 4");
+    }
+
+    #[test]
+    fn replace_select_with_count_cte_query() {
+        let query = "WITH top AS (SELECT rowid, rating FROM story WHERE rating > 4) \
+                     SELECT rowid, rating FROM top";
+        let got = replace_select_with_count(query);
+        assert_eq!(
+            got,
+            "WITH top AS (SELECT rowid, rating FROM story WHERE rating > 4) \
+             SELECT count(*) FROM top"
+        );
+    }
+
+    #[test]
+    fn replace_select_with_row_ids_multi_line_query() {
+        let query = "\n  select\n    rowid,\n    (select max(rating) from story) as best\n  from story\n  where rating > 1";
+        let got = replace_select_with_row_ids(query);
+        assert_eq!(got, "\n  SELECT rowid FROM story\n  where rating > 1");
+    }
+
+    #[test]
+    fn replace_select_with_row_ids_leading_comments() {
+        let query = "-- select from the wrong place\n/* SELECT x FROM y */ SELECT rowid, 'from' AS tag FROM story";
+        let got = replace_select_with_row_ids(query);
+        assert_eq!(
+            got,
+            "-- select from the wrong place\n/* SELECT x FROM y */ SELECT rowid FROM story"
+        );
+    }
+
+    #[test]
+    fn replace_select_without_select_returns_original() {
+        assert_eq!(replace_select_with_count("PRAGMA table_info(story)"), "PRAGMA table_info(story)");
+    }
+
+    #[test]
+    fn assigns_rowid_detects_rowid_targets() {
+        assert!(assigns_rowid("rowid = 5"));
+        assert!(assigns_rowid("category = 'a', \"rowid\" = rowid + 1"));
+        assert!(assigns_rowid("OID=3"));
+        assert!(!assigns_rowid("category = 'a', rank = rowid * 2"));
+        assert!(!assigns_rowid("void = 1"));
+    }
+
+    #[test]
+    fn created_table_name_reads_table_name() {
+        assert_eq!(
+            created_table_name("create table person (rowid integer primary key)").as_deref(),
+            Some("person")
+        );
+        assert_eq!(
+            created_table_name("  CREATE TABLE IF NOT EXISTS \"person\"(name text)").as_deref(),
+            Some("person")
+        );
+        assert_eq!(
+            created_table_name("create temp table main.[person] (name text)").as_deref(),
+            Some("person")
+        );
+        assert_eq!(
+            created_table_name("create table aux.person (name text)").as_deref(),
+            Some("aux.person")
+        );
+        assert_eq!(created_table_name("create index idx on person(name)"), None);
+    }
+
+    #[test]
+    fn rename_table_in_query_rewrites_from_and_join() {
+        assert_eq!(
+            rename_table_in_query("select rowid, name from person where name = 'x'", "person", "people"),
+            "select rowid, name from people where name = 'x'"
+        );
+        assert_eq!(
+            rename_table_in_query("SELECT p.rowid FROM \"person\" p JOIN person_tags t ON 1 = 1", "person", "people"),
+            "SELECT p.rowid FROM \"people\" p JOIN person_tags t ON 1 = 1"
+        );
+        assert_eq!(
+            rename_table_in_query("select person from other", "person", "people"),
+            "select person from other"
+        );
+    }
+
+    #[test]
+    fn insert_target_table_reads_table_name() {
+        assert_eq!(
+            insert_target_table("insert into person(rowid, name) values (?1, 'a')").as_deref(),
+            Some("person")
+        );
+        assert_eq!(
+            insert_target_table("  INSERT OR REPLACE INTO \"person\" VALUES (1)").as_deref(),
+            Some("person")
+        );
+        assert_eq!(
+            insert_target_table("insert into main.[person] values (1)").as_deref(),
+            Some("person")
+        );
+        assert_eq!(
+            insert_target_table("insert into aux.person values (1)").as_deref(),
+            Some("aux.person")
+        );
+        assert_eq!(insert_target_table("update person set name = 'a'"), None);
+    }
+
+    #[test]
+    fn apply_rerank_score_adds_score_column() {
+        let sql = apply_rerank_score(
+            "SELECT rowid, distance FROM vt".into(),
+            Some(DistanceFunction::Cosine),
+            "vt",
+        );
+        assert!(sql.starts_with("SELECT r.*, (SELECT vector_distance("));
+        assert!(sql.contains("vector_from_json(?1), 'cosine')"));
+        assert!(sql.contains("WHERE rv.rowid = r.rowid) AS rerank_score"));
+        assert!(sql.ends_with("FROM (SELECT rowid, distance FROM vt) AS r"));
+        assert_eq!(
+            apply_rerank_score("SELECT 1".into(), None, "vt"),
+            "SELECT 1"
+        );
+    }
+
+    #[test]
+    fn apply_include_vector_adds_vector_column() {
+        let sql = apply_include_vector("SELECT rowid, distance FROM vt".into(), true, "vt");
+        assert!(sql.starts_with("SELECT r.*, (SELECT vector_to_json(iv.vector_embedding)"));
+        assert!(sql.contains("WHERE iv.rowid = r.rowid) AS vector"));
+        assert!(sql.ends_with("FROM (SELECT rowid, distance FROM vt) AS r"));
+        assert_eq!(
+            apply_include_vector("SELECT 1".into(), false, "vt"),
+            "SELECT 1"
+        );
+    }
+
+    #[test]
+    fn as_insert_or_replace_rewrites_conflict_clause() {
+        assert_eq!(
+            as_insert_or_replace("INSERT INTO t (rowid, a) VALUES (1, 2)"),
+            "INSERT OR REPLACE INTO t (rowid, a) VALUES (1, 2)"
+        );
+        assert_eq!(
+            as_insert_or_replace("  insert or ignore into t values (1)"),
+            "  INSERT OR REPLACE INTO t values (1)"
+        );
+        assert_eq!(as_insert_or_replace("UPDATE t SET a = 1"), "UPDATE t SET a = 1");
+    }
+
+    #[test]
+    fn apply_max_distance_filters_on_distance_column() {
+        let sql = apply_max_distance(
+            "SELECT rowid, distance FROM vt".into(),
+            Some(0.25),
+            "distance",
+        );
+        assert_eq!(
+            sql,
+            "SELECT * FROM (SELECT rowid, distance FROM vt) WHERE \"distance\" <= 0.25"
+        );
+        assert_eq!(
+            apply_max_distance("SELECT 1".into(), None, "distance"),
+            "SELECT 1"
+        );
+    }
+
+    #[test]
+    fn vectorlite_distance_type_reads_column_definition() {
+        let sql = "create virtual table vt_vector_docs using vectorlite(vector_embedding float32[3] cosine, hnsw(max_elements=10))";
+        assert_eq!(vectorlite_distance_type(sql), "cosine");
+        assert_eq!(
+            vectorlite_distance_type("CREATE VIRTUAL TABLE t USING vectorlite(v FLOAT32[2] IP, hnsw(max_elements=1))"),
+            "ip"
+        );
+        assert_eq!(
+            vectorlite_distance_type("create virtual table t using vectorlite(v float32[2], hnsw(max_elements=1))"),
+            "l2"
+        );
+    }
+
+    #[test]
+    fn vectorlite_dimension_reads_column_definition() {
+        let sql = "create virtual table vt_vector_docs using vectorlite(vector_embedding float32[384] cosine, hnsw(max_elements=10))";
+        assert_eq!(vectorlite_dimension(sql), Some(384));
+        assert_eq!(vectorlite_dimension("create table docs (rowid integer primary key)"), None);
+    }
+
+    #[test]
+    fn vectorlite_hnsw_m_reads_hnsw_arguments() {
+        let sql = "create virtual table t using vectorlite(v float32[3] l2, hnsw(max_elements=10, M=32))";
+        assert_eq!(vectorlite_hnsw_m(sql), 32);
+        assert_eq!(
+            vectorlite_hnsw_m("create virtual table t using vectorlite(v float32[3] l2, hnsw(max_elements=10))"),
+            16
+        );
+    }
+
+    #[test]
+    fn estimate_hnsw_element_bytes_grows_with_dimension_and_m() {
+        assert_eq!(estimate_hnsw_element_bytes(1, 16) - estimate_hnsw_element_bytes(0, 16), 4.0);
+        assert!(estimate_hnsw_element_bytes(128, 16) > estimate_hnsw_element_bytes(64, 16));
+        assert!(estimate_hnsw_element_bytes(64, 32) > estimate_hnsw_element_bytes(64, 16));
+    }
+}
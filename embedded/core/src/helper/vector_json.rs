@@ -0,0 +1,150 @@
+use crate::error::VecXError;
+
+/// Serialize a vector into the JSON array format accepted by `vector_from_json`.
+///
+/// Each element is written with Rust's shortest round-trip representation, so
+/// parsing the output back as `f32` yields bit-identical values. Non-finite values
+/// (`NaN`, `inf`) have no JSON representation and are rejected.
+pub fn vector_to_json(vector: &[f32]) -> Result<String, VecXError> {
+    format_vector_json(vector, |value| value.to_string())
+}
+
+/// Serialize a vector like `vector_to_json`, rounding each element to `digits`
+/// fractional digits and dropping trailing zeros.
+///
+/// The output is lossy and only meant for readable logs and explain output.
+pub fn vector_to_json_rounded(vector: &[f32], digits: usize) -> Result<String, VecXError> {
+    format_vector_json(vector, |value| {
+        let rounded = format!("{:.*}", digits, value);
+        if rounded.contains('.') {
+            rounded.trim_end_matches('0').trim_end_matches('.').to_string()
+        } else {
+            rounded
+        }
+    })
+}
+
+/// Parse a JSON array of numbers, as written by vectorlite's `vector_to_json`, into a
+/// vector.
+pub fn vector_from_json(json: &str) -> Result<Vec<f32>, VecXError> {
+    let elements = json
+        .trim()
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .ok_or_else(|| VecXError::DataParsingError(format!("'{}' is not a JSON array", json)))?;
+
+    if elements.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    elements
+        .split(',')
+        .map(|element| {
+            element.trim().parse::<f32>().map_err(|e| {
+                VecXError::DataParsingError(format!("invalid vector element '{}': {}", element, e))
+            })
+        })
+        .collect()
+}
+
+fn format_vector_json(
+    vector: &[f32],
+    format_value: impl Fn(f32) -> String,
+) -> Result<String, VecXError> {
+    let mut json = String::with_capacity(vector.len() * 12 + 2);
+    json.push('[');
+
+    for (i, value) in vector.iter().enumerate() {
+        if !value.is_finite() {
+            return Err(VecXError::InvalidQueryError(format!(
+                "vector contains non-finite value {} at index {}",
+                value, i
+            )));
+        }
+        if i > 0 {
+            json.push(',');
+        }
+        json.push_str(&format_value(*value));
+    }
+
+    json.push(']');
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> Vec<f32> {
+        json.trim_start_matches('[')
+            .trim_end_matches(']')
+            .split(',')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse::<f32>().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn round_trips_high_precision_values() {
+        let vector = vec![
+            0.1,
+            -0.333_333_34,
+            std::f32::consts::PI,
+            1.000_000_1,
+            f32::MIN_POSITIVE,
+            f32::MAX,
+            -f32::MAX,
+            1e-38,
+            -0.0,
+        ];
+
+        let json = vector_to_json(&vector).unwrap();
+        let parsed = parse(&json);
+
+        assert_eq!(parsed.len(), vector.len());
+        for (original, parsed) in vector.iter().zip(parsed.iter()) {
+            assert_eq!(original.to_bits(), parsed.to_bits(), "json: {}", json);
+        }
+    }
+
+    #[test]
+    fn serializes_as_json_array() {
+        assert_eq!(vector_to_json(&[1.0, 2.5, -3.0]).unwrap(), "[1,2.5,-3]");
+        assert_eq!(vector_to_json(&[]).unwrap(), "[]");
+    }
+
+    #[test]
+    fn rounds_to_requested_digits() {
+        let vector = [0.123_456_79, 2.5, -3.0, 1.0 / 3.0];
+
+        assert_eq!(
+            vector_to_json_rounded(&vector, 3).unwrap(),
+            "[0.123,2.5,-3,0.333]"
+        );
+        assert_eq!(vector_to_json_rounded(&vector, 0).unwrap(), "[0,2,-3,0]");
+        assert!(vector_to_json_rounded(&[f32::NAN], 2).is_err());
+    }
+
+    #[test]
+    fn parses_json_arrays() {
+        assert_eq!(
+            vector_from_json("[1.0,0.10000000149011612, -3]").unwrap(),
+            vec![1.0, 0.1, -3.0]
+        );
+        assert_eq!(vector_from_json(" [] ").unwrap(), Vec::<f32>::new());
+        assert!(matches!(
+            vector_from_json("1,2"),
+            Err(VecXError::DataParsingError(_))
+        ));
+        assert!(vector_from_json("[1,x]").is_err());
+    }
+
+    #[test]
+    fn rejects_non_finite_values() {
+        assert!(matches!(
+            vector_to_json(&[1.0, f32::NAN]),
+            Err(VecXError::InvalidQueryError(_))
+        ));
+        assert!(vector_to_json(&[f32::INFINITY]).is_err());
+        assert!(vector_to_json(&[f32::NEG_INFINITY, 0.0]).is_err());
+    }
+}
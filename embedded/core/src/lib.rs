@@ -3,10 +3,18 @@ mod helper;
 mod planner;
 pub mod types;
 mod vector_xlite;
+mod vector_xlite_builder;
+mod vector_xlite_registry;
 mod constant;
 pub mod error;
 pub mod customizer;
 pub mod snapshot;
 
 pub use vector_xlite::*;
+pub use vector_xlite_builder::*;
+pub use vector_xlite_registry::*;
+#[cfg(feature = "arrow")]
+pub use arrow_array;
+#[cfg(feature = "arrow")]
+pub use arrow_schema;
 // pub use customizer::*;
@@ -1,7 +1,11 @@
 use crate::{
     error::VecXError,
-    types::{CollectionConfig, DeleteCollection, DeletePoint, InsertPoint, QueryPlan, SearchPoint},
+    types::{
+        BatchDelete, CollectionConfig, DeleteCollection, DeletePoint, DistanceFunction,
+        IdempotencyKeyPlan, InsertPoint, OnConflict, QueryPlan, SearchPlan, SearchPoint,
+    },
 };
+use std::path::Path;
 
 pub(crate) trait QueryPlanner: Send + Sync {
     fn plan_create_collection(
@@ -9,8 +13,92 @@ pub(crate) trait QueryPlanner: Send + Sync {
         collection_config: CollectionConfig,
     ) -> Result<Vec<QueryPlan>, VecXError>;
     fn plan_insert_query(&self, create_point: InsertPoint) -> Result<Vec<QueryPlan>, VecXError>;
+    fn plan_idempotency_key_query(
+        &self,
+        collection_name: &str,
+        key: &str,
+    ) -> Result<IdempotencyKeyPlan, VecXError>;
+    fn plan_upsert_query(&self, upsert_point: InsertPoint) -> Result<Vec<QueryPlan>, VecXError>;
     fn plan_delete_query(&self, delete_point: DeletePoint) -> Result<Vec<QueryPlan>, VecXError>;
+    fn plan_batch_delete_query(
+        &self,
+        batch_delete: BatchDelete,
+    ) -> Result<Vec<Vec<QueryPlan>>, VecXError>;
     fn plan_delete_collection_query(&self, delete_collection: DeleteCollection) -> Result<Vec<QueryPlan>, VecXError>;
+    fn plan_rename_collection_query(
+        &self,
+        old_name: &str,
+        new_name: &str,
+    ) -> Result<Vec<QueryPlan>, VecXError>;
     fn plan_search_query(&self, search_point: SearchPoint) -> Result<QueryPlan, VecXError>;
+    fn plan_search_query_with_kind(
+        &self,
+        search_point: SearchPoint,
+    ) -> Result<SearchPlan, VecXError>;
+    fn plan_explain_search_query(&self, search_point: SearchPoint) -> Result<String, VecXError>;
+    fn plan_explain_query_plan_query(
+        &self,
+        search_point: &SearchPoint,
+    ) -> Result<QueryPlan, VecXError>;
+    #[cfg(feature = "recall")]
+    fn plan_exact_search_query(
+        &self,
+        collection_name: &str,
+        vector: &[f32],
+        top_k: i64,
+    ) -> Result<QueryPlan, VecXError>;
+    fn plan_stored_vector_query(
+        &self,
+        collection_name: &str,
+        id: i64,
+    ) -> Result<QueryPlan, VecXError>;
+    fn plan_rerank_query(
+        &self,
+        collection_name: &str,
+        candidate_ids: &[i64],
+        query: &[f32],
+        metric: DistanceFunction,
+    ) -> Result<QueryPlan, VecXError>;
+    fn plan_candidate_count_query(
+        &self,
+        search_point: &SearchPoint,
+    ) -> Result<Option<QueryPlan>, VecXError>;
     fn plan_collection_exists_query(&self, collection_name: &str) -> Result<QueryPlan, VecXError>;
+    fn plan_count_query(
+        &self,
+        collection_name: &str,
+        predicate_sql: &str,
+    ) -> Result<QueryPlan, VecXError>;
+    fn plan_has_any_query(&self, collection_name: &str) -> Result<QueryPlan, VecXError>;
+    fn plan_estimate_memory_query(&self, collection_name: &str) -> Result<QueryPlan, VecXError>;
+    fn plan_update_where_query(
+        &self,
+        collection_name: &str,
+        set_sql: &str,
+        predicate_sql: &str,
+    ) -> Result<QueryPlan, VecXError>;
+    fn plan_create_payload_index_query(
+        &self,
+        collection_name: &str,
+        columns: &[&str],
+        on_conflict: OnConflict,
+    ) -> Result<QueryPlan, VecXError>;
+    fn plan_drop_payload_index_query(
+        &self,
+        collection_name: &str,
+        columns: &[&str],
+    ) -> Result<QueryPlan, VecXError>;
+    fn plan_list_payload_indexes_query(&self, collection_name: &str)
+    -> Result<QueryPlan, VecXError>;
+    fn plan_flush_query(&self) -> Result<QueryPlan, VecXError>;
+    fn plan_persist_query(
+        &self,
+        db_path: &Path,
+        idx_dir: &Path,
+    ) -> Result<Vec<QueryPlan>, VecXError>;
+    fn plan_version_info_query(&self) -> Result<QueryPlan, VecXError>;
+    fn plan_checkpoint_query(&self) -> Result<QueryPlan, VecXError>;
+    fn plan_vacuum_query(&self) -> Result<QueryPlan, VecXError>;
+    fn plan_compact_payload_query(&self, collection_name: &str)
+    -> Result<Vec<QueryPlan>, VecXError>;
 }
@@ -1,126 +1,682 @@
+use crate::constant::{
+    DEFAULT_IDEMPOTENCY_KEY_TTL_SECS, DEFAULT_MAX_ALLOWED_DIMENSION, DISTANCE_COLLISION_ALIAS,
+    COLLECTION_OPTIONS_TABLE, COMPACT_TABLE_PREFIX, FLUSH_MARKER_TABLE, IDEMPOTENCY_KEY_TABLE, PAYLOAD_INDEX_PREFIX,
+    PERSIST_ATTACH_ALIAS, STRICT_IP_MAX_NORM_RATIO, VECTOR_TABLE_PREFIX,
+};
 use crate::error::VecXError;
 use crate::helper::*;
 use crate::planner::query_planner::QueryPlanner;
 use crate::types::{
-    CollectionConfig, DeleteCollection, DeletePoint, InsertPoint, QueryPlan, SearchPoint,
+    BatchDelete, CollectionConfig, DeleteCollection, DeletePoint, DistanceFunction, FilterStrategy,
+    IdempotencyKeyPlan, InsertPoint, OnConflict, PlanKind, QueryPlan, RowidStrategy, SearchPlan,
+    SearchPoint, VectorXLiteConfig,
 };
-use r2d2::Pool;
-use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::limits::Limit;
+use rusqlite::{OptionalExtension, ToSql};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 pub(crate) struct SqliteQueryPlanner {
-    conn_pool: Pool<SqliteConnectionManager>,
+    connections: ConnectionSource,
+    config: VectorXLiteConfig,
 }
 
-impl SqliteQueryPlanner {
-    pub fn new(pool: Pool<SqliteConnectionManager>) -> Box<dyn QueryPlanner> {
-        Box::new(SqliteQueryPlanner { conn_pool: pool })
+/// Options a collection was created with, kept in one row of the collection options
+/// table so a planned operation reads them all at once.
+#[derive(Default)]
+struct CollectionOptions {
+    payload_only: bool,
+    no_payload: bool,
+    rowid_strategy: RowidStrategy,
+    default_payload_search: Option<String>,
+    /// Sum and count of the vector norms inserted so far, for `strict_ip` collections.
+    strict_ip_norms: Option<(f64, i64)>,
+}
+
+impl CollectionOptions {
+    /// Fails for collections created with `no_payload`, naming the operation that needs
+    /// the payload table.
+    fn check_has_payload(&self, collection_name: &str, operation: &str) -> Result<(), VecXError> {
+        if self.no_payload {
+            return Err(VecXError::InvalidQueryError(format!(
+                "collection '{}' has no payload table, which {} needs",
+                collection_name, operation
+            )));
+        }
+        Ok(())
     }
 }
 
-impl QueryPlanner for SqliteQueryPlanner {
-    fn plan_create_collection(
+impl SqliteQueryPlanner {
+    pub fn new(connections: ConnectionSource, config: VectorXLiteConfig) -> Box<dyn QueryPlanner> {
+        Box::new(SqliteQueryPlanner {
+            connections,
+            config,
+        })
+    }
+
+    fn payload_table_name(&self, collection_name: &str) -> String {
+        get_payload_table_name(collection_name, self.config.prefix_payload_tables)
+    }
+
+    /// Returns the collection's payload table name, failing unless it names a real
+    /// table rather than a virtual one.
+    fn existing_payload_table_name(&self, collection_name: &str) -> Result<String, VecXError> {
+        let payload_table_name = self.payload_table_name(collection_name);
+        let table_sql: Option<String> = self.connections.get()?
+            .query_row(
+                "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?1 COLLATE NOCASE",
+                [&payload_table_name],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        match table_sql {
+            Some(sql) if !sql.trim_start().to_lowercase().starts_with("create virtual") => {
+                Ok(payload_table_name)
+            }
+            _ => Err(VecXError::InvalidQueryError(format!(
+                "collection '{}' has no payload table",
+                collection_name
+            ))),
+        }
+    }
+
+    /// Validates the columns of a payload index and returns the payload table and the
+    /// index name.
+    fn payload_index_target(
         &self,
-        collection_config: CollectionConfig,
-    ) -> Result<Vec<QueryPlan>, VecXError> {
-        let mut query_plans: Vec<QueryPlan> = Vec::new();
+        collection_name: &str,
+        columns: &[&str],
+    ) -> Result<(String, String), VecXError> {
+        if columns.is_empty() {
+            return Err(VecXError::InvalidQueryError(
+                "a payload index needs at least one column".to_string(),
+            ));
+        }
+        if let Some(column) = columns.iter().find(|column| !is_plain_column_name(column)) {
+            return Err(VecXError::InvalidQueryError(format!(
+                "payload index column '{}' must be a plain column name",
+                column
+            )));
+        }
 
-        if collection_config.payload_table_schema.is_some() {
-            query_plans.push(QueryPlan {
-                sql: collection_config.payload_table_schema.unwrap(),
-                params: vec![],
-                post_process: None,
-            });
+        let payload_table_name = self.existing_payload_table_name(collection_name)?;
+        let index_name = get_payload_index_name(&payload_table_name, columns);
+        Ok((payload_table_name, index_name))
+    }
+
+    /// Picks the result column that carries the KNN distance: the search's
+    /// `distance_alias`, else `_vx_distance` when the payload query already returns a
+    /// `distance` column, else `distance`.
+    fn distance_column(&self, search_point: &SearchPoint) -> Result<String, VecXError> {
+        if let Some(alias) = &search_point.distance_alias {
+            return Ok(alias.clone());
         }
 
-        let virtual_table_name = get_vector_table_name(collection_config.collection_name.as_str());
+        let Some(payload_query) = &search_point.payload_search_query else {
+            return Ok("distance".to_string());
+        };
+        let conn = self.connections.get()?;
+        let payload_has_distance = conn
+            .prepare(payload_query)?
+            .column_names()
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case("distance"));
 
-        let mut virtual_table_query = format!(
-            "create virtual table {table_name} using vectorlite(vector_embedding float32[{vector_dimension}] {distance_func}, hnsw(max_elements={max_elements}))",
-            table_name = virtual_table_name,
-            vector_dimension = collection_config.dimension,
-            distance_func = collection_config.distance.as_str(),
-            max_elements = collection_config.max_elements
-        );
+        Ok(if payload_has_distance {
+            DISTANCE_COLLISION_ALIAS.to_string()
+        } else {
+            "distance".to_string()
+        })
+    }
 
-        if let Some(index_path) = collection_config.index_file_path {
-            virtual_table_query = format!(
-                "{} , {})",
-                &virtual_table_query[0..virtual_table_query.len() - 1],
-                index_path
-            );
+    /// Fails with `InvalidQueryError` when the payload query nests expressions deeper
+    /// than the search's `max_sql_complexity`. The query is only prepared, under a
+    /// lowered `SQLITE_LIMIT_EXPR_DEPTH` that is restored afterwards.
+    fn check_sql_complexity(&self, search_point: &SearchPoint) -> Result<(), VecXError> {
+        let (Some(max_depth), Some(payload_query)) = (
+            search_point.max_sql_complexity,
+            &search_point.payload_search_query,
+        ) else {
+            return Ok(());
+        };
+
+        let conn = self.connections.get()?;
+        let previous_depth = conn.set_limit(Limit::SQLITE_LIMIT_EXPR_DEPTH, max_depth)?;
+        let prepared = conn.prepare(payload_query).map(|_| ());
+        conn.set_limit(Limit::SQLITE_LIMIT_EXPR_DEPTH, previous_depth)?;
+
+        match prepared {
+            Err(err) if err.to_string().contains("Expression tree is too large") => {
+                Err(VecXError::InvalidQueryError(format!(
+                    "payload query exceeds max_sql_complexity {}: {}",
+                    max_depth, err
+                )))
+            }
+            result => Ok(result?),
         }
+    }
 
-        query_plans.push(QueryPlan {
-            sql: virtual_table_query,
-            params: vec![],
-            post_process: None,
-        });
+    /// Checks that a payload insert query writes to the collection's payload table and
+    /// no other table.
+    fn check_payload_insert_target(
+        &self,
+        collection_name: &str,
+        payload_insert_query: &str,
+    ) -> Result<(), VecXError> {
+        let payload_table_name = self.payload_table_name(collection_name);
 
-        Ok(query_plans)
+        match insert_target_table(payload_insert_query) {
+            Some(target) if target.eq_ignore_ascii_case(&payload_table_name) => Ok(()),
+            Some(target) => Err(VecXError::InvalidQueryError(format!(
+                "payload_insert_query of collection '{}' must insert into '{}', not '{}'",
+                collection_name, payload_table_name, target
+            ))),
+            None => Err(VecXError::InvalidQueryError(format!(
+                "payload_insert_query of collection '{}' must be an INSERT into '{}'",
+                collection_name, payload_table_name
+            ))),
+        }
     }
 
-    fn plan_insert_query(&self, create_point: InsertPoint) -> Result<Vec<QueryPlan>, VecXError> {
-        let mut query_plans: Vec<QueryPlan> = Vec::new();
+    /// Reads the options a collection was created with, or None when it was created
+    /// without any. The options table only exists once a collection used one.
+    fn collection_options(
+        &self,
+        collection_name: &str,
+    ) -> Result<Option<CollectionOptions>, VecXError> {
+        let conn = self.connections.get()?;
+        let has_options_table: bool = conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
+            [COLLECTION_OPTIONS_TABLE],
+            |row| row.get(0),
+        )?;
+        if !has_options_table {
+            return Ok(None);
+        }
 
-        let mut payload_insert_query = create_point.payload_insert_query;
-        if payload_insert_query.is_none() {
-            payload_insert_query = Some(
-                generate_insert_with_defaults(
-                    self.conn_pool.clone(),
-                    create_point.collection_name.as_str(),
-                )
-                .unwrap(),
-            );
+        let options = conn
+            .query_row(
+                &format!(
+                    "SELECT payload_only, no_payload, rowid_strategy, rowid_offset, default_payload_search, strict_ip, norm_sum, norm_count FROM {} WHERE collection_name = ?1",
+                    COLLECTION_OPTIONS_TABLE
+                ),
+                [canonical_collection_name(collection_name)],
+                |row| {
+                    let rowid_strategy = match row.get::<_, Option<String>>(2)?.as_deref() {
+                        Some("offset") => RowidStrategy::Offset(row.get(3)?),
+                        Some("hash") => RowidStrategy::Hash,
+                        _ => RowidStrategy::Direct,
+                    };
+                    Ok(CollectionOptions {
+                        payload_only: row.get(0)?,
+                        no_payload: row.get(1)?,
+                        rowid_strategy,
+                        default_payload_search: row.get(4)?,
+                        strict_ip_norms: match row.get(5)? {
+                            true => Some((row.get(6)?, row.get(7)?)),
+                            false => None,
+                        },
+                    })
+                },
+            )
+            .optional()?;
+        Ok(options)
+    }
+
+    /// Fails for collections created with `no_payload`, naming the operation that needs
+    /// the payload table.
+    fn check_has_payload(&self, collection_name: &str, operation: &str) -> Result<(), VecXError> {
+        self.collection_options(collection_name)?
+            .unwrap_or_default()
+            .check_has_payload(collection_name, operation)
+    }
+
+    /// Maps the search's `restrict_to_ids` to the rowids of the collection's points.
+    fn restricted_rowids(
+        &self,
+        search_point: &SearchPoint,
+        strategy: RowidStrategy,
+    ) -> Result<Option<Vec<i64>>, VecXError> {
+        let Some(ids) = &search_point.restrict_to_ids else {
+            return Ok(None);
+        };
+        if strategy == RowidStrategy::Direct {
+            return Ok(Some(ids.clone()));
         }
 
-        query_plans.push(QueryPlan {
-            sql: inject_rowid(
-                payload_insert_query.as_ref().unwrap(),
-                create_point.id.unwrap(),
+        ids.iter()
+            .map(|&id| {
+                let id = u64::try_from(id).map_err(|_| {
+                    VecXError::InvalidQueryError(format!(
+                        "restrict_to_ids contains negative id {}",
+                        id
+                    ))
+                })?;
+                Ok(strategy.rowid(id)? as i64)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some)
+    }
+
+    /// Checks the norm of a vector inserted into a `strict_ip` collection against the
+    /// mean norm of its earlier inserts, and plans adding the norm to that mean.
+    fn plan_strict_ip_norm(
+        &self,
+        create_point: &InsertPoint,
+        options: &CollectionOptions,
+    ) -> Result<Option<QueryPlan>, VecXError> {
+        let Some((norm_sum, norm_count)) = options.strict_ip_norms else {
+            return Ok(None);
+        };
+
+        let squared_norm: f64 = match (&create_point.vector_bytes, &create_point.vector_json) {
+            (Some(vector_bytes), _) => vector_bytes
+                .chunks_exact(4)
+                .map(|chunk| f32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) as f64)
+                .map(|value| value * value)
+                .sum(),
+            (None, Some(json)) => vector_from_json(json)?
+                .iter()
+                .map(|&value| value as f64 * value as f64)
+                .sum(),
+            (None, None) => create_point
+                .vector
+                .iter()
+                .map(|&value| value as f64 * value as f64)
+                .sum(),
+        };
+        let norm = squared_norm.sqrt();
+
+        let mean_norm = if norm_count > 0 {
+            norm_sum / norm_count as f64
+        } else {
+            0.0
+        };
+        if mean_norm > 0.0
+            && !(mean_norm / STRICT_IP_MAX_NORM_RATIO..=mean_norm * STRICT_IP_MAX_NORM_RATIO)
+                .contains(&norm)
+        {
+            return Err(VecXError::InvalidQueryError(format!(
+                "vector norm {:.4} deviates more than {}x from the mean norm {:.4} of strict_ip collection '{}'; normalize vectors before inserting them",
+                norm, STRICT_IP_MAX_NORM_RATIO, mean_norm, create_point.collection_name
+            )));
+        }
+
+        Ok(Some(QueryPlan {
+            sql: format!(
+                "UPDATE {} SET norm_sum = norm_sum + ?1, norm_count = norm_count + 1 WHERE collection_name = ?2",
+                COLLECTION_OPTIONS_TABLE
             ),
-            params: vec![],
+            params: vec![
+                Box::new(norm),
+                Box::new(canonical_collection_name(&create_point.collection_name)),
+            ],
             post_process: None,
-        });
+        }))
+    }
+
+    /// Plans a search of a payload-only collection, which runs the payload query (or a
+    /// scan of the payload table) without a KNN step and returns up to `top_k` rows in
+    /// rowid order, or by `order_by` when set.
+    fn plan_filter_only_search_query(
+        &self,
+        search_point: SearchPoint,
+    ) -> Result<QueryPlan, VecXError> {
+        let collection_name = &search_point.collection_name;
+        if !search_point.vector.is_empty() {
+            return Err(VecXError::InvalidQueryError(format!(
+                "collection '{}' is payload-only and does not support vector search; search it with an empty vector to run the payload query alone",
+                collection_name
+            )));
+        }
+
+        let unsupported = [
+            ("dedup_by", search_point.dedup_by.is_some()),
+            ("group_by", search_point.group_by.is_some()),
+            ("aggregate", search_point.aggregate.is_some()),
+            ("min_similarity", search_point.min_similarity.is_some()),
+            ("rerank_with", search_point.rerank_with.is_some()),
+            ("include_vector", search_point.include_vector),
+        ];
+        if let Some((option, _)) = unsupported.iter().find(|(_, is_set)| *is_set) {
+            return Err(VecXError::InvalidQueryError(format!(
+                "{} is not supported when searching payload-only collection '{}'",
+                option, collection_name
+            )));
+        }
+
+        let payload_query = search_point
+            .payload_search_query
+            .clone()
+            .unwrap_or_else(|| {
+                format!("SELECT * FROM {}", self.payload_table_name(collection_name))
+            });
+        let id_filter = search_point
+            .restrict_to_ids
+            .as_deref()
+            .map(|ids| format!(" WHERE rowid IN ({})", join_ids(ids)))
+            .unwrap_or_default();
+        let ordering = match &search_point.order_by {
+            Some((column, direction)) => format!("\"{}\" {}, rowid", column, direction.as_str()),
+            None => "rowid".to_string(),
+        };
+
+        Ok(QueryPlan {
+            sql: format!(
+                "SELECT * FROM ({}){} ORDER BY {} LIMIT ?1",
+                payload_query, id_filter, ordering
+            ),
+            params: vec![Box::new(search_point.top_k)],
+            post_process: Some(Box::new(parse_row_to_map)),
+        })
+    }
+
+    /// Reads the `CREATE VIRTUAL TABLE` statement of a collection's vector table.
+    fn virtual_table_sql(&self, collection_name: &str) -> Result<String, VecXError> {
+        self.connections
+            .get()?
+            .query_row(
+                "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?1 COLLATE NOCASE",
+                [get_vector_table_name(collection_name)],
+                |row| row.get(0),
+            )
+            .optional()?
+            .ok_or_else(|| {
+                VecXError::InvalidQueryError(format!(
+                    "collection '{}' does not exist",
+                    collection_name
+                ))
+            })
+    }
+
+    /// Returns the `max_elements` a collection's index was created with, or None when
+    /// the collection has no vector table.
+    fn max_elements(&self, collection_name: &str) -> Result<Option<i64>, VecXError> {
+        let virtual_table_sql: Option<String> = self
+            .connections
+            .get()?
+            .query_row(
+                "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?1 COLLATE NOCASE",
+                [get_vector_table_name(collection_name)],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(virtual_table_sql.as_deref().and_then(vectorlite_max_elements))
+    }
+
+    /// Returns a subquery yielding the exact `rowid, distance` to `?1` of every vector of
+    /// the collection when it holds fewer points than `exact_below`, or None when the
+    /// search should go through the HNSW index.
+    fn exact_scan_source(
+        &self,
+        collection_name: &str,
+        options: &CollectionOptions,
+    ) -> Result<Option<String>, VecXError> {
+        let Some(exact_below) = self.config.exact_below else {
+            return Ok(None);
+        };
+        // Without a payload table there is no way to list the rowids to scan
+        if options.no_payload {
+            return Ok(None);
+        }
+
+        let payload_table_name = self.payload_table_name(collection_name);
+        let points: i64 = self.connections.get()?.query_row(
+            &format!(
+                "SELECT count(*) FROM (SELECT 1 FROM {} LIMIT ?1)",
+                payload_table_name
+            ),
+            [exact_below.min(i64::MAX as u64) as i64],
+            |row| row.get(0),
+        )?;
+        if points as u64 >= exact_below {
+            return Ok(None);
+        }
+
+        // vectorlite cannot scan a virtual table, so every rowid is listed explicitly
+        // through the payload table. `LIMIT -1` keeps SQLite from pushing the search's
+        // own rowid filters down into a second rowid constraint, which vectorlite rejects.
+        let virtual_table_sql = self.virtual_table_sql(collection_name)?;
+        Ok(Some(format!(
+            "(SELECT rowid, vector_distance(vector_embedding, vector_from_json(?1), '{distance_type}') AS distance \
+             FROM {vt_table_name} WHERE rowid IN (SELECT rowid FROM {payload_table_name}) LIMIT -1)",
+            distance_type = vectorlite_distance_type(&virtual_table_sql),
+            vt_table_name = get_vector_table_name(collection_name),
+            payload_table_name = payload_table_name,
+        )))
+    }
+
+    /// Converts the search's `min_similarity` into the cosine distance results must
+    /// not exceed. Only cosine collections accept a similarity threshold.
+    fn max_distance(&self, search_point: &SearchPoint) -> Result<Option<f32>, VecXError> {
+        let Some(similarity) = search_point.min_similarity else {
+            return Ok(None);
+        };
+
+        let distance_type =
+            vectorlite_distance_type(&self.virtual_table_sql(&search_point.collection_name)?);
+        if distance_type != "cosine" {
+            return Err(VecXError::InvalidQueryError(format!(
+                "min_similarity requires a cosine collection, but '{}' uses {} distance",
+                search_point.collection_name, distance_type
+            )));
+        }
+        Ok(Some(1.0 - similarity))
+    }
+
+    /// Checks that pre-serialized vector bytes hold exactly one `f32` per dimension of
+    /// the collection.
+    fn check_vector_bytes_len(&self, collection_name: &str, len: usize) -> Result<(), VecXError> {
+        let dimension = self.vector_dimension(collection_name)?;
+
+        if len != 4 * dimension {
+            return Err(VecXError::InvalidQueryError(format!(
+                "vector_bytes has {} bytes but collection '{}' expects {} (4 * {} dimensions)",
+                len,
+                collection_name,
+                4 * dimension,
+                dimension
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks that a pre-serialized JSON vector has one element per dimension of the
+    /// collection, using the length the builder counted while validating it when given.
+    fn check_vector_json_len(
+        &self,
+        collection_name: &str,
+        json: &str,
+        parsed_len: Option<usize>,
+    ) -> Result<(), VecXError> {
+        let dimension = self.vector_dimension(collection_name)?;
+        let len = match parsed_len {
+            Some(len) => len,
+            None => vector_from_json(json)?.len(),
+        };
+
+        if len != dimension {
+            return Err(VecXError::InvalidQueryError(format!(
+                "vector_json has {} elements but collection '{}' expects {}",
+                len, collection_name, dimension
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks that a vector has one element per dimension of the collection before
+    /// vectorlite sees it. Collections without a vector table are left to fail when the
+    /// plan runs.
+    fn check_vector_len(&self, collection_name: &str, len: usize) -> Result<(), VecXError> {
+        match self.stored_vector_dimension(collection_name)? {
+            Some(dimension) if dimension != len => Err(VecXError::InvalidQueryError(format!(
+                "vector for collection '{}' has the wrong dimension: expected dim {}, got {}",
+                collection_name, dimension, len
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Dimension of a collection's vectors, read from its virtual table definition.
+    fn vector_dimension(&self, collection_name: &str) -> Result<usize, VecXError> {
+        self.stored_vector_dimension(collection_name)?
+            .ok_or_else(|| {
+                VecXError::InvalidQueryError(format!(
+                    "collection '{}' does not exist",
+                    collection_name
+                ))
+            })
+    }
 
-        let vector_json = format!("{:?}", create_point.vector);
+    /// Dimension of a collection's vectors, or None when it has no vector table.
+    fn stored_vector_dimension(&self, collection_name: &str) -> Result<Option<usize>, VecXError> {
+        let virtual_table_sql: Option<String> =
+            self.connections.get()?
+                .query_row(
+                    "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?1 COLLATE NOCASE",
+                    [get_vector_table_name(collection_name)],
+                    |row| row.get(0),
+                )
+                .optional()?;
+        Ok(virtual_table_sql.as_deref().and_then(vectorlite_dimension))
+    }
+
+    /// Plans an insert into a collection with the given options, read once by the caller.
+    fn plan_insert_with_options(
+        &self,
+        mut create_point: InsertPoint,
+        options: &CollectionOptions,
+    ) -> Result<Vec<QueryPlan>, VecXError> {
+        create_point.id = create_point
+            .id
+            .map(|id| options.rowid_strategy.rowid(id))
+            .transpose()?;
+
+        let payload_only = options.payload_only;
+        let has_serialized_vector =
+            create_point.vector_bytes.is_some() || create_point.vector_json.is_some();
+        if payload_only && (!create_point.vector.is_empty() || has_serialized_vector) {
+            return Err(VecXError::InvalidQueryError(format!(
+                "collection '{}' is payload-only and does not store vectors",
+                create_point.collection_name
+            )));
+        }
+        if !payload_only && create_point.vector.is_empty() && !has_serialized_vector {
+            return Err(VecXError::InvalidQueryError(format!(
+                "cannot insert an empty vector into collection '{}'",
+                create_point.collection_name
+            )));
+        }
+        if !payload_only && !has_serialized_vector {
+            self.check_vector_len(&create_point.collection_name, create_point.vector.len())?;
+        }
+        let no_payload = options.no_payload;
+        if no_payload && create_point.payload_insert_query.is_some() {
+            return Err(VecXError::InvalidQueryError(format!(
+                "collection '{}' has no payload table and does not accept a payload_insert_query",
+                create_point.collection_name
+            )));
+        }
+        let strict_ip_plan = self.plan_strict_ip_norm(&create_point, options)?;
+
+        let mut query_plans: Vec<QueryPlan> = Vec::new();
+
+        if !no_payload {
+            let mut payload_insert_query = create_point.payload_insert_query;
+            if let Some(query) = &payload_insert_query {
+                self.check_payload_insert_target(&create_point.collection_name, query)?;
+            }
+            if payload_insert_query.is_none() {
+                let conn = self.connections.get()?;
+                payload_insert_query = Some(generate_insert_with_defaults(
+                    &conn,
+                    &self.payload_table_name(&create_point.collection_name),
+                )?);
+            }
+
+            query_plans.push(QueryPlan {
+                sql: inject_rowid(
+                    payload_insert_query.as_ref().unwrap(),
+                    create_point.id.unwrap(),
+                ),
+                params: vec![],
+                post_process: None,
+            });
+        }
+
+        if payload_only {
+            return Ok(query_plans);
+        }
 
         let virtual_table_name = get_vector_table_name(create_point.collection_name.as_str());
 
+        // vectorlite takes raw f32 bytes as is, so pre-serialized vectors skip the JSON step
+        let (vector_sql, vector_param): (&str, Box<dyn ToSql>) = match (
+            create_point.vector_bytes,
+            create_point.vector_json,
+        ) {
+            (Some(vector_bytes), _) => {
+                self.check_vector_bytes_len(&create_point.collection_name, vector_bytes.len())?;
+                ("?", Box::new(vector_bytes))
+            }
+            (None, Some(vector_json)) => {
+                self.check_vector_json_len(
+                    &create_point.collection_name,
+                    &vector_json,
+                    create_point.vector_json_len,
+                )?;
+                ("vector_from_json(?)", Box::new(vector_json))
+            }
+            (None, None) => (
+                "vector_from_json(?)",
+                Box::new(vector_to_json(&create_point.vector)?),
+            ),
+        };
+
         let insert_query = format!(
-            "insert into {}(rowid, vector_embedding) values (?, vector_from_json(?))",
-            virtual_table_name
+            "insert into {}(rowid, vector_embedding) values (?, {})",
+            virtual_table_name, vector_sql
         );
 
         query_plans.push(QueryPlan {
             sql: insert_query,
-            params: vec![Box::new(create_point.id), Box::new(vector_json.clone())],
+            params: vec![Box::new(create_point.id), vector_param],
             post_process: None,
         });
+        query_plans.extend(strict_ip_plan);
 
         Ok(query_plans)
     }
 
-    /// Plans a delete operation for removing a vector from the collection.
-    ///
-    /// This creates query plans to atomically delete:
-    /// 1. The row from the payload table
-    /// 2. The vector from the HNSW index (virtual table)
-    ///
-    /// Both operations are executed in a transaction to ensure consistency.
-    fn plan_delete_query(&self, delete_point: DeletePoint) -> Result<Vec<QueryPlan>, VecXError> {
+    /// Plans deleting a point from a collection with the given options, read once by the
+    /// caller.
+    fn plan_delete_with_options(
+        &self,
+        delete_point: DeletePoint,
+        options: &CollectionOptions,
+    ) -> Result<Vec<QueryPlan>, VecXError> {
         let mut query_plans: Vec<QueryPlan> = Vec::new();
+        let rowid = options.rowid_strategy.rowid(delete_point.id)?;
 
         // Delete from payload table
-        let payload_delete_sql = format!(
-            "DELETE FROM {} WHERE rowid = ?",
-            delete_point.collection_name
-        );
+        if !options.no_payload {
+            let payload_delete_sql = format!(
+                "DELETE FROM {} WHERE rowid = ?",
+                self.payload_table_name(&delete_point.collection_name)
+            );
 
-        query_plans.push(QueryPlan {
-            sql: payload_delete_sql,
-            params: vec![Box::new(delete_point.id)],
-            post_process: None,
-        });
+            query_plans.push(QueryPlan {
+                sql: payload_delete_sql,
+                params: vec![Box::new(rowid)],
+                post_process: None,
+            });
+        }
+
+        if options.payload_only {
+            return Ok(query_plans);
+        }
 
         // Delete from vector table (HNSW index)
         let virtual_table_name = get_vector_table_name(delete_point.collection_name.as_str());
@@ -131,35 +687,141 @@ impl QueryPlanner for SqliteQueryPlanner {
 
         query_plans.push(QueryPlan {
             sql: vector_delete_sql,
-            params: vec![Box::new(delete_point.id)],
+            params: vec![Box::new(rowid)],
             post_process: None,
         });
 
         Ok(query_plans)
     }
+}
 
-    fn plan_delete_collection_query(
+impl QueryPlanner for SqliteQueryPlanner {
+    fn plan_create_collection(
         &self,
-        delete_collection: DeleteCollection,
+        collection_config: CollectionConfig,
     ) -> Result<Vec<QueryPlan>, VecXError> {
+        collection_config.validate()?;
+
+        let max_allowed_dimension = self
+            .config
+            .max_allowed_dimension
+            .unwrap_or(DEFAULT_MAX_ALLOWED_DIMENSION);
+        if collection_config.dimension > max_allowed_dimension {
+            return Err(VecXError::InvalidQueryError(format!(
+                "collection '{}' has vector dimension {}, above the allowed maximum of {}; raise VectorXLiteConfig::max_allowed_dimension to allow it",
+                collection_config.collection_name, collection_config.dimension, max_allowed_dimension
+            )));
+        }
+
         let mut query_plans: Vec<QueryPlan> = Vec::new();
 
-        // Drop payload table
-        let payload_drop_sql = format!("DROP TABLE {}", delete_collection.collection_name);
+        if let Some(payload_table_schema) = collection_config.payload_table_schema {
+            let payload_table_name = self.payload_table_name(&collection_config.collection_name);
+            let created_table = created_table_name(&payload_table_schema).ok_or_else(|| {
+                VecXError::InvalidQueryError(format!(
+                    "payload_table_schema must be a CREATE TABLE statement for table '{}'",
+                    collection_config.collection_name
+                ))
+            })?;
 
-        query_plans.push(QueryPlan {
-            sql: payload_drop_sql,
-            params: vec![],
-            post_process: None,
-        });
+            // The schema may name the collection itself or, with a prefix configured,
+            // the prefixed payload table; anything else would leave the collection
+            // without the payload table every other query expects.
+            if !created_table.eq_ignore_ascii_case(&payload_table_name)
+                && !created_table.eq_ignore_ascii_case(&collection_config.collection_name)
+            {
+                return Err(VecXError::InvalidQueryError(format!(
+                    "payload_table_schema creates table '{}' but the collection is named '{}'; the table name must match the collection name",
+                    created_table, collection_config.collection_name
+                )));
+            }
 
-        // Drop vector table (HNSW index)
-        let virtual_table_name =
-            get_vector_table_name(delete_collection.collection_name.as_str());
-        let vector_drop_sql = format!("DROP TABLE {}", virtual_table_name);
+            let sql = if created_table == payload_table_name {
+                payload_table_schema
+            } else {
+                rename_table_in_create_sql(&payload_table_schema, &created_table, &payload_table_name)
+                    .ok_or_else(|| {
+                        VecXError::InvalidQueryError(format!(
+                            "payload_table_schema must create table '{}'",
+                            collection_config.collection_name
+                        ))
+                    })?
+            };
+
+            query_plans.push(QueryPlan {
+                sql,
+                params: vec![],
+                post_process: None,
+            });
+        }
+
+        let (rowid_strategy, rowid_offset) = match collection_config.rowid_strategy {
+            RowidStrategy::Direct => (None, 0),
+            RowidStrategy::Offset(offset) => (Some("offset"), offset),
+            RowidStrategy::Hash => (Some("hash"), 0),
+        };
+        let has_options = collection_config.payload_only
+            || collection_config.no_payload
+            || collection_config.strict_ip
+            || rowid_strategy.is_some()
+            || collection_config.default_payload_search.is_some();
+        if has_options {
+            query_plans.push(QueryPlan {
+                sql: format!(
+                    "CREATE TABLE IF NOT EXISTS {} (collection_name TEXT PRIMARY KEY, payload_only INTEGER NOT NULL DEFAULT 0, no_payload INTEGER NOT NULL DEFAULT 0, rowid_strategy TEXT, rowid_offset INTEGER NOT NULL DEFAULT 0, default_payload_search TEXT, strict_ip INTEGER NOT NULL DEFAULT 0, norm_sum REAL NOT NULL DEFAULT 0, norm_count INTEGER NOT NULL DEFAULT 0)",
+                    COLLECTION_OPTIONS_TABLE
+                ),
+                params: vec![],
+                post_process: None,
+            });
+            query_plans.push(QueryPlan {
+                sql: format!(
+                    "INSERT INTO {} (collection_name, payload_only, no_payload, rowid_strategy, rowid_offset, default_payload_search, strict_ip) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    COLLECTION_OPTIONS_TABLE
+                ),
+                params: vec![
+                    Box::new(canonical_collection_name(&collection_config.collection_name)),
+                    Box::new(collection_config.payload_only),
+                    Box::new(collection_config.no_payload),
+                    Box::new(rowid_strategy),
+                    Box::new(rowid_offset),
+                    Box::new(collection_config.default_payload_search),
+                    Box::new(collection_config.strict_ip),
+                ],
+                post_process: None,
+            });
+        }
+
+        if collection_config.payload_only {
+            return Ok(query_plans);
+        }
+
+        let virtual_table_name = get_vector_table_name(collection_config.collection_name.as_str());
+
+        let random_seed = collection_config
+            .random_seed
+            .map(|seed| format!(",random_seed={}", seed))
+            .unwrap_or_default();
+
+        let mut virtual_table_query = format!(
+            "create virtual table {table_name} using vectorlite(vector_embedding float32[{vector_dimension}] {distance_func}, hnsw(max_elements={max_elements}{random_seed}))",
+            table_name = virtual_table_name,
+            vector_dimension = collection_config.dimension,
+            distance_func = collection_config.distance.as_str(),
+            max_elements = collection_config.max_elements,
+            random_seed = random_seed
+        );
+
+        if let Some(index_path) = collection_config.index_file_path {
+            virtual_table_query = format!(
+                "{} , {})",
+                &virtual_table_query[0..virtual_table_query.len() - 1],
+                index_path
+            );
+        }
 
         query_plans.push(QueryPlan {
-            sql: vector_drop_sql,
+            sql: virtual_table_query,
             params: vec![],
             post_process: None,
         });
@@ -167,31 +829,520 @@ impl QueryPlanner for SqliteQueryPlanner {
         Ok(query_plans)
     }
 
-    fn plan_search_query(&self, search_point: SearchPoint) -> Result<QueryPlan, VecXError> {
-        let vector_json = format!("{:?}", search_point.vector);
-        let virtual_table_name = get_vector_table_name(search_point.collection_name.as_str());
+    fn plan_insert_query(&self, create_point: InsertPoint) -> Result<Vec<QueryPlan>, VecXError> {
+        let options = self
+            .collection_options(&create_point.collection_name)?
+            .unwrap_or_default();
+        self.plan_insert_with_options(create_point, &options)
+    }
 
-        // --- Case 1: No payload filter ---
-        if search_point.payload_search_query.is_none() {
-            let sql = format!(
-                "SELECT rowid, distance
-             FROM {}
-             WHERE knn_search(vector_embedding, knn_param(vector_from_json(?1), ?2))
-             ORDER BY distance",
-                virtual_table_name
-            );
+    /// Plans the bookkeeping of an insert's idempotency key. Keys live in a small table
+    /// that is created on first use and are scoped to their collection; keys older than
+    /// the configured TTL are expired before every lookup.
+    fn plan_idempotency_key_query(
+        &self,
+        collection_name: &str,
+        key: &str,
+    ) -> Result<IdempotencyKeyPlan, VecXError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| VecXError::Other(e.to_string()))?
+            .as_secs() as i64;
+        let ttl = self
+            .config
+            .idempotency_key_ttl
+            .unwrap_or(Duration::from_secs(DEFAULT_IDEMPOTENCY_KEY_TTL_SECS))
+            .as_secs() as i64;
 
-            return Ok(QueryPlan {
-                sql,
-                params: vec![Box::new(vector_json), Box::new(search_point.top_k)],
-                post_process: Some(Box::new(parse_row_to_map)),
-            });
+        Ok(IdempotencyKeyPlan {
+            prepare: vec![
+                QueryPlan {
+                    sql: format!(
+                        "CREATE TABLE IF NOT EXISTS {} (collection_name TEXT NOT NULL, key TEXT NOT NULL, \
+                         created_at INTEGER NOT NULL, PRIMARY KEY (collection_name, key))",
+                        IDEMPOTENCY_KEY_TABLE
+                    ),
+                    params: vec![],
+                    post_process: None,
+                },
+                QueryPlan {
+                    sql: format!("DELETE FROM {} WHERE created_at <= ?1", IDEMPOTENCY_KEY_TABLE),
+                    params: vec![Box::new(now.saturating_sub(ttl))],
+                    post_process: None,
+                },
+            ],
+            lookup: QueryPlan {
+                sql: format!(
+                    "SELECT 1 FROM {} WHERE collection_name = ?1 AND key = ?2",
+                    IDEMPOTENCY_KEY_TABLE
+                ),
+                params: vec![
+                    Box::new(canonical_collection_name(collection_name)),
+                    Box::new(key.to_string()),
+                ],
+                post_process: None,
+            },
+            record: QueryPlan {
+                sql: format!(
+                    "INSERT INTO {} (collection_name, key, created_at) VALUES (?1, ?2, ?3)",
+                    IDEMPOTENCY_KEY_TABLE
+                ),
+                params: vec![
+                    Box::new(canonical_collection_name(collection_name)),
+                    Box::new(key.to_string()),
+                    Box::new(now),
+                ],
+                post_process: None,
+            },
+        })
+    }
+
+    /// Plans an insert that replaces an existing point with the same id. The payload row
+    /// is written with `INSERT OR REPLACE`; vectorlite cannot replace a row in place, so
+    /// the old vector is deleted before the new one is inserted, leaving no stale entry
+    /// in the HNSW index.
+    fn plan_upsert_query(&self, upsert_point: InsertPoint) -> Result<Vec<QueryPlan>, VecXError> {
+        let virtual_table_name = get_vector_table_name(upsert_point.collection_name.as_str());
+        let options = self
+            .collection_options(&upsert_point.collection_name)?
+            .unwrap_or_default();
+        let id = upsert_point
+            .id
+            .map(|id| options.rowid_strategy.rowid(id))
+            .transpose()?;
+        let no_payload = options.no_payload;
+        let mut query_plans = self.plan_insert_with_options(upsert_point, &options)?;
+
+        if !no_payload {
+            query_plans[0].sql = as_insert_or_replace(&query_plans[0].sql);
+        }
+        if options.payload_only {
+            return Ok(query_plans);
         }
+        query_plans.insert(
+            usize::from(!no_payload),
+            QueryPlan {
+                sql: format!("DELETE FROM {} WHERE rowid = ?", virtual_table_name),
+                params: vec![Box::new(id)],
+                post_process: None,
+            },
+        );
 
-        let payload_query = search_point.payload_search_query.as_ref().unwrap();
-        let payload_selection_count = self
-            .conn_pool
-            .get()?
+        Ok(query_plans)
+    }
+
+    /// Plans a delete operation for removing a vector from the collection.
+    ///
+    /// This creates query plans to atomically delete:
+    /// 1. The row from the payload table
+    /// 2. The vector from the HNSW index (virtual table)
+    ///
+    /// Both operations are executed in a transaction to ensure consistency.
+    fn plan_delete_query(&self, delete_point: DeletePoint) -> Result<Vec<QueryPlan>, VecXError> {
+        let options = self
+            .collection_options(&delete_point.collection_name)?
+            .unwrap_or_default();
+        self.plan_delete_with_options(delete_point, &options)
+    }
+
+    /// Plans a batch delete as one group of delete plans per id.
+    ///
+    /// Each group matches the output of `plan_delete_query`, so the executor can
+    /// tell per id whether anything was removed while running all groups in a
+    /// single transaction.
+    fn plan_batch_delete_query(
+        &self,
+        batch_delete: BatchDelete,
+    ) -> Result<Vec<Vec<QueryPlan>>, VecXError> {
+        let options = self
+            .collection_options(&batch_delete.collection_name)?
+            .unwrap_or_default();
+        batch_delete
+            .ids
+            .into_iter()
+            .map(|id| {
+                self.plan_delete_with_options(
+                    DeletePoint {
+                        collection_name: batch_delete.collection_name.clone(),
+                        id,
+                    },
+                    &options,
+                )
+            })
+            .collect()
+    }
+
+    fn plan_delete_collection_query(
+        &self,
+        delete_collection: DeleteCollection,
+    ) -> Result<Vec<QueryPlan>, VecXError> {
+        let mut query_plans: Vec<QueryPlan> = Vec::new();
+
+        let options = self.collection_options(&delete_collection.collection_name)?;
+        let no_payload = options.as_ref().is_some_and(|options| options.no_payload);
+
+        // Drop payload table
+        if !no_payload {
+            let payload_drop_sql = format!(
+                "DROP TABLE {}",
+                self.payload_table_name(&delete_collection.collection_name)
+            );
+
+            query_plans.push(QueryPlan {
+                sql: payload_drop_sql,
+                params: vec![],
+                post_process: None,
+            });
+        }
+
+        let payload_only = options.as_ref().is_some_and(|options| options.payload_only);
+        if options.is_some() {
+            query_plans.push(QueryPlan {
+                sql: format!(
+                    "DELETE FROM {} WHERE collection_name = ?1",
+                    COLLECTION_OPTIONS_TABLE
+                ),
+                params: vec![Box::new(canonical_collection_name(
+                    &delete_collection.collection_name,
+                ))],
+                post_process: None,
+            });
+        }
+        if payload_only {
+            return Ok(query_plans);
+        }
+
+        // Drop vector table (HNSW index)
+        let virtual_table_name =
+            get_vector_table_name(delete_collection.collection_name.as_str());
+        let vector_drop_sql = format!("DROP TABLE {}", virtual_table_name);
+
+        query_plans.push(QueryPlan {
+            sql: vector_drop_sql,
+            params: vec![],
+            post_process: None,
+        });
+
+        Ok(query_plans)
+    }
+
+    /// Plans renaming a collection by copying it under the new name and dropping the
+    /// original. `ALTER TABLE ... RENAME` is avoided because it reloads the whole schema,
+    /// which resets the in-memory indexes of every collection in the database.
+    ///
+    /// The vector table is recreated with its index file renamed alongside, since
+    /// vectorlite keeps the index file path in the table definition. The payload table
+    /// is recreated from its original definition together with its indexes.
+    fn plan_rename_collection_query(
+        &self,
+        old_name: &str,
+        new_name: &str,
+    ) -> Result<Vec<QueryPlan>, VecXError> {
+        let options = self.collection_options(old_name)?;
+        if let Some(options) = &options {
+            options.check_has_payload(old_name, "rename_collection")?;
+        }
+        let old_virtual_table_name = get_vector_table_name(old_name);
+        let new_virtual_table_name = get_vector_table_name(new_name);
+        let old_payload_table_name = self.payload_table_name(old_name);
+        let new_payload_table_name = self.payload_table_name(new_name);
+        let payload_only = options.as_ref().is_some_and(|options| options.payload_only);
+        let conn = self.connections.get()?;
+
+        let schema_sql = |table_type: &str, table_name: &str| -> Result<Vec<String>, VecXError> {
+            let mut stmt = conn.prepare(
+                "SELECT sql FROM sqlite_master WHERE type = ?1 AND tbl_name = ?2 COLLATE NOCASE AND sql IS NOT NULL",
+            )?;
+            let sqls = stmt
+                .query_map([table_type, table_name], |row| row.get(0))?
+                .collect::<Result<Vec<String>, _>>()?;
+            Ok(sqls)
+        };
+
+        let payload_table_sql = schema_sql("table", &old_payload_table_name)?
+            .pop()
+            .ok_or_else(|| {
+                VecXError::InvalidQueryError(format!("collection '{}' does not exist", old_name))
+            })?;
+        let payload_index_sqls = schema_sql("index", &old_payload_table_name)?;
+
+        let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", old_payload_table_name))?;
+        let payload_columns = stmt
+            .query_map([], |row| row.get::<_, String>(1))?
+            .map(|column| column.map(|column| quote_identifier(&column)))
+            .collect::<Result<Vec<_>, _>>()?
+            .join(", ");
+
+        let unrecognized = |table_name: &str| {
+            VecXError::DataParsingError(format!("unrecognized definition for '{}'", table_name))
+        };
+
+        let mut sqls = Vec::new();
+        let mut vector_drop_sql = None;
+        if !payload_only {
+            let virtual_table_sql = schema_sql("table", &old_virtual_table_name)?
+                .pop()
+                .ok_or_else(|| {
+                    VecXError::InvalidQueryError(format!(
+                        "collection '{}' does not exist",
+                        old_name
+                    ))
+                })?;
+
+            let vectorlite_args = vectorlite_args_without_index_path(&virtual_table_sql)
+                .ok_or_else(|| unrecognized(&old_virtual_table_name))?;
+
+            let mut virtual_table_query = format!(
+                "CREATE VIRTUAL TABLE {} USING vectorlite({})",
+                new_virtual_table_name, vectorlite_args
+            );
+
+            if let Some(index_path) = vectorlite_index_path(&virtual_table_sql) {
+                let new_index_path = get_renamed_index_path(&index_path, old_name, new_name);
+                if new_index_path.exists() {
+                    return Err(VecXError::InvalidQueryError(format!(
+                        "index file '{}' already exists",
+                        new_index_path.display()
+                    )));
+                }
+                virtual_table_query = format!(
+                    "{}, {})",
+                    &virtual_table_query[0..virtual_table_query.len() - 1],
+                    new_index_path.display()
+                );
+            }
+
+            sqls.extend([
+                virtual_table_query,
+                format!(
+                    "INSERT INTO {new_vt}(rowid, vector_embedding) SELECT rowid, vector_embedding FROM {old_vt} WHERE rowid IN (SELECT rowid FROM {old})",
+                    new_vt = new_virtual_table_name,
+                    old_vt = old_virtual_table_name,
+                    old = old_payload_table_name
+                ),
+            ]);
+            // Dropping the old vector table also removes its index file, which a
+            // rollback cannot restore, so it runs after every other statement.
+            vector_drop_sql = Some(format!("DROP TABLE {}", old_virtual_table_name));
+        }
+
+        sqls.extend([
+            rename_table_in_create_sql(
+                &payload_table_sql,
+                &old_payload_table_name,
+                &new_payload_table_name,
+            )
+            .ok_or_else(|| unrecognized(&old_payload_table_name))?,
+            format!(
+                "INSERT INTO {new}(rowid, {columns}) SELECT rowid, {columns} FROM {old}",
+                new = new_payload_table_name,
+                old = old_payload_table_name,
+                columns = payload_columns
+            ),
+            format!("DROP TABLE {}", old_payload_table_name),
+        ]);
+
+        for index_sql in payload_index_sqls {
+            sqls.push(
+                rename_table_in_create_sql(
+                    &index_sql,
+                    &old_payload_table_name,
+                    &new_payload_table_name,
+                )
+                .ok_or_else(|| unrecognized(&old_payload_table_name))?,
+            );
+        }
+
+        let mut query_plans: Vec<QueryPlan> = sqls
+            .into_iter()
+            .map(|sql| QueryPlan {
+                sql,
+                params: vec![],
+                post_process: None,
+            })
+            .collect();
+
+        if let Some(options) = options {
+            query_plans.push(QueryPlan {
+                sql: format!(
+                    "UPDATE {} SET collection_name = ?1, default_payload_search = ?2 WHERE collection_name = ?3",
+                    COLLECTION_OPTIONS_TABLE
+                ),
+                params: vec![
+                    Box::new(canonical_collection_name(new_name)),
+                    Box::new(options.default_payload_search.map(|query| {
+                        rename_table_in_query(
+                            &query,
+                            &old_payload_table_name,
+                            &new_payload_table_name,
+                        )
+                    })),
+                    Box::new(canonical_collection_name(old_name)),
+                ],
+                post_process: None,
+            });
+        }
+
+        query_plans.extend(vector_drop_sql.map(|sql| QueryPlan {
+            sql,
+            params: vec![],
+            post_process: None,
+        }));
+
+        Ok(query_plans)
+    }
+
+    fn plan_search_query(&self, search_point: SearchPoint) -> Result<QueryPlan, VecXError> {
+        Ok(self.plan_search_query_with_kind(search_point)?.query)
+    }
+
+    /// Plans a search and reports which plan was picked. `estimated_candidates` is the
+    /// number of payload rows matching the filter that decided between metadata-first
+    /// and vector-first, and 0 when no such decision was made. `approximate` is false
+    /// when every candidate is compared instead of walking the HNSW graph.
+    fn plan_search_query_with_kind(
+        &self,
+        search_point: SearchPoint,
+    ) -> Result<SearchPlan, VecXError> {
+        let mut search_point = search_point;
+        let options = self
+            .collection_options(&search_point.collection_name)?
+            .unwrap_or_default();
+        search_point.restrict_to_ids =
+            self.restricted_rowids(&search_point, options.rowid_strategy)?;
+        if search_point.payload_search_query.is_none() {
+            search_point.payload_search_query = options.default_payload_search.clone();
+        }
+        if search_point.payload_search_query.is_none() {
+            let payload_option = [
+                ("dedup_by", search_point.dedup_by.is_some()),
+                ("group_by", search_point.group_by.is_some()),
+                ("aggregate", search_point.aggregate.is_some()),
+            ]
+            .into_iter()
+            .find_map(|(option, set)| set.then_some(option));
+            if let Some(option) = payload_option {
+                return Err(VecXError::InvalidQueryError(format!(
+                    "{} requires a payload_search_query or a default_payload_search on collection '{}'",
+                    option, search_point.collection_name
+                )));
+            }
+        }
+        self.check_sql_complexity(&search_point)?;
+
+        if search_point.payload_search_query.is_some() && options.no_payload {
+            return Err(VecXError::InvalidQueryError(format!(
+                "collection '{}' has no payload table and does not accept a payload_search_query",
+                search_point.collection_name
+            )));
+        }
+
+        if options.payload_only {
+            return Ok(SearchPlan {
+                query: self.plan_filter_only_search_query(search_point)?,
+                kind: PlanKind::FilterOnly,
+                estimated_candidates: 0,
+                approximate: false,
+            });
+        }
+
+        self.check_vector_len(&search_point.collection_name, search_point.vector.len())?;
+
+        // No search can return more points than the index holds, so k is clamped to
+        // max_elements instead of letting vectorlite allocate for an impossible result.
+        let max_elements = self
+            .max_elements(&search_point.collection_name)?
+            .unwrap_or(i64::MAX);
+        search_point.top_k = search_point.top_k.min(max_elements);
+
+        let vector_json = vector_to_json(&search_point.vector)?;
+        let virtual_table_name = get_vector_table_name(search_point.collection_name.as_str());
+        let id_allowlist = search_point.restrict_to_ids.as_deref().map(join_ids);
+        let distance_column = self.distance_column(&search_point)?;
+        let max_distance = self.max_distance(&search_point)?;
+        let similarity = match &search_point.aggregate {
+            Some(_) => similarity_sql(
+                vectorlite_distance_type(&self.virtual_table_sql(&search_point.collection_name)?),
+                &distance_column,
+            ),
+            None => String::new(),
+        };
+        let distance_selection = if distance_column == "distance" {
+            "distance".to_string()
+        } else {
+            format!("distance AS \"{}\"", distance_column)
+        };
+
+        // Small collections are scanned exactly; the scan stands in for the virtual table
+        // and its `knn_search` constraint, and the outer LIMIT takes the place of k.
+        let exact_scan = self.exact_scan_source(&search_point.collection_name, &options)?;
+        let knn_source = exact_scan.as_deref().unwrap_or(&virtual_table_name);
+        let knn_constraint = |vector_column: &str| match exact_scan {
+            Some(_) => "1 = 1".to_string(),
+            None => format!(
+                "knn_search({}, knn_param(vector_from_json(?1), ?2))",
+                vector_column
+            ),
+        };
+
+        // --- Case 1: No payload filter ---
+        if search_point.payload_search_query.is_none() {
+            let id_filter = id_allowlist
+                .map(|ids| format!(" AND rowid IN ({})", ids))
+                .unwrap_or_default();
+            let limit = if exact_scan.is_some() { " LIMIT ?2" } else { "" };
+
+            let sql = format!(
+                "SELECT rowid, {}
+             FROM {}
+             WHERE {}{}
+             ORDER BY distance{}",
+                distance_selection,
+                knn_source,
+                knn_constraint("vector_embedding"),
+                id_filter,
+                limit
+            );
+
+            let sql = apply_max_distance(sql, max_distance, &distance_column);
+
+            let sql = apply_order_by(sql, &search_point.order_by, &distance_column);
+
+            let sql = apply_rerank_score(sql, search_point.rerank_with, &virtual_table_name);
+
+            return Ok(SearchPlan {
+                query: QueryPlan {
+                    sql: apply_aggregate(
+                        apply_include_vector(sql, search_point.include_vector, &virtual_table_name),
+                        &search_point.aggregate,
+                        &similarity,
+                    ),
+                    params: vec![Box::new(vector_json), Box::new(search_point.top_k)],
+                    post_process: Some(Box::new(parse_row_to_map)),
+                },
+                kind: PlanKind::VectorOnly,
+                estimated_candidates: 0,
+                approximate: exact_scan.is_none(),
+            });
+        }
+
+        // The ordered, de-duplicated, grouped, thresholded, reranked and vector-carrying variants are
+        // wrapped in an outer SELECT, which would rename the duplicate `rowid` column; the
+        // payload's own rowid carries the same value.
+        let selection = if search_point.order_by.is_some()
+            || search_point.dedup_by.is_some()
+            || search_point.group_by.is_some()
+            || max_distance.is_some()
+            || search_point.rerank_with.is_some()
+            || search_point.include_vector
+        {
+            format!("vt.{}, pt.*", distance_selection)
+        } else {
+            format!("vt.rowid, vt.{}, pt.*", distance_selection)
+        };
+
+        let payload_query = search_point.payload_search_query.as_ref().unwrap();
+        let payload_selection_count = self.connections.get()?
             .query_one(
                 &replace_select_with_count(search_point.payload_search_query.as_ref().unwrap()),
                 (),
@@ -202,76 +1353,877 @@ impl QueryPlanner for SqliteQueryPlanner {
             )
             .unwrap_or(0);
 
-        // --- Case 2: Selective payload (< 10k rows) ---
-        if payload_selection_count < 10_000 {
-            let payload_query_ids = replace_select_with_row_ids(payload_query);
+        // De-duplication drops rows after the KNN step, so more neighbours than top_k are
+        // fetched and the final cut to top_k happens after de-duplication.
+        let candidate_limit = |candidate_k: i64| {
+            if search_point.dedup_by.is_some() {
+                candidate_k.max(search_point.top_k)
+            } else {
+                search_point.top_k
+            }
+        };
+
+        let pushdown = match search_point.filter_strategy {
+            FilterStrategy::Auto => payload_selection_count < 10_000,
+            FilterStrategy::Pushdown => true,
+            FilterStrategy::PostFilter => false,
+        };
+
+        // --- Case 2: Selective payload (< 10k rows) or forced pushdown ---
+        if pushdown {
+            let mut payload_query_ids = replace_select_with_row_ids(payload_query);
+            if let Some(ids) = &id_allowlist {
+                payload_query_ids = format!(
+                    "SELECT rowid FROM ({}) WHERE rowid IN ({})",
+                    payload_query_ids, ids
+                );
+            }
 
             let sql = format!(
-                "SELECT vt.rowid, vt.distance, pt.*
+                "SELECT {selection}
              FROM (
                  SELECT vt_inner.rowid, vt_inner.distance
-                 FROM {vt_table_name} as vt_inner
-                 WHERE knn_search(vt_inner.vector_embedding, knn_param(vector_from_json(?1), ?2))
+                 FROM {knn_source} as vt_inner
+                 WHERE {knn_constraint}
                  AND vt_inner.rowid in ({payload_query_ids})
              ) AS vt
              INNER JOIN ({payload_query}) AS pt
                  ON vt.rowid = pt.rowid
              ORDER BY vt.distance LIMIT ?2",
+                selection = selection,
                 payload_query_ids = payload_query_ids,
-                vt_table_name = virtual_table_name,
+                knn_source = knn_source,
+                knn_constraint = knn_constraint("vt_inner.vector_embedding"),
                 payload_query = payload_query,
             );
 
-            return Ok(QueryPlan {
+            let sql = apply_dedup_by(
                 sql,
-                params: vec![Box::new(vector_json), Box::new(search_point.top_k)],
-                post_process: Some(Box::new(parse_row_to_map)),
+                &search_point.dedup_by,
+                search_point.top_k,
+                &distance_column,
+            );
+            let sql = apply_max_distance(sql, max_distance, &distance_column);
+            let sql = apply_group_by(sql, &search_point.group_by, &distance_column);
+            let sql = apply_order_by(sql, &search_point.order_by, &distance_column);
+
+            let sql = apply_rerank_score(sql, search_point.rerank_with, &virtual_table_name);
+
+            return Ok(SearchPlan {
+                query: QueryPlan {
+                    sql: apply_aggregate(
+                        apply_include_vector(sql, search_point.include_vector, &virtual_table_name),
+                        &search_point.aggregate,
+                        &similarity,
+                    ),
+                    params: vec![
+                        Box::new(vector_json),
+                        Box::new(candidate_limit(payload_selection_count)),
+                    ],
+                    post_process: Some(Box::new(parse_row_to_map)),
+                },
+                kind: PlanKind::MetadataFirst,
+                estimated_candidates: payload_selection_count,
+                approximate: exact_scan.is_none(),
             });
         }
 
-        // --- Case 3: Non-selective payload (> 10k rows) ---
+        // --- Case 3: Non-selective payload (> 10k rows) or forced post-filter ---
+        let id_filter = id_allowlist
+            .map(|ids| format!(" AND vt_inner.rowid IN ({})", ids))
+            .unwrap_or_default();
+
         let sql = format!(
-            "SELECT vt.rowid, vt.distance, pt.*
+            "SELECT {selection}
          FROM (
              SELECT vt_inner.rowid, vt_inner.distance
-             FROM {vt_table_name} as vt_inner
-             WHERE knn_search(vt_inner.vector_embedding, knn_param(vector_from_json(?1), ?2))
+             FROM {knn_source} as vt_inner
+             WHERE {knn_constraint}{id_filter}
          ) AS vt
          INNER JOIN ({payload_query}) AS pt
              ON vt.rowid = pt.rowid
          ORDER BY vt.distance LIMIT ?3",
-            vt_table_name = virtual_table_name,
+            selection = selection,
+            knn_source = knn_source,
+            knn_constraint = knn_constraint("vt_inner.vector_embedding"),
+            id_filter = id_filter,
             payload_query = payload_query,
         );
 
+        let sql = apply_dedup_by(
+            sql,
+            &search_point.dedup_by,
+            search_point.top_k,
+            &distance_column,
+        );
+        let sql = apply_max_distance(sql, max_distance, &distance_column);
+        let sql = apply_group_by(sql, &search_point.group_by, &distance_column);
+        let sql = apply_order_by(sql, &search_point.order_by, &distance_column);
+
+        let sql = apply_rerank_score(sql, search_point.rerank_with, &virtual_table_name);
+
+        Ok(SearchPlan {
+            query: QueryPlan {
+                sql: apply_aggregate(
+                    apply_include_vector(sql, search_point.include_vector, &virtual_table_name),
+                    &search_point.aggregate,
+                    &similarity,
+                ),
+                params: vec![
+                    Box::new(vector_json),
+                    Box::new(search_point.top_k.saturating_mul(10).min(max_elements)),
+                    Box::new(candidate_limit(search_point.top_k.saturating_mul(10))),
+                ],
+                post_process: Some(Box::new(parse_row_to_map)),
+            },
+            kind: PlanKind::VectorFirst,
+            estimated_candidates: payload_selection_count,
+            approximate: exact_scan.is_none(),
+        })
+    }
+
+    /// Renders the search SQL with the query vector inlined, rounded to the configured
+    /// `explain_precision`. The executed plan still binds the vector at full precision.
+    fn plan_explain_search_query(&self, search_point: SearchPoint) -> Result<String, VecXError> {
+        let vector_json = match self.config.explain_precision {
+            Some(digits) => vector_to_json_rounded(&search_point.vector, digits)?,
+            None => vector_to_json(&search_point.vector)?,
+        };
+        let query_plan = self.plan_search_query(search_point)?;
+
+        Ok(query_plan.sql.replace(
+            "vector_from_json(?1)",
+            &format!("vector_from_json('{}')", vector_json),
+        ))
+    }
+
+    /// Plans `EXPLAIN QUERY PLAN` of the search, bound to the same parameters.
+    fn plan_explain_query_plan_query(
+        &self,
+        search_point: &SearchPoint,
+    ) -> Result<QueryPlan, VecXError> {
+        let query_plan = self.plan_search_query(search_point.clone())?;
+
+        Ok(QueryPlan {
+            sql: format!("EXPLAIN QUERY PLAN {}", query_plan.sql),
+            params: query_plan.params,
+            post_process: None,
+        })
+    }
+
+    /// Plans an exact nearest-neighbour search that computes the distance to every
+    /// vector of the collection instead of walking the HNSW graph.
+    #[cfg(feature = "recall")]
+    fn plan_exact_search_query(
+        &self,
+        collection_name: &str,
+        vector: &[f32],
+        top_k: i64,
+    ) -> Result<QueryPlan, VecXError> {
+        self.check_has_payload(collection_name, "measure_recall")?;
+        let virtual_table_name = get_vector_table_name(collection_name);
+        let virtual_table_sql = self.virtual_table_sql(collection_name)?;
+
+        // vectorlite cannot scan a virtual table, so every rowid is listed explicitly
+        // through the payload table.
+        let sql = format!(
+            "SELECT rowid, vector_distance(vector_embedding, vector_from_json(?1), '{distance_type}') AS distance
+             FROM {vt_table_name}
+             WHERE rowid IN (SELECT rowid FROM {payload_table_name})
+             ORDER BY distance LIMIT ?2",
+            distance_type = vectorlite_distance_type(&virtual_table_sql),
+            vt_table_name = virtual_table_name,
+            payload_table_name = self.payload_table_name(collection_name),
+        );
+
         Ok(QueryPlan {
             sql,
-            params: vec![
-                Box::new(vector_json),
-                Box::new(10 * search_point.top_k),
-                Box::new(search_point.top_k),
-            ],
+            params: vec![Box::new(vector_to_json(vector)?), Box::new(top_k)],
+            post_process: Some(Box::new(parse_row_to_map)),
+        })
+    }
+
+    /// Plans reading the stored vector of point `id` as JSON, in a `vector` column next
+    /// to its `rowid`. Yields no row when the point has no vector.
+    fn plan_stored_vector_query(
+        &self,
+        collection_name: &str,
+        id: i64,
+    ) -> Result<QueryPlan, VecXError> {
+        let options = self.collection_options(collection_name)?.unwrap_or_default();
+        if options.payload_only {
+            return Err(VecXError::InvalidQueryError(format!(
+                "collection '{}' is payload-only and does not store vectors",
+                collection_name
+            )));
+        }
+        let rowid = match options.rowid_strategy {
+            RowidStrategy::Direct => id,
+            strategy => {
+                let id = u64::try_from(id).map_err(|_| {
+                    VecXError::InvalidQueryError(format!("point id {} is negative", id))
+                })?;
+                strategy.rowid(id)? as i64
+            }
+        };
+
+        Ok(QueryPlan {
+            sql: format!(
+                "SELECT rowid, vector_to_json(vector_embedding) AS vector FROM {} WHERE rowid = ?1",
+                get_vector_table_name(collection_name)
+            ),
+            params: vec![Box::new(rowid)],
             post_process: Some(Box::new(parse_row_to_map)),
         })
     }
 
+    /// Plans the distances from `query` to the stored vectors of `candidate_ids` under
+    /// `metric`, which may differ from the collection's own distance function.
+    fn plan_rerank_query(
+        &self,
+        collection_name: &str,
+        candidate_ids: &[i64],
+        query: &[f32],
+        metric: DistanceFunction,
+    ) -> Result<QueryPlan, VecXError> {
+        if candidate_ids.is_empty() {
+            return Err(VecXError::InvalidQueryError(
+                "rerank requires at least one candidate id".to_string(),
+            ));
+        }
+
+        let virtual_table_sql = self.virtual_table_sql(collection_name)?;
+        let dimension = vectorlite_dimension(&virtual_table_sql);
+        if dimension.is_some_and(|dimension| dimension != query.len()) {
+            return Err(VecXError::InvalidQueryError(format!(
+                "query vector has {} dimensions but collection '{}' expects {}",
+                query.len(),
+                collection_name,
+                dimension.unwrap()
+            )));
+        }
+
+        let sql = format!(
+            "SELECT rowid, vector_distance(vector_embedding, vector_from_json(?1), '{metric}') AS distance
+             FROM {vt_table_name}
+             WHERE rowid IN ({ids})
+             ORDER BY distance, rowid",
+            metric = metric.as_str(),
+            vt_table_name = get_vector_table_name(collection_name),
+            ids = join_ids(candidate_ids),
+        );
+
+        Ok(QueryPlan {
+            sql,
+            params: vec![Box::new(vector_to_json(query)?)],
+            post_process: None,
+        })
+    }
+
+    /// Plans a count of the payload rows a search can draw from, honoring
+    /// `restrict_to_ids`. Searches without a payload filter have no such count.
+    fn plan_candidate_count_query(
+        &self,
+        search_point: &SearchPoint,
+    ) -> Result<Option<QueryPlan>, VecXError> {
+        let Some(payload_query) = &search_point.payload_search_query else {
+            return Ok(None);
+        };
+
+        let mut count_query = replace_select_with_row_ids(payload_query);
+        let options = self
+            .collection_options(&search_point.collection_name)?
+            .unwrap_or_default();
+        if let Some(ids) = self
+            .restricted_rowids(search_point, options.rowid_strategy)?
+            .as_deref()
+        {
+            count_query = format!(
+                "SELECT rowid FROM ({}) WHERE rowid IN ({})",
+                count_query,
+                join_ids(ids)
+            );
+        }
+
+        Ok(Some(QueryPlan {
+            sql: format!("SELECT count(*) FROM ({})", count_query),
+            params: vec![],
+            post_process: None,
+        }))
+    }
+
     fn plan_collection_exists_query(&self, collection_name: &str) -> Result<QueryPlan, VecXError> {
         // Check if both the payload table and the virtual vector table exist
         let virtual_table_name = get_vector_table_name(collection_name);
 
         // Query to check if both tables exist in sqlite_master
-        let sql = format!(
-            "SELECT COUNT(*) as count FROM sqlite_master WHERE type='table' AND name IN (?, ?)"
-        );
+        let sql =
+            "SELECT COUNT(*) as count FROM sqlite_master WHERE type='table' AND name COLLATE NOCASE IN (?, ?)"
+                .to_string();
 
         Ok(QueryPlan {
             sql,
             params: vec![
-                Box::new(collection_name.to_string()),
+                Box::new(self.payload_table_name(collection_name)),
                 Box::new(virtual_table_name),
             ],
             post_process: None,
         })
     }
+
+    /// Plans a `count(*)` over the payload rows matching `predicate_sql`.
+    fn plan_count_query(
+        &self,
+        collection_name: &str,
+        predicate_sql: &str,
+    ) -> Result<QueryPlan, VecXError> {
+        self.check_has_payload(collection_name, "count_where")?;
+        let filtered_query = format!(
+            "SELECT * FROM {} WHERE {}",
+            self.payload_table_name(collection_name),
+            predicate_sql
+        );
+
+        Ok(QueryPlan {
+            sql: replace_select_with_count(&filtered_query),
+            params: vec![],
+            post_process: None,
+        })
+    }
+
+    /// Plans an `EXISTS` over the payload rows, which stops at the first row instead of
+    /// counting them all.
+    fn plan_has_any_query(&self, collection_name: &str) -> Result<QueryPlan, VecXError> {
+        self.check_has_payload(collection_name, "has_any")?;
+        Ok(QueryPlan {
+            sql: format!(
+                "SELECT EXISTS(SELECT 1 FROM {} LIMIT 1)",
+                self.payload_table_name(collection_name)
+            ),
+            params: vec![],
+            post_process: None,
+        })
+    }
+
+    /// Plans the in-memory size estimate of a collection's HNSW index as a count of its
+    /// payload rows times the estimated bytes per element. Payload-only collections
+    /// have no index and are estimated at 0 bytes.
+    fn plan_estimate_memory_query(&self, collection_name: &str) -> Result<QueryPlan, VecXError> {
+        let options = self.collection_options(collection_name)?.unwrap_or_default();
+        options.check_has_payload(collection_name, "estimate_memory")?;
+        let element_bytes = if options.payload_only {
+            0.0
+        } else {
+            let virtual_table_sql = self.virtual_table_sql(collection_name)?;
+            let dimension = vectorlite_dimension(&virtual_table_sql).ok_or_else(|| {
+                VecXError::DataParsingError(format!(
+                    "unrecognized vector column of collection '{}'",
+                    collection_name
+                ))
+            })?;
+            estimate_hnsw_element_bytes(dimension, vectorlite_hnsw_m(&virtual_table_sql))
+        };
+
+        Ok(QueryPlan {
+            sql: format!(
+                "SELECT CAST(round(count(*) * ?1) AS INTEGER) FROM {}",
+                self.payload_table_name(collection_name)
+            ),
+            params: vec![Box::new(element_bytes)],
+            post_process: None,
+        })
+    }
+
+    /// Plans an UPDATE of the payload rows matching `predicate_sql`.
+    ///
+    /// Only real payload tables are accepted; vector tables and SET clauses that change
+    /// the rowid are rejected so every payload row stays attached to its vector.
+    fn plan_update_where_query(
+        &self,
+        collection_name: &str,
+        set_sql: &str,
+        predicate_sql: &str,
+    ) -> Result<QueryPlan, VecXError> {
+        if collection_name.starts_with(VECTOR_TABLE_PREFIX) {
+            return Err(VecXError::InvalidQueryError(format!(
+                "update_where only updates payload tables, not '{}'",
+                collection_name
+            )));
+        }
+
+        if assigns_rowid(set_sql) {
+            return Err(VecXError::InvalidQueryError(
+                "update_where must not change the rowid".to_string(),
+            ));
+        }
+
+        let payload_table_name = self.existing_payload_table_name(collection_name)?;
+
+        Ok(QueryPlan {
+            sql: format!(
+                "UPDATE {} SET {} WHERE {}",
+                payload_table_name, set_sql, predicate_sql
+            ),
+            params: vec![],
+            post_process: None,
+        })
+    }
+
+    fn plan_create_payload_index_query(
+        &self,
+        collection_name: &str,
+        columns: &[&str],
+        on_conflict: OnConflict,
+    ) -> Result<QueryPlan, VecXError> {
+        let (payload_table_name, index_name) =
+            self.payload_index_target(collection_name, columns)?;
+
+        let if_not_exists = match on_conflict {
+            OnConflict::Ignore => "IF NOT EXISTS ",
+            OnConflict::Error => {
+                let exists: bool = self.connections.get()?.query_row(
+                    "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'index' AND name = ?1 COLLATE NOCASE)",
+                    [&index_name],
+                    |row| row.get(0),
+                )?;
+                if exists {
+                    return Err(VecXError::InvalidQueryError(format!(
+                        "payload index '{}' already exists",
+                        index_name
+                    )));
+                }
+                ""
+            }
+        };
+
+        Ok(QueryPlan {
+            sql: format!(
+                "CREATE INDEX {}{} ON {} ({})",
+                if_not_exists,
+                index_name,
+                payload_table_name,
+                columns.join(", ")
+            ),
+            params: vec![],
+            post_process: None,
+        })
+    }
+
+    fn plan_drop_payload_index_query(
+        &self,
+        collection_name: &str,
+        columns: &[&str],
+    ) -> Result<QueryPlan, VecXError> {
+        let (_, index_name) = self.payload_index_target(collection_name, columns)?;
+
+        Ok(QueryPlan {
+            sql: format!("DROP INDEX IF EXISTS {}", index_name),
+            params: vec![],
+            post_process: None,
+        })
+    }
+
+    /// Plans listing the payload indexes of a collection, one row per indexed column
+    /// in index and column order. Indexes the payload table schema declared itself are
+    /// not included.
+    fn plan_list_payload_indexes_query(
+        &self,
+        collection_name: &str,
+    ) -> Result<QueryPlan, VecXError> {
+        let payload_table_name = self.existing_payload_table_name(collection_name)?;
+
+        Ok(QueryPlan {
+            sql: "SELECT il.name, ii.name
+             FROM pragma_index_list(?1) AS il, pragma_index_info(il.name) AS ii
+             WHERE il.name GLOB ?2
+             ORDER BY il.name, ii.seqno"
+                .to_string(),
+            params: vec![
+                Box::new(payload_table_name),
+                Box::new(format!("{}_*", PAYLOAD_INDEX_PREFIX)),
+            ],
+            post_process: None,
+        })
+    }
+
+    /// Plans the schema change used to flush vectorlite indexes.
+    ///
+    /// vectorlite only writes an HNSW index to its file when the virtual table is
+    /// disconnected. Creating and dropping a marker table bumps the schema cookie,
+    /// which makes every other connection drop its cached schema (and with it the
+    /// virtual tables) the next time it reads `sqlite_master`.
+    fn plan_flush_query(&self) -> Result<QueryPlan, VecXError> {
+        let sql = format!(
+            "CREATE TABLE IF NOT EXISTS {marker} (id INTEGER); DROP TABLE {marker};",
+            marker = FLUSH_MARKER_TABLE
+        );
+
+        Ok(QueryPlan {
+            sql,
+            params: vec![],
+            post_process: None,
+        })
+    }
+
+    /// Plans copying every collection's vectors into file-backed indexes of the backup
+    /// at `db_path`, which is attached to the connection holding the source indexes.
+    ///
+    /// Each virtual table in the backup is recreated with an index file in `idx_dir` and
+    /// refilled with the vectors whose ids are present in the collection's payload table.
+    fn plan_persist_query(
+        &self,
+        db_path: &Path,
+        idx_dir: &Path,
+    ) -> Result<Vec<QueryPlan>, VecXError> {
+        // The connection is released before the loop, which looks up collection markers
+        let vector_tables = {
+            let conn = self.connections.get()?;
+            let mut stmt = conn.prepare(&format!(
+                "SELECT name, sql FROM sqlite_master WHERE type = 'table' AND name LIKE '{}\\_%' ESCAPE '\\' AND sql LIKE '%using vectorlite%'",
+                VECTOR_TABLE_PREFIX
+            ))?;
+            let vector_tables = stmt
+                .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+                .collect::<Result<Vec<_>, _>>()?;
+            vector_tables
+        };
+
+        let mut query_plans = vec![QueryPlan {
+            sql: format!("ATTACH DATABASE ?1 AS {}", PERSIST_ATTACH_ALIAS),
+            params: vec![Box::new(db_path.to_string_lossy().into_owned())],
+            post_process: None,
+        }];
+
+        for (virtual_table_name, create_sql) in vector_tables {
+            let collection_name = &virtual_table_name[VECTOR_TABLE_PREFIX.len() + 1..];
+            self.check_has_payload(collection_name, "persist")?;
+            let index_path = idx_dir.join(format!("{}.idx", collection_name));
+            if index_path.exists() {
+                return Err(VecXError::InvalidQueryError(format!(
+                    "index file '{}' already exists",
+                    index_path.display()
+                )));
+            }
+
+            let vectorlite_args = vectorlite_args_without_index_path(&create_sql).ok_or_else(|| {
+                VecXError::DataParsingError(format!(
+                    "unrecognized vectorlite definition for '{}'",
+                    virtual_table_name
+                ))
+            })?;
+
+            query_plans.push(QueryPlan {
+                sql: format!("DROP TABLE {}.{}", PERSIST_ATTACH_ALIAS, virtual_table_name),
+                params: vec![],
+                post_process: None,
+            });
+            query_plans.push(QueryPlan {
+                sql: format!(
+                    "CREATE VIRTUAL TABLE {}.{} USING vectorlite({}, {})",
+                    PERSIST_ATTACH_ALIAS,
+                    virtual_table_name,
+                    vectorlite_args,
+                    index_path.display()
+                ),
+                params: vec![],
+                post_process: None,
+            });
+            query_plans.push(QueryPlan {
+                sql: format!(
+                    "INSERT INTO {alias}.{vt}(rowid, vector_embedding) SELECT rowid, vector_embedding FROM main.{vt} WHERE rowid IN (SELECT rowid FROM main.{payload})",
+                    alias = PERSIST_ATTACH_ALIAS,
+                    vt = virtual_table_name,
+                    payload = self.payload_table_name(collection_name)
+                ),
+                params: vec![],
+                post_process: None,
+            });
+        }
+
+        query_plans.push(QueryPlan {
+            sql: format!("DETACH DATABASE {}", PERSIST_ATTACH_ALIAS),
+            params: vec![],
+            post_process: None,
+        });
+
+        Ok(query_plans)
+    }
+
+    fn plan_version_info_query(&self) -> Result<QueryPlan, VecXError> {
+        Ok(QueryPlan {
+            sql: "SELECT vectorlite_info()".to_string(),
+            params: vec![],
+            post_process: None,
+        })
+    }
+
+    /// Plans a checkpoint that copies the whole WAL into the database file and
+    /// truncates the WAL to zero bytes.
+    fn plan_checkpoint_query(&self) -> Result<QueryPlan, VecXError> {
+        Ok(QueryPlan {
+            sql: "PRAGMA wal_checkpoint(TRUNCATE)".to_string(),
+            params: vec![],
+            post_process: None,
+        })
+    }
+
+    /// Plans a `VACUUM`, refusing when a collection's payload table has no `INTEGER
+    /// PRIMARY KEY`: VACUUM may renumber the rowids of such a table, which would detach
+    /// its rows from their vectors.
+    fn plan_vacuum_query(&self) -> Result<QueryPlan, VecXError> {
+        let conn = self.connections.get()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name LIKE '{}\\_%' ESCAPE '\\' AND sql LIKE '%using vectorlite%'",
+            VECTOR_TABLE_PREFIX
+        ))?;
+        let virtual_table_names = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for virtual_table_name in virtual_table_names {
+            let collection_name = &virtual_table_name[VECTOR_TABLE_PREFIX.len() + 1..];
+            let payload_table_name = self.payload_table_name(collection_name);
+
+            // (declared type, position in the primary key) of every column
+            let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", payload_table_name))?;
+            let columns = stmt
+                .query_map([], |row| {
+                    Ok((row.get::<_, String>(2)?, row.get::<_, i64>(5)?))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            let primary_key: Vec<_> = columns.iter().filter(|(_, pk)| *pk > 0).collect();
+
+            let has_rowid_alias = matches!(
+                primary_key.as_slice(),
+                [(col_type, _)] if col_type.eq_ignore_ascii_case("INTEGER")
+            );
+            if !columns.is_empty() && !has_rowid_alias {
+                return Err(VecXError::InvalidQueryError(format!(
+                    "cannot vacuum: payload table '{}' of collection '{}' has no INTEGER PRIMARY KEY, so VACUUM could renumber its rowids",
+                    payload_table_name, collection_name
+                )));
+            }
+        }
+
+        Ok(QueryPlan {
+            sql: "VACUUM".to_string(),
+            params: vec![],
+            post_process: None,
+        })
+    }
+
+    /// Plans a rewrite of a collection's payload table: the rows are copied with their
+    /// rowids into a temporary table, deleted and inserted back in rowid order. The
+    /// table is not dropped and renamed, since `ALTER TABLE ... RENAME` reloads the
+    /// schema, which reconnects vectorlite tables and loses in-memory HNSW indexes.
+    fn plan_compact_payload_query(
+        &self,
+        collection_name: &str,
+    ) -> Result<Vec<QueryPlan>, VecXError> {
+        let payload_table_name = self.existing_payload_table_name(collection_name)?;
+        let compact_table_name = format!("{}_{}", COMPACT_TABLE_PREFIX, payload_table_name);
+
+        let conn = self.connections.get()?;
+        let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", payload_table_name))?;
+        let payload_columns = stmt
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // The copied rowid needs a column of its own that no payload column uses
+        let mut rowid_column = "vx_rowid".to_string();
+        while payload_columns
+            .iter()
+            .any(|column| column.eq_ignore_ascii_case(&rowid_column))
+        {
+            rowid_column.push('_');
+        }
+        let rowid_column = quote_identifier(&rowid_column);
+        let payload_columns = payload_columns
+            .iter()
+            .map(|column| quote_identifier(column))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let sqls = [
+            format!(
+                "CREATE TEMP TABLE {compact} AS SELECT rowid AS {rowid}, {columns} FROM {payload}",
+                compact = compact_table_name,
+                payload = payload_table_name,
+                rowid = rowid_column,
+                columns = payload_columns
+            ),
+            // Deleting every row releases all pages of the table and its indexes at once
+            format!("DELETE FROM {}", payload_table_name),
+            format!(
+                "INSERT INTO {payload}(rowid, {columns}) SELECT {rowid}, {columns} FROM temp.{compact} ORDER BY {rowid}",
+                compact = compact_table_name,
+                payload = payload_table_name,
+                rowid = rowid_column,
+                columns = payload_columns
+            ),
+            format!("DROP TABLE temp.{}", compact_table_name),
+        ];
+
+        Ok(sqls
+            .into_iter()
+            .map(|sql| QueryPlan {
+                sql,
+                params: vec![],
+                post_process: None,
+            })
+            .collect())
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Direction;
+    use r2d2::Pool;
+    use r2d2_sqlite::SqliteConnectionManager;
+    use std::time::Duration;
+
+    fn planner_with_table(schema: &str) -> Box<dyn QueryPlanner> {
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(SqliteConnectionManager::memory())
+            .unwrap();
+        pool.get().unwrap().execute_batch(schema).unwrap();
+        SqliteQueryPlanner::new(
+            ConnectionSource::pool(pool, Duration::from_secs(1)),
+            VectorXLiteConfig::default(),
+        )
+    }
+
+    /// Returns the part of the plan evaluated together with `knn_search`.
+    fn knn_clause(sql: &str) -> &str {
+        let start = sql.find("knn_search").unwrap();
+        let end = sql[start..]
+            .find(") AS vt")
+            .or_else(|| sql[start..].find("ORDER BY"))
+            .unwrap();
+        &sql[start..start + end]
+    }
+
+    #[test]
+    fn restrict_to_ids_is_pushed_into_knn_search_without_payload() {
+        let planner = planner_with_table("create table docs (rowid integer primary key);");
+        let search_point = SearchPoint::builder()
+            .collection_name("docs")
+            .vector(vec![1.0, 2.0])
+            .restrict_to_ids(vec![3, 5, 8])
+            .build()
+            .unwrap();
+
+        let plan = planner.plan_search_query(search_point).unwrap();
+
+        assert!(knn_clause(&plan.sql).contains("AND rowid IN (3, 5, 8)"));
+    }
+
+    #[test]
+    fn restrict_to_ids_is_pushed_into_knn_search_with_payload() {
+        let planner =
+            planner_with_table("create table docs (rowid integer primary key, tag text);");
+        let search_point = SearchPoint::builder()
+            .collection_name("docs")
+            .vector(vec![1.0, 2.0])
+            .payload_search_query("select rowid, tag from docs")
+            .restrict_to_ids(vec![1, 2])
+            .build()
+            .unwrap();
+
+        let plan = planner.plan_search_query(search_point).unwrap();
+
+        assert!(knn_clause(&plan.sql).contains(
+            "vt_inner.rowid in (SELECT rowid FROM (SELECT rowid FROM docs) WHERE rowid IN (1, 2))"
+        ));
+    }
+
+    #[test]
+    fn order_by_resorts_outside_the_knn_limit() {
+        let planner =
+            planner_with_table("create table docs (rowid integer primary key, created_at integer);");
+        let search_point = SearchPoint::builder()
+            .collection_name("docs")
+            .vector(vec![1.0, 2.0])
+            .payload_search_query("select rowid, created_at from docs")
+            .order_by("created_at", Direction::Desc)
+            .build()
+            .unwrap();
+
+        let plan = planner.plan_search_query(search_point).unwrap();
+
+        assert!(plan.sql.starts_with("SELECT * FROM (SELECT vt.distance, pt.*"));
+        assert!(plan
+            .sql
+            .ends_with("ORDER BY vt.distance LIMIT ?2) ORDER BY \"created_at\" DESC, \"distance\""));
+    }
+
+    #[test]
+    fn dedup_by_keeps_nearest_row_per_value_before_top_k() {
+        let planner =
+            planner_with_table("create table chunks (rowid integer primary key, doc_id integer);");
+        let search_point = SearchPoint::builder()
+            .collection_name("chunks")
+            .vector(vec![1.0, 2.0])
+            .top_k(3)
+            .payload_search_query("select rowid, doc_id from chunks")
+            .dedup_by("doc_id")
+            .build()
+            .unwrap();
+
+        let plan = planner.plan_search_query(search_point).unwrap();
+
+        assert!(plan
+            .sql
+            .starts_with("WITH vx_hits AS MATERIALIZED (SELECT vt.distance, pt.*"));
+        assert!(plan.sql.contains("WHERE d.\"doc_id\" IS h.\"doc_id\""));
+        assert!(plan.sql.ends_with("ORDER BY h.\"distance\" LIMIT 3"));
+    }
+
+    #[test]
+    fn group_by_ranks_rows_per_value_with_row_number() {
+        let planner =
+            planner_with_table("create table items (rowid integer primary key, category text);");
+        let search_point = SearchPoint::builder()
+            .collection_name("items")
+            .vector(vec![1.0, 2.0])
+            .payload_search_query("select rowid, category from items")
+            .group_by("category", 2)
+            .build()
+            .unwrap();
+
+        let plan = planner.plan_search_query(search_point).unwrap();
+
+        assert!(plan
+            .sql
+            .starts_with("WITH vx_hits AS MATERIALIZED (SELECT vt.distance, pt.*"));
+        assert!(plan.sql.contains(
+            "ROW_NUMBER() OVER (PARTITION BY \"category\" ORDER BY \"distance\", rowid)"
+        ));
+        assert!(plan.sql.contains("WHERE r.vx_group_rank <= 2"));
+        assert!(plan
+            .sql
+            .ends_with("ORDER BY h.\"category\", h.\"distance\", h.rowid"));
+    }
+
+    #[test]
+    fn group_by_ranks_only_restricted_ids() {
+        let planner =
+            planner_with_table("create table items (rowid integer primary key, category text);");
+        let search_point = SearchPoint::builder()
+            .collection_name("items")
+            .vector(vec![1.0, 2.0])
+            .payload_search_query("select rowid, category from items")
+            .restrict_to_ids(vec![4, 7])
+            .group_by("category", 1)
+            .build()
+            .unwrap();
+
+        let plan = planner.plan_search_query(search_point).unwrap();
+
+        let hits_end = plan.sql.find("), vx_ranked AS").unwrap();
+        assert!(knn_clause(&plan.sql[..hits_end]).contains("WHERE rowid IN (4, 7)"));
+        assert!(plan.sql[hits_end..].contains("WHERE r.vx_group_rank <= 1"));
+    }
+}
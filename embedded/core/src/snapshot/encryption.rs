@@ -0,0 +1,127 @@
+//! Snapshot chunk encryption
+//!
+//! Encrypts the data of every file chunk with ChaCha20-Poly1305. Each chunk gets a
+//! random nonce, stored in front of its ciphertext, and is bound to its file name and
+//! offset, so chunks cannot be swapped between files or positions unnoticed.
+
+use super::types::SnapshotEncryptionKey;
+use crate::error::VecXError;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+/// Length of the nonce stored in front of every encrypted chunk
+const NONCE_LEN: usize = 12;
+
+/// Encrypts and decrypts the data of snapshot file chunks.
+pub(crate) struct ChunkCipher {
+    cipher: ChaCha20Poly1305,
+}
+
+impl ChunkCipher {
+    pub(crate) fn new(key: &SnapshotEncryptionKey) -> Self {
+        Self {
+            cipher: ChaCha20Poly1305::new(Key::from_slice(&key.0)),
+        }
+    }
+
+    /// Encrypts one chunk of `file_name` starting at `offset`, returning the nonce
+    /// followed by the ciphertext and its authentication tag.
+    pub(crate) fn encrypt(
+        &self,
+        file_name: &str,
+        offset: u64,
+        data: &[u8],
+    ) -> Result<Vec<u8>, VecXError> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let aad = associated_data(file_name, offset);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: data,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| {
+                VecXError::Other(format!("Failed to encrypt chunk of file {}", file_name))
+            })?;
+
+        let mut encrypted = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        encrypted.extend_from_slice(&nonce);
+        encrypted.extend_from_slice(&ciphertext);
+        Ok(encrypted)
+    }
+
+    /// Decrypts a chunk written by `encrypt`, failing when the key is wrong or the
+    /// chunk was altered or moved.
+    pub(crate) fn decrypt(
+        &self,
+        file_name: &str,
+        offset: u64,
+        data: &[u8],
+    ) -> Result<Vec<u8>, VecXError> {
+        let failed = || {
+            VecXError::DataParsingError(format!(
+                "Failed to decrypt chunk of file {} at offset {}: wrong key or corrupted data",
+                file_name, offset
+            ))
+        };
+        if data.len() < NONCE_LEN {
+            return Err(failed());
+        }
+
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let aad = associated_data(file_name, offset);
+        self.cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| failed())
+    }
+}
+
+fn associated_data(file_name: &str, offset: u64) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(file_name.len() + 8);
+    aad.extend_from_slice(file_name.as_bytes());
+    aad.extend_from_slice(&offset.to_le_bytes());
+    aad
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cipher(byte: u8) -> ChunkCipher {
+        ChunkCipher::new(&SnapshotEncryptionKey::new([byte; 32]))
+    }
+
+    #[test]
+    fn decrypt_restores_encrypted_chunk() {
+        let encrypted = cipher(1).encrypt("database.db", 64, b"chunk data").unwrap();
+
+        assert_ne!(&encrypted[NONCE_LEN..], b"chunk data");
+        assert_eq!(
+            cipher(1).decrypt("database.db", 64, &encrypted).unwrap(),
+            b"chunk data"
+        );
+    }
+
+    #[test]
+    fn decrypt_rejects_wrong_key_and_moved_chunks() {
+        let encrypted = cipher(1).encrypt("database.db", 64, b"chunk data").unwrap();
+
+        for result in [
+            cipher(2).decrypt("database.db", 64, &encrypted),
+            cipher(1).decrypt("index_0.idx", 64, &encrypted),
+            cipher(1).decrypt("database.db", 0, &encrypted),
+            cipher(1).decrypt("database.db", 64, &encrypted[..4]),
+        ] {
+            assert!(matches!(result, Err(VecXError::DataParsingError(_))));
+        }
+    }
+}
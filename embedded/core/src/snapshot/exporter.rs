@@ -3,16 +3,23 @@
 //! Provides functionality to export consistent snapshots of the database
 //! and HNSW index files as streaming chunks for Raft FSM integration.
 
+use super::encryption::ChunkCipher;
 use super::sqlite_backup;
 use super::types::*;
+use crate::constant::{DEFAULT_SQLITE_TIMEOUT, FLUSH_MARKER_TABLE};
 use crate::error::VecXError;
+use crate::helper::acquire_connection;
 use r2d2::Pool;
 use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::Connection;
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::{BufRead, BufReader, Read};
 use std::path::{Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How often a consistent export retries when other connections keep committing
+const CONSISTENT_EXPORT_ATTEMPTS: usize = 5;
 
 /// Snapshot exporter that creates consistent snapshots and streams them as chunks.
 pub struct SnapshotExporter {
@@ -44,6 +51,12 @@ impl SnapshotExporter {
     /// 3. Generates metadata and checksums
     /// 4. Returns an iterator that yields chunks for streaming
     ///
+    /// With an encryption key configured, the data of every file chunk is encrypted;
+    /// checksums in the metadata are those of the unencrypted files.
+    ///
+    /// With `consistent_export` set, steps 1 and 2 run in one critical section so the
+    /// index files match the backed-up database.
+    ///
     /// # Returns
     ///
     /// An iterator that yields `SnapshotChunk` items suitable for streaming.
@@ -55,9 +68,23 @@ impl SnapshotExporter {
             VecXError::IoError(format!("Failed to create export directory: {}", e))
         })?;
 
-        // Step 1: Backup the SQLite database
+        // Step 1: Backup the SQLite database and collect HNSW index files if configured
         let db_backup_path = export_dir.join("database.db");
-        let db_size = sqlite_backup::backup_database(&self.pool, &db_backup_path)?;
+        let (db_size, index_files) = if self.config.consistent_export {
+            self.capture_consistent(&db_backup_path, &export_dir)?
+        } else {
+            let db_size = if self.config.online {
+                sqlite_backup::backup_database_online(&self.pool, &db_backup_path)?
+            } else {
+                sqlite_backup::backup_database(&self.pool, &db_backup_path)?
+            };
+            let index_files = if self.config.include_index_files {
+                copy_index_files(&sqlite_backup::get_index_files(&self.pool)?, &export_dir)?
+            } else {
+                Vec::new()
+            };
+            (db_size, index_files)
+        };
         let db_checksum = compute_file_checksum(&db_backup_path)?;
 
         let mut files = vec![SnapshotFileInfo {
@@ -70,35 +97,10 @@ impl SnapshotExporter {
         let mut file_paths: HashMap<String, PathBuf> = HashMap::new();
         file_paths.insert("database.db".to_string(), db_backup_path);
 
-        // Step 2: Collect HNSW index files if configured
-        if self.config.include_index_files {
-            let index_files = sqlite_backup::get_index_files(&self.pool)?;
-            for (idx, index_path) in index_files.iter().enumerate() {
-                let source_path = Path::new(index_path);
-                if source_path.exists() {
-                    // Copy index file to export directory
-                    let index_name = format!("index_{}.idx", idx);
-                    let dest_path = export_dir.join(&index_name);
-                    std::fs::copy(source_path, &dest_path).map_err(|e| {
-                        VecXError::IoError(format!("Failed to copy index file: {}", e))
-                    })?;
-
-                    let file_size = std::fs::metadata(&dest_path)
-                        .map(|m| m.len())
-                        .map_err(|e| {
-                            VecXError::IoError(format!("Failed to get index file size: {}", e))
-                        })?;
-                    let checksum = compute_file_checksum(&dest_path)?;
-
-                    files.push(SnapshotFileInfo {
-                        file_name: index_name.clone(),
-                        file_type: SnapshotFileType::HnswIndex,
-                        file_size,
-                        checksum,
-                    });
-                    file_paths.insert(index_name, dest_path);
-                }
-            }
+        // Step 2: Register the copied index files
+        for (file_info, dest_path) in index_files {
+            file_paths.insert(file_info.file_name.clone(), dest_path);
+            files.push(file_info);
         }
 
         // Step 3: Compute total size and snapshot checksum
@@ -117,6 +119,7 @@ impl SnapshotExporter {
             files,
             version: SNAPSHOT_VERSION,
             checksum: snapshot_checksum,
+            encrypted: self.config.encryption_key.is_some(),
         };
 
         // Step 4: Create the chunk iterator
@@ -125,9 +128,73 @@ impl SnapshotExporter {
             file_paths,
             self.config.chunk_size,
             export_dir,
+            self.config.encryption_key.as_ref().map(ChunkCipher::new),
         ))
     }
 
+    /// Backs up the database and copies the index files within one critical section.
+    ///
+    /// A single connection first writes its HNSW indexes to their files, then opens a
+    /// read transaction and keeps it, and the connection itself, until the backup and
+    /// the index copies are done. If another connection committed between the flush and
+    /// the transaction, the flush is repeated.
+    ///
+    /// Every connection keeps its own in-memory index, so only vectors written through
+    /// the exporting connection reach the index files. Use a single-connection pool for
+    /// file-backed collections to capture every write.
+    fn capture_consistent(
+        &self,
+        db_backup_path: &Path,
+        export_dir: &Path,
+    ) -> Result<(u64, Vec<(SnapshotFileInfo, PathBuf)>), VecXError> {
+        let conn = acquire_connection(&self.pool, self.pool.connection_timeout())?;
+
+        for _ in 0..CONSISTENT_EXPORT_ATTEMPTS {
+            flush_indexes(&conn)?;
+            let flushed_version = data_version(&conn)?;
+
+            // Reading inside a transaction pins the database snapshot the backup copies.
+            // The backup API cannot read from a connection holding a write transaction.
+            conn.execute_batch("BEGIN")?;
+            conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))?;
+            if data_version(&conn)? != flushed_version {
+                conn.execute_batch("ROLLBACK")?;
+                continue;
+            }
+
+            let online = self.config.online;
+            let captured = sqlite_backup::backup_database_from(&conn, db_backup_path, online)
+                .and_then(|db_size| {
+                    let index_files = if self.config.include_index_files {
+                        copy_index_files(&sqlite_backup::get_index_files_from(&conn)?, export_dir)?
+                    } else {
+                        Vec::new()
+                    };
+                    Ok((db_size, index_files))
+                });
+            conn.execute_batch("ROLLBACK")?;
+            return captured;
+        }
+
+        Err(VecXError::Other(format!(
+            "database kept changing during {} consistent export attempts",
+            CONSISTENT_EXPORT_ATTEMPTS
+        )))
+    }
+
+    /// Returns the metadata of a snapshot of the current database, e.g. to register it in
+    /// a catalog, without streaming any file data.
+    ///
+    /// The files are backed up and checksummed in the temp directory exactly as `export`
+    /// does, then removed. For an unchanged database the files, sizes and checksums match
+    /// those of a later `export`, while the snapshot id and timestamp differ.
+    pub fn export_metadata(&self) -> Result<SnapshotMetadata, VecXError> {
+        self.export()?
+            .metadata
+            .take()
+            .ok_or_else(|| VecXError::Other("Export produced no metadata".to_string()))
+    }
+
     /// Exports a snapshot directly to memory (for in-memory databases).
     ///
     /// This is a convenience method that collects all chunks into a vector.
@@ -150,6 +217,7 @@ pub struct SnapshotChunkIterator {
     sequence: u64,
     done: bool,
     export_dir: PathBuf,
+    cipher: Option<ChunkCipher>,
 }
 
 impl SnapshotChunkIterator {
@@ -158,6 +226,7 @@ impl SnapshotChunkIterator {
         file_paths: HashMap<String, PathBuf>,
         chunk_size: usize,
         export_dir: PathBuf,
+        cipher: Option<ChunkCipher>,
     ) -> Self {
         let file_order: Vec<String> = metadata.files.iter().map(|f| f.file_name.clone()).collect();
         Self {
@@ -171,6 +240,7 @@ impl SnapshotChunkIterator {
             sequence: 0,
             done: false,
             export_dir,
+            cipher,
         }
     }
 
@@ -234,8 +304,13 @@ impl SnapshotChunkIterator {
         let reader = self.current_reader.as_mut().unwrap();
         let file_name = self.file_order[self.current_file_idx].clone();
 
-        let mut buffer = vec![0u8; self.chunk_size];
-        let bytes_read = match reader.read(&mut buffer) {
+        // A single read may return less than a full chunk before the end of the file
+        let mut buffer = Vec::with_capacity(self.chunk_size);
+        let bytes_read = match reader
+            .by_ref()
+            .take(self.chunk_size as u64)
+            .read_to_end(&mut buffer)
+        {
             Ok(n) => n,
             Err(e) => {
                 return Some(Err(VecXError::IoError(format!(
@@ -252,23 +327,12 @@ impl SnapshotChunkIterator {
             return self.next_chunk();
         }
 
-        buffer.truncate(bytes_read);
         let offset = self.current_offset;
         self.current_offset += bytes_read as u64;
 
         // Check if this is the last chunk for this file
-        let is_last_chunk = {
-            let mut peek_buf = [0u8; 1];
-            match reader.read(&mut peek_buf) {
-                Ok(0) => true,
-                Ok(_) => {
-                    // Put the byte back (we can't actually do this, so we need to track differently)
-                    // For simplicity, we'll just check if we read less than chunk_size
-                    false
-                }
-                Err(_) => true,
-            }
-        };
+        // Peek through the buffer so the next chunk still starts with the peeked byte
+        let is_last_chunk = reader.fill_buf().map(|buf| buf.is_empty()).unwrap_or(true);
 
         let is_last = bytes_read < self.chunk_size || is_last_chunk;
         if is_last {
@@ -276,12 +340,20 @@ impl SnapshotChunkIterator {
             self.current_file_idx += 1;
         }
 
+        let data = match &self.cipher {
+            Some(cipher) => match cipher.encrypt(&file_name, offset, &buffer) {
+                Ok(data) => data,
+                Err(e) => return Some(Err(e)),
+            },
+            None => buffer,
+        };
+
         Some(Ok(SnapshotChunk {
             metadata: None,
             file_chunk: Some(FileChunk {
                 file_name,
                 offset,
-                data: buffer,
+                data,
                 is_last_chunk: is_last,
             }),
             sequence,
@@ -309,6 +381,71 @@ impl Drop for SnapshotChunkIterator {
     }
 }
 
+/// Copies existing index files into the export directory as `index_<n>.idx`.
+fn copy_index_files(
+    index_files: &[String],
+    export_dir: &Path,
+) -> Result<Vec<(SnapshotFileInfo, PathBuf)>, VecXError> {
+    let mut copied = Vec::new();
+    for (idx, index_path) in index_files.iter().enumerate() {
+        let source_path = Path::new(index_path);
+        if source_path.exists() {
+            // Copy index file to export directory
+            let index_name = format!("index_{}.idx", idx);
+            let dest_path = export_dir.join(&index_name);
+            std::fs::copy(source_path, &dest_path).map_err(|e| {
+                VecXError::IoError(format!("Failed to copy index file: {}", e))
+            })?;
+
+            let file_size = std::fs::metadata(&dest_path)
+                .map(|m| m.len())
+                .map_err(|e| {
+                    VecXError::IoError(format!("Failed to get index file size: {}", e))
+                })?;
+            let checksum = compute_file_checksum(&dest_path)?;
+
+            copied.push((
+                SnapshotFileInfo {
+                    file_name: index_name,
+                    file_type: SnapshotFileType::HnswIndex,
+                    file_size,
+                    checksum,
+                },
+                dest_path,
+            ));
+        }
+    }
+
+    Ok(copied)
+}
+
+/// Makes vectorlite write the connection's HNSW indexes to their files.
+///
+/// A schema change committed by another connection makes `conn` reconnect its virtual
+/// tables on the next statement, which saves their indexes.
+fn flush_indexes(conn: &Connection) -> Result<(), VecXError> {
+    let db_path = match conn.path() {
+        Some(path) if !path.is_empty() => path.to_string(),
+        _ => return Ok(()),
+    };
+
+    let side_conn = Connection::open(&db_path)?;
+    side_conn.busy_timeout(Duration::from_millis(u64::from(DEFAULT_SQLITE_TIMEOUT)))?;
+    side_conn.execute_batch(&format!(
+        "CREATE TABLE IF NOT EXISTS {marker} (id INTEGER); DROP TABLE {marker};",
+        marker = FLUSH_MARKER_TABLE
+    ))?;
+    drop(side_conn);
+
+    conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))?;
+    Ok(())
+}
+
+/// Reads `PRAGMA data_version`, which changes whenever another connection commits.
+fn data_version(conn: &Connection) -> Result<i64, VecXError> {
+    Ok(conn.query_row("PRAGMA data_version", [], |row| row.get(0))?)
+}
+
 /// Computes SHA-256 checksum of a file.
 fn compute_file_checksum(path: &Path) -> Result<String, VecXError> {
     use std::io::Read;
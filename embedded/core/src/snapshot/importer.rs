@@ -1,13 +1,18 @@
 //! Snapshot Importer
 //!
 //! Provides functionality to import snapshots with atomic restore guarantees.
-//! Uses a temp-file-then-replace strategy to ensure data integrity.
+//! Uses a temp-file-then-replace strategy to ensure data integrity. In low-disk mode
+//! the files are staged next to their destinations instead of in the temp directory.
 
+use super::encryption::ChunkCipher;
 use super::sqlite_backup;
 use super::types::*;
 use crate::error::VecXError;
+use crate::helper::acquire_connection;
+use crate::VectorXLite;
 use r2d2::Pool;
 use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::{Connection, OpenFlags};
 use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::Write;
@@ -19,6 +24,8 @@ pub struct SnapshotImporter {
     config: SnapshotConfig,
     /// Original index file paths for replacement
     index_file_paths: Vec<String>,
+    /// Metadata the streamed snapshot must match, e.g. from a snapshot catalog
+    expected_metadata: Option<SnapshotMetadata>,
 }
 
 impl SnapshotImporter {
@@ -33,6 +40,7 @@ impl SnapshotImporter {
             pool,
             config,
             index_file_paths: Vec::new(),
+            expected_metadata: None,
         }
     }
 
@@ -49,6 +57,18 @@ impl SnapshotImporter {
         self
     }
 
+    /// Sets metadata the streamed snapshot must match, e.g. one validated earlier and
+    /// kept in a snapshot catalog.
+    ///
+    /// An import whose files, sizes or checksums differ from `metadata` fails before
+    /// anything is restored. Snapshot ids and timestamps are not compared, so metadata
+    /// from `SnapshotExporter::export_metadata` matches a later export of the unchanged
+    /// database.
+    pub fn with_expected_metadata(mut self, metadata: SnapshotMetadata) -> Self {
+        self.expected_metadata = Some(metadata);
+        self
+    }
+
     /// Imports a snapshot from an iterator of chunks.
     ///
     /// This method:
@@ -56,6 +76,17 @@ impl SnapshotImporter {
     /// 2. Validates checksums
     /// 3. Atomically replaces the live database and index files
     ///
+    /// Chunks of an encrypted snapshot are decrypted with the configured key as they
+    /// arrive. A wrong key, a tampered chunk, or a key that does not match whether the
+    /// snapshot is encrypted fails the import with `VecXError::DataParsingError`
+    /// before anything is restored.
+    ///
+    /// With `low_disk` set, each index file is written straight to a staging file
+    /// beside the one it replaces and the database beside the live database file, then
+    /// fsynced. Nothing lands in `temp_dir` (except the database of an in-memory
+    /// destination), and the final renames stay on one filesystem instead of falling
+    /// back to a copy.
+    ///
     /// # Arguments
     ///
     /// * `chunks` - Iterator of snapshot chunks
@@ -67,15 +98,46 @@ impl SnapshotImporter {
     where
         I: IntoIterator<Item = SnapshotChunk>,
     {
-        let mut receiver = ChunkReceiver::new(&self.config.temp_dir)?;
+        self.import_with_progress(chunks, |_| {})
+    }
+
+    /// Imports a snapshot like `import`, reporting progress after each file chunk.
+    ///
+    /// The callback runs before the next chunk is pulled from `chunks`, so a slow
+    /// callback also slows down consumption of the stream. The last invocation
+    /// reports the snapshot's total bytes and file count.
+    pub fn import_with_progress<I, F>(
+        &self,
+        chunks: I,
+        mut callback: F,
+    ) -> Result<ImportResult, VecXError>
+    where
+        I: IntoIterator<Item = SnapshotChunk>,
+        F: FnMut(ImportProgress),
+    {
+        let mut receiver =
+            ChunkReceiver::new(&self.config.temp_dir, self.config.encryption_key.as_ref())?;
+        if self.config.low_disk {
+            receiver = receiver.with_staging_paths(self.staging_paths()?);
+        }
 
         // Process all chunks
         for chunk in chunks {
+            let current_file = chunk.file_chunk.as_ref().map(|c| c.file_name.clone());
             receiver.receive_chunk(chunk)?;
+
+            if let Some(current_file) = current_file {
+                callback(ImportProgress {
+                    bytes_restored: receiver.bytes_received,
+                    files_restored: receiver.completed_files.len() as u32,
+                    current_file,
+                });
+            }
         }
 
         // Validate and finalize
         let import_data = receiver.finalize()?;
+        self.check_expected_metadata(&import_data.metadata)?;
 
         // Perform atomic restore
         self.atomic_restore(&import_data)?;
@@ -95,15 +157,8 @@ impl SnapshotImporter {
         }
 
         // Step 2: Restore HNSW index files
-        let index_files: Vec<_> = import_data
-            .files
-            .iter()
-            .filter(|(name, _)| name.starts_with("index_"))
-            .collect();
-
-        for (idx, (_, temp_path)) in index_files.iter().enumerate() {
-            if idx < self.index_file_paths.len() {
-                let dest_path = &self.index_file_paths[idx];
+        for (name, temp_path) in &import_data.files {
+            if let Some(dest_path) = self.index_file_destination(name) {
                 atomic_file_replace(temp_path, Path::new(dest_path))?;
             }
         }
@@ -111,12 +166,135 @@ impl SnapshotImporter {
         Ok(())
     }
 
+    /// Checks the streamed snapshot against the metadata set with
+    /// `with_expected_metadata`, if any.
+    fn check_expected_metadata(&self, metadata: &SnapshotMetadata) -> Result<(), VecXError> {
+        let Some(expected) = &self.expected_metadata else {
+            return Ok(());
+        };
+        let mismatch = |reason: String| {
+            VecXError::Other(format!(
+                "Snapshot does not match the expected metadata: {}",
+                reason
+            ))
+        };
+
+        if metadata.version != expected.version {
+            return Err(mismatch(format!(
+                "version {} instead of {}",
+                metadata.version, expected.version
+            )));
+        }
+        if metadata.files.len() != expected.files.len() {
+            return Err(mismatch(format!(
+                "{} files instead of {}",
+                metadata.files.len(),
+                expected.files.len()
+            )));
+        }
+        for expected_file in &expected.files {
+            let file = metadata
+                .files
+                .iter()
+                .find(|file| file.file_name == expected_file.file_name)
+                .ok_or_else(|| mismatch(format!("missing file {}", expected_file.file_name)))?;
+            if file.file_size != expected_file.file_size
+                || file.checksum != expected_file.checksum
+            {
+                return Err(mismatch(format!(
+                    "file {} has size {} and checksum {} instead of {} and {}",
+                    file.file_name,
+                    file.file_size,
+                    file.checksum,
+                    expected_file.file_size,
+                    expected_file.checksum
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the live path an `index_<n>.idx` snapshot file replaces, the `n`-th
+    /// entry of the configured index paths.
+    fn index_file_destination(&self, file_name: &str) -> Option<&String> {
+        let idx: usize = file_name
+            .strip_prefix("index_")?
+            .strip_suffix(".idx")?
+            .parse()
+            .ok()?;
+        self.index_file_paths.get(idx)
+    }
+
+    /// Returns where a low-disk import writes each file: beside the live database file
+    /// and beside every index file it replaces.
+    fn staging_paths(&self) -> Result<HashMap<String, PathBuf>, VecXError> {
+        let mut paths = HashMap::new();
+        let db_path = acquire_connection(&self.pool, self.pool.connection_timeout())?
+            .path()
+            .filter(|path| !path.is_empty())
+            .map(str::to_string);
+        if let Some(db_path) = db_path {
+            paths.insert("database.db".to_string(), staging_path(Path::new(&db_path)));
+        }
+        for (idx, dest_path) in self.index_file_paths.iter().enumerate() {
+            paths.insert(format!("index_{}.idx", idx), staging_path(Path::new(dest_path)));
+        }
+        Ok(paths)
+    }
+
     /// Imports a snapshot from a vector of chunks.
     ///
     /// Convenience method for non-streaming imports.
     pub fn import_from_vec(&self, chunks: Vec<SnapshotChunk>) -> Result<ImportResult, VecXError> {
         self.import(chunks.into_iter())
     }
+
+    /// Opens a snapshot as a read-only `VectorXLite`, leaving the live database and
+    /// index files untouched.
+    ///
+    /// The snapshot is materialized in a temporary directory under the configured
+    /// `temp_dir`, with its collections pointed at the index files shipped in the
+    /// snapshot. Searches and counts work as usual, while writes fail with
+    /// `VecXError::SqlError`. Operations take turns on a single read-only connection.
+    /// The temporary files are removed when the returned instance is dropped.
+    pub fn open_read_only<I>(&self, chunks: I) -> Result<VectorXLite, VecXError>
+    where
+        I: IntoIterator<Item = SnapshotChunk>,
+    {
+        let mut receiver =
+            ChunkReceiver::new(&self.config.temp_dir, self.config.encryption_key.as_ref())?;
+        for chunk in chunks {
+            receiver.receive_chunk(chunk)?;
+        }
+        let mut import_data = receiver.finalize()?;
+        self.check_expected_metadata(&import_data.metadata)?;
+
+        // From here on the directory belongs to the returned instance
+        let snapshot_dir = SnapshotTempDir(std::mem::take(&mut import_data.temp_dir));
+        let db_path = import_data
+            .files
+            .get("database.db")
+            .ok_or_else(|| VecXError::Other("No database file in snapshot".to_string()))?;
+        sqlite_backup::relocate_index_files(db_path, &snapshot_dir.0)?;
+
+        let conn = Connection::open_with_flags(
+            db_path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY
+                | OpenFlags::SQLITE_OPEN_URI
+                | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        Ok(VectorXLite::from_connection(conn)?.with_snapshot_dir(snapshot_dir))
+    }
+}
+
+/// Temporary directory holding the files of a snapshot opened with
+/// `SnapshotImporter::open_read_only`, removed on drop.
+pub(crate) struct SnapshotTempDir(PathBuf);
+
+impl Drop for SnapshotTempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
 }
 
 /// Receives and assembles snapshot chunks into files.
@@ -125,12 +303,22 @@ struct ChunkReceiver {
     metadata: Option<SnapshotMetadata>,
     file_writers: HashMap<String, FileWriter>,
     completed_files: HashMap<String, PathBuf>,
+    /// Files written outside `temp_dir`, keyed by snapshot file name
+    staging_paths: HashMap<String, PathBuf>,
+    /// Whether completed files are fsynced
+    sync: bool,
+    /// Decrypts file chunks when an encryption key is configured
+    cipher: Option<ChunkCipher>,
     received_sequences: Vec<u64>,
+    bytes_received: u64,
     finalized: bool,
 }
 
 impl ChunkReceiver {
-    fn new(base_temp_dir: &Path) -> Result<Self, VecXError> {
+    fn new(
+        base_temp_dir: &Path,
+        encryption_key: Option<&SnapshotEncryptionKey>,
+    ) -> Result<Self, VecXError> {
         // Create a unique temp directory for this import
         let import_id = SnapshotMetadata::generate_id();
         let temp_dir = base_temp_dir.join(format!("import_{}", import_id));
@@ -143,11 +331,38 @@ impl ChunkReceiver {
             metadata: None,
             file_writers: HashMap::new(),
             completed_files: HashMap::new(),
+            staging_paths: HashMap::new(),
+            sync: false,
+            cipher: encryption_key.map(ChunkCipher::new),
             received_sequences: Vec::new(),
+            bytes_received: 0,
             finalized: false,
         })
     }
 
+    /// Writes the given files to their staging paths instead of `temp_dir` and
+    /// fsyncs every completed file.
+    fn with_staging_paths(mut self, staging_paths: HashMap<String, PathBuf>) -> Self {
+        self.staging_paths = staging_paths;
+        self.sync = true;
+        self
+    }
+
+    fn file_path(&self, file_name: &str) -> PathBuf {
+        self.staging_paths
+            .get(file_name)
+            .cloned()
+            .unwrap_or_else(|| self.temp_dir.join(file_name))
+    }
+
+    fn close_writer(&self, mut writer: FileWriter) -> Result<(), VecXError> {
+        writer.flush()?;
+        if self.sync {
+            writer.sync()?;
+        }
+        Ok(())
+    }
+
     fn receive_chunk(&mut self, chunk: SnapshotChunk) -> Result<(), VecXError> {
         if self.finalized {
             return Err(VecXError::Other("Import already finalized".to_string()));
@@ -158,6 +373,20 @@ impl ChunkReceiver {
 
         // Handle metadata (first chunk)
         if let Some(metadata) = chunk.metadata {
+            match (metadata.encrypted, self.cipher.is_some()) {
+                (true, false) => {
+                    return Err(VecXError::DataParsingError(
+                        "Snapshot is encrypted but no encryption key is configured".to_string(),
+                    ));
+                }
+                (false, true) => {
+                    return Err(VecXError::DataParsingError(
+                        "Snapshot is not encrypted but an encryption key is configured"
+                            .to_string(),
+                    ));
+                }
+                _ => {}
+            }
             self.metadata = Some(metadata);
         }
 
@@ -176,23 +405,28 @@ impl ChunkReceiver {
 
     fn write_file_chunk(&mut self, chunk: FileChunk) -> Result<(), VecXError> {
         let file_name = chunk.file_name.clone();
+        let data = match &self.cipher {
+            Some(cipher) => cipher.decrypt(&file_name, chunk.offset, &chunk.data)?,
+            None => chunk.data,
+        };
 
         // Get or create file writer
         if !self.file_writers.contains_key(&file_name) {
-            let file_path = self.temp_dir.join(&file_name);
+            let file_path = self.file_path(&file_name);
             let writer = FileWriter::new(&file_path)?;
             self.file_writers.insert(file_name.clone(), writer);
         }
 
         let writer = self.file_writers.get_mut(&file_name).unwrap();
-        writer.write(&chunk.data, chunk.offset)?;
+        writer.write(&data, chunk.offset)?;
+        self.bytes_received += data.len() as u64;
 
         // If this is the last chunk for this file, close it
         if chunk.is_last_chunk {
-            if let Some(mut writer) = self.file_writers.remove(&file_name) {
-                writer.flush()?;
-                self.completed_files
-                    .insert(file_name, self.temp_dir.join(&chunk.file_name));
+            if let Some(writer) = self.file_writers.remove(&file_name) {
+                self.close_writer(writer)?;
+                let file_path = self.file_path(&file_name);
+                self.completed_files.insert(file_name, file_path);
             }
         }
 
@@ -201,10 +435,11 @@ impl ChunkReceiver {
 
     fn finalize(mut self) -> Result<ImportData, VecXError> {
         // Close any remaining open files
-        for (name, mut writer) in self.file_writers.drain() {
-            writer.flush()?;
-            self.completed_files
-                .insert(name.clone(), self.temp_dir.join(&name));
+        let file_writers = std::mem::take(&mut self.file_writers);
+        for (name, writer) in file_writers {
+            self.close_writer(writer)?;
+            let file_path = self.file_path(&name);
+            self.completed_files.insert(name, file_path);
         }
 
         // Validate we received metadata - use take() to move out of Option
@@ -237,19 +472,24 @@ impl ChunkReceiver {
         // Use std::mem::take to move out of self without triggering Drop issues
         let completed_files = std::mem::take(&mut self.completed_files);
         let temp_dir = std::mem::take(&mut self.temp_dir);
+        let staged_files = std::mem::take(&mut self.staging_paths).into_values().collect();
 
         Ok(ImportData {
             metadata,
             files: completed_files,
             temp_dir,
+            staged_files,
         })
     }
 }
 
 impl Drop for ChunkReceiver {
     fn drop(&mut self) {
-        // Clean up temp directory on error/drop
+        // Clean up temp directory and staged files on error/drop
         let _ = fs::remove_dir_all(&self.temp_dir);
+        for path in self.staging_paths.values() {
+            let _ = fs::remove_file(path);
+        }
     }
 }
 
@@ -258,12 +498,17 @@ struct ImportData {
     metadata: SnapshotMetadata,
     files: HashMap<String, PathBuf>,
     temp_dir: PathBuf,
+    /// Files written outside `temp_dir`; those renamed into place are already gone
+    staged_files: Vec<PathBuf>,
 }
 
 impl Drop for ImportData {
     fn drop(&mut self) {
-        // Clean up temp directory
+        // Clean up temp directory and staged files
         let _ = fs::remove_dir_all(&self.temp_dir);
+        for path in &self.staged_files {
+            let _ = fs::remove_file(path);
+        }
     }
 }
 
@@ -298,6 +543,19 @@ impl FileWriter {
             VecXError::IoError(format!("Failed to flush file: {}", e))
         })
     }
+
+    fn sync(&mut self) -> Result<(), VecXError> {
+        self.file.sync_all().map_err(|e| {
+            VecXError::IoError(format!("Failed to sync file {}: {}", self.path.display(), e))
+        })
+    }
+}
+
+/// Returns the path a low-disk import stages the replacement of `dest` at.
+fn staging_path(dest: &Path) -> PathBuf {
+    let mut path = dest.as_os_str().to_owned();
+    path.push(".import");
+    PathBuf::from(path)
 }
 
 /// Atomically replaces a file using rename.
@@ -9,6 +9,7 @@
 //! - HNSW index file handling
 //! - Streaming chunk support for large snapshots
 //! - Atomic restore with temp file strategy
+//! - Optional ChaCha20-Poly1305 encryption of file chunks
 //!
 //! # Usage
 //!
@@ -26,6 +27,7 @@
 //! ```
 
 mod types;
+mod encryption;
 mod exporter;
 mod importer;
 mod sqlite_backup;
@@ -33,3 +35,5 @@ mod sqlite_backup;
 pub use types::*;
 pub use exporter::SnapshotExporter;
 pub use importer::SnapshotImporter;
+pub(crate) use importer::SnapshotTempDir;
+pub(crate) use sqlite_backup::{backup_connection, get_index_files_from};
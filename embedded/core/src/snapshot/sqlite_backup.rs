@@ -3,7 +3,9 @@
 //! Uses SQLite's online backup API to create consistent snapshots
 //! of both in-memory and on-disk databases.
 
+use crate::constant::DEFAULT_SQLITE_TIMEOUT;
 use crate::error::VecXError;
+use crate::helper::acquire_connection;
 use r2d2::Pool;
 use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::Connection;
@@ -13,6 +15,9 @@ use std::time::Duration;
 /// Number of pages to copy per backup step
 const PAGES_PER_STEP: i32 = 100;
 
+/// Number of pages to copy per backup step in online mode
+const ONLINE_PAGES_PER_STEP: i32 = 8;
+
 /// Delay between backup steps in milliseconds
 const STEP_DELAY_MS: u64 = 10;
 
@@ -35,17 +40,77 @@ pub fn backup_database(
     dest_path: &Path,
 ) -> Result<u64, VecXError> {
     // Get a connection from the pool
-    let source_conn = pool.get().map_err(|e| {
-        VecXError::Other(format!("Failed to get connection for backup: {}", e))
+    let source_conn = acquire_connection(pool, pool.connection_timeout())?;
+
+    backup_database_from(&source_conn, dest_path, false)
+}
+
+/// Performs a backup without holding a pooled connection or blocking writers.
+///
+/// The database is read through a dedicated connection, so every pooled connection
+/// stays available to writers, and is copied in small steps with a pause between them.
+/// In WAL mode the dedicated connection pins a read snapshot, so commits made during
+/// the backup neither wait for it nor restart it. In-memory databases cannot be opened
+/// twice and fall back to `backup_database`.
+///
+/// # Arguments
+///
+/// * `pool` - Connection pool to the source database
+/// * `dest_path` - Path where the backup will be written
+pub fn backup_database_online(
+    pool: &Pool<SqliteConnectionManager>,
+    dest_path: &Path,
+) -> Result<u64, VecXError> {
+    let db_path = acquire_connection(pool, pool.connection_timeout())?
+        .path()
+        .filter(|path| !path.is_empty())
+        .map(str::to_string);
+    let Some(db_path) = db_path else {
+        return backup_database(pool, dest_path);
+    };
+
+    let source_conn = Connection::open(&db_path).map_err(|e| {
+        VecXError::SqlError(format!("Failed to open source database: {}", e))
     })?;
+    source_conn.busy_timeout(Duration::from_millis(u64::from(DEFAULT_SQLITE_TIMEOUT)))?;
+
+    let journal_mode: String =
+        source_conn.query_row("PRAGMA journal_mode", [], |row| row.get(0))?;
+    let pin_snapshot = journal_mode.eq_ignore_ascii_case("wal");
+    if pin_snapshot {
+        source_conn.execute_batch("BEGIN")?;
+        source_conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))?;
+    }
+
+    let file_size = backup_database_from(&source_conn, dest_path, true)?;
+
+    if pin_snapshot {
+        source_conn.execute_batch("COMMIT")?;
+    }
+    Ok(file_size)
+}
 
+/// Performs a backup of the database through an already acquired connection.
+///
+/// Used when the backup must see the same state as other reads on that connection,
+/// e.g. while it holds an open transaction. `online` copies fewer pages per step.
+pub(crate) fn backup_database_from(
+    source_conn: &Connection,
+    dest_path: &Path,
+    online: bool,
+) -> Result<u64, VecXError> {
     // Open destination database (mutable for backup API)
     let mut dest_conn = Connection::open(dest_path).map_err(|e| {
         VecXError::SqlError(format!("Failed to open destination database: {}", e))
     })?;
 
     // Perform the backup
-    backup_connection(&source_conn, &mut dest_conn)?;
+    let pages_per_step = if online {
+        ONLINE_PAGES_PER_STEP
+    } else {
+        PAGES_PER_STEP
+    };
+    backup_in_steps(source_conn, &mut dest_conn, pages_per_step)?;
 
     // Get file size
     let file_size = std::fs::metadata(dest_path)
@@ -56,9 +121,18 @@ pub fn backup_database(
 }
 
 /// Performs backup from one connection to another using SQLite backup API.
-fn backup_connection(
+pub(crate) fn backup_connection(
+    source: &rusqlite::Connection,
+    dest: &mut rusqlite::Connection,
+) -> Result<(), VecXError> {
+    backup_in_steps(source, dest, PAGES_PER_STEP)
+}
+
+/// Copies `pages_per_step` pages at a time, pausing between steps.
+fn backup_in_steps(
     source: &rusqlite::Connection,
     dest: &mut rusqlite::Connection,
+    pages_per_step: i32,
 ) -> Result<(), VecXError> {
     // Use rusqlite's backup API
     let backup = rusqlite::backup::Backup::new(source, dest).map_err(|e| {
@@ -68,7 +142,7 @@ fn backup_connection(
     // Perform backup in steps to allow for progress tracking and
     // to avoid blocking the source database for too long
     loop {
-        let step_result = backup.step(PAGES_PER_STEP).map_err(|e| {
+        let step_result = backup.step(pages_per_step).map_err(|e| {
             VecXError::SqlError(format!("Backup step failed: {}", e))
         })?;
 
@@ -150,9 +224,7 @@ pub fn restore_database(
     })?;
 
     // Get a mutable connection to the destination
-    let mut dest_conn = dest_pool.get().map_err(|e| {
-        VecXError::Other(format!("Failed to get destination connection: {}", e))
-    })?;
+    let mut dest_conn = acquire_connection(dest_pool, dest_pool.connection_timeout())?;
 
     // Perform restore (backup from file to destination)
     // We need to dereference to get the underlying Connection
@@ -203,10 +275,13 @@ pub fn restore_from_memory(
 ///
 /// A vector of index file paths.
 pub fn get_index_files(pool: &Pool<SqliteConnectionManager>) -> Result<Vec<String>, VecXError> {
-    let conn = pool.get().map_err(|e| {
-        VecXError::Other(format!("Failed to get connection: {}", e))
-    })?;
+    let conn = acquire_connection(pool, pool.connection_timeout())?;
 
+    get_index_files_from(&conn)
+}
+
+/// Gets the list of HNSW index files through an already acquired connection.
+pub(crate) fn get_index_files_from(conn: &Connection) -> Result<Vec<String>, VecXError> {
     // Query sqlite_master for vectorlite virtual tables
     let mut stmt = conn
         .prepare(
@@ -235,6 +310,53 @@ pub fn get_index_files(pool: &Pool<SqliteConnectionManager>) -> Result<Vec<Strin
     Ok(index_files)
 }
 
+/// Points the vectorlite tables of a restored database at the index files shipped with
+/// its snapshot, `index_<n>.idx` in `index_dir`, and switches it to WAL mode so it can be
+/// opened read-only.
+///
+/// `n` is the position of the table's index file in `get_index_files_from`, matching the
+/// names the exporter gives the copies. Tables whose index file was not part of the
+/// snapshot get a fresh path in `index_dir` as well, so the original file is never used.
+pub(crate) fn relocate_index_files(db_path: &Path, index_dir: &Path) -> Result<(), VecXError> {
+    let conn = Connection::open(db_path)?;
+    let index_files = get_index_files_from(&conn)?;
+
+    let tables: Vec<(String, String)> = conn
+        .prepare(
+            "SELECT name, sql FROM sqlite_master WHERE type='table' AND sql LIKE '%vectorlite%'",
+        )?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+
+    conn.pragma_update(None, "writable_schema", true)?;
+    for (name, sql) in tables {
+        let Some(path) = extract_index_path(&sql) else {
+            continue;
+        };
+        let (Some(idx), Some(start)) = (
+            index_files.iter().position(|file| *file == path),
+            sql.rfind(&path),
+        ) else {
+            continue;
+        };
+        let relocated = index_dir.join(format!("index_{}.idx", idx));
+        let relocated_sql = format!(
+            "{}{}{}",
+            &sql[..start],
+            relocated.to_string_lossy(),
+            &sql[start + path.len()..]
+        );
+        conn.execute(
+            "UPDATE sqlite_master SET sql = ?1 WHERE type = 'table' AND name = ?2",
+            (relocated_sql, name),
+        )?;
+    }
+    conn.pragma_update(None, "writable_schema", false)?;
+    conn.pragma_update(None, "journal_mode", "WAL")?;
+
+    Ok(())
+}
+
 /// Extracts the index file path from a vectorlite CREATE VIRTUAL TABLE statement.
 fn extract_index_path(sql: &str) -> Option<String> {
     // Look for the last argument in USING vectorlite(...)
@@ -242,7 +364,8 @@ fn extract_index_path(sql: &str) -> Option<String> {
     let sql_lower = sql.to_lowercase();
     let using_pos = sql_lower.find("using vectorlite(")?;
     let start = using_pos + "using vectorlite(".len();
-    let end = sql[start..].find(')')? + start;
+    // The hnsw(...) argument has its own parentheses, so match the outermost one
+    let end = sql.rfind(')').filter(|&end| end >= start)?;
     let args = &sql[start..end];
 
     // Split by comma and take the last non-empty argument that looks like a path
@@ -17,6 +17,34 @@ pub struct SnapshotConfig {
     pub include_index_files: bool,
     /// Temporary directory for atomic restore operations
     pub temp_dir: PathBuf,
+    /// Whether to back up the database and copy index files while writers are
+    /// blocked, so both reflect the same point in time
+    pub consistent_export: bool,
+    /// Whether to back up the database through a dedicated connection in small steps,
+    /// so a long export does not hold back concurrent writers
+    pub online: bool,
+    /// Whether an import stages each file next to its destination instead of in
+    /// `temp_dir`, so no file is copied across filesystems on restore
+    pub low_disk: bool,
+    /// Key that file chunks are encrypted with on export and decrypted with on import
+    pub encryption_key: Option<SnapshotEncryptionKey>,
+}
+
+/// 256-bit ChaCha20-Poly1305 key for snapshot chunks, set with
+/// `SnapshotConfig::with_encryption`. Its `Debug` output does not show the key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SnapshotEncryptionKey(pub(crate) [u8; 32]);
+
+impl SnapshotEncryptionKey {
+    pub(crate) fn new(key: [u8; 32]) -> Self {
+        Self(key)
+    }
+}
+
+impl std::fmt::Debug for SnapshotEncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SnapshotEncryptionKey(..)")
+    }
 }
 
 impl Default for SnapshotConfig {
@@ -25,6 +53,10 @@ impl Default for SnapshotConfig {
             chunk_size: DEFAULT_CHUNK_SIZE,
             include_index_files: true,
             temp_dir: std::env::temp_dir(),
+            consistent_export: false,
+            online: false,
+            low_disk: false,
+            encryption_key: None,
         }
     }
 }
@@ -44,6 +76,34 @@ impl SnapshotConfig {
         self.temp_dir = dir;
         self
     }
+
+    pub fn with_consistent_export(mut self, consistent: bool) -> Self {
+        self.consistent_export = consistent;
+        self
+    }
+
+    pub fn with_online(mut self, online: bool) -> Self {
+        self.online = online;
+        self
+    }
+
+    pub fn with_low_disk(mut self, low_disk: bool) -> Self {
+        self.low_disk = low_disk;
+        self
+    }
+
+    /// Encrypts the data of every file chunk with ChaCha20-Poly1305 under `key` on
+    /// export, and decrypts it on import.
+    ///
+    /// The metadata chunk, including file names, sizes and checksums, stays readable
+    /// and marks the snapshot as encrypted. An importer fails with
+    /// `VecXError::DataParsingError` when its key does not match the exporter's, when
+    /// a chunk was altered, or when it has a key for an unencrypted snapshot or none
+    /// for an encrypted one.
+    pub fn with_encryption(mut self, key: [u8; 32]) -> Self {
+        self.encryption_key = Some(SnapshotEncryptionKey::new(key));
+        self
+    }
 }
 
 /// Type of file in a snapshot
@@ -104,6 +164,8 @@ pub struct SnapshotMetadata {
     pub version: u32,
     /// SHA-256 checksum of the entire snapshot
     pub checksum: String,
+    /// Whether the data of the file chunks is encrypted
+    pub encrypted: bool,
 }
 
 impl SnapshotMetadata {
@@ -155,6 +217,17 @@ pub struct FileChunk {
     pub is_last_chunk: bool,
 }
 
+/// Progress of a snapshot import, reported after each file chunk is written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportProgress {
+    /// Bytes of file data written so far
+    pub bytes_restored: u64,
+    /// Number of files fully received so far
+    pub files_restored: u32,
+    /// Name of the file the latest chunk belonged to
+    pub current_file: String,
+}
+
 /// Result of a snapshot import operation
 #[derive(Debug, Clone)]
 pub struct ImportResult {
@@ -0,0 +1,142 @@
+use crate::helper::validate_collection_name;
+
+/// Represents a delete operation for removing several vectors from a collection
+/// in a single transaction.
+///
+/// # Fields
+///
+/// * `collection_name` - The name of the collection to delete from
+/// * `ids` - The unique identifiers of the vectors to delete
+///
+/// # Examples
+///
+/// ```
+/// use vector_xlite::types::BatchDelete;
+///
+/// let batch_delete = BatchDelete::builder()
+///     .collection_name("products")
+///     .ids(vec![1, 2, 3])
+///     .build()
+///     .expect("Failed to build batch delete");
+/// ```
+#[derive(Debug, Clone)]
+pub struct BatchDelete {
+    pub collection_name: String,
+    pub ids: Vec<u64>,
+}
+
+impl BatchDelete {
+    /// Creates a new builder for constructing a BatchDelete.
+    pub fn builder() -> BatchDeleteBuilder {
+        BatchDeleteBuilder::default()
+    }
+}
+
+/// Builder for constructing BatchDelete instances with validation.
+#[derive(Debug, Default)]
+pub struct BatchDeleteBuilder {
+    collection_name: Option<String>,
+    ids: Option<Vec<u64>>,
+}
+
+impl BatchDeleteBuilder {
+    /// Sets the collection name for the delete operation.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the collection
+    pub fn collection_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.collection_name = Some(name.into());
+        self
+    }
+
+    /// Sets the IDs of the vectors to delete.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The unique identifiers of the vectors
+    pub fn ids(mut self, ids: Vec<u64>) -> Self {
+        self.ids = Some(ids);
+        self
+    }
+
+    /// Builds the BatchDelete with validation.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BatchDelete)` - Successfully built BatchDelete
+    /// * `Err(String)` - Validation error message
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// * `collection_name` is not provided or not a plain SQL identifier
+    /// * `ids` is not provided or empty
+    pub fn build(self) -> Result<BatchDelete, String> {
+        // Validate collection_name
+        let collection_name = self
+            .collection_name
+            .ok_or_else(|| "Collection name must be provided.".to_string())?;
+        validate_collection_name(&collection_name)?;
+
+        // Validate IDs
+        let ids = self
+            .ids
+            .filter(|ids| !ids.is_empty())
+            .ok_or_else(|| "At least one ID must be provided.".to_string())?;
+
+        Ok(BatchDelete {
+            collection_name,
+            ids,
+        })
+    }
+}
+
+/// Outcome of a batch delete.
+///
+/// # Fields
+///
+/// * `deleted_count` - Number of requested ids that were found and deleted
+/// * `missing_count` - Number of requested ids that did not exist in the collection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DeleteSummary {
+    pub deleted_count: u64,
+    pub missing_count: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_delete_builder_success() {
+        let batch_delete = BatchDelete::builder()
+            .collection_name("test_collection")
+            .ids(vec![1, 2, 3])
+            .build();
+
+        assert!(batch_delete.is_ok());
+        let batch = batch_delete.unwrap();
+        assert_eq!(batch.collection_name, "test_collection");
+        assert_eq!(batch.ids, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_batch_delete_builder_missing_collection_name() {
+        let batch_delete = BatchDelete::builder().ids(vec![1]).build();
+
+        assert!(batch_delete.is_err());
+        assert_eq!(batch_delete.unwrap_err(), "Collection name must be provided.");
+    }
+
+    #[test]
+    fn test_batch_delete_builder_empty_ids() {
+        let batch_delete = BatchDelete::builder()
+            .collection_name("test")
+            .ids(vec![])
+            .build();
+
+        assert!(batch_delete.is_err());
+        assert_eq!(batch_delete.unwrap_err(), "At least one ID must be provided.");
+    }
+}
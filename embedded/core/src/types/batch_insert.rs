@@ -0,0 +1,60 @@
+use crate::error::VecXError;
+
+/// Controls how `insert_batch` reacts to points that fail to insert.
+///
+/// # Fields
+///
+/// * `stop_on_error` - When true (the default) the batch is all-or-nothing: the first
+///   failure rolls back every insert and is returned as the error. When false, failed
+///   points are skipped and reported while all other points are committed.
+/// * `dedupe_ids` - When false (the default) a batch holding the same id twice for
+///   one collection is rejected before anything is written. When true only the last
+///   occurrence of each id is inserted and earlier ones are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use vector_xlite::types::BatchOptions;
+///
+/// let best_effort = BatchOptions::default().with_stop_on_error(false);
+/// assert!(!best_effort.stop_on_error);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchOptions {
+    pub stop_on_error: bool,
+    pub dedupe_ids: bool,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            stop_on_error: true,
+            dedupe_ids: false,
+        }
+    }
+}
+
+impl BatchOptions {
+    pub fn with_stop_on_error(mut self, stop_on_error: bool) -> Self {
+        self.stop_on_error = stop_on_error;
+        self
+    }
+
+    pub fn with_dedupe_ids(mut self, dedupe_ids: bool) -> Self {
+        self.dedupe_ids = dedupe_ids;
+        self
+    }
+}
+
+/// Outcome of a batch insert.
+///
+/// # Fields
+///
+/// * `succeeded` - Number of points that were inserted
+/// * `failed` - Position in the batch and error of every point that was not inserted,
+///   in batch order
+#[derive(Debug, Default)]
+pub struct BatchResult {
+    pub succeeded: usize,
+    pub failed: Vec<(usize, VecXError)>,
+}
@@ -1,5 +1,9 @@
-use crate:: types::enums::DistanceFunction;
+use crate:: types::enums::{DistanceFunction, RowidStrategy};
+use crate::error::VecXError;
+use crate::helper::validate_collection_name;
+use std::path::Path;
 
+#[derive(Clone)]
 pub struct CollectionConfig {
     pub collection_name: String,
     pub dimension: u16,
@@ -7,6 +11,18 @@ pub struct CollectionConfig {
     pub index_file_path: Option<String>,
     pub max_elements: u32,
     pub payload_table_schema: Option<String>,
+    pub random_seed: Option<u64>,
+    /// Creates only the payload table, without a vector index.
+    pub payload_only: bool,
+    /// Creates only the vector index, without a payload table.
+    pub no_payload: bool,
+    /// Rejects inserts into an IP collection whose vector norm is far off the mean
+    /// norm of the collection's earlier inserts.
+    pub strict_ip: bool,
+    /// How point ids map to the rowids points are stored under.
+    pub rowid_strategy: RowidStrategy,
+    /// Payload query used by searches that do not set their own.
+    pub default_payload_search: Option<String>,
 }
 
 impl Default for CollectionConfig {
@@ -18,6 +34,12 @@ impl Default for CollectionConfig {
             payload_table_schema: None,
             index_file_path: None,
             max_elements: 100000,
+            random_seed: None,
+            payload_only: false,
+            no_payload: false,
+            strict_ip: false,
+            rowid_strategy: RowidStrategy::Direct,
+            default_payload_search: None,
         }
     }
 }
@@ -26,16 +48,89 @@ impl CollectionConfig {
     pub fn builder() -> CollectionConfigBuilder {
         CollectionConfigBuilder::default()
     }
+
+    /// Checks the config before any table is created:
+    /// - collection_name must not be empty or blank
+    /// - collection_name must be a plain SQL identifier (ASCII letters, digits and
+    ///   underscores, not starting with a digit)
+    /// - dimension must be greater than 0
+    /// - max_elements must be greater than 0
+    /// - strict_ip requires IP distance and a vector index
+    /// - no_payload excludes payload_only, payload_table_schema and
+    ///   default_payload_search
+    /// - default_payload_search, when set, must not be blank
+    pub fn validate(&self) -> Result<(), VecXError> {
+        match self.validation_error() {
+            Some(message) => Err(VecXError::InvalidQueryError(message)),
+            None => Ok(()),
+        }
+    }
+
+    fn validation_error(&self) -> Option<String> {
+        let name = &self.collection_name;
+        if name.trim().is_empty() {
+            return Some("collection_name must not be empty.".into());
+        }
+        if let Err(message) = validate_collection_name(name) {
+            return Some(message);
+        }
+        if self.dimension == 0 {
+            return Some(format!(
+                "collection '{}' must have a vector dimension greater than 0",
+                name
+            ));
+        }
+        if self.max_elements == 0 {
+            return Some(format!(
+                "collection '{}' must allow max_elements greater than 0",
+                name
+            ));
+        }
+        if self.strict_ip && (self.payload_only || self.distance != DistanceFunction::IP) {
+            return Some(format!(
+                "collection '{}' must use IP distance to enable strict_ip",
+                name
+            ));
+        }
+        if self.no_payload
+            && (self.payload_only
+                || self.payload_table_schema.is_some()
+                || self.default_payload_search.is_some())
+        {
+            return Some(format!(
+                "collection '{}' has no payload table and cannot be payload_only or take a payload_table_schema or default_payload_search",
+                name
+            ));
+        }
+        if self
+            .default_payload_search
+            .as_deref()
+            .is_some_and(|query| query.trim().is_empty())
+        {
+            return Some(format!(
+                "collection '{}' must not have a blank default_payload_search",
+                name
+            ));
+        }
+        None
+    }
 }
 
 #[derive(Default)]
 pub struct CollectionConfigBuilder {
     dimension: Option<u16>,
     distance: Option<DistanceFunction>,
+    index_dir: Option<String>,
     index_file_path: Option<String>,
     max_elements: Option<u32>,
     name: Option<String>,
     payload_table_schema: Option<String>,
+    random_seed: Option<u64>,
+    payload_only: bool,
+    no_payload: bool,
+    strict_ip: bool,
+    rowid_strategy: RowidStrategy,
+    default_payload_search: Option<String>,
 }
 
 impl CollectionConfigBuilder {
@@ -64,29 +159,128 @@ impl CollectionConfigBuilder {
         self
     }
 
+    /// Stores the index at `<dir>/<collection_name>.idx` unless `index_file_path` is
+    /// set explicitly.
+    pub fn index_dir<S: Into<String>>(mut self, dir: S) -> Self {
+        self.index_dir = Some(dir.into());
+        self
+    }
+
     pub fn max_elements(mut self, max_elems: u32) -> Self {
         self.max_elements = Some(max_elems);
         self
     }
 
-    pub fn build(mut self) -> Result<CollectionConfig, &'static str> {
+    /// Sizes the index for a bulk load of `expected_elements` points by setting
+    /// `max_elements` to that count plus 25% headroom.
+    ///
+    /// vectorlite rejects inserts beyond `max_elements` and reserves the HNSW graph's
+    /// memory for all of them when the index is created, so the load neither fails
+    /// part way nor over-allocates the default 100,000 elements. The `.idx` file
+    /// itself only grows with the points stored; vectorlite cannot preallocate it.
+    pub fn preallocate_for(mut self, expected_elements: u32) -> Self {
+        self.max_elements = Some(expected_elements.saturating_add(expected_elements / 4));
+        self
+    }
+
+    /// Seeds the random level assignment of the HNSW graph, so identical inserts
+    /// build identical graphs and return identical search orderings.
+    pub fn random_seed(mut self, seed: u64) -> Self {
+        self.random_seed = Some(seed);
+        self
+    }
+
+    /// Creates only the payload table, for collections that are queried by payload
+    /// filters alone.
+    ///
+    /// Points of a payload-only collection carry an empty vector (`.vector(vec![])`):
+    /// inserts with a vector are rejected, and searches with an empty vector run the
+    /// payload query without a KNN step. Searching with a vector fails.
+    pub fn payload_only(mut self, payload_only: bool) -> Self {
+        self.payload_only = payload_only;
+        self
+    }
+
+    /// Creates only the vector index, without the payload table every other collection
+    /// gets, which saves its space for collections searched by vector alone.
+    ///
+    /// Inserts with a `payload_insert_query` and searches with a `payload_search_query`
+    /// are rejected. vectorlite cannot list the vectors of its index, so operations that
+    /// read every point through the payload table fail as well: `count_where`,
+    /// `has_any`, `estimate_memory`, `measure_recall`, `update_where`, payload
+    /// indexes, `compact_payload`, `rename_collection` and `persist_to`.
+    pub fn no_payload(mut self, no_payload: bool) -> Self {
+        self.no_payload = no_payload;
+        self
+    }
+
+    /// Guards an IP collection against vectors of very different magnitude, which
+    /// usually means a forgotten normalization: IP ranks large vectors first whatever
+    /// their direction.
+    ///
+    /// Every insert whose vector norm is more than 10 times above or below the mean
+    /// norm of the collection's earlier inserts fails with
+    /// `VecXError::InvalidQueryError`. Points of one `insert_batch` are checked against
+    /// the mean before the batch. Only IP collections accept this setting.
+    pub fn strict_ip(mut self, strict_ip: bool) -> Self {
+        self.strict_ip = strict_ip;
+        self
+    }
+
+    /// Maps point ids to rowids with `strategy` (default `RowidStrategy::Direct`).
+    pub fn rowid_strategy(mut self, strategy: RowidStrategy) -> Self {
+        self.rowid_strategy = strategy;
+        self
+    }
+
+    /// Stores a payload query, e.g. `select rowid, title from docs`, that every search of
+    /// the collection without its own `payload_search_query` runs with.
+    ///
+    /// The query is kept with the collection, so it also applies after reopening the
+    /// database. Renaming the collection points its `FROM` and `JOIN` clauses at the
+    /// renamed payload table.
+    pub fn default_payload_search(mut self, query: &str) -> Self {
+        self.default_payload_search = Some(query.to_string());
+        self
+    }
+
+    /// Builds the config and runs `CollectionConfig::validate` on it.
+    pub fn build(mut self) -> Result<CollectionConfig, String> {
         if self.name.is_none() {
             return Err("Collection_name must be provided.".into());
         }
 
-        if self.payload_table_schema.is_none(){
+        if self.payload_table_schema.is_none() && !self.no_payload {
             self.payload_table_schema = Some(format!("create table {no_payload_collection} ( rowid integer primary key );", no_payload_collection= self.name.as_ref().unwrap()));
         }
 
+        if self.index_file_path.is_none() {
+            self.index_file_path = self.index_dir.as_ref().map(|dir| {
+                let file_name = format!("{}.idx", self.name.as_ref().unwrap());
+                Path::new(dir).join(file_name).to_string_lossy().into_owned()
+            });
+        }
+
         let default = CollectionConfig::default();
         
-        Ok(CollectionConfig {
+        let config = CollectionConfig {
             collection_name: self.name.unwrap(),
             dimension: self.dimension.unwrap_or(default.dimension),
             distance: self.distance.unwrap_or(default.distance),
             payload_table_schema: self.payload_table_schema,
             index_file_path: self.index_file_path.or(default.index_file_path),
             max_elements: self.max_elements.unwrap_or(default.max_elements),
-        })
+            random_seed: self.random_seed.or(default.random_seed),
+            payload_only: self.payload_only,
+            no_payload: self.no_payload,
+            strict_ip: self.strict_ip,
+            rowid_strategy: self.rowid_strategy,
+            default_payload_search: self.default_payload_search,
+        };
+
+        match config.validation_error() {
+            Some(message) => Err(message),
+            None => Ok(config),
+        }
     }
 }
@@ -0,0 +1,16 @@
+/// Operation counts of a collection since the `VectorXLite` was created, as returned by
+/// `VectorXLite::collection_stats`.
+///
+/// Counts are kept in memory only and start from zero in every process.
+///
+/// # Fields
+///
+/// * `searches` - Successful searches, counting each query of `search_many`
+/// * `inserts` - Points written by successful inserts and upserts
+/// * `deletes` - Points removed by successful deletes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CollectionStats {
+    pub searches: u64,
+    pub inserts: u64,
+    pub deletes: u64,
+}
@@ -1,3 +1,4 @@
+use crate::helper::validate_collection_name;
 
 /// Represents a delete operation for removing a collection.
 ///
@@ -54,12 +55,13 @@ impl DeleteCollectionBuilder {
     /// # Errors
     ///
     /// Returns an error if:
-    /// * `collection_name` is not provided
+    /// * `collection_name` is not provided or not a plain SQL identifier
     pub fn build(self) -> Result<DeleteCollection, String> {
         // Validate collection_name
         let collection_name = self
             .collection_name
             .ok_or_else(|| "Collection name must be provided.".to_string())?;
+        validate_collection_name(&collection_name)?;
 
         Ok(DeleteCollection {
             collection_name,
@@ -1,3 +1,5 @@
+use crate::helper::validate_collection_name;
+
 /// Represents a delete operation for removing a vector from a collection.
 ///
 /// # Fields
@@ -30,7 +32,7 @@ impl DeletePoint {
 }
 
 /// Builder for constructing DeletePoint instances with validation.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct DeletePointBuilder {
     collection_name: Option<String>,
     id: Option<u64>,
@@ -67,13 +69,14 @@ impl DeletePointBuilder {
     /// # Errors
     ///
     /// Returns an error if:
-    /// * `collection_name` is not provided
+    /// * `collection_name` is not provided or not a plain SQL identifier
     /// * `id` is not provided
     pub fn build(self) -> Result<DeletePoint, String> {
         // Validate collection_name
         let collection_name = self
             .collection_name
             .ok_or_else(|| "Collection name must be provided.".to_string())?;
+        validate_collection_name(&collection_name)?;
 
         // Validate ID
         let id = self
@@ -123,4 +126,14 @@ mod tests {
         assert!(delete_point.is_err());
         assert_eq!(delete_point.unwrap_err(), "ID must be provided.");
     }
+
+    #[test]
+    fn test_delete_point_builder_rejects_sql_in_collection_name() {
+        let delete_point = DeletePoint::builder()
+            .collection_name("test; DROP TABLE test")
+            .id(1)
+            .build();
+
+        assert!(delete_point.is_err());
+    }
 }
@@ -1,3 +1,10 @@
+use crate::error::VecXError;
+
+/// Distance metric of a collection's vector index.
+///
+/// Search results are ordered by ascending `distance` for every metric. For `IP` the
+/// reported distance is `1 - inner product`, so the highest inner product ranks first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DistanceFunction {
     L2,
     Cosine,
@@ -13,3 +20,153 @@ impl DistanceFunction {
         }
     }
 }
+
+/// Sort direction for ordering search results by a payload column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+impl Direction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Direction::Asc => "ASC",
+            Direction::Desc => "DESC",
+        }
+    }
+}
+
+/// Which plan ran a search, as reported by `search_with_meta`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanKind {
+    /// KNN search without a payload filter.
+    VectorOnly,
+    /// The payload filter ran first and its rowids were pushed into the KNN search.
+    MetadataFirst,
+    /// The KNN search ran over the whole index and the payload filter was joined
+    /// afterwards.
+    VectorFirst,
+    /// The payload query alone, on a payload-only collection.
+    FilterOnly,
+}
+
+/// How `SearchPointBuilder::aggregate` reduces a payload column over the results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggOp {
+    Sum,
+    Avg,
+}
+
+/// How a payload filter is combined with the KNN search.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FilterStrategy {
+    /// Pushdown for selective filters (under 10k matching rows), post-filter otherwise.
+    #[default]
+    Auto,
+    /// Passes the matching rowids into `knn_search` as `rowid IN (...)`.
+    Pushdown,
+    /// Runs `knn_search` over the whole index and joins the payload filter afterwards.
+    PostFilter,
+}
+
+/// What `create_payload_index_with` does when the index already exists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OnConflict {
+    /// Keeps the existing index and succeeds.
+    #[default]
+    Ignore,
+    /// Fails with `VecXError::InvalidQueryError`.
+    Error,
+}
+
+/// Where SQLite keeps temporary tables and indexes, e.g. the sorter of a large
+/// `ORDER BY`, see `SqliteConnectionCustomizer::with_temp_store`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TempStore {
+    /// Uses SQLite's compile-time default, usually files.
+    #[default]
+    Default,
+    /// Writes temporary data to files in the temp directory.
+    File,
+    /// Keeps temporary data in memory.
+    Memory,
+}
+
+impl TempStore {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TempStore::Default => "DEFAULT",
+            TempStore::File => "FILE",
+            TempStore::Memory => "MEMORY",
+        }
+    }
+}
+
+/// How point ids are turned into the rowids a collection stores points under, e.g. to
+/// keep ids synced from an external system clear of rowids used by another.
+///
+/// The strategy applies wherever a point id is given: inserts, upserts, deletes and
+/// `restrict_to_ids`. Search results report the stored `rowid`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RowidStrategy {
+    /// The id is the rowid.
+    #[default]
+    Direct,
+    /// The rowid is the id plus a fixed offset; subtract it from a result's `rowid`
+    /// to get the id back.
+    Offset(i64),
+    /// The rowid is a stable 63-bit hash of the id. The id cannot be recovered from a
+    /// result's `rowid`, so store it in a payload column if it is needed back.
+    Hash,
+}
+
+impl RowidStrategy {
+    /// Returns the rowid the point with `id` is stored under.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VecXError::InvalidQueryError` if an offset moves the id outside the
+    /// non-negative rowid range.
+    pub fn rowid(&self, id: u64) -> Result<u64, VecXError> {
+        match *self {
+            RowidStrategy::Direct => Ok(id),
+            RowidStrategy::Offset(offset) => i64::try_from(id)
+                .ok()
+                .and_then(|id| id.checked_add(offset))
+                .and_then(|rowid| u64::try_from(rowid).ok())
+                .ok_or_else(|| {
+                    VecXError::InvalidQueryError(format!(
+                        "id {} with rowid offset {} is outside the rowid range",
+                        id, offset
+                    ))
+                }),
+            RowidStrategy::Hash => {
+                // splitmix64 finalizer, truncated to the positive rowid range
+                let mut z = id.wrapping_add(0x9E37_79B9_7F4A_7C15);
+                z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+                Ok((z ^ (z >> 31)) & i64::MAX as u64)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rowid_strategies() {
+        assert_eq!(RowidStrategy::Direct.rowid(42).unwrap(), 42);
+        assert_eq!(RowidStrategy::Offset(1000).rowid(42).unwrap(), 1042);
+        assert_eq!(RowidStrategy::Offset(-40).rowid(42).unwrap(), 2);
+        assert!(RowidStrategy::Offset(-50).rowid(42).is_err());
+        assert!(RowidStrategy::Offset(1).rowid(i64::MAX as u64).is_err());
+
+        let hashed = RowidStrategy::Hash.rowid(42).unwrap();
+        assert_eq!(hashed, RowidStrategy::Hash.rowid(42).unwrap());
+        assert_ne!(hashed, RowidStrategy::Hash.rowid(43).unwrap());
+        assert!(hashed <= i64::MAX as u64);
+    }
+}
@@ -1,10 +1,26 @@
+use crate::helper::{
+    dense_from_sparse, validate_collection_name, vector_from_json, BytesSummary, SparseSummary,
+    VectorSummary,
+};
+use std::collections::HashMap;
+use std::fmt;
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct InsertPoint {
     pub collection_name: String,
     pub id: Option<u64>,
     pub vector: Vec<f32>,
+    /// Raw little-endian `f32` bytes used instead of `vector` when set.
+    pub vector_bytes: Option<Vec<u8>>,
+    /// JSON array of numbers passed to `vector_from_json` as is, used instead of
+    /// `vector` when set.
+    pub vector_json: Option<String>,
     pub payload_insert_query: Option<String>,
+    /// Client-chosen key that makes retries of this insert safe, see
+    /// `InsertPointBuilder::idempotency_key`.
+    pub idempotency_key: Option<String>,
+    /// Number of elements of `vector_json`, counted when `build` parsed it.
+    pub(crate) vector_json_len: Option<usize>,
 }
 
 impl InsertPoint {
@@ -13,12 +29,52 @@ impl InsertPoint {
     }
 }
 
-#[derive(Debug, Default)]
+/// Prints only the first elements of the vector and its length.
+impl fmt::Debug for InsertPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InsertPoint")
+            .field("collection_name", &self.collection_name)
+            .field("id", &self.id)
+            .field("vector", &VectorSummary(&self.vector))
+            .field("vector_bytes", &self.vector_bytes.as_deref().map(BytesSummary))
+            .field("vector_json", &self.vector_json)
+            .field("payload_insert_query", &self.payload_insert_query)
+            .field("idempotency_key", &self.idempotency_key)
+            .finish()
+    }
+}
+
+#[derive(Clone, Default)]
 pub struct InsertPointBuilder {
     collection_name: Option<String>,
     id: Option<u64>,
     vector: Option<Vec<f32>>,
+    vector_bytes: Option<Vec<u8>>,
+    vector_json: Option<String>,
+    sparse_vector: Option<(HashMap<usize, f32>, usize)>,
     payload_insert_query: Option<String>,
+    idempotency_key: Option<String>,
+}
+
+impl fmt::Debug for InsertPointBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InsertPointBuilder")
+            .field("collection_name", &self.collection_name)
+            .field("id", &self.id)
+            .field("vector", &self.vector.as_deref().map(VectorSummary))
+            .field("vector_bytes", &self.vector_bytes.as_deref().map(BytesSummary))
+            .field("vector_json", &self.vector_json)
+            .field(
+                "sparse_vector",
+                &self
+                    .sparse_vector
+                    .as_ref()
+                    .map(|(entries, dimension)| SparseSummary(entries, *dimension)),
+            )
+            .field("payload_insert_query", &self.payload_insert_query)
+            .field("idempotency_key", &self.idempotency_key)
+            .finish()
+    }
 }
 
 impl InsertPointBuilder {
@@ -37,29 +93,129 @@ impl InsertPointBuilder {
         self
     }
 
+    /// Sets the vector as raw little-endian `f32` bytes, e.g. straight from an Arrow or
+    /// NumPy buffer. The bytes are stored as is, without converting them to `Vec<f32>`.
+    /// The length must be `4 * dimension` of the collection.
+    pub fn vector_bytes(mut self, bytes: &[u8]) -> Self {
+        self.vector_bytes = Some(bytes.to_vec());
+        self
+    }
+
+    /// Sets the vector as a JSON array of numbers, e.g. as received from an upstream
+    /// service. The string is handed to SQLite's `vector_from_json` as is, without
+    /// converting it to `Vec<f32>` and back. `build` checks that it is a non-empty array
+    /// of finite numbers; its length must match the collection dimension.
+    pub fn vector_json(mut self, json: &str) -> Self {
+        self.vector_json = Some(json.to_string());
+        self
+    }
+
+    /// Sets the vector from its non-zero entries, e.g. a sparse embedding, as
+    /// `index -> value` pairs. `build` expands it to a dense vector of `dimension`
+    /// elements with the missing entries set to zero, and fails if an index is not
+    /// below `dimension`.
+    pub fn sparse_vector(mut self, entries: HashMap<usize, f32>, dimension: usize) -> Self {
+        self.sparse_vector = Some((entries, dimension));
+        self
+    }
+
     pub fn payload_insert_query<S: Into<String>>(mut self, query: S) -> Self {
         self.payload_insert_query = Some(query.into());
         self
     }
 
+    /// Makes the insert safe to retry: an insert whose key was already committed
+    /// within the key TTL is not applied again and reports `InsertOutcome::Duplicate`.
+    pub fn idempotency_key(mut self, key: &str) -> Self {
+        self.idempotency_key = Some(key.to_string());
+        self
+    }
+
     /// ✅ Build with validation:
-    /// Ensures that either `collection_name` or `payload_insert_query` is provided.
+    /// Ensures that either `collection_name` or `payload_insert_query` is provided,
+    /// and that `collection_name` is a plain SQL identifier.
     pub fn build(self) -> Result<InsertPoint, String> {
         // Validate collection_name
-        if self.collection_name.is_none() {
+        let Some(collection_name) = &self.collection_name else {
             return Err("Collection_name must be provided.".into());
-        }
+        };
+        validate_collection_name(collection_name)?;
 
         // Validate vector presence
-        let vector = self
-            .vector
-            .ok_or_else(|| "Vector must be provided.".to_string())?;
+        let vector_json_len = match &self.vector_json {
+            Some(_)
+                if self.vector.is_some()
+                    || self.vector_bytes.is_some()
+                    || self.sparse_vector.is_some() =>
+            {
+                return Err(
+                    "Only one of vector, vector_bytes, vector_json or sparse_vector may be provided."
+                        .into(),
+                );
+            }
+            Some(json) => Some(check_vector_json(json)?),
+            None => None,
+        };
+        let vector = match self.sparse_vector {
+            Some(_) if self.vector.is_some() || self.vector_bytes.is_some() => {
+                return Err(
+                    "Only one of vector, vector_bytes or sparse_vector may be provided.".into(),
+                )
+            }
+            Some((entries, dimension)) => Some(dense_from_sparse(&entries, dimension)?),
+            None => self.vector,
+        };
+        let vector = match (vector, &self.vector_bytes) {
+            (Some(_), Some(_)) => {
+                return Err("Only one of vector or vector_bytes may be provided.".into())
+            }
+            (Some(vector), None) => vector,
+            (None, Some(bytes)) if bytes.is_empty() || bytes.len() % 4 != 0 => {
+                return Err("vector_bytes length must be a non-zero multiple of 4.".into())
+            }
+            (None, Some(_)) => Vec::new(),
+            (None, None) if self.vector_json.is_some() => Vec::new(),
+            (None, None) => return Err("Vector must be provided.".into()),
+        };
+
+        if self.idempotency_key.as_deref().is_some_and(str::is_empty) {
+            return Err("idempotency_key must not be empty.".into());
+        }
 
         Ok(InsertPoint {
             collection_name: self.collection_name.unwrap(),
             id: self.id,
             vector,
+            vector_bytes: self.vector_bytes,
+            vector_json: self.vector_json,
             payload_insert_query: self.payload_insert_query,
+            idempotency_key: self.idempotency_key,
+            vector_json_len,
         })
     }
 }
+
+/// Checks that `json` is a non-empty array of finite numbers and returns its length.
+fn check_vector_json(json: &str) -> Result<usize, String> {
+    let vector = vector_from_json(json).map_err(|e| format!("Invalid vector_json: {}", e))?;
+    if vector.is_empty() {
+        return Err("vector_json must not be an empty array.".into());
+    }
+    if let Some(i) = vector.iter().position(|value| !value.is_finite()) {
+        return Err(format!(
+            "Invalid vector_json: element {} is not a finite number.",
+            i
+        ));
+    }
+    Ok(vector.len())
+}
+
+/// What an insert did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertOutcome {
+    /// The point was inserted.
+    Inserted,
+    /// The insert's idempotency key was already used by a committed insert, so nothing
+    /// was written.
+    Duplicate,
+}
@@ -1,15 +1,31 @@
+pub mod batch_delete;
+pub mod batch_insert;
 pub mod collection_config;
+pub mod collection_stats;
 pub mod delete_collection;
 pub mod delete_point;
 pub mod enums;
 pub mod insert_point;
+pub mod payload_index;
 pub mod query_plan;
 pub mod search_point;
+pub mod search_response;
+pub mod sql_value;
+pub mod vector_xlite_config;
+pub mod version_info;
 
+pub use batch_delete::*;
+pub use batch_insert::*;
 pub use collection_config::*;
+pub use collection_stats::*;
 pub use delete_collection::*;
 pub use delete_point::*;
 pub use enums::*;
 pub use insert_point::*;
+pub use payload_index::*;
 pub use query_plan::*;
 pub use search_point::*;
+pub use search_response::*;
+pub use sql_value::*;
+pub use vector_xlite_config::*;
+pub use version_info::*;
@@ -0,0 +1,11 @@
+/// A payload index created by `create_payload_index`.
+///
+/// # Fields
+///
+/// * `name` - The index name, `vx_idx_<payload table>_<column>...`
+/// * `columns` - The indexed payload columns, in index order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayloadIndex {
+    pub name: String,
+    pub columns: Vec<String>,
+}
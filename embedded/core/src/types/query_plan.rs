@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 use rusqlite::ToSql;
 
+use crate::types::PlanKind;
+
 pub struct QueryPlan {
     pub sql: String,
 
@@ -10,3 +12,22 @@ pub struct QueryPlan {
     pub post_process:
         Option<Box<dyn Fn(&rusqlite::Row) -> rusqlite::Result<HashMap<String, String>>>>,
 }
+
+/// Statements that make an insert idempotent.
+///
+/// `prepare` creates the key table and expires old keys, `lookup` returns a row when
+/// the key was already used, and `record` stores the key next to the insert.
+pub struct IdempotencyKeyPlan {
+    pub prepare: Vec<QueryPlan>,
+    pub lookup: QueryPlan,
+    pub record: QueryPlan,
+}
+
+/// A search query together with the plan decision behind it, as reported by
+/// `search_with_meta`.
+pub struct SearchPlan {
+    pub query: QueryPlan,
+    pub kind: PlanKind,
+    pub estimated_candidates: i64,
+    pub approximate: bool,
+}
@@ -1,24 +1,139 @@
+use crate::helper::{
+    dense_from_sparse, is_plain_column_name, validate_collection_name, SparseSummary,
+    VectorSummary,
+};
+use crate::types::{AggOp, Direction, DistanceFunction, FilterStrategy, SearchResult};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
 
-#[derive(Debug, Clone)]
+/// Hook run on the results of a search before they are returned, see
+/// `SearchPointBuilder::post_process`.
+pub type SearchPostProcess = Arc<dyn Fn(&mut Vec<SearchResult>) + Send + Sync>;
+
+/// Aggregate computed over the results of a search instead of returning them, see
+/// `SearchPointBuilder::aggregate`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggSpec {
+    /// Numeric payload column to aggregate.
+    pub column: String,
+    pub op: AggOp,
+    /// Weights each value by the similarity of its point to the query vector.
+    pub weight_by_similarity: bool,
+}
+
+#[derive(Clone)]
 pub struct SearchPoint {
     pub collection_name: String,
     pub vector: Vec<f32>,
     pub top_k: i64,
     pub payload_search_query: Option<String>,
+    pub restrict_to_ids: Option<Vec<i64>>,
+    pub order_by: Option<(String, Direction)>,
+    pub dedup_by: Option<String>,
+    pub group_by: Option<(String, usize)>,
+    pub aggregate: Option<AggSpec>,
+    pub filter_strategy: FilterStrategy,
+    pub distance_alias: Option<String>,
+    pub min_similarity: Option<f32>,
+    pub rerank_with: Option<DistanceFunction>,
+    pub include_vector: bool,
+    pub max_sql_complexity: Option<i32>,
+    pub post_process: Option<SearchPostProcess>,
 }
 
 impl SearchPoint {
     pub fn builder() -> SearchPointBuilder {
         SearchPointBuilder::default()
     }
+
+    /// Runs the `post_process` hook, if any, on `results` and cuts them back to
+    /// `top_k`, since the hook may reorder or add rows.
+    pub(crate) fn apply_post_process(
+        post_process: Option<&SearchPostProcess>,
+        top_k: i64,
+        results: &mut Vec<SearchResult>,
+    ) {
+        if let Some(post_process) = post_process {
+            post_process(results);
+            results.truncate(usize::try_from(top_k).unwrap_or(usize::MAX));
+        }
+    }
+}
+
+/// Prints only the first elements of the vector and its length.
+impl fmt::Debug for SearchPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SearchPoint")
+            .field("collection_name", &self.collection_name)
+            .field("vector", &VectorSummary(&self.vector))
+            .field("top_k", &self.top_k)
+            .field("payload_search_query", &self.payload_search_query)
+            .field("restrict_to_ids", &self.restrict_to_ids)
+            .field("order_by", &self.order_by)
+            .field("dedup_by", &self.dedup_by)
+            .field("group_by", &self.group_by)
+            .field("aggregate", &self.aggregate)
+            .field("filter_strategy", &self.filter_strategy)
+            .field("distance_alias", &self.distance_alias)
+            .field("min_similarity", &self.min_similarity)
+            .field("rerank_with", &self.rerank_with)
+            .field("include_vector", &self.include_vector)
+            .field("max_sql_complexity", &self.max_sql_complexity)
+            .field("post_process", &self.post_process.as_ref().map(|_| "<hook>"))
+            .finish()
+    }
 }
 
-#[derive(Debug, Default)]
+#[derive(Clone, Default)]
 pub struct SearchPointBuilder {
     collection_name: Option<String>,
     vector: Option<Vec<f32>>,
+    sparse_vector: Option<(HashMap<usize, f32>, usize)>,
     top_k: Option<i64>,
     payload_search_query: Option<String>,
+    restrict_to_ids: Option<Vec<i64>>,
+    order_by: Option<(String, Direction)>,
+    dedup_by: Option<String>,
+    group_by: Option<(String, usize)>,
+    aggregate: Option<AggSpec>,
+    filter_strategy: FilterStrategy,
+    distance_alias: Option<String>,
+    min_similarity: Option<f32>,
+    rerank_with: Option<DistanceFunction>,
+    include_vector: bool,
+    max_sql_complexity: Option<i32>,
+    post_process: Option<SearchPostProcess>,
+}
+
+impl fmt::Debug for SearchPointBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SearchPointBuilder")
+            .field("collection_name", &self.collection_name)
+            .field("vector", &self.vector.as_deref().map(VectorSummary))
+            .field(
+                "sparse_vector",
+                &self
+                    .sparse_vector
+                    .as_ref()
+                    .map(|(entries, dimension)| SparseSummary(entries, *dimension)),
+            )
+            .field("top_k", &self.top_k)
+            .field("payload_search_query", &self.payload_search_query)
+            .field("restrict_to_ids", &self.restrict_to_ids)
+            .field("order_by", &self.order_by)
+            .field("dedup_by", &self.dedup_by)
+            .field("group_by", &self.group_by)
+            .field("aggregate", &self.aggregate)
+            .field("filter_strategy", &self.filter_strategy)
+            .field("distance_alias", &self.distance_alias)
+            .field("min_similarity", &self.min_similarity)
+            .field("rerank_with", &self.rerank_with)
+            .field("include_vector", &self.include_vector)
+            .field("max_sql_complexity", &self.max_sql_complexity)
+            .field("post_process", &self.post_process.as_ref().map(|_| "<hook>"))
+            .finish()
+    }
 }
 
 impl SearchPointBuilder {
@@ -32,6 +147,19 @@ impl SearchPointBuilder {
         self
     }
 
+    /// Sets the vector from its non-zero entries, e.g. a sparse embedding, as
+    /// `index -> value` pairs. `build` expands it to a dense vector of `dimension`
+    /// elements with the missing entries set to zero, and fails if an index is not
+    /// below `dimension`.
+    pub fn sparse_vector(mut self, entries: HashMap<usize, f32>, dimension: usize) -> Self {
+        self.sparse_vector = Some((entries, dimension));
+        self
+    }
+
+    /// Sets how many nearest neighbours to return (default 10).
+    ///
+    /// Values above the collection's `max_elements` are clamped to it, since the index
+    /// never holds more points.
     pub fn top_k(mut self, top_k: i64) -> Self {
         self.top_k = Some(top_k);
         self
@@ -42,29 +170,238 @@ impl SearchPointBuilder {
         self
     }
 
+    /// Restricts the search to an explicit allowlist of ids.
+    ///
+    /// The allowlist is pushed into the HNSW traversal (`rowid IN (...)` next to
+    /// `knn_search`), so the nearest neighbours are taken from these ids only
+    /// instead of being filtered after the KNN step.
+    pub fn restrict_to_ids(mut self, ids: Vec<i64>) -> Self {
+        self.restrict_to_ids = Some(ids);
+        self
+    }
+
+    /// Re-sorts the nearest `top_k` results by a result column.
+    ///
+    /// The ordering is applied after the KNN step and the payload join, so it only
+    /// changes the order of the nearest neighbours, never which ones are returned.
+    /// Ties are broken by distance.
+    pub fn order_by<S: Into<String>>(mut self, column: S, direction: Direction) -> Self {
+        self.order_by = Some((column.into(), direction));
+        self
+    }
+
+    /// Keeps only the nearest result per distinct value of a payload column.
+    ///
+    /// Useful when several rows belong to one logical document (e.g. text chunks
+    /// sharing a `doc_id`). De-duplication runs before `top_k` is applied, so up to
+    /// `top_k` distinct values are returned. Requires a payload search query, or a
+    /// collection with a default payload search.
+    pub fn dedup_by<S: Into<String>>(mut self, column: S) -> Self {
+        self.dedup_by = Some(column.into());
+        self
+    }
+
+    /// Keeps up to `per_group` nearest results per distinct value of a payload column,
+    /// e.g. the top 3 per category for faceted retrieval.
+    ///
+    /// Grouping runs over the nearest `top_k` results, so `top_k` bounds how many
+    /// neighbours are considered; groups are returned in column order, each ordered by
+    /// distance. With `restrict_to_ids`, only the restricted ids are grouped, e.g. to
+    /// diversify a candidate set. Requires a payload search query, or a collection with
+    /// a default payload search, and cannot be combined with `dedup_by`.
+    pub fn group_by<S: Into<String>>(mut self, column: S, per_group: usize) -> Self {
+        self.group_by = Some((column.into(), per_group));
+        self
+    }
+
+    /// Returns a single `aggregate` column computed over the nearest `top_k` results
+    /// instead of the results themselves, e.g. a similarity-weighted vote over a
+    /// numeric label column.
+    ///
+    /// With `weight_by_similarity`, `Sum` adds up each value times its similarity and
+    /// `Avg` is the weighted average. Similarity is `1 - distance` for cosine and ip
+    /// collections and `1 / (1 + distance)` for l2, so nearer points always weigh more.
+    /// The aggregate is NULL when nothing matches. Requires a payload search query
+    /// returning the column, or a collection whose default payload search returns it.
+    pub fn aggregate(mut self, spec: AggSpec) -> Self {
+        self.aggregate = Some(spec);
+        self
+    }
+
+    /// Forces how the payload filter is applied instead of choosing by its size.
+    ///
+    /// `Pushdown` restricts the HNSW traversal to the matching rowids, `PostFilter`
+    /// joins the payload filter after a wider KNN step. Defaults to `Auto`.
+    pub fn filter_strategy(mut self, strategy: FilterStrategy) -> Self {
+        self.filter_strategy = strategy;
+        self
+    }
+
+    /// Names the result column that carries the KNN distance.
+    ///
+    /// Defaults to `distance`, or `_vx_distance` when the payload query returns its own
+    /// `distance` column, so both values are kept under distinct keys.
+    pub fn distance_alias(mut self, alias: &str) -> Self {
+        self.distance_alias = Some(alias.to_string());
+        self
+    }
+
+    /// Keeps only results whose cosine similarity to the query vector is at least
+    /// `similarity`, a value in `-1.0..=1.0`.
+    ///
+    /// The threshold is converted to vectorlite's cosine distance (`1 - similarity`)
+    /// and applied after the KNN step, so fewer than `top_k` results may be returned.
+    /// Searching a collection that does not use cosine distance fails.
+    pub fn min_similarity(mut self, similarity: f32) -> Self {
+        self.min_similarity = Some(similarity);
+        self
+    }
+
+    /// Adds a `rerank_score` column with the distance between each result's stored
+    /// vector and the query vector under `metric`.
+    ///
+    /// The results are still selected and ordered by the collection's own metric, whose
+    /// value stays in the distance column, so hybrid pipelines get both scores. The
+    /// score follows vectorlite's conventions: squared `l2`, `1 - similarity` for cosine.
+    pub fn rerank_with(mut self, metric: DistanceFunction) -> Self {
+        self.rerank_with = Some(metric);
+        self
+    }
+
+    /// Adds a `vector` column with each result's stored vector as a JSON array, e.g. for
+    /// client-side re-ranking. Read it back with `search_result_vector`. Off by default,
+    /// since reading and serializing every vector costs time for wide embeddings.
+    pub fn include_vector(mut self, include_vector: bool) -> Self {
+        self.include_vector = include_vector;
+        self
+    }
+
+    /// Rejects payload queries whose expressions nest deeper than `max_depth`, e.g.
+    /// generated filters that grew out of hand.
+    ///
+    /// The payload query is prepared under SQLite's `SQLITE_LIMIT_EXPR_DEPTH` lowered to
+    /// `max_depth` before the search runs; a deeper query fails the search with
+    /// `VecXError::InvalidQueryError`. The limit is restored on the connection afterwards.
+    pub fn max_sql_complexity(mut self, max_depth: i32) -> Self {
+        self.max_sql_complexity = Some(max_depth);
+        self
+    }
+
+    /// Transforms the results of `search`, `search_many` and `search_with_meta` before
+    /// they are returned, e.g. to apply a business boost and re-sort.
+    ///
+    /// The hook gets the materialized result set, after every SQL-side step; the
+    /// results are cut back to `top_k` afterwards. `search_stream`, `search_typed`,
+    /// `search_to_ndjson` and `search_arrow` do not run it.
+    pub fn post_process<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&mut Vec<SearchResult>) + Send + Sync + 'static,
+    {
+        self.post_process = Some(Arc::new(hook));
+        self
+    }
+
     /// ✅ Build with validation:
-    /// - Requires vector
+    /// - Requires exactly one of vector or sparse_vector, whose indices must be below its dimension
     /// - top_k must be positive
     /// - Either collection_name or payload_search_query must be provided
+    /// - collection_name must be a plain SQL identifier
+    /// - restrict_to_ids, when set, must not be empty
+    /// - order_by column, when set, must be a plain column name
+    /// - dedup_by column, when set, must be a plain column name
+    /// - group_by column, when set, must be a plain column name and per_group must be
+    ///   positive; it cannot be combined with dedup_by
+    /// - aggregate column, when set, must be a plain column name
+    /// - distance_alias, when set, must be a plain column name
+    /// - min_similarity, when set, must be within -1.0..=1.0
+    /// - max_sql_complexity, when set, must be positive
     pub fn build(self) -> Result<SearchPoint, String> {
-        if self.collection_name.is_none() {
+        let Some(collection_name) = &self.collection_name else {
             return Err("Collection_name must be provided.".into());
-        }
+        };
+        validate_collection_name(collection_name)?;
 
-        let vector = self
-            .vector
-            .ok_or_else(|| "Vector must be provided.".to_string())?;
+        let vector = match (self.vector, self.sparse_vector) {
+            (Some(_), Some(_)) => {
+                return Err("Only one of vector or sparse_vector may be provided.".into())
+            }
+            (Some(vector), None) => vector,
+            (None, Some((entries, dimension))) => dense_from_sparse(&entries, dimension)?,
+            (None, None) => return Err("Vector must be provided.".into()),
+        };
 
         let top_k = self.top_k.unwrap_or(10);
         if top_k <= 0 {
             return Err("top_k must be greater than 0.".into());
         }
 
+        if self.restrict_to_ids.as_ref().is_some_and(Vec::is_empty) {
+            return Err("restrict_to_ids must not be empty.".into());
+        }
+
+        if let Some((column, _)) = &self.order_by {
+            if !is_plain_column_name(column) {
+                return Err("order_by column must be a plain column name.".into());
+            }
+        }
+
+        if let Some(column) = &self.dedup_by {
+            if !is_plain_column_name(column) {
+                return Err("dedup_by column must be a plain column name.".into());
+            }
+        }
+
+        if let Some((column, per_group)) = &self.group_by {
+            if !is_plain_column_name(column) {
+                return Err("group_by column must be a plain column name.".into());
+            }
+            if *per_group == 0 {
+                return Err("group_by per_group must be greater than 0.".into());
+            }
+            if self.dedup_by.is_some() {
+                return Err("group_by cannot be combined with dedup_by.".into());
+            }
+        }
+
+        if let Some(spec) = &self.aggregate {
+            if !is_plain_column_name(&spec.column) {
+                return Err("aggregate column must be a plain column name.".into());
+            }
+        }
+
+        if let Some(alias) = &self.distance_alias {
+            if !is_plain_column_name(alias) {
+                return Err("distance_alias must be a plain column name.".into());
+            }
+        }
+
+        if let Some(similarity) = self.min_similarity {
+            if !(-1.0..=1.0).contains(&similarity) {
+                return Err("min_similarity must be between -1.0 and 1.0.".into());
+            }
+        }
+
+        if self.max_sql_complexity.is_some_and(|max_depth| max_depth <= 0) {
+            return Err("max_sql_complexity must be greater than 0.".into());
+        }
+
         Ok(SearchPoint {
             collection_name: self.collection_name.unwrap(),
             vector,
             top_k,
             payload_search_query: self.payload_search_query,
+            restrict_to_ids: self.restrict_to_ids,
+            order_by: self.order_by,
+            dedup_by: self.dedup_by,
+            group_by: self.group_by,
+            aggregate: self.aggregate,
+            filter_strategy: self.filter_strategy,
+            distance_alias: self.distance_alias,
+            min_similarity: self.min_similarity,
+            rerank_with: self.rerank_with,
+            include_vector: self.include_vector,
+            max_sql_complexity: self.max_sql_complexity,
+            post_process: self.post_process,
         })
     }
 }
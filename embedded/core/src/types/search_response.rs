@@ -0,0 +1,102 @@
+use crate::error::VecXError;
+use crate::helper::vector_from_json;
+use crate::types::PlanKind;
+use std::collections::HashMap;
+
+/// A single search hit: `rowid`, `distance` and the selected payload columns.
+pub type SearchResult = HashMap<String, String>;
+
+/// Reads the stored vector of a hit searched with `SearchPointBuilder::include_vector`.
+///
+/// Returns `None` when the hit has no `vector` column, i.e. the search did not ask for
+/// vectors.
+pub fn search_result_vector(result: &SearchResult) -> Result<Option<Vec<f32>>, VecXError> {
+    result
+        .get("vector")
+        .map(|json| vector_from_json(json))
+        .transpose()
+}
+
+/// Relative tolerance used by `distance_approx_eq`.
+pub const DISTANCE_EPSILON: f32 = 1e-5;
+
+/// Compares two distances with a relative tolerance of `DISTANCE_EPSILON`, since the
+/// same search may differ in the last bits between index builds or platforms.
+pub fn distance_approx_eq(a: f32, b: f32) -> bool {
+    a == b || (a - b).abs() <= DISTANCE_EPSILON * a.abs().max(b.abs()).max(1.0)
+}
+
+/// Compares two hits column by column, using `distance_approx_eq` for `distance` and
+/// exact string equality for every other column.
+pub fn search_result_approx_eq(a: &SearchResult, b: &SearchResult) -> bool {
+    a.len() == b.len()
+        && a.iter().all(|(column, value)| match b.get(column) {
+            Some(other) if column == "distance" => {
+                match (value.parse::<f32>(), other.parse::<f32>()) {
+                    (Ok(x), Ok(y)) => distance_approx_eq(x, y),
+                    _ => value == other,
+                }
+            }
+            Some(other) => value == other,
+            None => false,
+        })
+}
+
+/// Search results together with metadata about how they were produced.
+///
+/// # Fields
+///
+/// * `results` - The nearest neighbours, ordered as returned by `search`
+/// * `truncated` - True when the number of results reached `top_k`, so more matches
+///   may exist beyond the limit
+/// * `total_candidates` - Number of payload rows matching the payload filter, or `None`
+///   when the search had no payload filter
+/// * `plan` - Whether the payload filter ran before or after the KNN search
+/// * `estimated_candidates` - The payload row count the planner chose `plan` by, which
+///   ignores `restrict_to_ids`; 0 when there was no payload filter to weigh
+/// * `approximate` - True when the results came from the HNSW index and may miss some
+///   of the true nearest neighbours; false when every candidate was compared, i.e. the
+///   exact search of a collection below `exact_below` or a payload-only search
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResponse {
+    pub results: Vec<SearchResult>,
+    pub truncated: bool,
+    pub total_candidates: Option<u64>,
+    pub plan: PlanKind,
+    pub estimated_candidates: i64,
+    pub approximate: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(rowid: &str, distance: &str) -> SearchResult {
+        HashMap::from([
+            ("rowid".to_string(), rowid.to_string()),
+            ("distance".to_string(), distance.to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_distance_approx_eq_uses_relative_tolerance() {
+        assert!(distance_approx_eq(0.1 + 0.2, 0.3));
+        assert!(distance_approx_eq(1000.0, 1000.001));
+        assert!(!distance_approx_eq(1.0, 1.001));
+        assert!(!distance_approx_eq(f32::NAN, f32::NAN));
+    }
+
+    #[test]
+    fn test_search_result_approx_eq_compares_distance_loosely() {
+        assert!(search_result_approx_eq(
+            &hit("1", "0.3"),
+            &hit("1", "0.30000001")
+        ));
+        assert!(!search_result_approx_eq(&hit("1", "0.3"), &hit("2", "0.3")));
+        assert!(!search_result_approx_eq(&hit("1", "0.3"), &hit("1", "0.4")));
+
+        let mut extra = hit("1", "0.3");
+        extra.insert("title".to_string(), "a".to_string());
+        assert!(!search_result_approx_eq(&hit("1", "0.3"), &extra));
+    }
+}
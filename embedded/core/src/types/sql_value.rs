@@ -0,0 +1,188 @@
+use rusqlite::types::Value;
+use std::hash::{Hash, Hasher};
+
+/// A payload column value as stored by SQLite, before stringification.
+///
+/// SQLite has no dedicated boolean or date types: booleans are stored as integers and
+/// dates usually as ISO-8601 text. The accessors convert between storage classes the
+/// way SQLite's type affinity would, returning `None` when no sensible conversion exists.
+///
+/// Values can be compared and hashed, e.g. to collect them in a `HashSet`. Reals are
+/// compared by bit pattern, so `NaN` equals itself and `0.0` differs from `-0.0`.
+#[derive(Debug, Clone)]
+pub enum SqlValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+impl SqlValue {
+    /// Returns true if the value is SQL NULL.
+    pub fn is_null(&self) -> bool {
+        matches!(self, SqlValue::Null)
+    }
+
+    /// Interprets the value as a boolean: non-zero numbers are true, and text is
+    /// accepted as a number or as `true`/`false` in any case.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            SqlValue::Integer(v) => Some(*v != 0),
+            SqlValue::Real(v) => Some(*v != 0.0),
+            SqlValue::Text(s) => {
+                let s = s.trim();
+                if s.eq_ignore_ascii_case("true") {
+                    Some(true)
+                } else if s.eq_ignore_ascii_case("false") {
+                    Some(false)
+                } else {
+                    SqlValue::Text(s.to_string()).as_f64().map(|v| v != 0.0)
+                }
+            }
+            SqlValue::Null | SqlValue::Blob(_) => None,
+        }
+    }
+
+    /// Interprets the value as an integer. Reals and numeric text convert only when
+    /// they hold a whole number that fits in an `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            SqlValue::Integer(v) => Some(*v),
+            SqlValue::Real(v) => real_to_i64(*v),
+            SqlValue::Text(s) => {
+                let s = s.trim();
+                s.parse::<i64>()
+                    .ok()
+                    .or_else(|| s.parse::<f64>().ok().and_then(real_to_i64))
+            }
+            SqlValue::Null | SqlValue::Blob(_) => None,
+        }
+    }
+
+    /// Interprets the value as a floating point number, parsing numeric text.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            SqlValue::Integer(v) => Some(*v as f64),
+            SqlValue::Real(v) => Some(*v),
+            SqlValue::Text(s) => s.trim().parse::<f64>().ok(),
+            SqlValue::Null | SqlValue::Blob(_) => None,
+        }
+    }
+
+    /// Borrows text values, such as ISO-8601 dates. Other storage classes return `None`.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            SqlValue::Text(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+fn real_to_i64(v: f64) -> Option<i64> {
+    (v.fract() == 0.0 && v >= i64::MIN as f64 && v < i64::MAX as f64).then_some(v as i64)
+}
+
+impl PartialEq for SqlValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SqlValue::Null, SqlValue::Null) => true,
+            (SqlValue::Integer(a), SqlValue::Integer(b)) => a == b,
+            (SqlValue::Real(a), SqlValue::Real(b)) => a.to_bits() == b.to_bits(),
+            (SqlValue::Text(a), SqlValue::Text(b)) => a == b,
+            (SqlValue::Blob(a), SqlValue::Blob(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for SqlValue {}
+
+impl Hash for SqlValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            SqlValue::Null => {}
+            SqlValue::Integer(v) => v.hash(state),
+            SqlValue::Real(v) => v.to_bits().hash(state),
+            SqlValue::Text(s) => s.hash(state),
+            SqlValue::Blob(b) => b.hash(state),
+        }
+    }
+}
+
+impl From<Value> for SqlValue {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Null => SqlValue::Null,
+            Value::Integer(v) => SqlValue::Integer(v),
+            Value::Real(v) => SqlValue::Real(v),
+            Value::Text(v) => SqlValue::Text(v),
+            Value::Blob(v) => SqlValue::Blob(v),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_as_bool_from_integer_and_text() {
+        assert_eq!(SqlValue::Integer(1).as_bool(), Some(true));
+        assert_eq!(SqlValue::Integer(0).as_bool(), Some(false));
+        assert_eq!(SqlValue::Text("TRUE".into()).as_bool(), Some(true));
+        assert_eq!(SqlValue::Text(" 0 ".into()).as_bool(), Some(false));
+        assert_eq!(SqlValue::Text("maybe".into()).as_bool(), None);
+        assert_eq!(SqlValue::Null.as_bool(), None);
+    }
+
+    #[test]
+    fn test_as_i64_requires_whole_numbers() {
+        assert_eq!(SqlValue::Real(3.0).as_i64(), Some(3));
+        assert_eq!(SqlValue::Real(3.5).as_i64(), None);
+        assert_eq!(SqlValue::Text("42".into()).as_i64(), Some(42));
+        assert_eq!(SqlValue::Text("4e2".into()).as_i64(), Some(400));
+        assert_eq!(SqlValue::Blob(vec![1]).as_i64(), None);
+    }
+
+    #[test]
+    fn test_as_f64_and_as_str() {
+        assert_eq!(SqlValue::Integer(2).as_f64(), Some(2.0));
+        assert_eq!(SqlValue::Text("2.5".into()).as_f64(), Some(2.5));
+        assert_eq!(SqlValue::Text("2024-01-15".into()).as_str(), Some("2024-01-15"));
+        assert_eq!(SqlValue::Integer(2).as_str(), None);
+    }
+
+    #[test]
+    fn test_equality_across_variants() {
+        assert_eq!(SqlValue::Null, SqlValue::Null);
+        assert_eq!(SqlValue::Integer(1), SqlValue::Integer(1));
+        assert_eq!(SqlValue::Real(f64::NAN), SqlValue::Real(f64::NAN));
+        assert_ne!(SqlValue::Real(0.0), SqlValue::Real(-0.0));
+        assert_ne!(SqlValue::Integer(1), SqlValue::Real(1.0));
+        assert_ne!(SqlValue::Text("1".into()), SqlValue::Integer(1));
+        assert_ne!(SqlValue::Blob(vec![]), SqlValue::Null);
+    }
+
+    #[test]
+    fn test_set_membership_across_variants() {
+        let values = [
+            SqlValue::Null,
+            SqlValue::Integer(7),
+            SqlValue::Real(7.0),
+            SqlValue::Text("7".into()),
+            SqlValue::Blob(vec![7]),
+        ];
+        let mut set: HashSet<SqlValue> = values.iter().cloned().collect();
+        assert_eq!(set.len(), values.len());
+
+        for value in &values {
+            assert!(set.contains(value));
+            assert!(!set.insert(value.clone()));
+        }
+        assert!(!set.contains(&SqlValue::Integer(8)));
+        assert!(!set.contains(&SqlValue::Blob(vec![])));
+    }
+}
@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+/// Instance-wide settings of a `VectorXLite`.
+///
+/// # Migrating to prefixed payload tables
+///
+/// Enabling `prefix_payload_tables` on an existing database does not rename its
+/// payload tables. Rename each one once, while no instance is using the database:
+///
+/// ```sql
+/// ALTER TABLE docs RENAME TO pt_docs;
+/// ```
+///
+/// Payload insert and search queries must then reference `pt_docs`.
+#[derive(Debug, Clone, Default)]
+pub struct VectorXLiteConfig {
+    /// How long an operation waits for a free pooled connection. Defaults to the
+    /// pool's own `connection_timeout`.
+    pub connection_timeout: Option<Duration>,
+    /// Names payload tables `pt_<collection>` instead of `<collection>`, so a
+    /// collection never collides with an unrelated table of the same name.
+    pub prefix_payload_tables: bool,
+    /// Number of fractional digits query vectors are written with in `explain_search`
+    /// output. Defaults to full precision. Inserts and searches always bind vectors
+    /// at full precision.
+    pub explain_precision: Option<usize>,
+    /// How long the idempotency key of an insert is remembered. Defaults to 24 hours.
+    pub idempotency_key_ttl: Option<Duration>,
+    /// Largest vector dimension `create_collection` accepts, guarding against a typo
+    /// allocating a huge index. Defaults to 4096.
+    pub max_allowed_dimension: Option<u16>,
+    /// Searches of collections holding fewer than this many points compute the exact
+    /// distance to every vector instead of walking the HNSW index, which is faster and
+    /// exact at that size. Off by default.
+    pub exact_below: Option<u64>,
+}
+
+impl VectorXLiteConfig {
+    pub fn with_connection_timeout(mut self, timeout: Duration) -> Self {
+        self.connection_timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_prefixed_payload_tables(mut self, prefixed: bool) -> Self {
+        self.prefix_payload_tables = prefixed;
+        self
+    }
+
+    pub fn with_explain_precision(mut self, digits: usize) -> Self {
+        self.explain_precision = Some(digits);
+        self
+    }
+
+    pub fn with_idempotency_key_ttl(mut self, ttl: Duration) -> Self {
+        self.idempotency_key_ttl = Some(ttl);
+        self
+    }
+
+    pub fn with_max_allowed_dimension(mut self, dimension: u16) -> Self {
+        self.max_allowed_dimension = Some(dimension);
+        self
+    }
+
+    pub fn with_exact_below(mut self, points: u64) -> Self {
+        self.exact_below = Some(points);
+        self
+    }
+}
@@ -0,0 +1,95 @@
+/// Build information reported by the vectorlite extension.
+///
+/// Parsed from the output of `vectorlite_info()`, which currently looks like
+/// `vectorlite extension version 0.2.0, built with SSE`. Parsing is best effort:
+/// unrecognized output is kept in `raw` instead of producing an error.
+///
+/// # Fields
+///
+/// * `raw` - The unparsed `vectorlite_info()` output
+/// * `version` - The extension version, or the raw output if no version could be found
+/// * `simd` - The SIMD instruction set the extension was built with, if reported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionInfo {
+    pub raw: String,
+    pub version: String,
+    pub simd: Option<String>,
+}
+
+impl VersionInfo {
+    /// Parses the output of `vectorlite_info()`.
+    pub fn parse(raw: &str) -> VersionInfo {
+        let raw = raw.trim();
+        let (head, build) = match raw.split_once(',') {
+            Some((head, build)) => (head, Some(build)),
+            None => (raw, None),
+        };
+
+        let words: Vec<&str> = head.split_whitespace().collect();
+        let version = words
+            .iter()
+            .position(|word| word.eq_ignore_ascii_case("version"))
+            .and_then(|idx| words.get(idx + 1))
+            .or_else(|| {
+                words
+                    .iter()
+                    .find(|word| word.starts_with(|c: char| c.is_ascii_digit()))
+            })
+            .map(|version| version.to_string())
+            .unwrap_or_else(|| raw.to_string());
+
+        let simd = build
+            .map(str::trim)
+            .map(|build| {
+                build
+                    .strip_prefix("built with")
+                    .map(str::trim)
+                    .unwrap_or(build)
+            })
+            .filter(|simd| !simd.is_empty())
+            .map(str::to_string);
+
+        VersionInfo {
+            raw: raw.to_string(),
+            version,
+            simd,
+        }
+    }
+
+    /// Returns true if the extension was built with a SIMD instruction set.
+    pub fn simd_supported(&self) -> bool {
+        self.simd.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_current_format() {
+        let info = VersionInfo::parse("vectorlite extension version 0.2.0, built with SSE");
+
+        assert_eq!(info.version, "0.2.0");
+        assert_eq!(info.simd.as_deref(), Some("SSE"));
+        assert!(info.simd_supported());
+    }
+
+    #[test]
+    fn test_parse_without_build_flags() {
+        let info = VersionInfo::parse("vectorlite 0.3.1");
+
+        assert_eq!(info.version, "0.3.1");
+        assert_eq!(info.simd, None);
+        assert!(!info.simd_supported());
+    }
+
+    #[test]
+    fn test_parse_unrecognized_format_keeps_raw() {
+        let info = VersionInfo::parse("  custom build  ");
+
+        assert_eq!(info.raw, "custom build");
+        assert_eq!(info.version, "custom build");
+        assert_eq!(info.simd, None);
+    }
+}
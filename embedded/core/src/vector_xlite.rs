@@ -1,27 +1,131 @@
+use crate::constant::DEFAULT_SQLITE_TIMEOUT;
+use crate::customizer::SqliteConnectionCustomizer;
 use crate::error::VecXError;
 use crate::executor::{QueryExecutor, SqliteQueryExecutor};
+use crate::helper::{
+    canonical_collection_name, check_index_file_path, validate_collection_name, vector_from_json,
+    CollectionCounters, CollectionLimiter, ConnectionSource, PermitIter,
+};
 use crate::planner::{QueryPlanner, SqliteQueryPlanner};
+use crate::snapshot::SnapshotTempDir;
 use crate::types::*;
-use r2d2::Pool;
+use r2d2::{CustomizeConnection, Pool};
 use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::Connection;
 use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
 
 pub struct VectorXLite {
     query_planner: Box<dyn QueryPlanner>,
     query_executor: Box<dyn QueryExecutor>,
+    counters: CollectionCounters,
+    collection_limiter: CollectionLimiter,
+    /// Files of a snapshot opened read-only. Declared last, so they are removed only
+    /// after the planner and executor have closed their connections.
+    snapshot_dir: Option<SnapshotTempDir>,
 }
 
 impl VectorXLite {
+    /// Creates a new instance that waits up to the pool's own `connection_timeout`
+    /// when acquiring connections.
     pub fn new(connection_pool: Pool<SqliteConnectionManager>) -> Result<VectorXLite, VecXError> {
-        Ok(VectorXLite {
-            query_planner: SqliteQueryPlanner::new(connection_pool.clone()),
-            query_executor: SqliteQueryExecutor::new(connection_pool),
-        })
+        Self::with_config(connection_pool, VectorXLiteConfig::default())
+    }
+
+    /// Creates a new instance with a custom connection acquisition timeout.
+    ///
+    /// # Arguments
+    /// * `connection_timeout` - How long an operation waits for a free pooled connection
+    ///   before failing with a `connection pool exhausted` error.
+    pub fn with_connection_timeout(
+        connection_pool: Pool<SqliteConnectionManager>,
+        connection_timeout: Duration,
+    ) -> Result<VectorXLite, VecXError> {
+        Self::with_config(
+            connection_pool,
+            VectorXLiteConfig::default().with_connection_timeout(connection_timeout),
+        )
+    }
+
+    /// Creates a new instance with custom instance-wide settings.
+    pub fn with_config(
+        connection_pool: Pool<SqliteConnectionManager>,
+        config: VectorXLiteConfig,
+    ) -> Result<VectorXLite, VecXError> {
+        let connection_timeout = config
+            .connection_timeout
+            .unwrap_or_else(|| connection_pool.connection_timeout());
+
+        let connections = ConnectionSource::pool(connection_pool, connection_timeout);
+        Ok(Self::with_connection_source(connections, config))
+    }
+
+    /// Creates an instance that runs every operation on one owned connection instead
+    /// of a pool, for embedded single-threaded use.
+    ///
+    /// The connection is set up like `SqliteConnectionCustomizer` sets up pooled ones,
+    /// loading the vector extension. Operations take turns on it, so a `search_stream`
+    /// iterator must be dropped before the next call.
+    pub fn from_connection(mut conn: Connection) -> Result<VectorXLite, VecXError> {
+        SqliteConnectionCustomizer::default().on_acquire(&mut conn)?;
+
+        let connections = ConnectionSource::single(
+            conn,
+            Duration::from_millis(u64::from(DEFAULT_SQLITE_TIMEOUT)),
+        );
+        Ok(Self::with_connection_source(
+            connections,
+            VectorXLiteConfig::default(),
+        ))
+    }
+
+    fn with_connection_source(connections: ConnectionSource, config: VectorXLiteConfig) -> Self {
+        VectorXLite {
+            collection_limiter: CollectionLimiter::new(connections.timeout()),
+            query_planner: SqliteQueryPlanner::new(connections.clone(), config),
+            query_executor: SqliteQueryExecutor::new(connections),
+            counters: CollectionCounters::default(),
+            snapshot_dir: None,
+        }
+    }
+
+    /// Limits how many operations may run at once on each collection, so a heavily
+    /// used collection cannot take every pooled connection and starve the others.
+    ///
+    /// Inserts, deletes, searches, `count_where` and `update_where` count against the
+    /// limit of their collections; a `search_stream` iterator counts until it is
+    /// dropped. An operation over the limit waits for one to finish, up to the
+    /// connection acquisition timeout, and then fails like an exhausted pool. A limit
+    /// of 0 is treated as 1.
+    ///
+    /// Independently of the limit, `delete_collection`, `rename_collection` and
+    /// `compact_payload` always run alone on their collections.
+    pub fn with_per_collection_limit(mut self, limit: usize) -> Self {
+        self.collection_limiter.set_limit(limit);
+        self
+    }
+
+    /// Ties the lifetime of a read-only snapshot's temporary files to this instance.
+    pub(crate) fn with_snapshot_dir(mut self, snapshot_dir: SnapshotTempDir) -> Self {
+        self.snapshot_dir = Some(snapshot_dir);
+        self
     }
 }
 
 impl VectorXLite {
+    /// Creates a collection's payload table and vectorlite virtual table.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VecXError::IoError` if `index_file_path` is a directory or its parent
+    /// directory is missing or not writable, before anything is created.
     pub fn create_collection(&self, collection_config: CollectionConfig) -> Result<(), VecXError> {
+        if let Some(index_path) = &collection_config.index_file_path {
+            check_index_file_path(index_path)?;
+        }
+
         let query_plans = self
             .query_planner
             .plan_create_collection(collection_config)?;
@@ -30,19 +134,499 @@ impl VectorXLite {
             .execute_create_collection_query(query_plans)
     }
 
+    /// Checks that `create_collection` would succeed for a config, without creating
+    /// anything.
+    ///
+    /// The payload schema and the vectorlite virtual table are created on a throwaway
+    /// in-memory connection, so SQL errors in either are reported while the database
+    /// is left untouched. The index file is neither read nor written.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VecXError::InvalidQueryError` if the config is invalid or the collection
+    /// already exists, and `VecXError::SqlError` if SQLite rejects the generated SQL.
+    pub fn validate_collection(
+        &self,
+        collection_config: &CollectionConfig,
+    ) -> Result<(), VecXError> {
+        if self.collection_exists(&collection_config.collection_name)? {
+            return Err(VecXError::InvalidQueryError(format!(
+                "collection '{}' already exists",
+                collection_config.collection_name
+            )));
+        }
+
+        let collection_config = CollectionConfig {
+            index_file_path: None,
+            ..collection_config.clone()
+        };
+        let query_plans = self
+            .query_planner
+            .plan_create_collection(collection_config)?;
+
+        self.query_executor
+            .execute_validate_collection_query(query_plans)
+    }
+
+    /// Inserts a point and its payload row in a single transaction.
+    ///
+    /// A point carrying an idempotency key is skipped when a committed insert already
+    /// used that key; use `insert_idempotent` to learn whether it was applied.
     pub fn insert(&self, create_point: InsertPoint) -> Result<(), VecXError> {
+        self.insert_idempotent(create_point).map(|_| ())
+    }
+
+    /// Inserts a point like `insert` and reports whether it was applied.
+    ///
+    /// When the point carries an idempotency key that a committed insert into the same
+    /// collection already used within the key TTL, nothing is written and
+    /// `InsertOutcome::Duplicate` is returned, so a client can safely retry an insert
+    /// whose response it lost.
+    pub fn insert_idempotent(&self, create_point: InsertPoint) -> Result<InsertOutcome, VecXError> {
+        let collection_name = create_point.collection_name.clone();
+        let _permit = self
+            .collection_limiter
+            .acquire([collection_name.as_str()])?;
+        let idempotency_key = create_point.idempotency_key.clone();
         let query_plans = self.query_planner.plan_insert_query(create_point)?;
 
-        self.query_executor.execute_insert_query(query_plans)
+        let outcome = match idempotency_key {
+            Some(key) => {
+                let key_plan = self
+                    .query_planner
+                    .plan_idempotency_key_query(&collection_name, &key)?;
+                self.query_executor
+                    .execute_idempotent_insert_query(key_plan, query_plans)?
+            }
+            None => {
+                self.query_executor.execute_insert_query(query_plans)?;
+                InsertOutcome::Inserted
+            }
+        };
+
+        if outcome == InsertOutcome::Inserted {
+            self.counters.record_inserts(&collection_name, 1);
+        }
+        Ok(outcome)
+    }
+
+    /// Inserts several points in a single transaction.
+    ///
+    /// With `options.stop_on_error` (the default) the batch is all-or-nothing and the
+    /// first failure is returned as the error. Otherwise points that fail are reported in
+    /// `BatchResult::failed` by their position in `points`, and all other points are
+    /// committed.
+    ///
+    /// An id given twice for the same collection rejects the whole batch with
+    /// `VecXError::InvalidQueryError`, unless `options.dedupe_ids` is set, in which case
+    /// only its last occurrence is inserted.
+    pub fn insert_batch(
+        &self,
+        points: Vec<InsertPoint>,
+        options: BatchOptions,
+    ) -> Result<BatchResult, VecXError> {
+        let points = positioned_batch_points(points, options.dedupe_ids)?;
+        let collection_names: Vec<(usize, String)> = points
+            .iter()
+            .map(|(index, point)| (*index, point.collection_name.clone()))
+            .collect();
+        let _permit = self
+            .collection_limiter
+            .acquire(collection_names.iter().map(|(_, name)| name.as_str()))?;
+
+        let mut query_plan_groups = Vec::with_capacity(points.len());
+        let mut planning_failures = Vec::new();
+        for (index, point) in points {
+            match self.query_planner.plan_insert_query(point) {
+                Ok(query_plans) => query_plan_groups.push((index, query_plans)),
+                Err(e) if options.stop_on_error => return Err(e),
+                Err(e) => planning_failures.push((index, e)),
+            }
+        }
+
+        let mut result = self
+            .query_executor
+            .execute_batch_insert_query(query_plan_groups, options.stop_on_error)?;
+        result.failed.extend(planning_failures);
+        result.failed.sort_by_key(|(index, _)| *index);
+
+        for (index, collection_name) in &collection_names {
+            if result
+                .failed
+                .binary_search_by_key(index, |(failed, _)| *failed)
+                .is_err()
+            {
+                self.counters.record_inserts(collection_name, 1);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Inserts several points in a single transaction, replacing the payload row and
+    /// vector of every id that already exists.
+    ///
+    /// The batch is all-or-nothing: the first failure rolls back every point and is
+    /// returned as the error. vectorlite's index does not take part in the rollback, so
+    /// the payload rows of all points are written before any vector is touched.
+    pub fn upsert_batch(&self, points: Vec<InsertPoint>) -> Result<(), VecXError> {
+        let collection_names: Vec<String> = points
+            .iter()
+            .map(|point| point.collection_name.clone())
+            .collect();
+        let _permit = self
+            .collection_limiter
+            .acquire(collection_names.iter().map(String::as_str))?;
+        let mut payload_plans = Vec::with_capacity(points.len());
+        let mut vector_plans = Vec::with_capacity(2 * points.len());
+        for point in points {
+            let mut query_plans = self.query_planner.plan_upsert_query(point)?.into_iter();
+            payload_plans.extend(query_plans.next());
+            vector_plans.extend(query_plans);
+        }
+        payload_plans.append(&mut vector_plans);
+
+        self.query_executor.execute_insert_query(payload_plans)?;
+        for collection_name in &collection_names {
+            self.counters.record_inserts(collection_name, 1);
+        }
+        Ok(())
     }
 
     pub fn search(
         &self,
         search_point: SearchPoint,
     ) -> Result<Vec<HashMap<String, String>>, VecXError> {
+        let collection_name = search_point.collection_name.clone();
+        let _permit = self
+            .collection_limiter
+            .acquire([collection_name.as_str()])?;
+        let (post_process, top_k) = (search_point.post_process.clone(), search_point.top_k);
+        let query_plan = self.query_planner.plan_search_query(search_point)?;
+
+        let mut results = self.query_executor.execute_search_query(query_plan)?;
+        SearchPoint::apply_post_process(post_process.as_ref(), top_k, &mut results);
+        self.counters.record_searches(&collection_name, 1);
+        Ok(results)
+    }
+
+    /// Runs several searches on one connection, returning their results in the order
+    /// of `search_points`.
+    ///
+    /// The first search that fails fails the whole call.
+    pub fn search_many(
+        &self,
+        search_points: Vec<SearchPoint>,
+    ) -> Result<Vec<Vec<HashMap<String, String>>>, VecXError> {
+        let collection_names: Vec<String> = search_points
+            .iter()
+            .map(|search_point| search_point.collection_name.clone())
+            .collect();
+        let _permit = self
+            .collection_limiter
+            .acquire(collection_names.iter().map(String::as_str))?;
+        let post_processes: Vec<_> = search_points
+            .iter()
+            .map(|search_point| (search_point.post_process.clone(), search_point.top_k))
+            .collect();
+        let query_plans = search_points
+            .into_iter()
+            .map(|search_point| self.query_planner.plan_search_query(search_point))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut results = self.query_executor.execute_search_many_query(query_plans)?;
+        for (results, (post_process, top_k)) in results.iter_mut().zip(&post_processes) {
+            SearchPoint::apply_post_process(post_process.as_ref(), *top_k, results);
+        }
+        for collection_name in &collection_names {
+            self.counters.record_searches(collection_name, 1);
+        }
+        Ok(results)
+    }
+
+    /// Searches like `search`, yielding results lazily instead of collecting them.
+    ///
+    /// Rows are read from SQLite a page at a time as the iterator advances, so large
+    /// `top_k` values do not buffer the whole result set in memory. The iterator holds
+    /// a pooled connection and a read transaction for its whole lifetime; drop it as
+    /// soon as it is no longer needed, in particular before calling other methods on a
+    /// pool with a single connection.
+    pub fn search_stream(
+        &self,
+        search_point: SearchPoint,
+    ) -> Result<impl Iterator<Item = Result<SearchResult, VecXError>>, VecXError> {
+        let collection_name = search_point.collection_name.clone();
+        let permit = self
+            .collection_limiter
+            .acquire([collection_name.as_str()])?;
+        let query_plan = self.query_planner.plan_search_query(search_point)?;
+
+        let results = self
+            .query_executor
+            .execute_search_stream_query(query_plan)?;
+        self.counters.record_searches(&collection_name, 1);
+        // The iterator keeps its connection until dropped, so it also keeps the permit.
+        Ok(PermitIter::new(results, permit))
+    }
+
+    /// Searches like `search`, returning each column as a typed `SqlValue` instead of
+    /// a string, so booleans, numbers and dates can be read back unambiguously.
+    pub fn search_typed(
+        &self,
+        search_point: SearchPoint,
+    ) -> Result<Vec<HashMap<String, SqlValue>>, VecXError> {
+        let collection_name = search_point.collection_name.clone();
+        let _permit = self
+            .collection_limiter
+            .acquire([collection_name.as_str()])?;
         let query_plan = self.query_planner.plan_search_query(search_point)?;
 
-        self.query_executor.execute_search_query(query_plan)
+        let results = self.query_executor.execute_typed_search_query(query_plan)?;
+        self.counters.record_searches(&collection_name, 1);
+        Ok(results)
+    }
+
+    /// Searches like `search`, writing each result to `writer` as a line of JSON
+    /// (NDJSON) and returning the number of results written.
+    ///
+    /// Rows are written as they are read from SQLite. Values keep their SQL types:
+    /// `rowid` and integer columns are JSON numbers, `distance` a float, text a string,
+    /// NULL `null`, and blobs base64-encoded strings.
+    pub fn search_to_ndjson<W: Write>(
+        &self,
+        search_point: SearchPoint,
+        mut writer: W,
+    ) -> Result<usize, VecXError> {
+        let collection_name = search_point.collection_name.clone();
+        let _permit = self
+            .collection_limiter
+            .acquire([collection_name.as_str()])?;
+        let query_plan = self.query_planner.plan_search_query(search_point)?;
+
+        let written = self
+            .query_executor
+            .execute_ndjson_search_query(query_plan, &mut writer)?;
+        self.counters.record_searches(&collection_name, 1);
+        Ok(written)
+    }
+
+    /// Searches like `search`, returning the results as a single Apache Arrow record
+    /// batch for analytics pipelines. Columns keep their SQL order and are typed from
+    /// their values, so `rowid` is `Int64`, `distance` is `Float64` and blob payload
+    /// columns are `Binary`; NULLs become Arrow nulls. Requires the `arrow` feature.
+    #[cfg(feature = "arrow")]
+    pub fn search_arrow(
+        &self,
+        search_point: SearchPoint,
+    ) -> Result<arrow_array::RecordBatch, VecXError> {
+        let collection_name = search_point.collection_name.clone();
+        let _permit = self
+            .collection_limiter
+            .acquire([collection_name.as_str()])?;
+        let query_plan = self.query_planner.plan_search_query(search_point)?;
+
+        let batch = self.query_executor.execute_arrow_search_query(query_plan)?;
+        self.counters.record_searches(&collection_name, 1);
+        Ok(batch)
+    }
+
+    /// Searches like `search`, additionally reporting whether the results were cut off
+    /// by `top_k`, how many payload rows matched the payload filter, and which plan the
+    /// planner picked for the filter.
+    pub fn search_with_meta(&self, search_point: SearchPoint) -> Result<SearchResponse, VecXError> {
+        let collection_name = search_point.collection_name.clone();
+        let _permit = self
+            .collection_limiter
+            .acquire([collection_name.as_str()])?;
+        let top_k = search_point.top_k;
+        let post_process = search_point.post_process.clone();
+        let total_candidates = match self
+            .query_planner
+            .plan_candidate_count_query(&search_point)?
+        {
+            Some(query_plan) => Some(self.query_executor.execute_count_query(query_plan)?),
+            None => None,
+        };
+
+        let search_plan = self
+            .query_planner
+            .plan_search_query_with_kind(search_point)?;
+        let mut results = self
+            .query_executor
+            .execute_search_query(search_plan.query)?;
+        let truncated = results.len() as i64 >= top_k;
+        SearchPoint::apply_post_process(post_process.as_ref(), top_k, &mut results);
+        self.counters.record_searches(&collection_name, 1);
+
+        Ok(SearchResponse {
+            truncated,
+            results,
+            total_candidates,
+            plan: search_plan.kind,
+            estimated_candidates: search_plan.estimated_candidates,
+            approximate: search_plan.approximate,
+        })
+    }
+
+    /// Returns how often a collection was searched, inserted into and deleted from
+    /// through this instance since it was created.
+    ///
+    /// Counts live in memory and reset with the process; a collection that was never
+    /// used reports zeros. Deleting a collection resets its counts, renaming it keeps
+    /// them.
+    pub fn collection_stats(&self, collection_name: &str) -> CollectionStats {
+        self.counters.stats(collection_name)
+    }
+
+    /// Returns the SQL a search would run, without running it.
+    ///
+    /// Useful to see which filter strategy the planner picked for a payload query.
+    /// The query vector is inlined, written with `VectorXLiteConfig::explain_precision`
+    /// fractional digits when set.
+    pub fn explain_search(&self, search_point: SearchPoint) -> Result<String, VecXError> {
+        let _permit = self
+            .collection_limiter
+            .acquire([search_point.collection_name.as_str()])?;
+        self.query_planner.plan_explain_search_query(search_point)
+    }
+
+    /// Runs `EXPLAIN QUERY PLAN` on the SQL a search would run and returns the plan,
+    /// one line per step, nested steps indented.
+    ///
+    /// Useful to check whether a payload query uses an index or scans whole tables.
+    pub fn explain_query_plan(&self, search_point: &SearchPoint) -> Result<Vec<String>, VecXError> {
+        let _permit = self
+            .collection_limiter
+            .acquire([search_point.collection_name.as_str()])?;
+        let query_plan = self
+            .query_planner
+            .plan_explain_query_plan_query(search_point)?;
+
+        self.query_executor
+            .execute_explain_query_plan_query(query_plan)
+    }
+
+    /// Measures how many of the exact nearest neighbours the HNSW index finds.
+    ///
+    /// For every query vector the approximate `top_k` hits are compared with an exact
+    /// brute-force search over the whole collection, using the collection's distance
+    /// function. Returns the mean recall in `0.0..=1.0`.
+    ///
+    /// Meant for benchmarking index parameters; the brute-force pass is slow on large
+    /// collections. Requires the `recall` feature.
+    #[cfg(feature = "recall")]
+    pub fn measure_recall(
+        &self,
+        collection_name: &str,
+        queries: &[Vec<f32>],
+        top_k: i64,
+    ) -> Result<f32, VecXError> {
+        if queries.is_empty() {
+            return Err(VecXError::InvalidQueryError(
+                "measure_recall requires at least one query vector".to_string(),
+            ));
+        }
+
+        let rowids = |results: Vec<HashMap<String, String>>| -> std::collections::HashSet<String> {
+            results
+                .into_iter()
+                .filter_map(|mut row| row.remove("rowid"))
+                .collect()
+        };
+
+        let mut total_recall = 0.0f32;
+        for query in queries {
+            let search_point = SearchPoint::builder()
+                .collection_name(collection_name)
+                .vector(query.clone())
+                .top_k(top_k)
+                .build()
+                .map_err(VecXError::InvalidQueryError)?;
+            let approximate = rowids(self.search(search_point)?);
+
+            let exact = {
+                let _permit = self.collection_limiter.acquire([collection_name])?;
+                let exact_plan =
+                    self.query_planner
+                        .plan_exact_search_query(collection_name, query, top_k)?;
+                rowids(self.query_executor.execute_search_query(exact_plan)?)
+            };
+
+            total_recall += if exact.is_empty() {
+                1.0
+            } else {
+                exact.intersection(&approximate).count() as f32 / exact.len() as f32
+            };
+        }
+
+        Ok(total_recall / queries.len() as f32)
+    }
+
+    /// Finds the nearest neighbors of a stored point ("more like this").
+    ///
+    /// Reads the vector stored for `id` and runs a plain `search` with it, so results
+    /// have the same shape as `search` results, including the collection's
+    /// `default_payload_search`. With `exclude_self` the point itself is left out and up
+    /// to `top_k` other points are returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VecXError::InvalidQueryError` if the collection stores no vector for
+    /// `id` or is payload-only.
+    pub fn search_similar(
+        &self,
+        collection_name: &str,
+        id: i64,
+        top_k: i64,
+        exclude_self: bool,
+    ) -> Result<Vec<SearchResult>, VecXError> {
+        // The permit is released before searching, which takes its own
+        let stored = {
+            let _permit = self.collection_limiter.acquire([collection_name])?;
+            let query_plan = self
+                .query_planner
+                .plan_stored_vector_query(collection_name, id)?;
+            self.query_executor.execute_search_query(query_plan)?
+        };
+        let stored = stored.into_iter().next().ok_or_else(|| {
+            VecXError::InvalidQueryError(format!(
+                "point {} not found in collection '{}'",
+                id, collection_name
+            ))
+        })?;
+
+        let search_point = SearchPoint::builder()
+            .collection_name(collection_name)
+            .vector(vector_from_json(&stored["vector"])?)
+            .top_k(top_k.saturating_add(i64::from(exclude_self)))
+            .build()
+            .map_err(VecXError::InvalidQueryError)?;
+        let mut results = self.search(search_point)?;
+        if exclude_self {
+            results.retain(|row| row.get("rowid") != stored.get("rowid"));
+        }
+        results.truncate(top_k.max(0) as usize);
+        Ok(results)
+    }
+
+    /// Recomputes the distances from `query` to a set of stored vectors under `metric`.
+    ///
+    /// The metric does not have to be the collection's distance function, so the same
+    /// candidates (e.g. the hits of a `search`) can be compared under several metrics
+    /// without creating one collection per metric. Returns `(id, distance)` pairs
+    /// sorted by ascending distance; ids without a stored vector are left out.
+    pub fn rerank(
+        &self,
+        collection_name: &str,
+        candidate_ids: &[i64],
+        query: &[f32],
+        metric: DistanceFunction,
+    ) -> Result<Vec<(i64, f32)>, VecXError> {
+        let _permit = self.collection_limiter.acquire([collection_name])?;
+        let query_plan =
+            self.query_planner
+                .plan_rerank_query(collection_name, candidate_ids, query, metric)?;
+
+        self.query_executor.execute_rerank_query(query_plan)
     }
 
     /// Checks whether a collection with the given name exists.
@@ -89,19 +673,494 @@ impl VectorXLite {
             .query_planner
             .plan_collection_exists_query(collection_name)?;
 
-        self.query_executor.execute_collection_exists_query(query_plan)
+        self.query_executor
+            .execute_collection_exists_query(query_plan)
+    }
+
+    /// Counts the points of a collection whose payload matches a predicate.
+    ///
+    /// Runs `SELECT count(*) FROM <collection> WHERE <predicate_sql>` against the
+    /// payload table without materializing any rows, which makes it suitable for
+    /// faceting.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection to count in
+    /// * `predicate_sql` - A SQL boolean expression over the payload columns,
+    ///   e.g. `"rating >= 4 AND rating < 8"`
+    pub fn count_where(
+        &self,
+        collection_name: &str,
+        predicate_sql: &str,
+    ) -> Result<u64, VecXError> {
+        let _permit = self.collection_limiter.acquire([collection_name])?;
+        let query_plan = self
+            .query_planner
+            .plan_count_query(collection_name, predicate_sql)?;
+
+        self.query_executor.execute_count_query(query_plan)
+    }
+
+    /// Returns whether a collection holds any point, without counting them.
+    ///
+    /// Unlike `count_where(name, "1 = 1")`, which scans every payload row, this stops at
+    /// the first one, so it takes the same time on any collection size.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VecXError::SqlError` if the collection does not exist.
+    pub fn has_any(&self, collection_name: &str) -> Result<bool, VecXError> {
+        let _permit = self.collection_limiter.acquire([collection_name])?;
+        let query_plan = self.query_planner.plan_has_any_query(collection_name)?;
+
+        Ok(self.query_executor.execute_count_query(query_plan)? > 0)
+    }
+
+    /// Estimates the memory the HNSW index of a collection takes once loaded, for
+    /// capacity planning.
+    ///
+    /// The estimate follows hnswlib's layout for float32 vectors:
+    ///
+    /// ```text
+    /// bytes ≈ count * (dim * 4 + graph_overhead(M))
+    /// graph_overhead(M) = 4 * (2M + 1)          level-0 neighbour ids and their count
+    ///                   + 16                    label and pointer to the upper levels
+    ///                   + 4 * (M + 1) / (M - 1) expected upper-level neighbour lists
+    /// ```
+    ///
+    /// `count` is the number of payload rows and `M` the index's neighbour parameter
+    /// (16 unless set in the table definition). Allocator and SQLite overhead are not
+    /// included. Payload-only collections are estimated at 0 bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VecXError::InvalidQueryError` if the collection does not exist.
+    pub fn estimate_memory(&self, collection_name: &str) -> Result<u64, VecXError> {
+        let _permit = self.collection_limiter.acquire([collection_name])?;
+        let query_plan = self
+            .query_planner
+            .plan_estimate_memory_query(collection_name)?;
+
+        self.query_executor.execute_count_query(query_plan)
+    }
+
+    /// Updates payload columns on every row of a collection matching a predicate.
+    ///
+    /// Runs `UPDATE <collection> SET <set_sql> WHERE <predicate_sql>` in a transaction.
+    /// Vectors are left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The collection whose payload table is updated
+    /// * `set_sql` - The assignments, e.g. `"category = 'archived'"`
+    /// * `predicate_sql` - A SQL boolean expression over the payload columns
+    ///
+    /// # Returns
+    ///
+    /// The number of updated rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VecXError::InvalidQueryError` if the collection has no payload table or
+    /// if `set_sql` assigns the rowid.
+    pub fn update_where(
+        &self,
+        collection_name: &str,
+        set_sql: &str,
+        predicate_sql: &str,
+    ) -> Result<u64, VecXError> {
+        let _permit = self.collection_limiter.acquire([collection_name])?;
+        let query_plan =
+            self.query_planner
+                .plan_update_where_query(collection_name, set_sql, predicate_sql)?;
+
+        self.query_executor.execute_update_query(query_plan)
     }
 
     pub fn delete(&self, delete_point: DeletePoint) -> Result<(), VecXError> {
+        let collection_name = delete_point.collection_name.clone();
+        let _permit = self
+            .collection_limiter
+            .acquire([collection_name.as_str()])?;
         let delete_query_plan = self.query_planner.plan_delete_query(delete_point)?;
-        self.query_executor.execute_delete_query(delete_query_plan)
+        self.query_executor
+            .execute_delete_query(delete_query_plan)?;
+        self.counters.record_deletes(&collection_name, 1);
+        Ok(())
     }
 
+    /// Deletes several vectors from a collection in a single transaction.
+    ///
+    /// Ids that do not exist are skipped and reported in the returned summary
+    /// instead of failing the whole batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_delete` - The collection name and the ids to delete
+    ///
+    /// # Returns
+    ///
+    /// A `DeleteSummary` with the number of deleted and missing ids.
+    pub fn batch_delete(&self, batch_delete: BatchDelete) -> Result<DeleteSummary, VecXError> {
+        let collection_name = batch_delete.collection_name.clone();
+        let _permit = self
+            .collection_limiter
+            .acquire([collection_name.as_str()])?;
+        let query_plan_groups = self.query_planner.plan_batch_delete_query(batch_delete)?;
+        let summary = self
+            .query_executor
+            .execute_batch_delete_query(query_plan_groups)?;
+        self.counters
+            .record_deletes(&collection_name, summary.deleted_count);
+        Ok(summary)
+    }
+
+    /// Drops a collection's payload and vector tables.
+    ///
+    /// Waits for operations already running on the collection to finish and holds back
+    /// new ones until the tables are gone.
     pub fn delete_collection(&self, delete_collection: DeleteCollection) -> Result<(), VecXError> {
+        let collection_name = delete_collection.collection_name.clone();
+        let _permit = self
+            .collection_limiter
+            .acquire_exclusive([collection_name.as_str()])?;
         let delete_query_plan = self
             .query_planner
             .plan_delete_collection_query(delete_collection)?;
         self.query_executor
-            .execute_delete_collection_query(delete_query_plan)
+            .execute_delete_collection_query(delete_query_plan)?;
+        self.counters.remove(&collection_name);
+        Ok(())
+    }
+
+    /// Renames a collection without re-inserting its points.
+    ///
+    /// The payload and vector tables are renamed in a single transaction, while
+    /// operations on either name wait. For a file-backed collection the index file is
+    /// renamed too: the old collection name in its file name is replaced by the new
+    /// one, or the new name is prefixed when the file name does not contain it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VecXError::InvalidQueryError` if `old_name` does not exist, if
+    /// `new_name` is not a plain SQL identifier, or if `new_name` or its index file
+    /// already exists.
+    pub fn rename_collection(&self, old_name: &str, new_name: &str) -> Result<(), VecXError> {
+        validate_collection_name(new_name).map_err(VecXError::InvalidQueryError)?;
+        let _permit = self
+            .collection_limiter
+            .acquire_exclusive([old_name, new_name])?;
+        if !self.collection_exists(old_name)? {
+            return Err(VecXError::InvalidQueryError(format!(
+                "collection '{}' does not exist",
+                old_name
+            )));
+        }
+        if self.collection_exists(new_name)? {
+            return Err(VecXError::InvalidQueryError(format!(
+                "collection '{}' already exists",
+                new_name
+            )));
+        }
+
+        let query_plans = self
+            .query_planner
+            .plan_rename_collection_query(old_name, new_name)?;
+        self.query_executor
+            .execute_rename_collection_query(query_plans)?;
+        self.counters.rename(old_name, new_name);
+
+        self.flush(new_name)
+    }
+
+    /// Creates an index on payload columns of a collection, so payload queries that
+    /// filter or join on them avoid scanning the payload table.
+    ///
+    /// The index is named `vx_idx_<payload table>_<column>...` and persists with the
+    /// database. Creating an index that already exists does nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The collection whose payload table is indexed
+    /// * `columns` - The indexed payload columns, in index order
+    ///
+    /// # Errors
+    ///
+    /// Returns `VecXError::InvalidQueryError` if the collection has no payload table or
+    /// a column is not a plain column name.
+    pub fn create_payload_index(
+        &self,
+        collection_name: &str,
+        columns: &[&str],
+    ) -> Result<(), VecXError> {
+        self.create_payload_index_with(collection_name, columns, OnConflict::Ignore)
+    }
+
+    /// Creates a payload index like `create_payload_index`, choosing with `on_conflict`
+    /// whether an existing index over the same columns is kept or is an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VecXError::InvalidQueryError` if the collection has no payload table, a
+    /// column is not a plain column name, or the index exists and `on_conflict` is
+    /// `OnConflict::Error`.
+    pub fn create_payload_index_with(
+        &self,
+        collection_name: &str,
+        columns: &[&str],
+        on_conflict: OnConflict,
+    ) -> Result<(), VecXError> {
+        let _permit = self
+            .collection_limiter
+            .acquire_exclusive([collection_name])?;
+        let query_plan = self.query_planner.plan_create_payload_index_query(
+            collection_name,
+            columns,
+            on_conflict,
+        )?;
+
+        self.query_executor.execute_payload_index_query(query_plan)
+    }
+
+    /// Lists the payload indexes `create_payload_index` created on a collection, ordered
+    /// by name. Pass an index's `columns` to `drop_payload_index` to drop it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VecXError::InvalidQueryError` if the collection has no payload table.
+    pub fn list_payload_indexes(
+        &self,
+        collection_name: &str,
+    ) -> Result<Vec<PayloadIndex>, VecXError> {
+        let _permit = self.collection_limiter.acquire([collection_name])?;
+        let query_plan = self
+            .query_planner
+            .plan_list_payload_indexes_query(collection_name)?;
+
+        self.query_executor
+            .execute_list_payload_indexes_query(query_plan)
+    }
+
+    /// Drops the payload index `create_payload_index` created for the same columns.
+    ///
+    /// Dropping an index that does not exist does nothing.
+    pub fn drop_payload_index(
+        &self,
+        collection_name: &str,
+        columns: &[&str],
+    ) -> Result<(), VecXError> {
+        let _permit = self
+            .collection_limiter
+            .acquire_exclusive([collection_name])?;
+        let query_plan = self
+            .query_planner
+            .plan_drop_payload_index_query(collection_name, columns)?;
+
+        self.query_executor.execute_payload_index_query(query_plan)
     }
+
+    /// Forces the HNSW index of a file-backed collection to be written to disk.
+    ///
+    /// vectorlite keeps inserted vectors in memory and only persists the index file
+    /// when its virtual table is disconnected, which normally happens when a pooled
+    /// connection is closed. Calling `flush` persists recent inserts without relying
+    /// on a clean connection teardown, so a crash does not lose them.
+    ///
+    /// Collections without an index file, and in-memory databases, are unaffected.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection to flush
+    ///
+    /// # Errors
+    ///
+    /// Returns `VecXError::InvalidQueryError` if the collection does not exist.
+    pub fn flush(&self, collection_name: &str) -> Result<(), VecXError> {
+        if !self.collection_exists(collection_name)? {
+            return Err(VecXError::InvalidQueryError(format!(
+                "collection '{}' does not exist",
+                collection_name
+            )));
+        }
+
+        let flush_query_plan = self.query_planner.plan_flush_query()?;
+        self.query_executor.execute_flush_query(flush_query_plan)
+    }
+
+    /// Copies the database, including every collection's HNSW index, to files.
+    ///
+    /// Intended for moving an in-memory prototype to disk without re-inserting
+    /// anything. The database is written to `db_path` with SQLite's backup API, and
+    /// each collection's index is written to `<idx_dir>/<collection>.idx`, which the
+    /// persisted virtual tables reference. Reopen the result with a pool on `db_path`.
+    ///
+    /// Only vectors whose ids are present in the collection's payload table are copied.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VecXError::InvalidQueryError` if an index file already exists in
+    /// `idx_dir`.
+    pub fn persist_to<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        db_path: P,
+        idx_dir: Q,
+    ) -> Result<(), VecXError> {
+        std::fs::create_dir_all(idx_dir.as_ref())?;
+
+        let query_plans = self
+            .query_planner
+            .plan_persist_query(db_path.as_ref(), idx_dir.as_ref())?;
+        self.query_executor
+            .execute_persist_query(db_path.as_ref(), query_plans)
+    }
+
+    /// Reports the version and build flags of the loaded vectorlite extension.
+    ///
+    /// Useful when debugging compatibility issues between the extension and the
+    /// host platform. Unrecognized `vectorlite_info()` output does not fail; it is
+    /// returned verbatim in `VersionInfo::raw`.
+    pub fn version_info(&self) -> Result<VersionInfo, VecXError> {
+        let query_plan = self.query_planner.plan_version_info_query()?;
+        self.query_executor.execute_version_info_query(query_plan)
+    }
+
+    /// Reopens the idle pooled connections so they see database and index files that
+    /// were replaced outside of this instance, e.g. by restoring a snapshot in place.
+    ///
+    /// Pooled connections keep the file handle and the in-memory HNSW indexes they
+    /// opened with, so without a refresh they keep serving the replaced data. Call this
+    /// while no other thread is using the pool: connections checked out elsewhere are
+    /// not refreshed. In-memory databases are left untouched.
+    pub fn refresh_connections(&self) -> Result<(), VecXError> {
+        self.query_executor.execute_refresh_connections()
+    }
+
+    /// Rebuilds the database file to reclaim the space left behind by deleted rows.
+    ///
+    /// `VACUUM` runs on a dedicated connection, since it cannot run inside a
+    /// transaction or while the connection has statements in progress; pooled
+    /// connections are left untouched. The WAL is checkpointed afterwards, so the main
+    /// file shrinks right away unless another connection is reading at the time.
+    /// HNSW index files are not affected.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VecXError::InvalidQueryError` if a collection's payload table has no
+    /// `INTEGER PRIMARY KEY`, since `VACUUM` may renumber the rowids linking its rows to
+    /// their vectors.
+    pub fn vacuum(&self) -> Result<(), VecXError> {
+        let vacuum_query_plan = self.query_planner.plan_vacuum_query()?;
+        let checkpoint_query_plan = self.query_planner.plan_checkpoint_query()?;
+
+        self.query_executor
+            .execute_vacuum_query(vacuum_query_plan, checkpoint_query_plan)
+    }
+
+    /// Rewrites a collection's payload table to drop the free space that deletes and
+    /// updates left inside its pages.
+    ///
+    /// In a single transaction the rows are copied aside, deleted and inserted back in
+    /// rowid order, which packs the table and its payload indexes into as few pages as
+    /// possible. Unlike `vacuum`, only this table is rewritten and the pages it releases
+    /// are kept for later writes rather than returned to the file system. Rowids, and
+    /// with them the links to the vectors, are preserved; the vector table and HNSW
+    /// index are not touched. Delete and insert triggers on the payload table fire for
+    /// every row. Searches and writes on the collection wait until it is compacted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VecXError::InvalidQueryError` if the collection does not exist or has no
+    /// payload table.
+    pub fn compact_payload(&self, collection_name: &str) -> Result<(), VecXError> {
+        let _permit = self
+            .collection_limiter
+            .acquire_exclusive([collection_name])?;
+        if !self.collection_exists(collection_name)? {
+            return Err(VecXError::InvalidQueryError(format!(
+                "collection '{}' does not exist",
+                collection_name
+            )));
+        }
+
+        let query_plans = self
+            .query_planner
+            .plan_compact_payload_query(collection_name)?;
+        self.query_executor
+            .execute_compact_payload_query(query_plans)
+    }
+
+    /// Flushes every HNSW index, checkpoints the WAL and closes the connections.
+    ///
+    /// Unlike dropping the instance, which ignores errors and leaves connections open
+    /// for as long as the pool lives, `shutdown` reports failures and leaves a
+    /// file-backed database fully contained in its main file and index files, without
+    /// `-wal` or `-shm` files.
+    ///
+    /// Only idle connections are closed: connections checked out of a shared pool
+    /// elsewhere keep the database open. The pool's idle connections are replaced with
+    /// in-memory ones, so a pool shared with other code must not be used afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error encountered. Every connection is still closed if the
+    /// flush or the checkpoint fails.
+    pub fn shutdown(self) -> Result<(), VecXError> {
+        let flush_query_plan = self.query_planner.plan_flush_query()?;
+        let flush_result = self.query_executor.execute_flush_query(flush_query_plan);
+
+        let checkpoint_query_plan = self.query_planner.plan_checkpoint_query()?;
+        let shutdown_result = self
+            .query_executor
+            .execute_shutdown_query(checkpoint_query_plan);
+
+        flush_result.and(shutdown_result)
+    }
+}
+
+impl Drop for VectorXLite {
+    /// Flushes file-backed HNSW indexes so the last inserts survive even if the
+    /// underlying pool outlives this instance or is never closed cleanly.
+    fn drop(&mut self) {
+        if let Ok(flush_query_plan) = self.query_planner.plan_flush_query() {
+            let _ = self.query_executor.execute_flush_query(flush_query_plan);
+        }
+    }
+}
+
+/// Pairs every point of a batch with its position, resolving ids that occur more than
+/// once for the same collection: an error unless `dedupe_ids`, otherwise the last
+/// occurrence wins. Points without an id are always kept.
+fn positioned_batch_points(
+    points: Vec<InsertPoint>,
+    dedupe_ids: bool,
+) -> Result<Vec<(usize, InsertPoint)>, VecXError> {
+    let batch_key = |point: &InsertPoint| {
+        point
+            .id
+            .map(|id| (canonical_collection_name(&point.collection_name), id))
+    };
+
+    let mut last_positions = HashMap::new();
+    for (index, point) in points.iter().enumerate() {
+        let Some(key) = batch_key(point) else {
+            continue;
+        };
+        if let Some(previous) = last_positions.insert(key, index) {
+            if !dedupe_ids {
+                return Err(VecXError::InvalidQueryError(format!(
+                    "batch contains id {} for collection '{}' more than once, at positions {} and {}",
+                    point.id.unwrap_or_default(),
+                    point.collection_name,
+                    previous,
+                    index
+                )));
+            }
+        }
+    }
+
+    Ok(points
+        .into_iter()
+        .enumerate()
+        .filter(|(index, point)| batch_key(point).is_none_or(|key| last_positions[&key] == *index))
+        .collect())
 }
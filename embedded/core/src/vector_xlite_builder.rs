@@ -0,0 +1,120 @@
+use crate::constant::DEFAULT_SQLITE_TIMEOUT;
+use crate::customizer::SqliteConnectionCustomizer;
+use crate::error::VecXError;
+use crate::types::VectorXLiteConfig;
+use crate::VectorXLite;
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::path::PathBuf;
+
+/// Builder that sets up the connection pool and the `VectorXLite` on top of it in one
+/// step, instead of assembling the r2d2 pool and `SqliteConnectionCustomizer` by hand.
+///
+/// # Examples
+///
+/// ```no_run
+/// use vector_xlite::VectorXLite;
+///
+/// let vlite = VectorXLite::builder()
+///     .file("/var/lib/vectors/app.db")
+///     .max_size(5)
+///     .busy_timeout(10_000)
+///     .build()?;
+/// # Ok::<(), vector_xlite::error::VecXError>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct VectorXLiteBuilder {
+    location: Option<DatabaseLocation>,
+    max_size: Option<u32>,
+    busy_timeout_ms: Option<u32>,
+    extension_path: Option<PathBuf>,
+    config: VectorXLiteConfig,
+}
+
+#[derive(Debug)]
+enum DatabaseLocation {
+    Memory,
+    File(PathBuf),
+}
+
+impl VectorXLite {
+    /// Creates a new builder for constructing a VectorXLite together with its pool.
+    pub fn builder() -> VectorXLiteBuilder {
+        VectorXLiteBuilder::default()
+    }
+}
+
+impl VectorXLiteBuilder {
+    /// Keeps the database in memory.
+    ///
+    /// Every connection opens its own in-memory database, so the pool defaults to a
+    /// single connection.
+    pub fn memory(mut self) -> Self {
+        self.location = Some(DatabaseLocation::Memory);
+        self
+    }
+
+    /// Stores the database in the file at `path`, creating it if needed.
+    pub fn file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.location = Some(DatabaseLocation::File(path.into()));
+        self
+    }
+
+    /// Sets the maximum number of pooled connections (default 1 for `memory`, r2d2's
+    /// default of 10 for `file`).
+    pub fn max_size(mut self, size: u32) -> Self {
+        self.max_size = Some(size);
+        self
+    }
+
+    /// Sets the SQLite busy timeout in milliseconds applied to every connection.
+    pub fn busy_timeout(mut self, timeout_ms: u32) -> Self {
+        self.busy_timeout_ms = Some(timeout_ms);
+        self
+    }
+
+    /// Loads the vector extension from a native library on disk instead of the copy
+    /// embedded in this crate.
+    pub fn extension_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.extension_path = Some(path.into());
+        self
+    }
+
+    /// Sets the instance-wide settings passed to `VectorXLite::with_config`.
+    pub fn config(mut self, config: VectorXLiteConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Builds the pool and the VectorXLite on top of it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VecXError::Other` if neither `memory` nor `file` was called or
+    /// `max_size` is 0, and the pool's error if its first connection cannot be opened,
+    /// e.g. `VecXError::ExtensionLoadError` for an unloadable `extension_path`.
+    pub fn build(self) -> Result<VectorXLite, VecXError> {
+        let location = self.location.ok_or_else(|| {
+            VecXError::Other("Database location must be provided (memory or file).".into())
+        })?;
+
+        let (manager, default_max_size) = match location {
+            DatabaseLocation::Memory => (SqliteConnectionManager::memory(), 1),
+            DatabaseLocation::File(path) => (SqliteConnectionManager::file(path), 10),
+        };
+        let max_size = self.max_size.unwrap_or(default_max_size);
+        if max_size == 0 {
+            return Err(VecXError::Other("max_size must be greater than 0.".into()));
+        }
+
+        let pool = Pool::builder()
+            .max_size(max_size)
+            .connection_customizer(SqliteConnectionCustomizer::with_options(
+                self.busy_timeout_ms.unwrap_or(DEFAULT_SQLITE_TIMEOUT),
+                self.extension_path,
+            ))
+            .build(manager)?;
+
+        VectorXLite::with_config(pool, self.config)
+    }
+}
@@ -0,0 +1,268 @@
+use crate::constant::DEFAULT_SQLITE_TIMEOUT;
+use crate::customizer::SqliteConnectionCustomizer;
+use crate::error::VecXError;
+use crate::VectorXLite;
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Maps tenant keys to isolated `VectorXLite` instances, one database file per tenant.
+///
+/// Tenants are opened lazily on first use at `<base_dir>/<tenant>.db`, each with its own
+/// connection pool built from the shared pool and customizer settings. Tenants that have
+/// not been used for `idle_timeout` are closed by `evict_idle`, which also runs on every
+/// `get`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use vector_xlite::VectorXLiteRegistry;
+///
+/// let registry = VectorXLiteRegistry::builder()
+///     .base_dir("/var/lib/vectors")
+///     .build()
+///     .expect("Failed to build registry");
+///
+/// let tenant_a = registry.get("tenant_a")?;
+/// assert!(!tenant_a.collection_exists("docs")?);
+/// # Ok::<(), vector_xlite::error::VecXError>(())
+/// ```
+pub struct VectorXLiteRegistry {
+    base_dir: PathBuf,
+    pool_size: u32,
+    customizer: CustomizerFactory,
+    idle_timeout: Duration,
+    tenants: Mutex<HashMap<String, TenantEntry>>,
+}
+
+/// Creates the connection customizer of each tenant pool.
+type CustomizerFactory = Arc<dyn Fn() -> Box<SqliteConnectionCustomizer> + Send + Sync>;
+
+struct TenantEntry {
+    vlite: Arc<VectorXLite>,
+    last_used: Instant,
+}
+
+impl VectorXLiteRegistry {
+    /// Creates a new builder for constructing a VectorXLiteRegistry.
+    pub fn builder() -> VectorXLiteRegistryBuilder {
+        VectorXLiteRegistryBuilder::default()
+    }
+
+    /// Returns the instance for `tenant`, opening its database if it is not open yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VecXError::InvalidQueryError` if the tenant key is empty or contains
+    /// characters other than ASCII letters, digits, `_` and `-`.
+    pub fn get(&self, tenant: &str) -> Result<Arc<VectorXLite>, VecXError> {
+        validate_tenant_key(tenant)?;
+
+        let mut tenants = self.tenants.lock().expect("registry mutex poisoned");
+        self.evict_idle_locked(&mut tenants);
+
+        if let Some(entry) = tenants.get_mut(tenant) {
+            entry.last_used = Instant::now();
+            return Ok(Arc::clone(&entry.vlite));
+        }
+
+        let vlite = Arc::new(self.open_tenant(tenant)?);
+        tenants.insert(
+            tenant.to_string(),
+            TenantEntry {
+                vlite: Arc::clone(&vlite),
+                last_used: Instant::now(),
+            },
+        );
+
+        Ok(vlite)
+    }
+
+    /// Closes every tenant that has been idle for longer than `idle_timeout`.
+    ///
+    /// Tenants still referenced by a caller are kept open.
+    ///
+    /// # Returns
+    ///
+    /// The number of evicted tenants.
+    pub fn evict_idle(&self) -> usize {
+        let mut tenants = self.tenants.lock().expect("registry mutex poisoned");
+        self.evict_idle_locked(&mut tenants)
+    }
+
+    /// Closes `tenant` if it is open and no caller still holds it, like `evict_idle`.
+    ///
+    /// # Returns
+    ///
+    /// True if the tenant was closed, false if it was not open or is still in use.
+    pub fn evict(&self, tenant: &str) -> bool {
+        let mut tenants = self.tenants.lock().expect("registry mutex poisoned");
+        match tenants.get(tenant) {
+            Some(entry) if Arc::strong_count(&entry.vlite) == 1 => {
+                tenants.remove(tenant);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the number of currently open tenants.
+    pub fn open_tenant_count(&self) -> usize {
+        self.tenants.lock().expect("registry mutex poisoned").len()
+    }
+
+    fn evict_idle_locked(&self, tenants: &mut HashMap<String, TenantEntry>) -> usize {
+        let before = tenants.len();
+        tenants.retain(|_, entry| {
+            Arc::strong_count(&entry.vlite) > 1 || entry.last_used.elapsed() <= self.idle_timeout
+        });
+        before - tenants.len()
+    }
+
+    fn open_tenant(&self, tenant: &str) -> Result<VectorXLite, VecXError> {
+        std::fs::create_dir_all(&self.base_dir)?;
+
+        let manager = SqliteConnectionManager::file(self.base_dir.join(format!("{}.db", tenant)));
+        let pool = Pool::builder()
+            .max_size(self.pool_size)
+            .connection_customizer((self.customizer)())
+            .build(manager)?;
+
+        VectorXLite::new(pool)
+    }
+}
+
+fn validate_tenant_key(tenant: &str) -> Result<(), VecXError> {
+    let is_valid = !tenant.is_empty()
+        && tenant
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(VecXError::InvalidQueryError(format!(
+            "invalid tenant key '{}'",
+            tenant
+        )))
+    }
+}
+
+/// Builder for constructing VectorXLiteRegistry instances with validation.
+#[derive(Default)]
+pub struct VectorXLiteRegistryBuilder {
+    base_dir: Option<PathBuf>,
+    pool_size: Option<u32>,
+    busy_timeout_ms: Option<u32>,
+    customizer: Option<CustomizerFactory>,
+    idle_timeout: Option<Duration>,
+}
+
+impl fmt::Debug for VectorXLiteRegistryBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VectorXLiteRegistryBuilder")
+            .field("base_dir", &self.base_dir)
+            .field("pool_size", &self.pool_size)
+            .field("busy_timeout_ms", &self.busy_timeout_ms)
+            .field("customizer", &self.customizer.as_ref().map(|_| "<factory>"))
+            .field("idle_timeout", &self.idle_timeout)
+            .finish()
+    }
+}
+
+impl VectorXLiteRegistryBuilder {
+    /// Sets the directory holding one database file per tenant.
+    pub fn base_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.base_dir = Some(dir.into());
+        self
+    }
+
+    /// Sets the maximum number of pooled connections per tenant (default 5).
+    pub fn pool_size(mut self, size: u32) -> Self {
+        self.pool_size = Some(size);
+        self
+    }
+
+    /// Sets the SQLite busy timeout applied to every tenant connection.
+    pub fn busy_timeout_ms(mut self, timeout_ms: u32) -> Self {
+        self.busy_timeout_ms = Some(timeout_ms);
+        self
+    }
+
+    /// Sets the connection customizer of every tenant pool, e.g. to load the extension
+    /// from disk, run init SQL or register scalar functions for all tenants.
+    ///
+    /// `factory` is called once per opened tenant, since every pool owns its customizer.
+    /// It replaces the default customizer, so it cannot be combined with
+    /// `busy_timeout_ms`; use `SqliteConnectionCustomizer::with_busy_timeout` instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use vector_xlite::{customizer::SqliteConnectionCustomizer, VectorXLiteRegistry};
+    ///
+    /// let registry = VectorXLiteRegistry::builder()
+    ///     .base_dir("/var/lib/vectors")
+    ///     .customizer(|| {
+    ///         SqliteConnectionCustomizer::with_busy_timeout(10_000)
+    ///             .with_init_sql(vec!["PRAGMA foreign_keys = ON".to_string()])
+    ///     })
+    ///     .build()
+    ///     .expect("Failed to build registry");
+    /// ```
+    pub fn customizer<F>(mut self, factory: F) -> Self
+    where
+        F: Fn() -> Box<SqliteConnectionCustomizer> + Send + Sync + 'static,
+    {
+        self.customizer = Some(Arc::new(factory));
+        self
+    }
+
+    /// Sets how long a tenant may stay unused before it is evicted (default 10 minutes).
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Builds the VectorXLiteRegistry with validation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// * `base_dir` is not provided
+    /// * `pool_size` is 0
+    /// * both `customizer` and `busy_timeout_ms` are set
+    pub fn build(self) -> Result<VectorXLiteRegistry, String> {
+        let base_dir = self
+            .base_dir
+            .ok_or_else(|| "Base directory must be provided.".to_string())?;
+
+        let pool_size = self.pool_size.unwrap_or(5);
+        if pool_size == 0 {
+            return Err("pool_size must be greater than 0.".into());
+        }
+
+        let customizer = match (self.customizer, self.busy_timeout_ms) {
+            (Some(_), Some(_)) => {
+                return Err("customizer cannot be combined with busy_timeout_ms.".into());
+            }
+            (Some(customizer), None) => customizer,
+            (None, busy_timeout_ms) => {
+                let busy_timeout_ms = busy_timeout_ms.unwrap_or(DEFAULT_SQLITE_TIMEOUT);
+                Arc::new(move || SqliteConnectionCustomizer::with_busy_timeout(busy_timeout_ms))
+            }
+        };
+
+        Ok(VectorXLiteRegistry {
+            base_dir,
+            pool_size,
+            customizer,
+            idle_timeout: self.idle_timeout.unwrap_or(Duration::from_secs(600)),
+            tenants: Mutex::new(HashMap::new()),
+        })
+    }
+}
@@ -1,3 +1,13 @@
+use std::{env, path::PathBuf};
+
 fn main() {
-    tonic_prost_build::compile_protos("../../proto/vectorxlite/v1/vectorxlite.proto").unwrap();
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    tonic_prost_build::configure()
+        .file_descriptor_set_path(out_dir.join("vectorxlite_descriptor.bin"))
+        .compile_protos(
+            &["../../proto/vectorxlite/v1/vectorxlite.proto"],
+            &["../../proto/vectorxlite/v1"],
+        )
+        .unwrap();
 }
@@ -1,51 +1,83 @@
 use crate::proto::{
-    CollectionConfigPb, DeleteCollectionRequestPb, DeleteRequestPb, InsertPointPb, SearchPointPb,
+    BatchDeleteRequestPb, BatchSearchRequestPb, CollectionConfigPb, DeleteCollectionRequestPb,
+    DeleteRequestPb, DeleteSummaryPb, InsertPointPb, SearchPointPb,
 };
 use std::convert::TryFrom;
+use vector_xlite::error::VecXError;
 use vector_xlite::types::{
-    CollectionConfig, CollectionConfigBuilder, DeleteCollection, DeletePoint, DistanceFunction,
-    InsertPoint, SearchPoint,
+    BatchDelete, CollectionConfig, CollectionConfigBuilder, DeleteCollection, DeletePoint,
+    DeleteSummary, DistanceFunction, InsertPoint, SearchPoint,
 };
 
+fn invalid_argument<S: Into<String>>(message: S) -> VecXError {
+    VecXError::InvalidQueryError(message.into())
+}
+
+fn non_negative_id(id: i64) -> Result<u64, VecXError> {
+    u64::try_from(id).map_err(|_| invalid_argument(format!("id must not be negative: {}", id)))
+}
+
 impl TryFrom<CollectionConfigPb> for CollectionConfig {
-    type Error = String;
+    type Error = VecXError;
     fn try_from(pb: CollectionConfigPb) -> Result<Self, Self::Error> {
         let distance = match pb.distance.to_lowercase().as_str() {
             "cosine" => DistanceFunction::Cosine,
             "l2" => DistanceFunction::L2,
             "ip" => DistanceFunction::IP,
-            other => return Err(format!("unknown distance: {}", other)),
+            other => return Err(invalid_argument(format!("unknown distance: {}", other))),
         };
 
+        let dimension = u16::try_from(pb.vector_dimension).map_err(|_| {
+            invalid_argument(format!(
+                "vector_dimension must be at most {}: {}",
+                u16::MAX,
+                pb.vector_dimension
+            ))
+        })?;
+        if dimension == 0 {
+            return Err(invalid_argument("vector_dimension must be greater than 0"));
+        }
+
         CollectionConfigBuilder::default()
             .collection_name(&pb.collection_name)
             .distance(distance)
-            .vector_dimension(pb.vector_dimension as u16)
+            .vector_dimension(dimension)
             .payload_table_schema(&pb.payload_table_schema)
             .index_file_path(pb.index_file_path)
             .build()
-            .map_err(|e| e.to_string())
+            .map_err(invalid_argument)
     }
 }
 
 impl TryFrom<InsertPointPb> for InsertPoint {
-    type Error = String;
+    type Error = VecXError;
     fn try_from(pb: InsertPointPb) -> Result<Self, Self::Error> {
+        if pb.vector.is_empty() {
+            return Err(invalid_argument("vector must not be empty"));
+        }
+
         let mut b = InsertPoint::builder()
             .collection_name(&pb.collection_name)
-            .id(pb.id as u64)
+            .id(non_negative_id(pb.id)?)
             .vector(pb.vector);
 
         if !pb.payload_insert_query.is_empty() {
             b = b.payload_insert_query(&pb.payload_insert_query);
         }
-        b.build().map_err(|e| e.to_string())
+        if !pb.idempotency_key.is_empty() {
+            b = b.idempotency_key(&pb.idempotency_key);
+        }
+        b.build().map_err(invalid_argument)
     }
 }
 
 impl TryFrom<SearchPointPb> for SearchPoint {
-    type Error = String;
+    type Error = VecXError;
     fn try_from(pb: SearchPointPb) -> Result<Self, Self::Error> {
+        if pb.vector.is_empty() {
+            return Err(invalid_argument("vector must not be empty"));
+        }
+
         let mut b: vector_xlite::types::SearchPointBuilder = SearchPoint::builder();
         b = b.collection_name(&pb.collection_name);
         b = b.vector(pb.vector);
@@ -53,33 +85,70 @@ impl TryFrom<SearchPointPb> for SearchPoint {
         if !pb.payload_search_query.is_empty() {
             b = b.payload_search_query(&pb.payload_search_query);
         }
-        b.build().map_err(|e| e.to_string())
+        b.build().map_err(invalid_argument)
+    }
+}
+
+impl TryFrom<BatchSearchRequestPb> for Vec<SearchPoint> {
+    type Error = VecXError;
+    fn try_from(pb: BatchSearchRequestPb) -> Result<Self, Self::Error> {
+        if pb.queries.is_empty() {
+            return Err(invalid_argument("queries must not be empty"));
+        }
+
+        pb.queries.into_iter().map(SearchPoint::try_from).collect()
     }
 }
 
 impl TryFrom<DeleteRequestPb> for DeletePoint {
-    type Error = String;
+    type Error = VecXError;
     fn try_from(pb: DeleteRequestPb) -> Result<Self, Self::Error> {
         DeletePoint::builder()
             .collection_name(&pb.collection_name)
-            .id(pb.id as u64)
+            .id(non_negative_id(pb.id)?)
             .build()
-            .map_err(|e| e.to_string())
+            .map_err(invalid_argument)
+    }
+}
+
+impl TryFrom<BatchDeleteRequestPb> for BatchDelete {
+    type Error = VecXError;
+    fn try_from(pb: BatchDeleteRequestPb) -> Result<Self, Self::Error> {
+        let ids = pb
+            .ids
+            .into_iter()
+            .map(non_negative_id)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        BatchDelete::builder()
+            .collection_name(&pb.collection_name)
+            .ids(ids)
+            .build()
+            .map_err(invalid_argument)
     }
 }
 
 impl TryFrom<DeleteCollectionRequestPb> for DeleteCollection {
-    type Error = String;
+    type Error = VecXError;
     fn try_from(pb: DeleteCollectionRequestPb) -> Result<Self, Self::Error> {
         DeleteCollection::builder()
             .collection_name(&pb.collection_name)
             .build()
-            .map_err(|e| e.to_string())
+            .map_err(invalid_argument)
+    }
+}
+
+impl From<DeleteSummary> for DeleteSummaryPb {
+    fn from(summary: DeleteSummary) -> Self {
+        DeleteSummaryPb {
+            deleted_count: summary.deleted_count,
+            missing_count: summary.missing_count,
+        }
     }
 }
 
 // Conversion helpers for responses
-use crate::proto::{KeyValuePb, SearchResultItemPb};
+use crate::proto::{BatchSearchResponsePb, KeyValuePb, SearchResponsePb, SearchResultItemPb};
 use std::collections::HashMap;
 
 pub fn map_payload_to_kvs(map: &HashMap<String, String>) -> Vec<KeyValuePb> {
@@ -103,17 +172,55 @@ pub fn build_search_item(
     }
 }
 
+/// Convert a search result row to SearchResultItemPb.
+///
+/// `rowid` and `distance` are read from the row; missing or unparsable values become 0.
+impl From<HashMap<String, String>> for SearchResultItemPb {
+    fn from(row: HashMap<String, String>) -> Self {
+        let rowid = row
+            .get("rowid")
+            .and_then(|s| s.parse::<i64>().ok())
+            .unwrap_or(0);
+        let distance = row
+            .get("distance")
+            .and_then(|s| s.parse::<f32>().ok())
+            .unwrap_or(0.0);
+        build_search_item(rowid, distance, row)
+    }
+}
+
+/// Convert search result rows to SearchResponsePb
+impl From<Vec<HashMap<String, String>>> for SearchResponsePb {
+    fn from(rows: Vec<HashMap<String, String>>) -> Self {
+        SearchResponsePb {
+            results: rows.into_iter().map(SearchResultItemPb::from).collect(),
+        }
+    }
+}
+
+/// Convert the result lists of a batch search to BatchSearchResponsePb, keeping their order
+impl From<Vec<Vec<HashMap<String, String>>>> for BatchSearchResponsePb {
+    fn from(result_lists: Vec<Vec<HashMap<String, String>>>) -> Self {
+        BatchSearchResponsePb {
+            results: result_lists
+                .into_iter()
+                .map(SearchResponsePb::from)
+                .collect(),
+        }
+    }
+}
+
 // ============================================================================
 // Snapshot Conversions
 // ============================================================================
 
 use crate::proto::{
-    ExportSnapshotRequestPb, ImportSnapshotResponsePb, SnapshotChunkPb, SnapshotFilePb,
-    SnapshotFileInfoPb, SnapshotFileTypePb, SnapshotMetadataPb,
+    ExportSnapshotRequestPb, ImportSnapshotResponsePb, SnapshotChunkPb, SnapshotFileInfoPb,
+    SnapshotFilePb, SnapshotFileTypePb, SnapshotMetadataPb,
 };
 use vector_xlite::snapshot::{
-    FileChunk, ImportResult, SnapshotChunk, SnapshotConfig, SnapshotFileInfo,
-    SnapshotFileType, SnapshotMetadata,
+    FileChunk, ImportResult, SnapshotChunk, SnapshotConfig, SnapshotFileInfo, SnapshotFileType,
+    SnapshotMetadata,
 };
 
 /// Convert ExportSnapshotRequestPb to SnapshotConfig
@@ -188,6 +295,7 @@ impl From<SnapshotMetadata> for SnapshotMetadataPb {
             files: meta.files.into_iter().map(|f| f.into()).collect(),
             version: meta.version,
             checksum: meta.checksum,
+            encrypted: meta.encrypted,
         }
     }
 }
@@ -202,6 +310,7 @@ impl From<SnapshotMetadataPb> for SnapshotMetadata {
             files: pb.files.into_iter().map(|f| f.into()).collect(),
             version: pb.version,
             checksum: pb.checksum,
+            encrypted: pb.encrypted,
         }
     }
 }
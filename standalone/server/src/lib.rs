@@ -3,4 +3,22 @@ pub mod vector_xlite_grpc;
 
 pub mod proto {
     tonic::include_proto!("vectorxlite_pb");
+
+    /// Encoded file descriptor set for the VectorXLitePB service, used by server reflection.
+    pub const FILE_DESCRIPTOR_SET: &[u8] =
+        tonic::include_file_descriptor_set!("vectorxlite_descriptor");
+}
+
+/// Builds the gRPC server reflection service (v1) describing the VectorXLitePB API.
+///
+/// Lets tools such as `grpcurl` discover services and methods without the proto file.
+pub fn reflection_service() -> Result<
+    tonic_reflection::server::v1::ServerReflectionServer<
+        impl tonic_reflection::server::v1::ServerReflection,
+    >,
+    tonic_reflection::server::Error,
+> {
+    tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(proto::FILE_DESCRIPTOR_SET)
+        .build_v1()
 }
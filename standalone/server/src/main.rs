@@ -6,7 +6,8 @@ use std::env;
 use tonic::transport::Server;
 use vector_xlite::customizer::SqliteConnectionCustomizer;
 use vector_xlite_grpc::{
-    proto::vector_x_lite_pb_server::VectorXLitePbServer, vector_xlite_grpc::VectorXLiteGrpc,
+    proto::vector_x_lite_pb_server::VectorXLitePbServer, reflection_service,
+    vector_xlite_grpc::VectorXLiteGrpc,
 };
 
 #[derive(Parser, Debug)]
@@ -47,6 +48,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Server::builder()
         .add_service(VectorXLitePbServer::new(vxlite))
+        .add_service(reflection_service()?)
         .serve(addr)
         .await?;
 
@@ -1,12 +1,15 @@
-use crate::conversions::*;
 use crate::proto::{self as pb, vector_x_lite_pb_server::VectorXLitePb};
 use r2d2::Pool;
 use r2d2_sqlite::SqliteConnectionManager;
-use tonic::{Request, Response, Status};
 use tokio_stream::wrappers::ReceiverStream;
-use vector_xlite::VectorXLite;
+use tonic::metadata::MetadataValue;
+use tonic::{Request, Response, Status};
 use vector_xlite::snapshot::{SnapshotChunk, SnapshotConfig, SnapshotExporter, SnapshotImporter};
-use vector_xlite::types::{CollectionConfig, DeleteCollection, DeletePoint, InsertPoint, SearchPoint};
+use vector_xlite::types::{
+    BatchDelete, CollectionConfig, DeleteCollection, DeletePoint, InsertOutcome, InsertPoint,
+    SearchPoint,
+};
+use vector_xlite::VectorXLite;
 
 pub struct VectorXLiteGrpc {
     vxlite: VectorXLite,
@@ -26,7 +29,10 @@ impl VectorXLiteGrpc {
     }
 
     /// Creates a new VectorXLiteGrpc with specified index file paths for snapshot restore.
-    pub fn with_index_paths(connection_pool: Pool<SqliteConnectionManager>, index_paths: Vec<String>) -> Self {
+    pub fn with_index_paths(
+        connection_pool: Pool<SqliteConnectionManager>,
+        index_paths: Vec<String>,
+    ) -> Self {
         let inner = VectorXLite::new(connection_pool.clone()).expect("failed to setup vector db.");
 
         VectorXLiteGrpc {
@@ -44,7 +50,8 @@ impl VectorXLitePb for VectorXLiteGrpc {
         req: Request<pb::CollectionConfigPb>,
     ) -> Result<Response<pb::EmptyPb>, Status> {
         let cfg = req.into_inner();
-        let cfg = CollectionConfig::try_from(cfg).map_err(|e| Status::invalid_argument(e))?;
+        let cfg =
+            CollectionConfig::try_from(cfg).map_err(|e| Status::invalid_argument(e.to_string()))?;
 
         self.vxlite
             .create_collection(cfg)
@@ -57,12 +64,21 @@ impl VectorXLitePb for VectorXLiteGrpc {
         req: Request<pb::InsertPointPb>,
     ) -> Result<Response<pb::EmptyPb>, Status> {
         let ip = req.into_inner();
-        let point = InsertPoint::try_from(ip).map_err(|e| Status::invalid_argument(e))?;
+        let point =
+            InsertPoint::try_from(ip).map_err(|e| Status::invalid_argument(e.to_string()))?;
 
-        self.vxlite
-            .insert(point)
+        let outcome = self
+            .vxlite
+            .insert_idempotent(point)
             .map_err(|e| Status::internal(e.to_string()))?;
-        Ok(Response::new(pb::EmptyPb {}))
+
+        let mut response = Response::new(pb::EmptyPb {});
+        if outcome == InsertOutcome::Duplicate {
+            response
+                .metadata_mut()
+                .insert("x-idempotent-replay", MetadataValue::from_static("true"));
+        }
+        Ok(response)
     }
 
     async fn delete(
@@ -70,7 +86,8 @@ impl VectorXLitePb for VectorXLiteGrpc {
         req: Request<pb::DeleteRequestPb>,
     ) -> Result<Response<pb::DeleteResponsePb>, Status> {
         let dr = req.into_inner();
-        let delete_point = DeletePoint::try_from(dr).map_err(|e| Status::invalid_argument(e))?;
+        let delete_point =
+            DeletePoint::try_from(dr).map_err(|e| Status::invalid_argument(e.to_string()))?;
 
         self.vxlite
             .delete(delete_point)
@@ -88,7 +105,7 @@ impl VectorXLitePb for VectorXLiteGrpc {
     ) -> Result<Response<pb::DeleteResponsePb>, Status> {
         let dcr = req.into_inner();
         let delete_collection =
-            DeleteCollection::try_from(dcr).map_err(|e| Status::invalid_argument(e))?;
+            DeleteCollection::try_from(dcr).map_err(|e| Status::invalid_argument(e.to_string()))?;
 
         self.vxlite
             .delete_collection(delete_collection)
@@ -100,37 +117,52 @@ impl VectorXLitePb for VectorXLiteGrpc {
         }))
     }
 
+    async fn batch_delete(
+        &self,
+        req: Request<pb::BatchDeleteRequestPb>,
+    ) -> Result<Response<pb::DeleteSummaryPb>, Status> {
+        let bdr = req.into_inner();
+        let batch_delete =
+            BatchDelete::try_from(bdr).map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let summary = self
+            .vxlite
+            .batch_delete(batch_delete)
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(summary.into()))
+    }
+
     async fn search(
         &self,
         req: Request<pb::SearchPointPb>,
     ) -> Result<Response<pb::SearchResponsePb>, Status> {
         let sp = req.into_inner();
-        let search_point = SearchPoint::try_from(sp).map_err(|e| Status::invalid_argument(e))?;
+        let search_point =
+            SearchPoint::try_from(sp).map_err(|e| Status::invalid_argument(e.to_string()))?;
 
         let results = self
             .vxlite
             .search(search_point)
             .map_err(|e| Status::internal(e.to_string()))?;
 
-        let pb_results = results
-            .into_iter()
-            .map(|row_map| {
-                // expect rowid and distance presence (planner/executor should provide names)
-                let rowid = row_map
-                    .get("rowid")
-                    .and_then(|s| s.parse::<i64>().ok())
-                    .unwrap_or(0);
-                let distance = row_map
-                    .get("distance")
-                    .and_then(|s| s.parse::<f32>().ok())
-                    .unwrap_or(0.0);
-                build_search_item(rowid, distance, row_map)
-            })
-            .collect();
-
-        Ok(Response::new(pb::SearchResponsePb {
-            results: pb_results,
-        }))
+        Ok(Response::new(results.into()))
+    }
+
+    async fn batch_search(
+        &self,
+        req: Request<pb::BatchSearchRequestPb>,
+    ) -> Result<Response<pb::BatchSearchResponsePb>, Status> {
+        let bsr = req.into_inner();
+        let search_points = Vec::<SearchPoint>::try_from(bsr)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let result_lists = self
+            .vxlite
+            .search_many(search_points)
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(result_lists.into()))
     }
 
     async fn collection_exists(
@@ -0,0 +1,182 @@
+//! Tests for conversions between gRPC proto messages and core types
+//!
+//! These tests call the `TryFrom`/`From` implementations directly, without a server.
+
+use std::collections::HashMap;
+use vector_xlite::error::VecXError;
+use vector_xlite::types::{BatchDelete, CollectionConfig, InsertPoint, SearchPoint};
+use vector_xlite_grpc::proto as pb;
+
+fn collection_config_pb(distance: &str, vector_dimension: u32) -> pb::CollectionConfigPb {
+    pb::CollectionConfigPb {
+        collection_name: "docs".to_string(),
+        distance: distance.to_string(),
+        vector_dimension,
+        payload_table_schema: "create table docs (rowid integer primary key)".to_string(),
+        index_file_path: String::new(),
+    }
+}
+
+fn assert_invalid_argument<T>(result: Result<T, VecXError>, message: &str) {
+    match result {
+        Err(VecXError::InvalidQueryError(msg)) => {
+            assert!(msg.contains(message), "expected '{}' in '{}'", message, msg)
+        }
+        Err(other) => panic!("expected InvalidQueryError, got {:?}", other),
+        Ok(_) => panic!("expected InvalidQueryError, got Ok"),
+    }
+}
+
+#[test]
+fn collection_config_accepts_known_distances() {
+    for distance in ["cosine", "L2", "ip"] {
+        let config = CollectionConfig::try_from(collection_config_pb(distance, 3))
+            .expect("valid collection config");
+        assert_eq!(config.dimension, 3);
+    }
+}
+
+#[test]
+fn collection_config_rejects_unknown_distance() {
+    assert_invalid_argument(
+        CollectionConfig::try_from(collection_config_pb("manhattan", 3)),
+        "unknown distance: manhattan",
+    );
+}
+
+#[test]
+fn collection_config_rejects_out_of_range_dimension() {
+    assert_invalid_argument(
+        CollectionConfig::try_from(collection_config_pb("l2", 0)),
+        "vector_dimension must be greater than 0",
+    );
+    assert_invalid_argument(
+        CollectionConfig::try_from(collection_config_pb("l2", 70_000)),
+        "vector_dimension must be at most",
+    );
+}
+
+#[test]
+fn insert_point_converts_valid_input() {
+    let point = InsertPoint::try_from(pb::InsertPointPb {
+        collection_name: "docs".to_string(),
+        id: 7,
+        vector: vec![1.0, 2.0],
+        payload_insert_query: String::new(),
+        idempotency_key: String::new(),
+    })
+    .expect("valid insert point");
+
+    assert_eq!(point.id, Some(7));
+    assert_eq!(point.vector, vec![1.0, 2.0]);
+    assert!(point.payload_insert_query.is_none());
+    assert!(point.idempotency_key.is_none());
+}
+
+#[test]
+fn insert_point_keeps_idempotency_key() {
+    let point = InsertPoint::try_from(pb::InsertPointPb {
+        collection_name: "docs".to_string(),
+        id: 7,
+        vector: vec![1.0, 2.0],
+        payload_insert_query: String::new(),
+        idempotency_key: "req-7".to_string(),
+    })
+    .expect("valid insert point");
+
+    assert_eq!(point.idempotency_key.as_deref(), Some("req-7"));
+}
+
+#[test]
+fn insert_point_rejects_empty_vector_and_negative_id() {
+    assert_invalid_argument(
+        InsertPoint::try_from(pb::InsertPointPb {
+            collection_name: "docs".to_string(),
+            id: 1,
+            vector: vec![],
+            payload_insert_query: String::new(),
+            idempotency_key: String::new(),
+        }),
+        "vector must not be empty",
+    );
+    assert_invalid_argument(
+        InsertPoint::try_from(pb::InsertPointPb {
+            collection_name: "docs".to_string(),
+            id: -1,
+            vector: vec![1.0],
+            payload_insert_query: String::new(),
+            idempotency_key: String::new(),
+        }),
+        "id must not be negative",
+    );
+}
+
+#[test]
+fn search_point_converts_valid_input() {
+    let search_point = SearchPoint::try_from(pb::SearchPointPb {
+        collection_name: "docs".to_string(),
+        vector: vec![1.0, 2.0],
+        top_k: 5,
+        payload_search_query: "select rowid from docs".to_string(),
+    })
+    .expect("valid search point");
+
+    assert_eq!(search_point.top_k, 5);
+    assert_eq!(
+        search_point.payload_search_query.as_deref(),
+        Some("select rowid from docs")
+    );
+}
+
+#[test]
+fn search_point_rejects_empty_vector_and_zero_top_k() {
+    assert_invalid_argument(
+        SearchPoint::try_from(pb::SearchPointPb {
+            collection_name: "docs".to_string(),
+            vector: vec![],
+            top_k: 5,
+            payload_search_query: String::new(),
+        }),
+        "vector must not be empty",
+    );
+    assert_invalid_argument(
+        SearchPoint::try_from(pb::SearchPointPb {
+            collection_name: "docs".to_string(),
+            vector: vec![1.0],
+            top_k: 0,
+            payload_search_query: String::new(),
+        }),
+        "top_k must be greater than 0",
+    );
+}
+
+#[test]
+fn batch_delete_rejects_negative_ids() {
+    assert_invalid_argument(
+        BatchDelete::try_from(pb::BatchDeleteRequestPb {
+            collection_name: "docs".to_string(),
+            ids: vec![1, -2],
+        }),
+        "id must not be negative: -2",
+    );
+}
+
+#[test]
+fn search_results_convert_to_response() {
+    let row = HashMap::from([
+        ("rowid".to_string(), "3".to_string()),
+        ("distance".to_string(), "0.5".to_string()),
+        ("tag".to_string(), "a".to_string()),
+    ]);
+
+    let response = pb::SearchResponsePb::from(vec![row]);
+
+    assert_eq!(response.results.len(), 1);
+    let item = &response.results[0];
+    assert_eq!(item.rowid, 3);
+    assert_eq!(item.distance, 0.5);
+    assert!(item
+        .payload
+        .iter()
+        .any(|kv| kv.key == "tag" && kv.value == "a"));
+}
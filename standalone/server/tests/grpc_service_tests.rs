@@ -0,0 +1,269 @@
+//! gRPC integration tests for the VectorXLite server
+//!
+//! Each test starts the tonic server on a free local port backed by an
+//! in-memory database and talks to it through the generated client.
+
+use prost::Message;
+use prost_types::FileDescriptorProto;
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio_stream::StreamExt;
+use tonic::transport::{Channel, Server};
+use tonic_reflection::pb::v1::{
+    server_reflection_client::ServerReflectionClient, server_reflection_request::MessageRequest,
+    server_reflection_response::MessageResponse, ServerReflectionRequest,
+};
+use vector_xlite::customizer::SqliteConnectionCustomizer;
+use vector_xlite_grpc::proto::{
+    self as pb, vector_x_lite_pb_client::VectorXLitePbClient,
+    vector_x_lite_pb_server::VectorXLitePbServer,
+};
+use vector_xlite_grpc::reflection_service;
+use vector_xlite_grpc::vector_xlite_grpc::VectorXLiteGrpc;
+
+/// Starts a server on a free port and returns a connected client.
+async fn start_server() -> VectorXLitePbClient<Channel> {
+    let addr = spawn_server().await;
+    VectorXLitePbClient::connect(format!("http://{}", addr))
+        .await
+        .expect("connect client")
+}
+
+/// Starts a server with reflection enabled on a free port and returns its address.
+async fn spawn_server() -> SocketAddr {
+    let pool = Pool::builder()
+        .max_size(5)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(SqliteConnectionManager::memory())
+        .expect("create pool");
+
+    let addr: SocketAddr = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind free port");
+        listener.local_addr().expect("local addr")
+    };
+
+    tokio::spawn(async move {
+        Server::builder()
+            .add_service(VectorXLitePbServer::new(VectorXLiteGrpc::new(pool)))
+            .add_service(reflection_service().expect("reflection service"))
+            .serve(addr)
+            .await
+            .expect("serve");
+    });
+
+    for _ in 0..50 {
+        if Channel::from_shared(format!("http://{}", addr))
+            .expect("valid uri")
+            .connect()
+            .await
+            .is_ok()
+        {
+            return addr;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("server did not start on {}", addr);
+}
+
+async fn create_collection_with_points(
+    client: &mut VectorXLitePbClient<Channel>,
+    collection_name: &str,
+    count: i64,
+) {
+    client
+        .create_collection(pb::CollectionConfigPb {
+            collection_name: collection_name.to_string(),
+            distance: "l2".to_string(),
+            vector_dimension: 2,
+            payload_table_schema: format!(
+                "create table {} (rowid integer primary key)",
+                collection_name
+            ),
+            index_file_path: String::new(),
+        })
+        .await
+        .expect("create collection");
+
+    for id in 1..=count {
+        client
+            .insert(pb::InsertPointPb {
+                collection_name: collection_name.to_string(),
+                id,
+                vector: vec![id as f32, 0.0],
+                payload_insert_query: String::new(),
+                idempotency_key: String::new(),
+            })
+            .await
+            .expect("insert");
+    }
+}
+
+async fn search_count(client: &mut VectorXLitePbClient<Channel>, collection_name: &str) -> usize {
+    client
+        .search(pb::SearchPointPb {
+            collection_name: collection_name.to_string(),
+            vector: vec![0.0, 0.0],
+            top_k: 100,
+            payload_search_query: String::new(),
+        })
+        .await
+        .expect("search")
+        .into_inner()
+        .results
+        .len()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn batch_delete_removes_ids_and_reports_summary() {
+    let mut client = start_server().await;
+    create_collection_with_points(&mut client, "batch_items", 10).await;
+
+    let summary = client
+        .batch_delete(pb::BatchDeleteRequestPb {
+            collection_name: "batch_items".to_string(),
+            ids: vec![2, 4, 6, 8, 10, 42],
+        })
+        .await
+        .expect("batch delete")
+        .into_inner();
+
+    assert_eq!(summary.deleted_count, 5);
+    assert_eq!(summary.missing_count, 1);
+    assert_eq!(search_count(&mut client, "batch_items").await, 5);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn batch_delete_without_ids_is_invalid_argument() {
+    let mut client = start_server().await;
+
+    let status = client
+        .batch_delete(pb::BatchDeleteRequestPb {
+            collection_name: "batch_items".to_string(),
+            ids: vec![],
+        })
+        .await
+        .expect_err("empty batch should be rejected");
+
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn insert_retry_with_same_idempotency_key_is_marked_as_replay() {
+    let mut client = start_server().await;
+    create_collection_with_points(&mut client, "retried_items", 0).await;
+
+    let point = pb::InsertPointPb {
+        collection_name: "retried_items".to_string(),
+        id: 1,
+        vector: vec![1.0, 0.0],
+        payload_insert_query: String::new(),
+        idempotency_key: "req-1".to_string(),
+    };
+    let first = client.insert(point.clone()).await.expect("insert");
+    let retry = client.insert(point).await.expect("retried insert");
+
+    assert!(first.metadata().get("x-idempotent-replay").is_none());
+    assert_eq!(retry.metadata().get("x-idempotent-replay").unwrap(), "true");
+    assert_eq!(search_count(&mut client, "retried_items").await, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn batch_search_returns_one_result_list_per_query_in_order() {
+    let mut client = start_server().await;
+    create_collection_with_points(&mut client, "batch_search_items", 10).await;
+
+    let query_ids = [3, 10, 1, 7, 5];
+    let queries = query_ids
+        .iter()
+        .map(|&id| pb::SearchPointPb {
+            collection_name: "batch_search_items".to_string(),
+            vector: vec![id as f32, 0.0],
+            top_k: 2,
+            payload_search_query: String::new(),
+        })
+        .collect();
+
+    let response = client
+        .batch_search(pb::BatchSearchRequestPb { queries })
+        .await
+        .expect("batch search")
+        .into_inner();
+
+    assert_eq!(response.results.len(), 5);
+    for (results, id) in response.results.iter().zip(query_ids) {
+        assert_eq!(results.results.len(), 2);
+        assert_eq!(results.results[0].rowid, id);
+    }
+}
+
+/// Sends a single reflection request and returns the response payload.
+async fn reflect(addr: SocketAddr, request: MessageRequest) -> MessageResponse {
+    let channel = Channel::from_shared(format!("http://{}", addr))
+        .expect("valid uri")
+        .connect()
+        .await
+        .expect("connect reflection client");
+    let mut client = ServerReflectionClient::new(channel);
+
+    let request = ServerReflectionRequest {
+        host: String::new(),
+        message_request: Some(request),
+    };
+
+    let mut responses = client
+        .server_reflection_info(tokio_stream::once(request))
+        .await
+        .expect("reflection call")
+        .into_inner();
+
+    responses
+        .next()
+        .await
+        .expect("reflection response")
+        .expect("reflection status")
+        .message_response
+        .expect("message response")
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reflection_lists_vector_xlite_service() {
+    let addr = spawn_server().await;
+
+    let MessageResponse::ListServicesResponse(list) =
+        reflect(addr, MessageRequest::ListServices(String::new())).await
+    else {
+        panic!("expected list services response");
+    };
+
+    let names: Vec<String> = list.service.into_iter().map(|s| s.name).collect();
+    assert!(names.contains(&"vectorxlite_pb.VectorXLitePB".to_string()));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reflection_describes_vector_xlite_methods() {
+    let addr = spawn_server().await;
+
+    let MessageResponse::FileDescriptorResponse(files) = reflect(
+        addr,
+        MessageRequest::FileContainingSymbol("vectorxlite_pb.VectorXLitePB".to_string()),
+    )
+    .await
+    else {
+        panic!("expected file descriptor response");
+    };
+
+    let service = files
+        .file_descriptor_proto
+        .iter()
+        .map(|bytes| FileDescriptorProto::decode(bytes.as_slice()).expect("decode descriptor"))
+        .flat_map(|file| file.service)
+        .find(|service| service.name() == "VectorXLitePB")
+        .expect("service descriptor");
+
+    let methods: Vec<&str> = service.method.iter().map(|m| m.name()).collect();
+    for expected in ["CreateCollection", "Insert", "Search", "BatchDelete"] {
+        assert!(methods.contains(&expected), "missing method {}", expected);
+    }
+}
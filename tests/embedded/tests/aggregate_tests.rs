@@ -0,0 +1,155 @@
+//! Tests for SearchPoint aggregate
+//!
+//! These tests verify:
+//! - Sum and average of a payload column over the top_k neighbours
+//! - Similarity weighting for l2 and cosine collections matches hand-computed values
+//! - Invalid aggregate settings are rejected
+
+use vector_xlite::{error::VecXError, types::*, VectorXLite};
+
+fn setup_vlite(distance: DistanceFunction, points: &[(u64, [f32; 2], f64)]) -> VectorXLite {
+    let vlite = VectorXLite::builder()
+        .memory()
+        .build()
+        .expect("build in-memory instance");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("votes")
+        .distance(distance)
+        .vector_dimension(2)
+        .payload_table_schema("create table votes (rowid integer primary key, label real)")
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for (id, vector, label) in points {
+        let point = InsertPoint::builder()
+            .collection_name("votes")
+            .id(*id)
+            .vector(vector.to_vec())
+            .payload_insert_query(format!(
+                "insert into votes(rowid, label) values (?1, {})",
+                label
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+    vlite
+}
+
+/// Squared l2 distances to the origin are 1, 4, 9 and 100 for the labels 10, 20, 30
+/// and 100, so the top 3 have similarities 1/2, 1/5 and 1/10.
+fn l2_votes() -> VectorXLite {
+    setup_vlite(
+        DistanceFunction::L2,
+        &[
+            (1, [1.0, 0.0], 10.0),
+            (2, [2.0, 0.0], 20.0),
+            (3, [3.0, 0.0], 30.0),
+            (4, [10.0, 0.0], 100.0),
+        ],
+    )
+}
+
+fn aggregate(vlite: &VectorXLite, query: [f32; 2], op: AggOp, weighted: bool) -> f64 {
+    let search_point = SearchPoint::builder()
+        .collection_name("votes")
+        .vector(query.to_vec())
+        .top_k(3)
+        .payload_search_query("select rowid, label from votes")
+        .aggregate(AggSpec {
+            column: "label".to_string(),
+            op,
+            weight_by_similarity: weighted,
+        })
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).expect("search should succeed");
+
+    assert_eq!(results.len(), 1, "{:?}", results);
+    results[0]["aggregate"].parse().unwrap()
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-4,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn unweighted_aggregates_cover_top_k() {
+    let vlite = l2_votes();
+
+    assert_close(aggregate(&vlite, [0.0, 0.0], AggOp::Sum, false), 60.0);
+    assert_close(aggregate(&vlite, [0.0, 0.0], AggOp::Avg, false), 20.0);
+}
+
+#[test]
+fn weighted_aggregates_on_l2_collection() {
+    let vlite = l2_votes();
+
+    // 10/2 + 20/5 + 30/10
+    assert_close(aggregate(&vlite, [0.0, 0.0], AggOp::Sum, true), 12.0);
+    // 12 / (1/2 + 1/5 + 1/10)
+    assert_close(aggregate(&vlite, [0.0, 0.0], AggOp::Avg, true), 15.0);
+}
+
+#[test]
+fn weighted_aggregates_on_cosine_collection() {
+    let vlite = setup_vlite(
+        DistanceFunction::Cosine,
+        &[
+            (1, [1.0, 0.0], 10.0),
+            (2, [1.0, 1.0], 20.0),
+            (3, [0.0, 1.0], 30.0),
+            (4, [-1.0, 0.0], 100.0),
+        ],
+    );
+    let inv_sqrt2 = std::f64::consts::FRAC_1_SQRT_2;
+
+    // Similarities to [1, 0] are 1, 1/sqrt(2) and 0.
+    assert_close(
+        aggregate(&vlite, [1.0, 0.0], AggOp::Sum, true),
+        10.0 + 20.0 * inv_sqrt2,
+    );
+    assert_close(
+        aggregate(&vlite, [1.0, 0.0], AggOp::Avg, true),
+        (10.0 + 20.0 * inv_sqrt2) / (1.0 + inv_sqrt2),
+    );
+}
+
+#[test]
+fn invalid_aggregates_are_rejected() {
+    let spec = AggSpec {
+        column: "label; drop table votes".to_string(),
+        op: AggOp::Sum,
+        weight_by_similarity: false,
+    };
+    let err = SearchPoint::builder()
+        .collection_name("votes")
+        .vector(vec![0.0, 0.0])
+        .payload_search_query("select rowid, label from votes")
+        .aggregate(spec.clone())
+        .build()
+        .unwrap_err();
+    assert_eq!(err, "aggregate column must be a plain column name.");
+
+    let search_point = SearchPoint::builder()
+        .collection_name("votes")
+        .vector(vec![0.0, 0.0])
+        .aggregate(AggSpec {
+            column: "label".to_string(),
+            ..spec
+        })
+        .build()
+        .unwrap();
+    assert!(matches!(
+        l2_votes().search(search_point),
+        Err(VecXError::InvalidQueryError(_))
+    ));
+}
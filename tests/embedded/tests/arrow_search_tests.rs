@@ -0,0 +1,160 @@
+//! Tests for search_arrow method in VectorXLite
+//!
+//! These tests verify:
+//! - Results come back as one record batch with a row per hit
+//! - rowid is Int64, distance is Float64 and payload columns keep their types
+//! - NULL payload values become Arrow nulls and blobs are Binary
+//! - Searches without hits still return the result columns
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{
+    arrow_array::{Array, BinaryArray, Float64Array, Int64Array, StringArray},
+    arrow_schema::DataType,
+    customizer::SqliteConnectionCustomizer,
+    types::*,
+    VectorXLite,
+};
+
+fn setup_vlite() -> VectorXLite {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool).expect("create VectorXLite");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("images")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema(
+            "create table images (rowid integer primary key, caption text, rating real, thumbnail blob)",
+        )
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    let payloads = [
+        "insert into images(rowid, caption, rating, thumbnail) values (?1, 'cat', 4.5, x'0102')",
+        "insert into images(rowid, caption, rating, thumbnail) values (?1, 'dog', null, x'03')",
+        "insert into images(rowid, caption, rating, thumbnail) values (?1, 'owl', 3.0, null)",
+    ];
+    for (id, payload) in (1..).zip(payloads) {
+        let point = InsertPoint::builder()
+            .collection_name("images")
+            .id(id)
+            .vector(vec![id as f32, 0.0])
+            .payload_insert_query(payload)
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+    vlite
+}
+
+fn search_point(payload_search_query: &str) -> SearchPoint {
+    SearchPoint::builder()
+        .collection_name("images")
+        .vector(vec![0.0, 0.0])
+        .top_k(10)
+        .payload_search_query(payload_search_query)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn search_arrow_returns_typed_columns() {
+    let vlite = setup_vlite();
+
+    let batch = vlite
+        .search_arrow(search_point(
+            "select rowid, caption, rating, thumbnail from images",
+        ))
+        .unwrap();
+
+    assert_eq!(batch.num_rows(), 3);
+    let schema = batch.schema();
+    let column = |name: &str| schema.index_of(name).map(|i| batch.column(i)).unwrap();
+    assert_eq!(
+        schema.field_with_name("rowid").unwrap().data_type(),
+        &DataType::Int64
+    );
+    assert_eq!(
+        schema.field_with_name("distance").unwrap().data_type(),
+        &DataType::Float64
+    );
+    assert_eq!(
+        schema.field_with_name("caption").unwrap().data_type(),
+        &DataType::Utf8
+    );
+
+    let rowids = column("rowid")
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .unwrap();
+    assert_eq!(rowids.values(), &[1, 2, 3]);
+    let distances = column("distance")
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .unwrap();
+    assert!(distances.value(0) < distances.value(1));
+    let captions = column("caption")
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(captions.value(2), "owl");
+}
+
+#[test]
+fn search_arrow_keeps_nulls_and_blobs() {
+    let vlite = setup_vlite();
+
+    let batch = vlite
+        .search_arrow(search_point("select rowid, rating, thumbnail from images"))
+        .unwrap();
+    let schema = batch.schema();
+
+    let ratings = batch.column(schema.index_of("rating").unwrap());
+    let ratings = ratings.as_any().downcast_ref::<Float64Array>().unwrap();
+    assert_eq!(ratings.value(0), 4.5);
+    assert!(ratings.is_null(1));
+
+    let thumbnails = batch.column(schema.index_of("thumbnail").unwrap());
+    let thumbnails = thumbnails.as_any().downcast_ref::<BinaryArray>().unwrap();
+    assert_eq!(thumbnails.value(0), &[0x01, 0x02]);
+    assert!(thumbnails.is_null(2));
+}
+
+#[test]
+fn search_arrow_without_payload_query() {
+    let vlite = setup_vlite();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("images")
+        .vector(vec![0.0, 0.0])
+        .top_k(2)
+        .build()
+        .unwrap();
+    let batch = vlite.search_arrow(search_point).unwrap();
+
+    assert_eq!(batch.num_rows(), 2);
+    assert_eq!(batch.schema().field(0).name(), "rowid");
+}
+
+#[test]
+fn search_arrow_without_hits_keeps_columns() {
+    let vlite = setup_vlite();
+
+    let batch = vlite
+        .search_arrow(search_point(
+            "select rowid, caption from images where caption = 'none'",
+        ))
+        .unwrap();
+
+    assert_eq!(batch.num_rows(), 0);
+    assert!(batch.schema().index_of("caption").is_ok());
+}
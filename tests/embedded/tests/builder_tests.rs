@@ -4,6 +4,7 @@
 //! - Required field validation
 //! - Default value behavior
 //! - Edge cases in builder inputs
+//! - Cloning points and builders, and Debug output summarizing vectors
 
 use vector_xlite::types::*;
 
@@ -122,6 +123,29 @@ mod collection_config_builder {
         assert!(schema.to_lowercase().contains("create table"));
     }
 
+    #[test]
+    fn no_payload_skips_default_payload_schema() {
+        let config = CollectionConfigBuilder::default()
+            .collection_name("my_collection")
+            .no_payload(true)
+            .build()
+            .unwrap();
+
+        assert!(config.no_payload);
+        assert!(config.payload_table_schema.is_none());
+    }
+
+    #[test]
+    fn no_payload_with_payload_schema_fails() {
+        let result = CollectionConfigBuilder::default()
+            .collection_name("my_collection")
+            .no_payload(true)
+            .payload_table_schema("create table my_collection (rowid integer primary key)")
+            .build();
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn index_file_path_is_set() {
         let config = CollectionConfigBuilder::default()
@@ -136,6 +160,35 @@ mod collection_config_builder {
         );
     }
 
+    #[test]
+    fn index_dir_derives_index_file_path() {
+        let config = CollectionConfigBuilder::default()
+            .collection_name("test")
+            .index_dir("/path/to/indexes")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            config.index_file_path,
+            Some("/path/to/indexes/test.idx".to_string())
+        );
+    }
+
+    #[test]
+    fn index_file_path_overrides_index_dir() {
+        let config = CollectionConfigBuilder::default()
+            .collection_name("test")
+            .index_file_path("/path/to/index.bin")
+            .index_dir("/path/to/indexes")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            config.index_file_path,
+            Some("/path/to/index.bin".to_string())
+        );
+    }
+
     #[test]
     fn max_elements_is_set() {
         let config = CollectionConfigBuilder::default()
@@ -147,6 +200,38 @@ mod collection_config_builder {
         assert_eq!(config.max_elements, 500000);
     }
 
+    #[test]
+    fn preallocate_for_sizes_max_elements_with_headroom() {
+        let config = CollectionConfigBuilder::default()
+            .collection_name("test")
+            .preallocate_for(400)
+            .build()
+            .unwrap();
+
+        assert_eq!(config.max_elements, 500);
+    }
+
+    #[test]
+    fn random_seed_defaults_to_none() {
+        let config = CollectionConfigBuilder::default()
+            .collection_name("test")
+            .build()
+            .unwrap();
+
+        assert_eq!(config.random_seed, None);
+    }
+
+    #[test]
+    fn random_seed_is_set() {
+        let config = CollectionConfigBuilder::default()
+            .collection_name("test")
+            .random_seed(42)
+            .build()
+            .unwrap();
+
+        assert_eq!(config.random_seed, Some(42));
+    }
+
     #[test]
     fn builder_accepts_string_types() {
         // Test that Into<String> works for various string types
@@ -160,15 +245,75 @@ mod collection_config_builder {
     }
 
     #[test]
-    fn zero_dimension_is_accepted() {
-        // Note: This might be a design issue - 0 dimension doesn't make sense
-        let config = CollectionConfigBuilder::default()
+    fn zero_dimension_is_rejected() {
+        let result = CollectionConfigBuilder::default()
             .collection_name("test")
             .vector_dimension(0)
-            .build()
-            .unwrap();
+            .build();
 
-        assert_eq!(config.dimension, 0);
+        let msg = result.err().expect("zero dimension should be rejected");
+        assert!(msg.contains("vector dimension greater than 0"), "{}", msg);
+    }
+
+    #[test]
+    fn blank_collection_name_is_rejected() {
+        for name in ["", "   "] {
+            let result = CollectionConfigBuilder::default()
+                .collection_name(name)
+                .build();
+
+            assert_eq!(
+                result.err().as_deref(),
+                Some("collection_name must not be empty.")
+            );
+        }
+    }
+
+    #[test]
+    fn collection_name_with_invalid_characters_is_rejected() {
+        for name in ["my-collection", "1st", "docs; drop table x", "naïve"] {
+            let result = CollectionConfigBuilder::default()
+                .collection_name(name)
+                .build();
+
+            let msg = result.err().expect("name should be rejected");
+            assert!(msg.contains(&format!("'{}'", name)), "{}", msg);
+            assert!(
+                msg.contains("ASCII letters, digits and underscores"),
+                "{}",
+                msg
+            );
+        }
+    }
+
+    #[test]
+    fn zero_max_elements_is_rejected() {
+        let result = CollectionConfigBuilder::default()
+            .collection_name("test")
+            .max_elements(0)
+            .build();
+
+        let msg = result.err().expect("zero max_elements should be rejected");
+        assert!(msg.contains("max_elements greater than 0"), "{}", msg);
+    }
+
+    #[test]
+    fn validate_checks_configs_built_without_builder() {
+        let config = CollectionConfig {
+            collection_name: "_docs_2".to_string(),
+            ..CollectionConfig::default()
+        };
+        assert!(config.validate().is_ok());
+
+        let config = CollectionConfig {
+            collection_name: "docs".to_string(),
+            max_elements: 0,
+            ..CollectionConfig::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(vector_xlite::error::VecXError::InvalidQueryError(_))
+        ));
     }
 
     #[test]
@@ -270,7 +415,7 @@ mod insert_point_builder {
 
     #[test]
     fn empty_vector_is_accepted() {
-        // Note: This might be a design issue - empty vectors don't make sense
+        // The builder accepts an empty vector; insert rejects it.
         let point = InsertPoint::builder()
             .collection_name("test")
             .vector(vec![])
@@ -316,6 +461,55 @@ mod insert_point_builder {
 
         assert_eq!(point.id, Some(u64::MAX));
     }
+
+    #[test]
+    fn collection_name_with_sql_characters_is_rejected() {
+        for name in ["docs; DROP TABLE docs", "docs\"", "do'cs", "docs--"] {
+            let result = InsertPoint::builder()
+                .collection_name(name)
+                .id(1)
+                .vector(vec![1.0])
+                .build();
+
+            assert!(result.is_err(), "{} should be rejected", name);
+        }
+
+        let point = InsertPoint::builder()
+            .collection_name("Docs_2")
+            .vector(vec![1.0])
+            .build()
+            .unwrap();
+        assert_eq!(point.collection_name, "Docs_2");
+    }
+
+    #[test]
+    fn builder_clone_builds_independent_points() {
+        let builder = InsertPoint::builder()
+            .collection_name("test")
+            .vector(vec![1.0, 2.0]);
+
+        let first = builder.clone().id(1).build().unwrap();
+        let second = builder.id(2).build().unwrap();
+
+        assert_eq!(first.id, Some(1));
+        assert_eq!(second.id, Some(2));
+        assert_eq!(first.vector, second.vector);
+    }
+
+    #[test]
+    fn debug_summarizes_vector() {
+        let point = InsertPoint::builder()
+            .collection_name("test")
+            .id(7)
+            .vector((0..1536).map(|i| i as f32).collect())
+            .build()
+            .unwrap();
+
+        let debug = format!("{:?}", point);
+        assert!(debug.contains("[0.0, 1.0, 2.0, 3.0, ... (1536 values)]"), "{}", debug);
+        assert!(!debug.contains("1535.0"), "{}", debug);
+        assert!(debug.contains("id: Some(7)"), "{}", debug);
+    }
 }
 
 // ============================================================================
@@ -449,6 +643,55 @@ mod search_point_builder {
 
         assert!(search.vector.is_empty());
     }
+
+    #[test]
+    fn collection_name_with_sql_characters_is_rejected() {
+        for name in ["docs; DROP TABLE docs", "\"docs\"", "docs'", "1docs"] {
+            let result = SearchPoint::builder()
+                .collection_name(name)
+                .vector(vec![1.0])
+                .build();
+
+            assert!(result.is_err(), "{} should be rejected", name);
+        }
+
+        let search = SearchPoint::builder()
+            .collection_name("_docs")
+            .vector(vec![1.0])
+            .build()
+            .unwrap();
+        assert_eq!(search.collection_name, "_docs");
+    }
+
+    #[test]
+    fn clone_is_independent_of_original() {
+        let search = SearchPoint::builder()
+            .collection_name("test")
+            .vector(vec![1.0, 0.0])
+            .top_k(5)
+            .build()
+            .unwrap();
+
+        let mut clone = search.clone();
+        clone.top_k = 50;
+
+        assert_eq!(search.top_k, 5);
+        assert_eq!(clone.top_k, 50);
+        assert_eq!(clone.vector, search.vector);
+    }
+
+    #[test]
+    fn debug_summarizes_vector() {
+        let builder = SearchPoint::builder()
+            .collection_name("test")
+            .vector((0..768).map(|i| i as f32 / 2.0).collect());
+        let search = builder.clone().build().unwrap();
+
+        for debug in [format!("{:?}", search), format!("{:?}", builder)] {
+            assert!(debug.contains("[0.0, 0.5, 1.0, 1.5, ... (768 values)]"), "{}", debug);
+            assert!(!debug.contains("383.5"), "{}", debug);
+        }
+    }
 }
 
 // ============================================================================
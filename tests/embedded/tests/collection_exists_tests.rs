@@ -173,7 +173,7 @@ fn collection_exists_with_special_characters() {
 }
 
 #[test]
-fn collection_exists_case_sensitive() {
+fn collection_exists_case_insensitive() {
     let (vlite, _) = setup_vlite();
 
     // Create a collection with lowercase name
@@ -195,16 +195,14 @@ fn collection_exists_case_sensitive() {
         "Collection with exact case should exist"
     );
 
-    // SQLite table names are case-sensitive when quoted
-    // This test documents the current behavior
+    // Collection names follow SQLite's case-insensitive table names
     let exists_uppercase = vlite
         .collection_exists("LOWERCASE_COLLECTION")
         .expect("check should succeed");
 
-    // Note: SQLite treats table names as case-sensitive for unquoted identifiers
     assert!(
-        !exists_uppercase,
-        "Collection names are case-sensitive"
+        exists_uppercase,
+        "Collection names are case-insensitive"
     );
 }
 
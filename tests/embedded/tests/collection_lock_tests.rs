@@ -0,0 +1,250 @@
+//! Tests for serializing collection changes with running operations
+//!
+//! These tests verify:
+//! - delete_collection waits for operations running on the collection
+//! - A collection change that cannot start in time fails without side effects
+//! - Payload index changes wait for running operations like other collection changes
+//! - A collection change that times out leaves a running change's lock in place
+//! - Searches running during repeated compactions see whole, consistent results
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::fs;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use vector_xlite::{error::VecXError, types::*, VectorXLite};
+
+const ROWS: u64 = 500;
+
+struct TestDir(String);
+
+impl TestDir {
+    fn new(name: &str) -> Self {
+        let dir = format!("/tmp/vxlite_test_collection_lock_{}", name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        TestDir(dir)
+    }
+
+    fn db_path(&self) -> String {
+        format!("{}/lock.db", self.0)
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn setup_vlite(dir: &TestDir, connection_timeout: Duration) -> VectorXLite {
+    let vlite = VectorXLite::builder()
+        .file(dir.db_path())
+        .max_size(4)
+        .config(VectorXLiteConfig::default().with_connection_timeout(connection_timeout))
+        .build()
+        .expect("build file-backed instance");
+
+    let config = CollectionConfigBuilder::default()
+        .collection_name("events")
+        .payload_table_schema("create table events (rowid integer primary key, body text)")
+        .payload_only(true)
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for id in 1..=ROWS {
+        let point = InsertPoint::builder()
+            .collection_name("events")
+            .id(id)
+            .vector(vec![])
+            .payload_insert_query(format!(
+                "insert into events(rowid, body) values (?1, 'event {}')",
+                id
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+    vlite
+}
+
+fn scan_all() -> SearchPoint {
+    SearchPoint::builder()
+        .collection_name("events")
+        .vector(vec![])
+        .top_k(ROWS as i64 * 2)
+        .payload_search_query("select rowid, body from events")
+        .build()
+        .unwrap()
+}
+
+fn table_exists(db_path: &str) -> bool {
+    let pool = Pool::builder()
+        .max_size(1)
+        .build(SqliteConnectionManager::file(db_path))
+        .unwrap();
+    pool.get()
+        .unwrap()
+        .query_row(
+            "SELECT count(*) FROM sqlite_master WHERE name = 'events'",
+            [],
+            |row| row.get::<_, i64>(0),
+        )
+        .unwrap()
+        == 1
+}
+
+fn delete_events(vlite: &VectorXLite) -> Result<(), VecXError> {
+    vlite.delete_collection(
+        DeleteCollection::builder()
+            .collection_name("events")
+            .build()
+            .unwrap(),
+    )
+}
+
+#[test]
+fn delete_collection_waits_for_running_operations() {
+    let dir = TestDir::new("wait");
+    let vlite = Arc::new(setup_vlite(&dir, Duration::from_secs(5)));
+
+    let stream = vlite
+        .search_stream(scan_all())
+        .expect("stream should start");
+    let deleter = {
+        let vlite = Arc::clone(&vlite);
+        thread::spawn(move || delete_events(&vlite))
+    };
+
+    thread::sleep(Duration::from_millis(200));
+    assert!(!deleter.is_finished());
+    assert!(table_exists(&dir.db_path()));
+
+    drop(stream);
+    deleter
+        .join()
+        .unwrap()
+        .expect("delete collection should succeed");
+    assert!(!table_exists(&dir.db_path()));
+}
+
+#[test]
+fn collection_change_that_cannot_start_fails_cleanly() {
+    let dir = TestDir::new("timeout");
+    let vlite = setup_vlite(&dir, Duration::from_millis(200));
+
+    let stream = vlite
+        .search_stream(scan_all())
+        .expect("stream should start");
+    let err = vlite
+        .compact_payload("events")
+        .expect_err("compaction should time out");
+    assert!(err.to_string().contains("is busy"), "{}", err);
+    assert_eq!(stream.count(), ROWS as usize);
+
+    assert!(vlite.has_any("events").unwrap());
+    vlite
+        .compact_payload("events")
+        .expect("compaction should succeed once idle");
+}
+
+#[test]
+fn payload_index_changes_wait_for_running_operations() {
+    let dir = TestDir::new("index");
+    let vlite = setup_vlite(&dir, Duration::from_millis(200));
+
+    let stream = vlite
+        .search_stream(scan_all())
+        .expect("stream should start");
+    let err = vlite
+        .create_payload_index("events", &["body"])
+        .expect_err("index creation should time out");
+    assert!(err.to_string().contains("is busy"), "{}", err);
+    drop(stream);
+
+    vlite
+        .create_payload_index("events", &["body"])
+        .expect("index creation should succeed once idle");
+    assert_eq!(vlite.list_payload_indexes("events").unwrap().len(), 1);
+}
+
+#[test]
+fn timed_out_collection_change_keeps_running_change_locked() {
+    let dir = TestDir::new("second_exclusive");
+    let vlite = Arc::new(setup_vlite(&dir, Duration::from_millis(300)));
+
+    // A write transaction on another connection stalls the delete inside SQLite while
+    // it holds the collection exclusively.
+    let blocker = Pool::builder()
+        .max_size(1)
+        .build(SqliteConnectionManager::file(dir.db_path()))
+        .unwrap()
+        .get()
+        .unwrap();
+    blocker.execute_batch("BEGIN IMMEDIATE").unwrap();
+    let deleter = {
+        let vlite = Arc::clone(&vlite);
+        thread::spawn(move || delete_events(&vlite))
+    };
+    thread::sleep(Duration::from_millis(100));
+
+    let err = vlite
+        .compact_payload("events")
+        .expect_err("compaction should time out");
+    assert!(err.to_string().contains("is busy"), "{}", err);
+    let err = vlite
+        .has_any("events")
+        .expect_err("reads should wait for the delete");
+    assert!(err.to_string().contains("locked"), "{}", err);
+
+    blocker.execute_batch("ROLLBACK").unwrap();
+    deleter
+        .join()
+        .unwrap()
+        .expect("delete collection should succeed");
+    assert!(!table_exists(&dir.db_path()));
+}
+
+#[test]
+fn searches_during_compaction_see_consistent_results() {
+    let dir = TestDir::new("consistent");
+    let vlite = Arc::new(setup_vlite(&dir, Duration::from_secs(10)));
+    let stop = Arc::new(AtomicBool::new(false));
+    let searches = Arc::new(AtomicUsize::new(0));
+
+    let searchers: Vec<_> = (0..3)
+        .map(|_| {
+            let (vlite, stop, searches) =
+                (Arc::clone(&vlite), Arc::clone(&stop), Arc::clone(&searches));
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let results = vlite.search(scan_all()).expect("search should succeed");
+                    let rowids: Vec<u64> = results
+                        .iter()
+                        .map(|row| row["rowid"].parse().unwrap())
+                        .collect();
+                    assert_eq!(rowids, (1..=ROWS).collect::<Vec<_>>());
+                    searches.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+        })
+        .collect();
+
+    for _ in 0..10 {
+        vlite
+            .compact_payload("events")
+            .expect("compaction should succeed");
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    stop.store(true, Ordering::Relaxed);
+    for searcher in searchers {
+        searcher.join().expect("searches should stay consistent");
+    }
+    assert!(searches.load(Ordering::Relaxed) > 0);
+}
@@ -0,0 +1,139 @@
+//! Tests for case-insensitive collection names
+//!
+//! These tests verify:
+//! - A collection created with a mixed-case name is found under any casing
+//! - Backing tables are stored under the canonical lowercase name
+//! - Names differing only in case cannot create a second collection
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+fn setup_vlite(config: VectorXLiteConfig) -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::with_config(pool.clone(), config).expect("create VectorXLite");
+    (vlite, pool)
+}
+
+fn create_my_docs(vlite: &VectorXLite) {
+    let config = CollectionConfigBuilder::default()
+        .collection_name("MyDocs")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema("create table MyDocs (rowid integer primary key, title text)")
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+}
+
+fn insert_doc(vlite: &VectorXLite, collection_name: &str, payload_table: &str, id: u64) {
+    let point = InsertPoint::builder()
+        .collection_name(collection_name)
+        .id(id)
+        .vector(vec![id as f32, 0.0])
+        .payload_insert_query(format!(
+            "insert into {}(rowid, title) values (?1, 'doc {}')",
+            payload_table, id
+        ))
+        .build()
+        .unwrap();
+    vlite.insert(point).expect("insert should be successful.");
+}
+
+fn table_names(pool: &Pool<SqliteConnectionManager>) -> Vec<String> {
+    let conn = pool.get().unwrap();
+    let mut stmt = conn
+        .prepare("select name from sqlite_master where type = 'table' order by name")
+        .unwrap();
+    stmt.query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+#[test]
+fn mixed_case_collection_is_found_under_any_casing() {
+    let (vlite, _pool) = setup_vlite(VectorXLiteConfig::default());
+    create_my_docs(&vlite);
+
+    insert_doc(&vlite, "mydocs", "MYDOCS", 1);
+    insert_doc(&vlite, "MYDOCS", "mydocs", 2);
+
+    assert!(vlite.collection_exists("mydocs").unwrap());
+    assert!(vlite.collection_exists("MyDocs").unwrap());
+    assert_eq!(vlite.count_where("MYDOCS", "1 = 1").unwrap(), 2);
+
+    let search_point = SearchPoint::builder()
+        .collection_name("myDOCS")
+        .vector(vec![0.0, 0.0])
+        .top_k(10)
+        .payload_search_query("select rowid, title from mydocs")
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["title"], "doc 1");
+
+    let delete_collection = DeleteCollection::builder()
+        .collection_name("mydocs")
+        .build()
+        .unwrap();
+    vlite
+        .delete_collection(delete_collection)
+        .expect("delete should be successful.");
+    assert!(!vlite.collection_exists("MyDocs").unwrap());
+}
+
+#[test]
+fn backing_tables_use_canonical_name() {
+    let (vlite, pool) = setup_vlite(VectorXLiteConfig::default());
+    create_my_docs(&vlite);
+
+    let tables = table_names(&pool);
+    assert!(tables.contains(&"mydocs".to_string()), "{:?}", tables);
+    assert!(tables.iter().all(|name| name != "MyDocs"), "{:?}", tables);
+    assert!(
+        tables.iter().any(|name| name.ends_with("_mydocs")),
+        "{:?}",
+        tables
+    );
+}
+
+#[test]
+fn prefixed_payload_table_uses_canonical_name() {
+    let (vlite, pool) =
+        setup_vlite(VectorXLiteConfig::default().with_prefixed_payload_tables(true));
+    create_my_docs(&vlite);
+
+    insert_doc(&vlite, "mydocs", "PT_MyDocs", 1);
+
+    assert!(table_names(&pool).contains(&"pt_mydocs".to_string()));
+    assert_eq!(vlite.count_where("MyDocs", "1 = 1").unwrap(), 1);
+}
+
+#[test]
+fn names_differing_in_case_cannot_coexist() {
+    let (vlite, _pool) = setup_vlite(VectorXLiteConfig::default());
+    create_my_docs(&vlite);
+
+    let config = CollectionConfigBuilder::default()
+        .collection_name("MYDOCS")
+        .vector_dimension(2)
+        .build()
+        .unwrap();
+
+    let err = vlite
+        .create_collection(config)
+        .expect_err("collection with the same name in another case should be rejected");
+    assert!(matches!(err, VecXError::SqlError(_)), "{:?}", err);
+}
@@ -0,0 +1,206 @@
+//! Tests for VectorXLite::collection_stats
+//!
+//! These tests verify:
+//! - Searches, inserts and deletes are counted per collection
+//! - Failed operations and duplicate idempotent inserts are not counted
+//! - Unknown collections report zeros and names are matched case-insensitively
+//! - Renaming a collection keeps its counts, deleting it resets them
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+fn setup_vlite() -> VectorXLite {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool).expect("create VectorXLite");
+    for name in ["points", "others"] {
+        let config = CollectionConfigBuilder::default()
+            .collection_name(name)
+            .distance(DistanceFunction::L2)
+            .vector_dimension(2)
+            .payload_table_schema(format!("create table {} (rowid integer primary key)", name))
+            .build()
+            .unwrap();
+        vlite
+            .create_collection(config)
+            .expect("collection should be created");
+    }
+    vlite
+}
+
+fn point(collection_name: &str, id: u64) -> InsertPoint {
+    InsertPoint::builder()
+        .collection_name(collection_name)
+        .id(id)
+        .vector(vec![id as f32, 0.0])
+        .build()
+        .unwrap()
+}
+
+fn query(collection_name: &str) -> SearchPoint {
+    SearchPoint::builder()
+        .collection_name(collection_name)
+        .vector(vec![1.0, 0.0])
+        .top_k(3)
+        .build()
+        .unwrap()
+}
+
+fn delete(collection_name: &str, id: u64) -> DeletePoint {
+    DeletePoint::builder()
+        .collection_name(collection_name)
+        .id(id)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn operations_are_counted_per_collection() {
+    let vlite = setup_vlite();
+
+    for id in 1..=5 {
+        vlite.insert(point("points", id)).unwrap();
+    }
+    vlite.insert(point("others", 1)).unwrap();
+    vlite.search(query("points")).unwrap();
+    vlite.search(query("points")).unwrap();
+    vlite
+        .search_many(vec![query("points"), query("others")])
+        .unwrap();
+    vlite.delete(delete("points", 1)).unwrap();
+
+    assert_eq!(
+        vlite.collection_stats("points"),
+        CollectionStats {
+            searches: 3,
+            inserts: 5,
+            deletes: 1,
+        }
+    );
+    assert_eq!(
+        vlite.collection_stats("others"),
+        CollectionStats {
+            searches: 1,
+            inserts: 1,
+            deletes: 0,
+        }
+    );
+}
+
+#[test]
+fn unknown_collection_reports_zeros() {
+    let vlite = setup_vlite();
+
+    assert_eq!(
+        vlite.collection_stats("missing"),
+        CollectionStats::default()
+    );
+}
+
+#[test]
+fn collection_names_are_case_insensitive() {
+    let vlite = setup_vlite();
+
+    vlite.insert(point("Points", 1)).unwrap();
+    vlite.search(query("POINTS")).unwrap();
+
+    let stats = vlite.collection_stats("points");
+    assert_eq!(stats.inserts, 1);
+    assert_eq!(stats.searches, 1);
+}
+
+#[test]
+fn failed_operations_are_not_counted() {
+    let vlite = setup_vlite();
+
+    vlite.insert(point("points", 1)).unwrap();
+    assert!(vlite.insert(point("points", 1)).is_err());
+    let bad_query = SearchPoint::builder()
+        .collection_name("points")
+        .vector(vec![1.0, 0.0, 0.0])
+        .build()
+        .unwrap();
+    assert!(vlite.search(bad_query).is_err());
+
+    let stats = vlite.collection_stats("points");
+    assert_eq!(stats.inserts, 1);
+    assert_eq!(stats.searches, 0);
+}
+
+#[test]
+fn duplicate_idempotent_insert_is_not_counted() {
+    let vlite = setup_vlite();
+    let keyed_point = || {
+        InsertPoint::builder()
+            .collection_name("points")
+            .id(1)
+            .vector(vec![1.0, 0.0])
+            .idempotency_key("request-1")
+            .build()
+            .unwrap()
+    };
+
+    vlite.insert(keyed_point()).unwrap();
+    vlite.insert(keyed_point()).unwrap();
+
+    assert_eq!(vlite.collection_stats("points").inserts, 1);
+}
+
+#[test]
+fn batch_operations_count_each_point() {
+    let vlite = setup_vlite();
+
+    let result = vlite
+        .insert_batch(
+            (1..=4).map(|id| point("points", id)).collect(),
+            BatchOptions::default(),
+        )
+        .unwrap();
+    assert!(result.failed.is_empty());
+    let summary = vlite
+        .batch_delete(
+            BatchDelete::builder()
+                .collection_name("points")
+                .ids(vec![1, 2, 99])
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+    let stats = vlite.collection_stats("points");
+    assert_eq!(stats.inserts, 4);
+    assert_eq!(stats.deletes, summary.deleted_count);
+    assert_eq!(stats.deletes, 2);
+}
+
+#[test]
+fn rename_keeps_counts_and_delete_resets_them() {
+    let vlite = setup_vlite();
+
+    vlite.insert(point("points", 1)).unwrap();
+    vlite.search(query("points")).unwrap();
+    vlite.rename_collection("points", "renamed").unwrap();
+
+    assert_eq!(vlite.collection_stats("points"), CollectionStats::default());
+    assert_eq!(vlite.collection_stats("renamed").inserts, 1);
+    assert_eq!(vlite.collection_stats("renamed").searches, 1);
+
+    vlite
+        .delete_collection(
+            DeleteCollection::builder()
+                .collection_name("renamed")
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    assert_eq!(
+        vlite.collection_stats("renamed"),
+        CollectionStats::default()
+    );
+}
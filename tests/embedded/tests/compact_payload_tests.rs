@@ -0,0 +1,207 @@
+//! Tests for compact_payload method in VectorXLite
+//!
+//! These tests verify:
+//! - Compacting after heavy deletes leaves the payload table with fewer pages and less free space
+//! - Remaining rows keep their rowids, payload data and vectors
+//! - Payload indexes are kept
+//! - Columns that need quoting, or are named like the copied rowid, are kept
+//! - Compacting a missing collection fails
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+fn setup_vlite() -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("logs")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .max_elements(1000)
+        .payload_table_schema(
+            "create table logs (rowid integer primary key, level text, body text)",
+        )
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+    vlite
+        .create_payload_index("logs", &["level"])
+        .expect("index should be created");
+
+    let points = (1..=500u64)
+        .map(|id| {
+            InsertPoint::builder()
+                .collection_name("logs")
+                .id(id)
+                .vector(vec![id as f32, 0.0])
+                .payload_insert_query(format!(
+                    "insert into logs(rowid, level, body) values (?1, '{}', '{}-{}')",
+                    if id % 2 == 0 { "info" } else { "warn" },
+                    id,
+                    "x".repeat(300)
+                ))
+                .build()
+                .unwrap()
+        })
+        .collect();
+    vlite
+        .insert_batch(points, BatchOptions::default())
+        .expect("insert should succeed");
+
+    // Keep every other row, leaving the pages of the table about half empty
+    let deleted = (1..=500u64).filter(|id| id % 2 != 0).collect();
+    vlite
+        .batch_delete(
+            BatchDelete::builder()
+                .collection_name("logs")
+                .ids(deleted)
+                .build()
+                .unwrap(),
+        )
+        .expect("delete should succeed");
+
+    (vlite, pool)
+}
+
+/// (pages, unused bytes) of a table, as reported by `dbstat`.
+fn table_pages(pool: &Pool<SqliteConnectionManager>, table: &str) -> (i64, i64) {
+    pool.get()
+        .unwrap()
+        .query_row(
+            "SELECT count(*), sum(unused) FROM dbstat WHERE name = ?1",
+            [table],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap()
+}
+
+fn rows(pool: &Pool<SqliteConnectionManager>) -> Vec<(i64, String, String)> {
+    let conn = pool.get().unwrap();
+    let mut stmt = conn
+        .prepare("SELECT rowid, level, body FROM logs ORDER BY rowid")
+        .unwrap();
+    stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap()
+}
+
+#[test]
+fn compact_payload_releases_free_space_in_table() {
+    let (vlite, pool) = setup_vlite();
+    let (pages_before, unused_before) = table_pages(&pool, "logs");
+
+    vlite
+        .compact_payload("logs")
+        .expect("compact should succeed");
+
+    let (pages_after, unused_after) = table_pages(&pool, "logs");
+    assert!(
+        pages_after < pages_before,
+        "pages {} -> {}",
+        pages_before,
+        pages_after
+    );
+    assert!(
+        unused_after < unused_before,
+        "unused bytes {} -> {}",
+        unused_before,
+        unused_after
+    );
+}
+
+#[test]
+fn compact_payload_keeps_rows_and_vectors() {
+    let (vlite, pool) = setup_vlite();
+    let before = rows(&pool);
+
+    vlite
+        .compact_payload("logs")
+        .expect("compact should succeed");
+
+    assert_eq!(before.len(), 250);
+    assert_eq!(rows(&pool), before);
+
+    let search_point = SearchPoint::builder()
+        .collection_name("logs")
+        .vector(vec![43.2, 0.0])
+        .top_k(2)
+        .payload_search_query("select rowid, level from logs")
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).expect("search should succeed");
+    let rowids: Vec<&str> = results.iter().map(|row| row["rowid"].as_str()).collect();
+    assert_eq!(rowids, vec!["44", "42"]);
+    assert_eq!(results[0]["level"], "info");
+}
+
+#[test]
+fn compact_payload_keeps_payload_indexes() {
+    let (vlite, _pool) = setup_vlite();
+    let before = vlite.list_payload_indexes("logs").unwrap();
+
+    vlite
+        .compact_payload("logs")
+        .expect("compact should succeed");
+
+    assert_eq!(before.len(), 1);
+    assert_eq!(vlite.list_payload_indexes("logs").unwrap(), before);
+}
+
+#[test]
+fn compact_payload_of_missing_collection_fails() {
+    let (vlite, _pool) = setup_vlite();
+
+    let result = vlite.compact_payload("missing");
+
+    assert!(matches!(result, Err(VecXError::InvalidQueryError(_))));
+}
+
+#[test]
+fn compact_payload_keeps_columns_that_need_quoting() {
+    let (vlite, pool) = setup_vlite();
+    let config = CollectionConfigBuilder::default()
+        .collection_name("orders")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema(
+            "create table orders (rowid integer primary key, \"order\" text, vx_rowid integer)",
+        )
+        .build()
+        .unwrap();
+    vlite.create_collection(config).unwrap();
+    let point = InsertPoint::builder()
+        .collection_name("orders")
+        .id(7)
+        .vector(vec![1.0, 0.0])
+        .payload_insert_query(
+            "insert into orders(rowid, \"order\", vx_rowid) values (?1, 'first', 42)",
+        )
+        .build()
+        .unwrap();
+    vlite.insert(point).unwrap();
+
+    vlite
+        .compact_payload("orders")
+        .expect("compaction should succeed");
+
+    let row: (i64, String, i64) = pool
+        .get()
+        .unwrap()
+        .query_row("select rowid, \"order\", vx_rowid from orders", [], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })
+        .unwrap();
+    assert_eq!(row, (7, "first".to_string(), 42));
+}
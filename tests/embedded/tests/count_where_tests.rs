@@ -0,0 +1,98 @@
+//! Tests for count_where method in VectorXLite
+//!
+//! These tests verify:
+//! - Counting payload rows matching a range predicate
+//! - The count matches an equivalent filtered search
+//! - Invalid collections and predicates surface errors
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+fn setup_vlite() -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(5)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    (vlite, pool)
+}
+
+fn create_rated_collection(vlite: &VectorXLite) {
+    let config = CollectionConfigBuilder::default()
+        .collection_name("rated")
+        .vector_dimension(2)
+        .payload_table_schema("create table rated (rowid integer primary key, rating integer)")
+        .build()
+        .unwrap();
+
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for i in 1..=20u64 {
+        let point = InsertPoint::builder()
+            .collection_name("rated")
+            .id(i)
+            .vector(vec![i as f32, 1.0])
+            .payload_insert_query(format!(
+                "insert into rated(rowid, rating) values (?1, {})",
+                i
+            ))
+            .build()
+            .expect("Builder should create insert point.");
+
+        vlite.insert(point).expect("insert should be successful.");
+    }
+}
+
+#[test]
+fn count_where_with_range_predicate() {
+    let (vlite, _) = setup_vlite();
+    create_rated_collection(&vlite);
+
+    let count = vlite
+        .count_where("rated", "rating >= 5 AND rating < 12")
+        .expect("count should succeed");
+
+    assert_eq!(count, 7);
+}
+
+#[test]
+fn count_where_matches_filtered_search() {
+    let (vlite, _) = setup_vlite();
+    create_rated_collection(&vlite);
+
+    let predicate = "rating > 14 OR rating <= 3";
+    let count = vlite.count_where("rated", predicate).unwrap();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("rated")
+        .vector(vec![1.0, 1.0])
+        .top_k(100)
+        .payload_search_query(format!("select rowid, rating from rated where {}", predicate))
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).unwrap();
+
+    assert_eq!(count, results.len() as u64);
+    assert_eq!(count, 9);
+}
+
+#[test]
+fn count_where_without_matches_is_zero() {
+    let (vlite, _) = setup_vlite();
+    create_rated_collection(&vlite);
+
+    assert_eq!(vlite.count_where("rated", "rating > 100").unwrap(), 0);
+}
+
+#[test]
+fn count_where_on_missing_collection_is_error() {
+    let (vlite, _) = setup_vlite();
+
+    assert!(vlite.count_where("missing", "1 = 1").is_err());
+}
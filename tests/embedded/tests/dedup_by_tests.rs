@@ -0,0 +1,191 @@
+//! Tests for de-duplicating search results by a payload column
+//!
+//! These tests verify:
+//! - At most one result is returned per distinct payload value
+//! - The kept result is the nearest chunk of its document
+//! - top_k is applied after de-duplication
+//! - dedup_by runs with the collection's default payload search
+//! - dedup_by is rejected without a payload query or default
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::collections::HashMap;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+fn setup_vlite() -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(5)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    (vlite, pool)
+}
+
+/// Chunks as `(rowid, doc_id, x)`; each chunk sits at distance `x` from the origin.
+const CHUNKS: [(u64, i64, f32); 7] = [
+    (1, 1, 1.0),
+    (2, 1, 1.5),
+    (3, 1, 6.0),
+    (4, 2, 2.0),
+    (5, 2, 2.5),
+    (6, 3, 3.0),
+    (7, 4, 10.0),
+];
+
+fn create_chunks_collection(vlite: &VectorXLite) {
+    create_chunks_collection_with(vlite, CollectionConfigBuilder::default());
+}
+
+fn create_chunks_collection_with(vlite: &VectorXLite, builder: CollectionConfigBuilder) {
+    let config = builder
+        .collection_name("chunks")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema("create table chunks (rowid integer primary key, doc_id integer)")
+        .build()
+        .unwrap();
+
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for (id, doc_id, x) in CHUNKS {
+        let point = InsertPoint::builder()
+            .collection_name("chunks")
+            .id(id)
+            .vector(vec![x, 0.0])
+            .payload_insert_query(format!(
+                "insert into chunks(rowid, doc_id) values (?1, {})",
+                doc_id
+            ))
+            .build()
+            .expect("Builder should create insert point.");
+
+        vlite.insert(point).expect("insert should be successful.");
+    }
+}
+
+fn search_chunks(vlite: &VectorXLite, top_k: i64, dedup: bool) -> Vec<HashMap<String, String>> {
+    let mut builder = SearchPoint::builder()
+        .collection_name("chunks")
+        .vector(vec![0.0, 0.0])
+        .top_k(top_k)
+        .payload_search_query("select rowid, doc_id from chunks");
+    if dedup {
+        builder = builder.dedup_by("doc_id");
+    }
+
+    vlite
+        .search(builder.build().unwrap())
+        .expect("search should succeed")
+}
+
+fn column(results: &[HashMap<String, String>], name: &str) -> Vec<i64> {
+    results
+        .iter()
+        .map(|row| row[name].parse::<i64>().unwrap())
+        .collect()
+}
+
+#[test]
+fn dedup_by_returns_each_doc_once_with_its_nearest_chunk() {
+    let (vlite, _) = setup_vlite();
+    create_chunks_collection(&vlite);
+
+    let results = search_chunks(&vlite, 10, true);
+
+    assert_eq!(column(&results, "doc_id"), vec![1, 2, 3, 4]);
+    assert_eq!(column(&results, "rowid"), vec![1, 4, 6, 7]);
+}
+
+#[test]
+fn dedup_by_applies_top_k_after_deduplication() {
+    let (vlite, _) = setup_vlite();
+    create_chunks_collection(&vlite);
+
+    let plain = search_chunks(&vlite, 3, false);
+    assert_eq!(column(&plain, "doc_id"), vec![1, 1, 2]);
+
+    let deduped = search_chunks(&vlite, 3, true);
+    assert_eq!(column(&deduped, "doc_id"), vec![1, 2, 3]);
+    assert_eq!(column(&deduped, "rowid"), vec![1, 4, 6]);
+}
+
+#[test]
+fn dedup_by_combines_with_order_by() {
+    let (vlite, _) = setup_vlite();
+    create_chunks_collection(&vlite);
+
+    let search_point = SearchPoint::builder()
+        .collection_name("chunks")
+        .vector(vec![0.0, 0.0])
+        .top_k(3)
+        .payload_search_query("select rowid, doc_id from chunks")
+        .dedup_by("doc_id")
+        .order_by("doc_id", Direction::Desc)
+        .build()
+        .unwrap();
+
+    let results = vlite.search(search_point).unwrap();
+
+    assert_eq!(column(&results, "doc_id"), vec![3, 2, 1]);
+}
+
+#[test]
+fn dedup_by_uses_default_payload_search() {
+    let (vlite, _) = setup_vlite();
+    create_chunks_collection_with(
+        &vlite,
+        CollectionConfigBuilder::default()
+            .default_payload_search("select rowid, doc_id from chunks"),
+    );
+
+    let search_point = SearchPoint::builder()
+        .collection_name("chunks")
+        .vector(vec![0.0, 0.0])
+        .top_k(10)
+        .dedup_by("doc_id")
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).unwrap();
+
+    assert_eq!(column(&results, "rowid"), vec![1, 4, 6, 7]);
+}
+
+#[test]
+fn dedup_by_requires_payload_search_query() {
+    let (vlite, _) = setup_vlite();
+    create_chunks_collection(&vlite);
+
+    let search_point = SearchPoint::builder()
+        .collection_name("chunks")
+        .vector(vec![0.0, 0.0])
+        .dedup_by("doc_id")
+        .build()
+        .unwrap();
+
+    assert!(matches!(
+        vlite.search(search_point),
+        Err(VecXError::InvalidQueryError(_))
+    ));
+}
+
+#[test]
+fn dedup_by_rejects_non_column_expressions() {
+    let result = SearchPoint::builder()
+        .collection_name("chunks")
+        .vector(vec![0.0, 0.0])
+        .payload_search_query("select rowid, doc_id from chunks")
+        .dedup_by("doc_id; drop table chunks")
+        .build();
+
+    assert_eq!(
+        result.unwrap_err(),
+        "dedup_by column must be a plain column name."
+    );
+}
@@ -0,0 +1,159 @@
+//! Tests for CollectionConfigBuilder::default_payload_search
+//!
+//! These tests verify:
+//! - Searches without a payload query run with the collection's default
+//! - An explicit payload query overrides the default
+//! - The default survives reopening a file-backed database
+//! - Renaming a collection keeps its default, deleting it drops the default
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::collections::HashMap;
+use std::fs;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+fn pool(manager: SqliteConnectionManager) -> Pool<SqliteConnectionManager> {
+    Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool")
+}
+
+fn create_docs(vlite: &VectorXLite, index_file_path: Option<&str>) {
+    let mut builder = CollectionConfigBuilder::default()
+        .collection_name("docs")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema(
+            "create table docs (rowid integer primary key, title text, lang text)",
+        )
+        .default_payload_search("select rowid, title from docs where lang = 'en'");
+    if let Some(path) = index_file_path {
+        builder = builder.index_file_path(path);
+    }
+    vlite
+        .create_collection(builder.build().unwrap())
+        .expect("collection should be created");
+
+    for (id, title, lang) in [(1, "one", "en"), (2, "zwei", "de"), (3, "three", "en")] {
+        let point = InsertPoint::builder()
+            .collection_name("docs")
+            .id(id)
+            .vector(vec![id as f32, 0.0])
+            .payload_insert_query(format!(
+                "insert into docs(rowid, title, lang) values (?1, '{}', '{}')",
+                title, lang
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+}
+
+fn search(collection_name: &str) -> SearchPointBuilder {
+    SearchPoint::builder()
+        .collection_name(collection_name)
+        .vector(vec![2.0, 0.0])
+        .top_k(3)
+}
+
+fn titles(results: &[HashMap<String, String>]) -> Vec<&str> {
+    let mut titles: Vec<&str> = results.iter().map(|row| row["title"].as_str()).collect();
+    titles.sort();
+    titles
+}
+
+#[test]
+fn search_without_payload_query_uses_default() {
+    let vlite = VectorXLite::new(pool(SqliteConnectionManager::memory())).unwrap();
+    create_docs(&vlite, None);
+
+    let results = vlite.search(search("docs").build().unwrap()).unwrap();
+
+    assert_eq!(titles(&results), vec!["one", "three"]);
+    assert!(results.iter().all(|row| !row.contains_key("lang")));
+}
+
+#[test]
+fn explicit_payload_query_overrides_default() {
+    let vlite = VectorXLite::new(pool(SqliteConnectionManager::memory())).unwrap();
+    create_docs(&vlite, None);
+
+    let search_point = search("docs")
+        .payload_search_query("select rowid, title, lang from docs where lang = 'de'")
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).unwrap();
+
+    assert_eq!(titles(&results), vec!["zwei"]);
+    assert_eq!(results[0]["lang"], "de");
+}
+
+#[test]
+fn blank_default_is_rejected() {
+    let result = CollectionConfigBuilder::default()
+        .collection_name("docs")
+        .default_payload_search("  ")
+        .build();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn default_survives_reopening_file_database() {
+    let dir = "/tmp/vxlite_test_default_payload_search";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let db_path = format!("{}/docs.db", dir);
+    let idx_path = format!("{}/docs.idx", dir);
+
+    {
+        let vlite = VectorXLite::new(pool(SqliteConnectionManager::file(&db_path))).unwrap();
+        create_docs(&vlite, Some(&idx_path));
+    }
+
+    let vlite = VectorXLite::new(pool(SqliteConnectionManager::file(&db_path))).unwrap();
+    let results = vlite.search(search("docs").build().unwrap()).unwrap();
+    assert_eq!(titles(&results), vec!["one", "three"]);
+
+    drop(vlite);
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn rename_keeps_default_and_delete_drops_it() {
+    let vlite = VectorXLite::new(pool(SqliteConnectionManager::memory())).unwrap();
+    create_docs(&vlite, None);
+
+    vlite.rename_collection("docs", "articles").unwrap();
+    let results = vlite.search(search("articles").build().unwrap()).unwrap();
+    assert_eq!(titles(&results), vec!["one", "three"]);
+
+    vlite
+        .delete_collection(
+            DeleteCollection::builder()
+                .collection_name("articles")
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    let config = CollectionConfigBuilder::default()
+        .collection_name("articles")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .build()
+        .unwrap();
+    vlite.create_collection(config).unwrap();
+    let point = InsertPoint::builder()
+        .collection_name("articles")
+        .id(1)
+        .vector(vec![1.0, 0.0])
+        .build()
+        .unwrap();
+    vlite.insert(point).unwrap();
+
+    let results = vlite.search(search("articles").build().unwrap()).unwrap();
+    assert_eq!(results.len(), 1);
+    assert!(!results[0].contains_key("title"));
+}
@@ -283,4 +283,65 @@ fn insert_and_search_without_payload() {
         1,
         "Should find the inserted vector"
     );
-}
\ No newline at end of file
+}
+#[test]
+fn batch_delete_removes_existing_ids_and_counts_missing() {
+    let (vlite, _) = setup_vlite();
+
+    let config = CollectionConfigBuilder::default()
+        .collection_name("batch_person")
+        .distance(DistanceFunction::Cosine)
+        .vector_dimension(2)
+        .payload_table_schema("create table batch_person (rowid integer primary key, name text)")
+        .build()
+        .unwrap();
+
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for id in 1..=6u64 {
+        let point = InsertPoint::builder()
+            .collection_name("batch_person")
+            .id(id)
+            .vector(vec![id as f32, 1.0])
+            .payload_insert_query("insert into batch_person(rowid, name) values (?1, 'someone')")
+            .build()
+            .expect("Builder should create insert point.");
+
+        vlite
+            .insert(point)
+            .expect("insert should be successful.");
+    }
+
+    let batch_delete = BatchDelete::builder()
+        .collection_name("batch_person")
+        .ids(vec![1, 3, 5, 99])
+        .build()
+        .expect("Builder should create batch delete.");
+
+    let summary = vlite
+        .batch_delete(batch_delete)
+        .expect("batch delete should be successful");
+
+    assert_eq!(summary.deleted_count, 3);
+    assert_eq!(summary.missing_count, 1);
+
+    let search_point = SearchPoint::builder()
+        .collection_name("batch_person")
+        .vector(vec![1.0, 1.0])
+        .top_k(10)
+        .payload_search_query("select * from batch_person")
+        .build()
+        .expect("Builder should create search point.");
+
+    let mut remaining: Vec<String> = vlite
+        .search(search_point)
+        .unwrap()
+        .into_iter()
+        .map(|hm| hm["rowid"].clone())
+        .collect();
+    remaining.sort();
+
+    assert_eq!(remaining, vec!["2", "4", "6"], "Only undeleted ids should remain");
+}
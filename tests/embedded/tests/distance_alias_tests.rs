@@ -0,0 +1,136 @@
+//! Tests for naming the KNN distance column of search results
+//!
+//! These tests verify:
+//! - A payload `distance` column no longer collides with the KNN distance
+//! - distance_alias renames the KNN distance column
+//! - Ordering and de-duplication use the renamed column
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+fn setup_vlite() -> VectorXLite {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    VectorXLite::new(pool).expect("create VectorXLite")
+}
+
+/// Stores with a `distance` payload column holding the distance to the city centre in km.
+fn create_stores_collection(vlite: &VectorXLite) {
+    let config = CollectionConfigBuilder::default()
+        .collection_name("stores")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema(
+            "create table stores (rowid integer primary key, city text, distance real)",
+        )
+        .build()
+        .unwrap();
+
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    let stores = [(1, "oslo", 12.5), (2, "oslo", 3.0), (3, "bergen", 7.25)];
+    for (id, city, km) in stores {
+        let point = InsertPoint::builder()
+            .collection_name("stores")
+            .id(id)
+            .vector(vec![id as f32, 0.0])
+            .payload_insert_query(format!(
+                "insert into stores(rowid, city, distance) values (?1, '{}', {})",
+                city, km
+            ))
+            .build()
+            .expect("Builder should create insert point.");
+
+        vlite.insert(point).expect("insert should be successful.");
+    }
+}
+
+fn stores_search() -> SearchPointBuilder {
+    SearchPoint::builder()
+        .collection_name("stores")
+        .vector(vec![0.0, 0.0])
+        .top_k(3)
+        .payload_search_query("select rowid, city, distance from stores")
+}
+
+#[test]
+fn payload_distance_column_is_kept_next_to_knn_distance() {
+    let vlite = setup_vlite();
+    create_stores_collection(&vlite);
+
+    let results = vlite.search(stores_search().build().unwrap()).unwrap();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0]["rowid"], "1");
+    assert_eq!(results[0]["distance"], "12.5");
+    assert_eq!(results[0]["_vx_distance"].parse::<f32>().unwrap(), 1.0);
+    assert_eq!(results[1]["distance"], "3");
+    assert_eq!(results[1]["_vx_distance"].parse::<f32>().unwrap(), 4.0);
+}
+
+#[test]
+fn distance_alias_renames_knn_distance() {
+    let vlite = setup_vlite();
+    create_stores_collection(&vlite);
+
+    let results = vlite
+        .search(stores_search().distance_alias("knn_score").build().unwrap())
+        .unwrap();
+
+    assert_eq!(results[0]["distance"], "12.5");
+    assert_eq!(results[0]["knn_score"].parse::<f32>().unwrap(), 1.0);
+    assert!(!results[0].contains_key("_vx_distance"));
+}
+
+#[test]
+fn distance_alias_without_payload_query() {
+    let vlite = setup_vlite();
+    create_stores_collection(&vlite);
+
+    let search_point = SearchPoint::builder()
+        .collection_name("stores")
+        .vector(vec![0.0, 0.0])
+        .top_k(2)
+        .distance_alias("score")
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["score"].parse::<f32>().unwrap(), 1.0);
+    assert!(!results[0].contains_key("distance"));
+}
+
+#[test]
+fn order_by_and_dedup_by_use_aliased_distance() {
+    let vlite = setup_vlite();
+    create_stores_collection(&vlite);
+
+    let results = vlite
+        .search(
+            stores_search()
+                .dedup_by("city")
+                .order_by("distance", Direction::Asc)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+    // The nearest store per city, re-sorted by the payload's distance to the centre
+    let cities: Vec<&str> = results.iter().map(|row| row["city"].as_str()).collect();
+    assert_eq!(cities, vec!["bergen", "oslo"]);
+    assert_eq!(results[1]["rowid"], "1");
+}
+
+#[test]
+fn invalid_distance_alias_is_rejected() {
+    assert!(stores_search().distance_alias("a; drop").build().is_err());
+}
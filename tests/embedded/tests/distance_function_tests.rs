@@ -353,7 +353,52 @@ mod ip_distance {
 
         let results = vlite.search(search).expect("search");
         assert_eq!(results.len(), 3);
-        // Note: IP distance typically returns negative inner product for ordering
+        // IP distance is 1 - inner product, so the largest inner product comes first
+        let ids: Vec<&str> = results.iter().map(|r| r["rowid"].as_str()).collect();
+        assert_eq!(ids, vec!["3", "1", "2"]);
+    }
+
+    #[test]
+    fn ip_ranks_largest_magnitude_first() {
+        let (vlite, _) = setup_vlite();
+
+        let config = CollectionConfigBuilder::default()
+            .collection_name("ip_magnitude")
+            .vector_dimension(3)
+            .distance(DistanceFunction::IP)
+            .build()
+            .unwrap();
+
+        vlite.create_collection(config).expect("create collection");
+
+        // Same direction, increasing magnitude
+        for id in 1..=5u64 {
+            let scale = id as f32;
+            let point = InsertPoint::builder()
+                .collection_name("ip_magnitude")
+                .id(id)
+                .vector(vec![scale, scale, 0.0])
+                .build()
+                .unwrap();
+            vlite.insert(point).expect("insert");
+        }
+
+        let search = SearchPoint::builder()
+            .collection_name("ip_magnitude")
+            .vector(vec![1.0, 1.0, 0.0])
+            .top_k(5)
+            .build()
+            .unwrap();
+
+        let results = vlite.search(search).expect("search");
+        let ids: Vec<&str> = results.iter().map(|r| r["rowid"].as_str()).collect();
+        assert_eq!(ids, vec!["5", "4", "3", "2", "1"]);
+
+        let distances: Vec<f32> = results
+            .iter()
+            .map(|r| r["distance"].parse().unwrap())
+            .collect();
+        assert_eq!(distances, vec![-9.0, -7.0, -5.0, -3.0, -1.0]);
     }
 
     #[test]
@@ -27,6 +27,7 @@ fn setup_vlite() -> (VectorXLite, Pool<SqliteConnectionManager>) {
 
 mod collection_creation_errors {
     use super::*;
+    use vector_xlite::error::VecXError;
 
     #[test]
     fn duplicate_collection_name_fails() {
@@ -82,6 +83,29 @@ mod collection_creation_errors {
         let result = vlite.create_collection(config);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn zero_dimension_collection_is_rejected() {
+        let (vlite, _) = setup_vlite();
+
+        // The builder rejects a zero dimension, so the config is assembled directly
+        let config = CollectionConfig {
+            collection_name: "zero_dim".to_string(),
+            dimension: 0,
+            payload_table_schema: Some(
+                "create table zero_dim (rowid integer primary key)".to_string(),
+            ),
+            ..CollectionConfig::default()
+        };
+
+        let err = vlite
+            .create_collection(config)
+            .expect_err("zero dimension should be rejected");
+
+        assert!(matches!(err, VecXError::InvalidQueryError(_)));
+        assert!(err.to_string().contains("vector dimension greater than 0"));
+        assert!(!vlite.collection_exists("zero_dim").unwrap());
+    }
 }
 
 // ============================================================================
@@ -90,6 +114,7 @@ mod collection_creation_errors {
 
 mod insert_errors {
     use super::*;
+    use vector_xlite::error::VecXError;
 
     #[test]
     fn insert_into_nonexistent_collection_fails() {
@@ -138,6 +163,34 @@ mod insert_errors {
         println!("Duplicate ID insert result: {:?}", result);
     }
 
+    #[test]
+    fn insert_empty_vector_is_rejected() {
+        let (vlite, _) = setup_vlite();
+
+        let config = CollectionConfigBuilder::default()
+            .collection_name("empty_vec")
+            .vector_dimension(3)
+            .payload_table_schema("create table empty_vec (rowid integer primary key)")
+            .build()
+            .unwrap();
+        vlite.create_collection(config).expect("create collection");
+
+        let point = InsertPoint::builder()
+            .collection_name("empty_vec")
+            .id(1)
+            .vector(vec![])
+            .build()
+            .unwrap();
+
+        let err = vlite
+            .insert(point)
+            .expect_err("empty vector should be rejected");
+
+        assert!(matches!(err, VecXError::InvalidQueryError(_)));
+        assert!(err.to_string().contains("empty vector"));
+        assert_eq!(vlite.count_where("empty_vec", "1 = 1").unwrap(), 0);
+    }
+
     #[test]
     fn insert_wrong_dimension_fails() {
         let (vlite, _) = setup_vlite();
@@ -448,6 +501,53 @@ mod error_recovery {
     }
 }
 
+// ============================================================================
+// Connection Pool Exhaustion
+// ============================================================================
+
+mod pool_exhaustion {
+    use super::*;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+    use vector_xlite::error::VecXError;
+
+    #[test]
+    fn operation_fails_with_pool_exhausted_error_when_connection_is_held() {
+        let pool = Pool::builder()
+            .max_size(1)
+            .connection_customizer(SqliteConnectionCustomizer::new())
+            .build(SqliteConnectionManager::memory())
+            .expect("create pool");
+        let vlite = VectorXLite::with_connection_timeout(pool.clone(), Duration::from_millis(200))
+            .expect("create VectorXLite");
+
+        let (held_tx, held_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let holder = thread::spawn(move || {
+            let _conn = pool.get().expect("holder should get the only connection");
+            held_tx.send(()).unwrap();
+            release_rx.recv().unwrap();
+        });
+        held_rx.recv().unwrap();
+
+        let result = vlite.collection_exists("any_collection");
+        match result {
+            Err(VecXError::Other(msg)) => assert!(
+                msg.contains("connection pool exhausted"),
+                "unexpected message: {}",
+                msg
+            ),
+            other => panic!("expected pool exhausted error, got {:?}", other),
+        }
+
+        release_tx.send(()).unwrap();
+        holder.join().unwrap();
+
+        assert!(!vlite.collection_exists("any_collection").unwrap());
+    }
+}
+
 // ============================================================================
 // VecXError Display Tests
 // ============================================================================
@@ -0,0 +1,96 @@
+//! Tests for estimate_memory method in VectorXLite
+//!
+//! These tests verify:
+//! - The estimate grows with the number of elements and with the vector dimension
+//! - Empty and payload-only collections are estimated at 0 bytes
+//! - Missing collections are rejected
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+fn setup_vlite() -> VectorXLite {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    VectorXLite::new(pool).expect("create VectorXLite")
+}
+
+fn create_collection_with_points(vlite: &VectorXLite, name: &str, dimension: u16, count: u64) {
+    let config = CollectionConfigBuilder::default()
+        .collection_name(name)
+        .vector_dimension(dimension)
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for id in 1..=count {
+        let point = InsertPoint::builder()
+            .collection_name(name)
+            .id(id)
+            .vector(vec![id as f32; dimension as usize])
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+}
+
+#[test]
+fn estimate_grows_with_element_count() {
+    let vlite = setup_vlite();
+    create_collection_with_points(&vlite, "small", 8, 10);
+    create_collection_with_points(&vlite, "large", 8, 40);
+
+    let small = vlite.estimate_memory("small").unwrap();
+    let large = vlite.estimate_memory("large").unwrap();
+
+    assert!(small > 0);
+    assert!(large > small);
+    // Every element costs the same, so four times the elements cost four times as much
+    assert!(large.abs_diff(4 * small) <= 4, "{} vs {}", large, small);
+}
+
+#[test]
+fn estimate_grows_with_dimension() {
+    let vlite = setup_vlite();
+    create_collection_with_points(&vlite, "narrow", 8, 10);
+    create_collection_with_points(&vlite, "wide", 128, 10);
+
+    let narrow = vlite.estimate_memory("narrow").unwrap();
+    let wide = vlite.estimate_memory("wide").unwrap();
+
+    // 120 more float32 components per element
+    assert_eq!(wide - narrow, 10 * 120 * 4);
+}
+
+#[test]
+fn empty_and_payload_only_collections_are_estimated_at_zero() {
+    let vlite = setup_vlite();
+    create_collection_with_points(&vlite, "empty", 8, 0);
+    let config = CollectionConfigBuilder::default()
+        .collection_name("notes")
+        .payload_only(true)
+        .build()
+        .unwrap();
+    vlite.create_collection(config).unwrap();
+
+    assert_eq!(vlite.estimate_memory("empty").unwrap(), 0);
+    assert_eq!(vlite.estimate_memory("notes").unwrap(), 0);
+}
+
+#[test]
+fn estimate_of_missing_collection_fails() {
+    let vlite = setup_vlite();
+
+    let result = vlite.estimate_memory("missing");
+
+    assert!(matches!(result, Err(VecXError::InvalidQueryError(_))));
+}
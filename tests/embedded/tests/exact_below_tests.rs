@@ -0,0 +1,140 @@
+//! Tests for the exact search fallback of small collections
+//!
+//! These tests verify:
+//! - Searches of a collection below `exact_below` return the exact nearest neighbours
+//! - Payload-filtered and id-restricted searches are exact as well
+//! - The HNSW index is used again once the collection reaches the threshold
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+fn setup_vlite(exact_below: u64) -> VectorXLite {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let config = VectorXLiteConfig::default().with_exact_below(exact_below);
+    let vlite = VectorXLite::with_config(pool, config).expect("create VectorXLite");
+    let collection = CollectionConfigBuilder::default()
+        .collection_name("points")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema("create table points (rowid integer primary key, label text)")
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(collection)
+        .expect("collection should be created");
+
+    for id in 1..=5 {
+        insert(&vlite, id);
+    }
+    vlite
+}
+
+fn insert(vlite: &VectorXLite, id: u64) {
+    let label = if id.is_multiple_of(2) { "even" } else { "odd" };
+    let point = InsertPoint::builder()
+        .collection_name("points")
+        .id(id)
+        .vector(vec![id as f32, 0.0])
+        .payload_insert_query(format!(
+            "insert into points(rowid, label) values (?1, '{}')",
+            label
+        ))
+        .build()
+        .unwrap();
+    vlite.insert(point).expect("insert should be successful.");
+}
+
+fn search(top_k: i64, payload_search_query: Option<&str>) -> SearchPoint {
+    let builder = SearchPoint::builder()
+        .collection_name("points")
+        .vector(vec![3.2, 0.0])
+        .top_k(top_k);
+    match payload_search_query {
+        Some(query) => builder.payload_search_query(query),
+        None => builder,
+    }
+    .build()
+    .unwrap()
+}
+
+fn rowids(results: &[std::collections::HashMap<String, String>]) -> Vec<&str> {
+    results.iter().map(|row| row["rowid"].as_str()).collect()
+}
+
+#[test]
+fn small_collection_is_searched_exactly() {
+    let vlite = setup_vlite(10);
+
+    let sql = vlite.explain_search(search(3, None)).unwrap();
+    assert!(sql.contains("vector_distance"), "{}", sql);
+    assert!(!sql.contains("knn_search"), "{}", sql);
+
+    let results = vlite
+        .search(search(3, None))
+        .expect("search should succeed");
+    assert_eq!(rowids(&results), vec!["3", "4", "2"]);
+
+    let distances: Vec<f32> = results
+        .iter()
+        .map(|row| row["distance"].parse().unwrap())
+        .collect();
+    for (distance, expected) in distances.iter().zip([0.04, 0.64, 1.44]) {
+        assert!((distance - expected).abs() < 1e-4, "{:?}", distances);
+    }
+}
+
+#[test]
+fn filtered_search_of_small_collection_is_exact() {
+    let vlite = setup_vlite(10);
+
+    let results = vlite
+        .search(search(
+            2,
+            Some("select rowid, label from points where label = 'odd'"),
+        ))
+        .expect("search should succeed");
+
+    assert_eq!(rowids(&results), vec!["3", "5"]);
+    assert!(results.iter().all(|row| row["label"] == "odd"));
+}
+
+#[test]
+fn fallback_is_skipped_once_collection_reaches_threshold() {
+    let vlite = setup_vlite(6);
+    assert!(!vlite
+        .explain_search(search(3, None))
+        .unwrap()
+        .contains("knn_search"));
+
+    insert(&vlite, 6);
+
+    let sql = vlite.explain_search(search(3, None)).unwrap();
+    assert!(sql.contains("knn_search"), "{}", sql);
+    let results = vlite
+        .search(search(3, None))
+        .expect("search should succeed");
+    assert_eq!(rowids(&results), vec!["3", "4", "2"]);
+}
+
+#[test]
+fn restrict_to_ids_applies_to_exact_search() {
+    let vlite = setup_vlite(10);
+
+    let search_point = SearchPoint::builder()
+        .collection_name("points")
+        .vector(vec![3.2, 0.0])
+        .top_k(2)
+        .restrict_to_ids(vec![1, 2, 5])
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).expect("search should succeed");
+
+    assert_eq!(rowids(&results), vec!["2", "5"]);
+}
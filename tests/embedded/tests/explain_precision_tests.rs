@@ -0,0 +1,109 @@
+//! Tests for the explain_precision option of VectorXLiteConfig
+//!
+//! These tests verify:
+//! - explain_search inlines the query vector at full precision by default
+//! - explain_precision rounds the inlined query vector
+//! - Inserted vectors keep full precision regardless of explain_precision
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+const PRECISE: [f32; 3] = [0.123_456_79, -2.718_281_7, 1.0];
+
+fn setup_vlite(config: VectorXLiteConfig) -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::with_config(pool.clone(), config).expect("create VectorXLite");
+    vlite
+        .create_collection(
+            CollectionConfigBuilder::default()
+                .collection_name("points")
+                .distance(DistanceFunction::L2)
+                .vector_dimension(3)
+                .build()
+                .unwrap(),
+        )
+        .expect("collection should be created");
+    (vlite, pool)
+}
+
+fn precise_search() -> SearchPoint {
+    SearchPoint::builder()
+        .collection_name("points")
+        .vector(PRECISE.to_vec())
+        .top_k(1)
+        .build()
+        .unwrap()
+}
+
+/// Reads a stored vector back through vectorlite.
+fn stored_vector(pool: &Pool<SqliteConnectionManager>, id: i64) -> Vec<f32> {
+    let conn = pool.get().unwrap();
+    let json: String = conn
+        .query_row(
+            "SELECT vector_to_json(vector_embedding) FROM vt_vector_points WHERE rowid = ?1",
+            [id],
+            |row| row.get(0),
+        )
+        .unwrap();
+
+    json.trim_matches(|c| c == '[' || c == ']')
+        .split(',')
+        .map(|v| v.parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn explain_inlines_full_precision_vector_by_default() {
+    let (vlite, _) = setup_vlite(VectorXLiteConfig::default());
+
+    let sql = vlite.explain_search(precise_search()).unwrap();
+
+    assert!(
+        sql.contains("vector_from_json('[0.12345679,-2.7182817,1]')"),
+        "{}",
+        sql
+    );
+}
+
+#[test]
+fn explain_precision_rounds_inlined_vector() {
+    let (vlite, _) = setup_vlite(VectorXLiteConfig::default().with_explain_precision(2));
+
+    let sql = vlite.explain_search(precise_search()).unwrap();
+
+    assert!(
+        sql.contains("vector_from_json('[0.12,-2.72,1]')"),
+        "{}",
+        sql
+    );
+    assert!(!sql.contains("0.12345679"));
+}
+
+#[test]
+fn insert_keeps_full_precision_with_explain_precision() {
+    let (vlite, pool) = setup_vlite(VectorXLiteConfig::default().with_explain_precision(2));
+
+    let point = InsertPoint::builder()
+        .collection_name("points")
+        .id(1)
+        .vector(PRECISE.to_vec())
+        .build()
+        .unwrap();
+    vlite.insert(point).expect("insert should be successful.");
+
+    let stored = stored_vector(&pool, 1);
+    assert_eq!(stored.len(), 3);
+    for (stored, original) in stored.iter().zip(PRECISE.iter()) {
+        assert_eq!(stored.to_bits(), original.to_bits());
+    }
+
+    let results = vlite.search(precise_search()).unwrap();
+    assert_eq!(results[0]["distance"].parse::<f32>().unwrap(), 0.0);
+}
@@ -0,0 +1,142 @@
+//! Tests for explain_query_plan method in VectorXLite
+//!
+//! These tests verify:
+//! - The plan of a join-heavy payload query is returned
+//! - Index usage and full-table scans show up in the plan text
+//! - Unknown collections are rejected
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+fn setup_vlite() -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    vlite
+        .create_collection(
+            CollectionConfigBuilder::default()
+                .collection_name("orders")
+                .distance(DistanceFunction::L2)
+                .vector_dimension(2)
+                .payload_table_schema(
+                    "create table orders (rowid integer primary key, customer_id integer, total real)",
+                )
+                .build()
+                .unwrap(),
+        )
+        .expect("collection should be created");
+
+    pool.get()
+        .unwrap()
+        .execute_batch(
+            "create table customers (id integer primary key, country text);
+             create table shipments (order_id integer, carrier text);
+             create index shipments_order on shipments(order_id);
+             insert into customers values (1, 'NO'), (2, 'SE');
+             insert into shipments values (1, 'post'), (2, 'courier');",
+        )
+        .unwrap();
+
+    for id in 1..=2u64 {
+        let point = InsertPoint::builder()
+            .collection_name("orders")
+            .id(id)
+            .vector(vec![id as f32, 0.0])
+            .payload_insert_query(format!(
+                "insert into orders(rowid, customer_id, total) values (?1, {}, 10.0)",
+                id
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+
+    (vlite, pool)
+}
+
+fn joined_orders_search() -> SearchPoint {
+    SearchPoint::builder()
+        .collection_name("orders")
+        .vector(vec![0.0, 0.0])
+        .top_k(5)
+        .payload_search_query(
+            "select o.rowid, o.total, c.country, s.carrier
+             from orders o
+             join customers c on c.id = o.customer_id
+             join shipments s on s.order_id = o.rowid
+             where c.country = 'NO'",
+        )
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn explain_query_plan_returns_plan_of_join() {
+    let (vlite, _) = setup_vlite();
+
+    let plan = vlite
+        .explain_query_plan(&joined_orders_search())
+        .expect("plan should be returned");
+
+    assert!(!plan.is_empty());
+    assert!(plan.iter().all(|line| !line.trim().is_empty()));
+    let text = plan.join("\n");
+    assert!(text.contains("VIRTUAL TABLE"), "{}", text);
+    assert!(text.contains("SEARCH c "), "{}", text);
+    assert!(
+        text.contains("  SCAN s "),
+        "nested steps should be indented: {}",
+        text
+    );
+}
+
+#[test]
+fn explain_query_plan_shows_index_usage() {
+    let (vlite, _) = setup_vlite();
+
+    let text = vlite
+        .explain_query_plan(&joined_orders_search())
+        .unwrap()
+        .join("\n");
+
+    assert!(
+        text.contains("USING INDEX shipments_order")
+            || text.contains("USING COVERING INDEX shipments_order"),
+        "{}",
+        text
+    );
+    assert!(text.contains("USING INTEGER PRIMARY KEY"), "{}", text);
+}
+
+#[test]
+fn search_point_can_be_run_after_explaining() {
+    let (vlite, _) = setup_vlite();
+    let search_point = joined_orders_search();
+
+    vlite.explain_query_plan(&search_point).unwrap();
+
+    // The search point is only borrowed and can still be run
+    let results = vlite.search(search_point).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["carrier"], "post");
+}
+
+#[test]
+fn explain_query_plan_of_missing_collection_fails() {
+    let (vlite, _) = setup_vlite();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("missing")
+        .vector(vec![0.0, 0.0])
+        .top_k(5)
+        .build()
+        .unwrap();
+
+    assert!(vlite.explain_query_plan(&search_point).is_err());
+}
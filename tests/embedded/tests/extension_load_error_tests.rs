@@ -0,0 +1,83 @@
+//! Tests for extension load failures on pooled connections
+//!
+//! These tests verify:
+//! - A customizer pointed at a missing library makes the first operation fail with
+//!   ExtensionLoadError instead of a generic SQL or pool error
+//! - The error names the library path that failed to load
+//! - A customizer pointed at a valid library works like the default one
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::time::Duration;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+const MISSING_EXTENSION_PATH: &str = "/tmp/vxlite_test_missing_extension/vectorlite.so";
+
+fn setup_vlite(customizer: Box<SqliteConnectionCustomizer>) -> VectorXLite {
+    let manager = SqliteConnectionManager::memory();
+    // build_unchecked: the pool cannot open a single working connection
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_timeout(Duration::from_millis(300))
+        .connection_customizer(customizer)
+        .build_unchecked(manager);
+
+    VectorXLite::new(pool).expect("create VectorXLite")
+}
+
+fn notes_config() -> CollectionConfig {
+    CollectionConfigBuilder::default()
+        .collection_name("notes")
+        .vector_dimension(2)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn missing_extension_fails_first_operation_with_extension_load_error() {
+    let vlite = setup_vlite(SqliteConnectionCustomizer::with_extension_path(
+        MISSING_EXTENSION_PATH,
+    ));
+
+    let err = vlite
+        .create_collection(notes_config())
+        .expect_err("operation should fail without the extension");
+
+    assert!(matches!(err, VecXError::ExtensionLoadError(_)), "{:?}", err);
+    assert!(err.to_string().contains(MISSING_EXTENSION_PATH), "{}", err);
+}
+
+#[test]
+fn missing_extension_is_reported_for_every_operation() {
+    let vlite = setup_vlite(SqliteConnectionCustomizer::with_extension_path(
+        MISSING_EXTENSION_PATH,
+    ));
+
+    assert!(matches!(
+        vlite.collection_exists("notes"),
+        Err(VecXError::ExtensionLoadError(_))
+    ));
+    assert!(matches!(
+        vlite.count_where("notes", "1 = 1"),
+        Err(VecXError::ExtensionLoadError(_))
+    ));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn extension_loaded_from_valid_path_works() {
+    let extension_path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../embedded/core/assets/vectorlite.so"
+    );
+    let vlite = setup_vlite(SqliteConnectionCustomizer::with_extension_path(
+        extension_path,
+    ));
+
+    vlite
+        .create_collection(notes_config())
+        .expect("collection should be created");
+    assert!(vlite.collection_exists("notes").unwrap());
+}
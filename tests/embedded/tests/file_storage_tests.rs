@@ -6,7 +6,7 @@
 use r2d2::Pool;
 use r2d2_sqlite::SqliteConnectionManager;
 use std::fs;
-use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+use vector_xlite::{customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite};
 
 /// Helper to create unique test file paths
 fn test_paths(name: &str) -> (String, String) {
@@ -552,3 +552,298 @@ mod distance_functions_file {
         cleanup(&db_path, &idx_path);
     }
 }
+
+// ============================================================================
+// Index Flush Tests
+// ============================================================================
+
+mod index_flush {
+    use super::*;
+
+    fn insert_points(vlite: &VectorXLite, collection_name: &str, count: u64) {
+        for i in 0..count {
+            let point = InsertPoint::builder()
+                .collection_name(collection_name)
+                .id(i)
+                .vector(vec![i as f32, 1.0, 0.0, 0.0])
+                .build()
+                .unwrap();
+            vlite.insert(point).expect("insert");
+        }
+    }
+
+    fn search_count(vlite: &VectorXLite, collection_name: &str) -> usize {
+        let search = SearchPoint::builder()
+            .collection_name(collection_name)
+            .vector(vec![5.0, 1.0, 0.0, 0.0])
+            .top_k(100)
+            .build()
+            .unwrap();
+        vlite.search(search).expect("search").len()
+    }
+
+    #[test]
+    fn flush_persists_index_without_connection_teardown() {
+        let (db_path, idx_path) = test_paths("flush_no_teardown");
+        cleanup(&db_path, &idx_path);
+
+        {
+            let (vlite, pool) = create_vlite(&db_path, 2);
+
+            let config = CollectionConfigBuilder::default()
+                .collection_name("flush_test")
+                .vector_dimension(4)
+                .index_file_path(&idx_path)
+                .build()
+                .unwrap();
+            vlite.create_collection(config).expect("create collection");
+
+            insert_points(&vlite, "flush_test", 25);
+            vlite.flush("flush_test").expect("flush");
+
+            assert!(
+                fs::metadata(&idx_path).map(|m| m.len()).unwrap_or(0) > 0,
+                "Index file should be written by flush"
+            );
+
+            // Simulate a crash: the pooled connections are never closed, so
+            // vectorlite never gets a chance to save the index on disconnect.
+            std::mem::forget(vlite);
+            std::mem::forget(pool);
+        }
+
+        let (vlite, _) = create_vlite(&db_path, 1);
+        assert_eq!(
+            search_count(&vlite, "flush_test"),
+            25,
+            "All flushed vectors should be present after reopen"
+        );
+
+        cleanup(&db_path, &idx_path);
+    }
+
+    #[test]
+    fn drop_flushes_index_while_pool_is_alive() {
+        let (db_path, idx_path) = test_paths("flush_on_drop");
+        cleanup(&db_path, &idx_path);
+
+        {
+            let (vlite, pool) = create_vlite(&db_path, 1);
+
+            let config = CollectionConfigBuilder::default()
+                .collection_name("drop_flush_test")
+                .vector_dimension(4)
+                .index_file_path(&idx_path)
+                .build()
+                .unwrap();
+            vlite.create_collection(config).expect("create collection");
+
+            insert_points(&vlite, "drop_flush_test", 10);
+
+            drop(vlite);
+            std::mem::forget(pool);
+        }
+
+        let (vlite, _) = create_vlite(&db_path, 1);
+        assert_eq!(
+            search_count(&vlite, "drop_flush_test"),
+            10,
+            "Vectors should be flushed when VectorXLite is dropped"
+        );
+
+        cleanup(&db_path, &idx_path);
+    }
+
+    #[test]
+    fn flush_unknown_collection_returns_error() {
+        let (db_path, idx_path) = test_paths("flush_unknown");
+        cleanup(&db_path, &idx_path);
+
+        let (vlite, _) = create_vlite(&db_path, 1);
+        let result = vlite.flush("missing_collection");
+        assert!(matches!(result, Err(VecXError::InvalidQueryError(_))));
+
+        cleanup(&db_path, &idx_path);
+    }
+}
+
+// ============================================================================
+// Persisting In-Memory Collections
+// ============================================================================
+
+mod persist_to_file {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn create_memory_vlite() -> VectorXLite {
+        // A single connection, since every connection holds its own in-memory index
+        let pool = Pool::builder()
+            .max_size(1)
+            .connection_customizer(SqliteConnectionCustomizer::new())
+            .build(SqliteConnectionManager::memory())
+            .expect("create pool");
+
+        VectorXLite::new(pool).expect("create VectorXLite")
+    }
+
+    fn create_notes(vlite: &VectorXLite, count: u64) {
+        let config = CollectionConfigBuilder::default()
+            .collection_name("notes")
+            .distance(DistanceFunction::L2)
+            .vector_dimension(2)
+            .payload_table_schema("create table notes (rowid integer primary key, title text)")
+            .build()
+            .unwrap();
+        vlite.create_collection(config).expect("create collection");
+
+        for i in 1..=count {
+            let point = InsertPoint::builder()
+                .collection_name("notes")
+                .id(i)
+                .vector(vec![i as f32, 0.0])
+                .payload_insert_query(format!(
+                    "insert into notes(rowid, title) values (?1, 'note {}')",
+                    i
+                ))
+                .build()
+                .unwrap();
+            vlite.insert(point).expect("insert");
+        }
+    }
+
+    #[test]
+    fn in_memory_collection_survives_reopen_from_file() {
+        let (db_path, _) = test_paths("persist_to");
+        let idx_dir = "/tmp/vxlite_test_persist_to_idx";
+        let idx_path = format!("{}/notes.idx", idx_dir);
+        cleanup(&db_path, &idx_path);
+
+        {
+            let vlite = create_memory_vlite();
+            create_notes(&vlite, 25);
+            vlite.persist_to(&db_path, idx_dir).expect("persist");
+            assert!(fs::metadata(&idx_path).is_ok(), "index file should be written");
+        }
+
+        {
+            let (vlite, _) = create_vlite(&db_path, 1);
+            assert!(vlite.collection_exists("notes").unwrap());
+
+            let search = SearchPoint::builder()
+                .collection_name("notes")
+                .vector(vec![0.0, 0.0])
+                .top_k(100)
+                .payload_search_query("select rowid, title from notes")
+                .build()
+                .unwrap();
+            let results = vlite.search(search).expect("search persisted collection");
+
+            let ids: HashSet<String> = results.iter().map(|r| r["rowid"].clone()).collect();
+            assert_eq!(ids.len(), 25);
+            for row in &results {
+                assert_eq!(row["title"], format!("note {}", row["rowid"]));
+            }
+            assert_eq!(results[0]["rowid"], "1");
+        }
+
+        cleanup(&db_path, &idx_path);
+    }
+
+    #[test]
+    fn persist_refuses_to_overwrite_index_file() {
+        let (db_path, _) = test_paths("persist_to_existing");
+        let idx_dir = "/tmp/vxlite_test_persist_to_existing_idx";
+        let idx_path = format!("{}/notes.idx", idx_dir);
+        cleanup(&db_path, &idx_path);
+        fs::create_dir_all(idx_dir).unwrap();
+        fs::write(&idx_path, b"stale").unwrap();
+
+        let vlite = create_memory_vlite();
+        create_notes(&vlite, 3);
+
+        let result = vlite.persist_to(&db_path, idx_dir);
+        assert!(matches!(result, Err(VecXError::InvalidQueryError(_))));
+
+        cleanup(&db_path, &idx_path);
+    }
+}
+
+// ============================================================================
+// Preallocated Bulk Loads
+// ============================================================================
+
+mod preallocation {
+    use super::*;
+
+    fn bulk_load(name: &str, config: CollectionConfigBuilder, count: u64) -> (u64, u64) {
+        let (db_path, idx_path) = test_paths(name);
+        cleanup(&db_path, &idx_path);
+
+        let (vlite, _) = create_vlite(&db_path, 1);
+        let config = config
+            .collection_name("bulk")
+            .vector_dimension(8)
+            .index_file_path(&idx_path)
+            .build()
+            .unwrap();
+        vlite.create_collection(config).expect("create collection");
+
+        for id in 1..=count {
+            let point = InsertPoint::builder()
+                .collection_name("bulk")
+                .id(id)
+                .vector((0..8).map(|d| ((id * 7 + d) % 13) as f32).collect())
+                .build()
+                .unwrap();
+            vlite.insert(point).expect("insert");
+        }
+        vlite.flush("bulk").expect("flush");
+
+        let search = SearchPoint::builder()
+            .collection_name("bulk")
+            .vector((0..8).map(|d| ((7 + d) % 13) as f32).collect())
+            .top_k(5)
+            .build()
+            .unwrap();
+        let results = vlite.search(search).expect("search");
+        assert_eq!(results.len(), 5);
+        assert!(results[0]["distance"].parse::<f32>().unwrap() < 1e-5);
+
+        let file_size = fs::metadata(&idx_path).expect("index file").len();
+        let estimate = vlite.estimate_memory("bulk").unwrap();
+
+        drop(vlite);
+        cleanup(&db_path, &idx_path);
+        (file_size, estimate)
+    }
+
+    #[test]
+    fn bulk_load_into_preallocated_collection() {
+        let (file_size, estimate) = bulk_load(
+            "preallocated",
+            CollectionConfigBuilder::default().preallocate_for(400),
+            400,
+        );
+
+        // The file holds the loaded points, not the 500 preallocated slots.
+        assert!(file_size > 0);
+        assert!(file_size <= 2 * estimate, "{} > 2 * {}", file_size, estimate);
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_preallocated_vs_default_index_file_size() {
+        let count = 20_000;
+        let (preallocated, _) = bulk_load(
+            "bench_preallocated",
+            CollectionConfigBuilder::default().preallocate_for(count as u32),
+            count,
+        );
+        let (default, _) = bulk_load("bench_default", CollectionConfigBuilder::default(), count);
+
+        println!(
+            "index file size: preallocated {} bytes, default {} bytes",
+            preallocated, default
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Tests for forcing the payload filter strategy of a search
+//!
+//! These tests verify:
+//! - Pushdown places the payload rowids inside knn_search
+//! - PostFilter joins the payload filter after the KNN step
+//! - Every strategy returns the same results for the same search
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::collections::HashMap;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+fn setup_vlite() -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(5)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    (vlite, pool)
+}
+
+fn create_items_collection(vlite: &VectorXLite) {
+    let config = CollectionConfigBuilder::default()
+        .collection_name("items")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema("create table items (rowid integer primary key, parity text)")
+        .build()
+        .unwrap();
+
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for i in 1..=20u64 {
+        let parity = if i % 2 == 0 { "even" } else { "odd" };
+        let point = InsertPoint::builder()
+            .collection_name("items")
+            .id(i)
+            .vector(vec![i as f32, 0.0])
+            .payload_insert_query(format!(
+                "insert into items(rowid, parity) values (?1, '{}')",
+                parity
+            ))
+            .build()
+            .expect("Builder should create insert point.");
+
+        vlite.insert(point).expect("insert should be successful.");
+    }
+}
+
+fn even_items_search(strategy: FilterStrategy) -> SearchPoint {
+    SearchPoint::builder()
+        .collection_name("items")
+        .vector(vec![0.0, 0.0])
+        .top_k(3)
+        .payload_search_query("select rowid, parity from items where parity = 'even'")
+        .filter_strategy(strategy)
+        .build()
+        .unwrap()
+}
+
+fn rowids(results: &[HashMap<String, String>]) -> Vec<i64> {
+    results
+        .iter()
+        .map(|row| row["rowid"].parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn pushdown_passes_payload_rowids_into_knn_search() {
+    let (vlite, _) = setup_vlite();
+    create_items_collection(&vlite);
+
+    let sql = vlite
+        .explain_search(even_items_search(FilterStrategy::Pushdown))
+        .unwrap();
+
+    assert!(sql.contains("AND vt_inner.rowid in (SELECT rowid FROM items where parity = 'even')"));
+}
+
+#[test]
+fn post_filter_joins_payload_after_knn_search() {
+    let (vlite, _) = setup_vlite();
+    create_items_collection(&vlite);
+
+    let sql = vlite
+        .explain_search(even_items_search(FilterStrategy::PostFilter))
+        .unwrap();
+
+    assert!(!sql.contains("vt_inner.rowid in"));
+    assert!(sql.contains("INNER JOIN (select rowid, parity from items where parity = 'even')"));
+}
+
+#[test]
+fn auto_uses_pushdown_for_selective_filters() {
+    let (vlite, _) = setup_vlite();
+    create_items_collection(&vlite);
+
+    let auto = vlite
+        .explain_search(even_items_search(FilterStrategy::Auto))
+        .unwrap();
+    let pushdown = vlite
+        .explain_search(even_items_search(FilterStrategy::Pushdown))
+        .unwrap();
+
+    assert_eq!(auto, pushdown);
+}
+
+#[test]
+fn all_strategies_return_identical_results() {
+    let (vlite, _) = setup_vlite();
+    create_items_collection(&vlite);
+
+    let auto = vlite
+        .search(even_items_search(FilterStrategy::Auto))
+        .unwrap();
+    let pushdown = vlite
+        .search(even_items_search(FilterStrategy::Pushdown))
+        .unwrap();
+    let post_filter = vlite
+        .search(even_items_search(FilterStrategy::PostFilter))
+        .unwrap();
+
+    assert_eq!(rowids(&auto), vec![2, 4, 6]);
+    assert_eq!(rowids(&pushdown), rowids(&auto));
+    assert_eq!(rowids(&post_filter), rowids(&auto));
+}
@@ -0,0 +1,125 @@
+//! Tests for VectorXLite::from_connection
+//!
+//! These tests verify:
+//! - A bare connection supports the simple example's create/insert/search flow
+//! - Operations take turns on the single connection
+//! - File databases opened through a single connection persist
+
+use rusqlite::Connection;
+use std::fs;
+use vector_xlite::{types::*, VectorXLite};
+
+fn create_person_collection(vlite: &VectorXLite) {
+    let config = CollectionConfigBuilder::default()
+        .collection_name("person")
+        .distance(DistanceFunction::Cosine)
+        .vector_dimension(4)
+        .payload_table_schema("create table person (rowid integer primary key, name text)")
+        .build()
+        .unwrap();
+
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+}
+
+fn insert_people(vlite: &VectorXLite) {
+    let people = [
+        (1, vec![1.0, 2.0, 3.0, 4.0], "Alice"),
+        (2, vec![4.0, 5.0, 6.0, 4.0], "Bob"),
+        (3, vec![7.0, 8.0, 9.0, 4.0], "Charlie"),
+    ];
+
+    for (id, vector, name) in people {
+        let point = InsertPoint::builder()
+            .collection_name("person")
+            .id(id)
+            .vector(vector)
+            .payload_insert_query(format!(
+                "insert into person(rowid, name) values (?1, '{}')",
+                name
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+}
+
+fn person_search() -> SearchPoint {
+    SearchPoint::builder()
+        .collection_name("person")
+        .vector(vec![7.0, 8.0, 9.0, 2.0])
+        .top_k(10)
+        .payload_search_query("select * from person")
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn simple_example_runs_on_bare_connection() {
+    let conn = Connection::open_in_memory().unwrap();
+    let vlite = VectorXLite::from_connection(conn).expect("create VectorXLite");
+
+    assert!(!vlite.collection_exists("person").unwrap());
+    create_person_collection(&vlite);
+    insert_people(&vlite);
+
+    let results = vlite.search(person_search()).unwrap();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0]["name"], "Charlie");
+    assert!(vlite.collection_exists("person").unwrap());
+}
+
+#[test]
+fn operations_take_turns_on_single_connection() {
+    let conn = Connection::open_in_memory().unwrap();
+    let vlite = VectorXLite::from_connection(conn).unwrap();
+    create_person_collection(&vlite);
+    insert_people(&vlite);
+
+    let mut stream = vlite.search_stream(person_search()).unwrap();
+    let first = stream.next().unwrap().unwrap();
+    assert_eq!(first["name"], "Charlie");
+    drop(stream);
+
+    // The connection is back once the stream is gone
+    assert_eq!(vlite.count_where("person", "name LIKE 'A%'").unwrap(), 1);
+    assert_eq!(vlite.search(person_search()).unwrap().len(), 3);
+}
+
+#[test]
+fn file_database_persists_through_single_connection() {
+    let db_path = "/tmp/vxlite_test_from_connection.db";
+    let idx_path = "/tmp/vxlite_test_from_connection.idx";
+    let cleanup = || {
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = fs::remove_file(format!("{}{}", db_path, suffix));
+        }
+        let _ = fs::remove_file(idx_path);
+    };
+    cleanup();
+
+    {
+        let vlite = VectorXLite::from_connection(Connection::open(db_path).unwrap()).unwrap();
+        let config = CollectionConfigBuilder::default()
+            .collection_name("person")
+            .distance(DistanceFunction::Cosine)
+            .vector_dimension(4)
+            .index_file_path(idx_path)
+            .payload_table_schema("create table person (rowid integer primary key, name text)")
+            .build()
+            .unwrap();
+        vlite.create_collection(config).unwrap();
+        insert_people(&vlite);
+    }
+
+    let reopened = VectorXLite::from_connection(Connection::open(db_path).unwrap()).unwrap();
+    let results = reopened.search(person_search()).unwrap();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0]["name"], "Charlie");
+
+    drop(reopened);
+    cleanup();
+}
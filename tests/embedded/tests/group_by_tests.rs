@@ -0,0 +1,226 @@
+//! Tests for grouping search results by a payload column
+//!
+//! These tests verify:
+//! - Each category returns at most `per_group` nearest results
+//! - Groups come back in column order, each ordered by distance
+//! - Both pushdown and post-filter plans are grouped
+//! - Combined with restrict_to_ids, groups only hold the restricted ids
+//! - Invalid group_by settings are rejected by the builder
+//! - group_by is rejected without a payload query or default
+
+use std::collections::HashMap;
+use vector_xlite::{error::VecXError, types::*, VectorXLite};
+
+/// Items as `(rowid, category, x)`; each item sits at distance `x` from the origin.
+const ITEMS: [(u64, &str, f32); 9] = [
+    (1, "books", 1.0),
+    (2, "books", 2.0),
+    (3, "books", 3.0),
+    (4, "books", 4.0),
+    (5, "games", 1.5),
+    (6, "games", 5.0),
+    (7, "music", 2.5),
+    (8, "music", 3.5),
+    (9, "music", 6.0),
+];
+
+fn setup_vlite() -> VectorXLite {
+    let vlite = VectorXLite::builder()
+        .memory()
+        .build()
+        .expect("create VectorXLite");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("items")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema("create table items (rowid integer primary key, category text)")
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for (id, category, x) in ITEMS {
+        let point = InsertPoint::builder()
+            .collection_name("items")
+            .id(id)
+            .vector(vec![x, 0.0])
+            .payload_insert_query(format!(
+                "insert into items(rowid, category) values (?1, '{}')",
+                category
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+    vlite
+}
+
+fn grouped_search(per_group: usize) -> SearchPointBuilder {
+    SearchPoint::builder()
+        .collection_name("items")
+        .vector(vec![0.0, 0.0])
+        .top_k(20)
+        .payload_search_query("select rowid, category from items")
+        .group_by("category", per_group)
+}
+
+fn rows(results: &[HashMap<String, String>]) -> Vec<(String, i64)> {
+    results
+        .iter()
+        .map(|row| (row["category"].clone(), row["rowid"].parse().unwrap()))
+        .collect()
+}
+
+fn expected(rows: &[(&str, i64)]) -> Vec<(String, i64)> {
+    rows.iter()
+        .map(|(category, rowid)| (category.to_string(), *rowid))
+        .collect()
+}
+
+#[test]
+fn group_by_returns_at_most_per_group_nearest_results() {
+    let vlite = setup_vlite();
+
+    let results = vlite
+        .search(grouped_search(2).build().unwrap())
+        .expect("search should succeed");
+
+    assert_eq!(
+        rows(&results),
+        expected(&[
+            ("books", 1),
+            ("books", 2),
+            ("games", 5),
+            ("games", 6),
+            ("music", 7),
+            ("music", 8),
+        ])
+    );
+}
+
+#[test]
+fn group_by_keeps_smaller_groups_whole() {
+    let vlite = setup_vlite();
+
+    let results = vlite
+        .search(grouped_search(3).build().unwrap())
+        .expect("search should succeed");
+
+    let mut per_category: HashMap<String, usize> = HashMap::new();
+    for (category, _) in rows(&results) {
+        *per_category.entry(category).or_default() += 1;
+    }
+    assert_eq!(per_category["books"], 3);
+    assert_eq!(per_category["games"], 2);
+    assert_eq!(per_category["music"], 3);
+}
+
+#[test]
+fn group_by_groups_post_filter_searches() {
+    let vlite = setup_vlite();
+
+    let results = vlite
+        .search(
+            grouped_search(1)
+                .filter_strategy(FilterStrategy::PostFilter)
+                .build()
+                .unwrap(),
+        )
+        .expect("search should succeed");
+
+    assert_eq!(
+        rows(&results),
+        expected(&[("books", 1), ("games", 5), ("music", 7)])
+    );
+}
+
+#[test]
+fn group_by_groups_within_restricted_ids() {
+    let vlite = setup_vlite();
+
+    for strategy in [FilterStrategy::Pushdown, FilterStrategy::PostFilter] {
+        let restricted = |per_group| {
+            grouped_search(per_group)
+                .restrict_to_ids(vec![2, 3, 6, 8, 9])
+                .filter_strategy(strategy)
+                .build()
+                .unwrap()
+        };
+
+        let nearest = vlite.search(restricted(1)).expect("search should succeed");
+        assert_eq!(
+            rows(&nearest),
+            expected(&[("books", 2), ("games", 6), ("music", 8)]),
+            "{:?}",
+            strategy
+        );
+
+        let two_per_group = vlite.search(restricted(2)).expect("search should succeed");
+        assert_eq!(
+            rows(&two_per_group),
+            expected(&[
+                ("books", 2),
+                ("books", 3),
+                ("games", 6),
+                ("music", 8),
+                ("music", 9),
+            ]),
+            "{:?}",
+            strategy
+        );
+    }
+}
+
+#[test]
+fn group_by_only_considers_top_k_nearest() {
+    let vlite = setup_vlite();
+
+    let results = vlite
+        .search(grouped_search(2).top_k(3).build().unwrap())
+        .expect("search should succeed");
+
+    assert_eq!(
+        rows(&results),
+        expected(&[("books", 1), ("books", 2), ("games", 5)])
+    );
+}
+
+#[test]
+fn group_by_rejects_invalid_settings() {
+    let cases = [
+        (
+            grouped_search(0),
+            "group_by per_group must be greater than 0.",
+        ),
+        (
+            grouped_search(2).group_by("category; drop table items", 2),
+            "group_by column must be a plain column name.",
+        ),
+        (
+            grouped_search(2).dedup_by("category"),
+            "group_by cannot be combined with dedup_by.",
+        ),
+    ];
+
+    for (builder, message) in cases {
+        assert_eq!(builder.build().unwrap_err(), message);
+    }
+}
+
+#[test]
+fn group_by_requires_payload_search_query() {
+    let vlite = setup_vlite();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("items")
+        .vector(vec![0.0, 0.0])
+        .group_by("category", 2)
+        .build()
+        .unwrap();
+
+    assert!(matches!(
+        vlite.search(search_point),
+        Err(VecXError::InvalidQueryError(_))
+    ));
+}
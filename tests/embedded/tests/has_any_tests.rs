@@ -0,0 +1,107 @@
+//! Tests for has_any method in VectorXLite
+//!
+//! These tests verify:
+//! - A fresh collection has no points, and has one after an insert
+//! - Deleting the last point makes the collection empty again
+//! - Missing collections fail with an SQL error
+//! - has_any is cheaper than a full count on a large collection (ignored benchmark)
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::time::{Duration, Instant};
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+fn setup_vlite() -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("docs")
+        .vector_dimension(2)
+        .payload_table_schema("create table docs (rowid integer primary key, title text)")
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+    (vlite, pool)
+}
+
+#[test]
+fn has_any_tracks_inserts_and_deletes() {
+    let (vlite, _pool) = setup_vlite();
+    assert!(!vlite.has_any("docs").unwrap());
+
+    let point = InsertPoint::builder()
+        .collection_name("docs")
+        .id(1)
+        .vector(vec![1.0, 0.0])
+        .build()
+        .unwrap();
+    vlite.insert(point).expect("insert should be successful.");
+    assert!(vlite.has_any("docs").unwrap());
+
+    let delete_point = DeletePoint::builder()
+        .collection_name("docs")
+        .id(1)
+        .build()
+        .unwrap();
+    vlite.delete(delete_point).expect("delete should succeed");
+    assert!(!vlite.has_any("docs").unwrap());
+}
+
+#[test]
+fn has_any_on_missing_collection_fails() {
+    let (vlite, _pool) = setup_vlite();
+
+    let result = vlite.has_any("missing");
+
+    assert!(
+        matches!(result, Err(VecXError::SqlError(_))),
+        "{:?}",
+        result
+    );
+}
+
+#[test]
+#[ignore]
+fn has_any_is_cheaper_than_count() {
+    let (vlite, pool) = setup_vlite();
+    pool.get()
+        .unwrap()
+        .execute(
+            "insert into docs(rowid, title)
+             with recursive n(i) as (select 1 union all select i + 1 from n where i < 1000000)
+             select i, 'doc ' || i from n",
+            [],
+        )
+        .expect("load payload rows");
+
+    let fastest = |run: &dyn Fn()| {
+        (0..5)
+            .map(|_| {
+                let started = Instant::now();
+                run();
+                started.elapsed()
+            })
+            .min()
+            .unwrap_or(Duration::MAX)
+    };
+    let has_any = fastest(&|| assert!(vlite.has_any("docs").unwrap()));
+    let count = fastest(&|| assert_eq!(vlite.count_where("docs", "1 = 1").unwrap(), 1_000_000));
+
+    println!("has_any: {:?}, count_where: {:?}", has_any, count);
+    assert!(
+        has_any < count,
+        "has_any {:?} vs count {:?}",
+        has_any,
+        count
+    );
+}
@@ -0,0 +1,192 @@
+//! Tests for the idempotency_key option of InsertPoint
+//!
+//! These tests verify:
+//! - Repeating an insert with the same key writes a single row and reports a duplicate
+//! - A failed insert does not use up its key
+//! - Keys expire after the configured TTL
+//! - The same key can be used in different collections
+//! - Inserts without a key behave as before
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::time::Duration;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+fn setup_vlite(config: VectorXLiteConfig) -> VectorXLite {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::with_config(pool, config).expect("create VectorXLite");
+    vlite
+        .create_collection(
+            CollectionConfigBuilder::default()
+                .collection_name("orders")
+                .distance(DistanceFunction::L2)
+                .vector_dimension(2)
+                .payload_table_schema("create table orders (rowid integer primary key, item text)")
+                .build()
+                .unwrap(),
+        )
+        .expect("collection should be created");
+    vlite
+}
+
+fn order(id: u64, item: &str, key: Option<&str>) -> InsertPoint {
+    let mut builder = InsertPoint::builder()
+        .collection_name("orders")
+        .id(id)
+        .vector(vec![id as f32, 0.0])
+        .payload_insert_query(format!(
+            "insert into orders(rowid, item) values (?1, '{}')",
+            item
+        ));
+    if let Some(key) = key {
+        builder = builder.idempotency_key(key);
+    }
+    builder.build().unwrap()
+}
+
+fn items(vlite: &VectorXLite) -> Vec<String> {
+    let search_point = SearchPoint::builder()
+        .collection_name("orders")
+        .vector(vec![0.0, 0.0])
+        .top_k(10)
+        .payload_search_query("select rowid, item from orders")
+        .build()
+        .unwrap();
+    vlite
+        .search(search_point)
+        .unwrap()
+        .into_iter()
+        .map(|mut row| row.remove("item").unwrap())
+        .collect()
+}
+
+#[test]
+fn repeated_insert_with_same_key_is_applied_once() {
+    let vlite = setup_vlite(VectorXLiteConfig::default());
+
+    let first = vlite
+        .insert_idempotent(order(1, "book", Some("req-1")))
+        .unwrap();
+    let retry = vlite
+        .insert_idempotent(order(1, "book", Some("req-1")))
+        .unwrap();
+
+    assert_eq!(first, InsertOutcome::Inserted);
+    assert_eq!(retry, InsertOutcome::Duplicate);
+    assert_eq!(items(&vlite), vec!["book"]);
+}
+
+#[test]
+fn duplicate_key_does_not_apply_changed_request() {
+    let vlite = setup_vlite(VectorXLiteConfig::default());
+
+    vlite.insert(order(1, "book", Some("req-1"))).unwrap();
+    let retry = vlite
+        .insert_idempotent(order(2, "pen", Some("req-1")))
+        .unwrap();
+
+    assert_eq!(retry, InsertOutcome::Duplicate);
+    assert_eq!(items(&vlite), vec!["book"]);
+}
+
+#[test]
+fn failed_insert_does_not_use_up_key() {
+    let vlite = setup_vlite(VectorXLiteConfig::default());
+
+    let failing = InsertPoint::builder()
+        .collection_name("orders")
+        .id(1)
+        .vector(vec![1.0, 0.0])
+        .payload_insert_query("insert into orders(rowid, missing_column) values (?1, 1)")
+        .idempotency_key("req-1")
+        .build()
+        .unwrap();
+    assert!(vlite.insert(failing).is_err());
+
+    let outcome = vlite
+        .insert_idempotent(order(1, "book", Some("req-1")))
+        .unwrap();
+
+    assert_eq!(outcome, InsertOutcome::Inserted);
+    assert_eq!(items(&vlite), vec!["book"]);
+}
+
+#[test]
+fn same_key_in_different_collections_is_applied_to_both() {
+    let vlite = setup_vlite(VectorXLiteConfig::default());
+    vlite
+        .create_collection(
+            CollectionConfigBuilder::default()
+                .collection_name("returns")
+                .distance(DistanceFunction::L2)
+                .vector_dimension(2)
+                .payload_table_schema("create table returns (rowid integer primary key, item text)")
+                .build()
+                .unwrap(),
+        )
+        .expect("collection should be created");
+    let returned = InsertPoint::builder()
+        .collection_name("returns")
+        .id(1)
+        .vector(vec![1.0, 0.0])
+        .payload_insert_query("insert into returns(rowid, item) values (?1, 'book')")
+        .idempotency_key("req-1")
+        .build()
+        .unwrap();
+
+    let first = vlite
+        .insert_idempotent(order(1, "book", Some("req-1")))
+        .unwrap();
+    let second = vlite.insert_idempotent(returned).unwrap();
+
+    assert_eq!(first, InsertOutcome::Inserted);
+    assert_eq!(second, InsertOutcome::Inserted);
+    assert_eq!(items(&vlite), vec!["book"]);
+    assert!(vlite.has_any("returns").unwrap());
+}
+
+#[test]
+fn expired_key_is_accepted_again() {
+    let vlite = setup_vlite(VectorXLiteConfig::default().with_idempotency_key_ttl(Duration::ZERO));
+
+    vlite.insert(order(1, "book", Some("req-1"))).unwrap();
+    let outcome = vlite
+        .insert_idempotent(order(2, "pen", Some("req-1")))
+        .unwrap();
+
+    assert_eq!(outcome, InsertOutcome::Inserted);
+    assert_eq!(items(&vlite), vec!["book", "pen"]);
+}
+
+#[test]
+fn inserts_without_key_are_not_deduplicated() {
+    let vlite = setup_vlite(VectorXLiteConfig::default());
+
+    assert_eq!(
+        vlite.insert_idempotent(order(1, "book", None)).unwrap(),
+        InsertOutcome::Inserted
+    );
+    assert!(vlite.insert(order(1, "book", None)).is_err());
+    assert_eq!(
+        vlite.insert_idempotent(order(2, "pen", None)).unwrap(),
+        InsertOutcome::Inserted
+    );
+}
+
+#[test]
+fn empty_key_is_rejected() {
+    let result = InsertPoint::builder()
+        .collection_name("orders")
+        .id(1)
+        .vector(vec![1.0, 0.0])
+        .idempotency_key("")
+        .build();
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,138 @@
+//! Tests for the include_vector option of SearchPoint
+//!
+//! These tests verify:
+//! - Each hit carries the vector it was inserted with, with and without a payload filter
+//! - The vector column combines with other options that wrap the search query
+//! - Searches without include_vector have no vector column
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+fn stored_vector(id: u64) -> Vec<f32> {
+    vec![id as f32, 0.1 * id as f32, -1.0 / id as f32]
+}
+
+fn setup_vlite() -> VectorXLite {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool).expect("create VectorXLite");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("docs")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(3)
+        .payload_table_schema("create table docs (rowid integer primary key, title text)")
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for id in 1..=4 {
+        let point = InsertPoint::builder()
+            .collection_name("docs")
+            .id(id)
+            .vector(stored_vector(id))
+            .payload_insert_query(format!(
+                "insert into docs(rowid, title) values (?1, 'doc {}')",
+                id
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+    vlite
+}
+
+fn assert_vectors_match_inserted(results: &[SearchResult]) {
+    for row in results {
+        let id: u64 = row["rowid"].parse().unwrap();
+        let vector = search_result_vector(row)
+            .unwrap()
+            .expect("hit should carry its vector");
+        assert_eq!(vector, stored_vector(id), "{:?}", row);
+    }
+}
+
+#[test]
+fn vectors_match_inserted_without_payload_filter() {
+    let vlite = setup_vlite();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("docs")
+        .vector(vec![0.0, 0.0, 0.0])
+        .top_k(4)
+        .include_vector(true)
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).unwrap();
+
+    assert_eq!(results.len(), 4);
+    assert_vectors_match_inserted(&results);
+}
+
+#[test]
+fn vectors_match_inserted_with_payload_filter() {
+    let vlite = setup_vlite();
+
+    for strategy in [FilterStrategy::Pushdown, FilterStrategy::PostFilter] {
+        let search_point = SearchPoint::builder()
+            .collection_name("docs")
+            .vector(vec![0.0, 0.0, 0.0])
+            .top_k(4)
+            .payload_search_query("select rowid, title from docs where rowid > 1")
+            .filter_strategy(strategy)
+            .include_vector(true)
+            .build()
+            .unwrap();
+        let results = vlite.search(search_point).unwrap();
+
+        assert_eq!(results.len(), 3, "{:?}", strategy);
+        assert_eq!(results[0]["title"], "doc 2");
+        assert_vectors_match_inserted(&results);
+    }
+}
+
+#[test]
+fn include_vector_combines_with_rerank_and_order_by() {
+    let vlite = setup_vlite();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("docs")
+        .vector(vec![0.0, 0.0, 0.0])
+        .top_k(4)
+        .payload_search_query("select rowid, title from docs")
+        .order_by("title", Direction::Desc)
+        .rerank_with(DistanceFunction::Cosine)
+        .include_vector(true)
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).unwrap();
+
+    assert_eq!(results[0]["title"], "doc 4");
+    assert!(results.iter().all(|row| row.contains_key("rerank_score")));
+    assert_vectors_match_inserted(&results);
+}
+
+#[test]
+fn search_without_include_vector_has_no_vector() {
+    let vlite = setup_vlite();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("docs")
+        .vector(vec![0.0, 0.0, 0.0])
+        .top_k(4)
+        .include_vector(false)
+        .build()
+        .unwrap();
+
+    for row in vlite.search(search_point).unwrap() {
+        assert!(!row.contains_key("vector"));
+        assert_eq!(search_result_vector(&row).unwrap(), None);
+    }
+}
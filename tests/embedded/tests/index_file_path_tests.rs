@@ -0,0 +1,84 @@
+//! Tests for the index file path pre-check of create_collection
+//!
+//! These tests verify:
+//! - A directory path fails with a clear IoError
+//! - A missing or unwritable parent directory fails with a clear IoError
+//! - A failed pre-check creates nothing
+//! - A writable path still creates the collection, leaving no probe file behind
+
+use std::fs;
+use vector_xlite::{error::VecXError, types::*, VectorXLite};
+
+const TEST_DIR: &str = "/tmp/vxlite_test_index_file_path";
+
+fn cleanup() {
+    let _ = fs::remove_dir_all(TEST_DIR);
+}
+
+fn setup_vlite() -> VectorXLite {
+    VectorXLite::builder()
+        .memory()
+        .build()
+        .expect("create VectorXLite")
+}
+
+fn notes_config(index_file_path: &str) -> CollectionConfig {
+    CollectionConfigBuilder::default()
+        .collection_name("notes")
+        .vector_dimension(2)
+        .index_file_path(index_file_path)
+        .build()
+        .unwrap()
+}
+
+fn create_error(vlite: &VectorXLite, index_file_path: &str) -> String {
+    match vlite.create_collection(notes_config(index_file_path)) {
+        Err(VecXError::IoError(message)) => message,
+        other => panic!("expected an IoError, got {:?}", other),
+    }
+}
+
+#[test]
+fn directory_or_missing_parent_fails_with_io_error() {
+    cleanup();
+    fs::create_dir_all(TEST_DIR).unwrap();
+    let vlite = setup_vlite();
+
+    let message = create_error(&vlite, TEST_DIR);
+    assert!(message.contains("is a directory"), "{}", message);
+
+    let missing = format!("{}/missing/notes.idx", TEST_DIR);
+    let message = create_error(&vlite, &missing);
+    assert!(message.contains("does not exist"), "{}", message);
+
+    assert!(!vlite.collection_exists("notes").unwrap());
+
+    let index_file_path = format!("{}/notes.idx", TEST_DIR);
+    vlite
+        .create_collection(notes_config(&index_file_path))
+        .expect("collection should be created");
+    assert!(vlite.collection_exists("notes").unwrap());
+
+    // The writability probe leaves nothing behind
+    let leftovers: Vec<_> = fs::read_dir(TEST_DIR)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .filter(|name| name != "notes.idx")
+        .collect();
+    assert!(leftovers.is_empty(), "{:?}", leftovers);
+
+    drop(vlite);
+    cleanup();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn unwritable_index_directory_fails_with_io_error() {
+    let vlite = setup_vlite();
+
+    // sysfs refuses new files even for root
+    let message = create_error(&vlite, "/sys/notes.idx");
+
+    assert!(message.contains("is not writable"), "{}", message);
+    assert!(!vlite.collection_exists("notes").unwrap());
+}
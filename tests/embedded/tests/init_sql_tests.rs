@@ -0,0 +1,80 @@
+//! Tests for SqliteConnectionCustomizer::with_init_sql
+//!
+//! These tests verify:
+//! - Init SQL runs on every acquired connection, e.g. enabling foreign keys
+//! - Statements run in order and can use the loaded vector extension
+//! - A failing statement fails connection acquisition with an error naming it
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::time::Duration;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, error::VecXError, VectorXLite};
+
+fn pool_with_init_sql(statements: &[&str]) -> Pool<SqliteConnectionManager> {
+    let customizer = SqliteConnectionCustomizer::new()
+        .with_init_sql(statements.iter().map(|sql| sql.to_string()).collect());
+
+    // build_unchecked: a failing statement leaves the pool without a connection
+    Pool::builder()
+        .max_size(2)
+        .connection_timeout(Duration::from_millis(300))
+        .connection_customizer(customizer)
+        .build_unchecked(SqliteConnectionManager::memory())
+}
+
+#[test]
+fn init_sql_enables_foreign_keys_on_every_connection() {
+    let pool = pool_with_init_sql(&["PRAGMA foreign_keys = ON"]);
+
+    let first = pool.get().expect("acquire connection");
+    let second = pool.get().expect("acquire connection");
+    for conn in [&first, &second] {
+        let foreign_keys: i64 = conn
+            .query_row("PRAGMA foreign_keys", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(foreign_keys, 1);
+    }
+
+    first
+        .execute_batch(
+            "create table parents (id integer primary key);
+             create table children (id integer primary key, parent_id integer references parents(id));",
+        )
+        .unwrap();
+    let result = first.execute("insert into children(id, parent_id) values (1, 42)", []);
+    assert!(result.is_err(), "foreign key should be enforced");
+}
+
+#[test]
+fn init_sql_runs_in_order_after_extension_load() {
+    let pool = pool_with_init_sql(&[
+        "create temp table settings (name text, value text)",
+        "insert into temp.settings values ('origin', vector_to_json(vector_from_json('[1,2]')))",
+    ]);
+
+    let value: String = pool
+        .get()
+        .expect("acquire connection")
+        .query_row("select value from temp.settings", [], |row| row.get(0))
+        .unwrap();
+
+    assert_eq!(value, "[1.0,2.0]");
+}
+
+#[test]
+fn failing_init_sql_fails_acquisition() {
+    let pool = pool_with_init_sql(&["PRAGMA foreign_keys = ON", "attach 'x' as"]);
+    let vlite = VectorXLite::new(pool).expect("create VectorXLite");
+
+    let err = vlite
+        .collection_exists("notes")
+        .expect_err("acquisition should fail");
+
+    assert!(matches!(err, VecXError::Other(_)), "{:?}", err);
+    assert!(
+        err.to_string()
+            .contains("connection init SQL `attach 'x' as` failed"),
+        "{}",
+        err
+    );
+}
@@ -0,0 +1,238 @@
+//! Tests for insert_batch method in VectorXLite
+//!
+//! These tests verify:
+//! - A batch without failures inserts every point
+//! - stop_on_error keeps the batch all-or-nothing
+//! - Best-effort batches commit the good points and report the failed ones by position
+//! - Ids repeated within a batch are rejected, or deduplicated keeping the last occurrence
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+fn setup_vlite() -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    vlite
+        .create_collection(
+            CollectionConfigBuilder::default()
+                .collection_name("items")
+                .distance(DistanceFunction::L2)
+                .vector_dimension(2)
+                .payload_table_schema("create table items (rowid integer primary key, name text)")
+                .build()
+                .unwrap(),
+        )
+        .expect("collection should be created");
+    (vlite, pool)
+}
+
+fn point(id: u64) -> InsertPoint {
+    named_point(id, &format!("item {}", id))
+}
+
+fn named_point(id: u64, name: &str) -> InsertPoint {
+    InsertPoint::builder()
+        .collection_name("items")
+        .id(id)
+        .vector(vec![id as f32, 0.0])
+        .payload_insert_query(format!(
+            "insert into items(rowid, name) values (?1, '{}')",
+            name
+        ))
+        .build()
+        .unwrap()
+}
+
+/// A point whose payload insert fails when executed.
+fn failing_point(id: u64) -> InsertPoint {
+    InsertPoint::builder()
+        .collection_name("items")
+        .id(id)
+        .vector(vec![id as f32, 0.0])
+        .payload_insert_query("insert into items(rowid, missing_column) values (?1, 'x')")
+        .build()
+        .unwrap()
+}
+
+/// Five points with a failing one in the middle.
+fn batch_with_failure() -> Vec<InsertPoint> {
+    vec![point(1), point(2), failing_point(3), point(4), point(5)]
+}
+
+fn payload_ids(pool: &Pool<SqliteConnectionManager>) -> Vec<i64> {
+    let conn = pool.get().unwrap();
+    let mut stmt = conn
+        .prepare("SELECT rowid FROM items ORDER BY rowid")
+        .unwrap();
+    stmt.query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+fn searchable_ids(vlite: &VectorXLite) -> Vec<i64> {
+    let search_point = SearchPoint::builder()
+        .collection_name("items")
+        .vector(vec![0.0, 0.0])
+        .top_k(10)
+        .payload_search_query("select rowid, name from items")
+        .build()
+        .unwrap();
+
+    vlite
+        .search(search_point)
+        .expect("search should succeed")
+        .iter()
+        .map(|row| row["rowid"].parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn insert_batch_inserts_every_point() {
+    let (vlite, pool) = setup_vlite();
+
+    let result = vlite
+        .insert_batch((1..=4).map(point).collect(), BatchOptions::default())
+        .expect("batch should succeed");
+
+    assert_eq!(result.succeeded, 4);
+    assert!(result.failed.is_empty());
+    assert_eq!(payload_ids(&pool), vec![1, 2, 3, 4]);
+    assert_eq!(searchable_ids(&vlite), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn stop_on_error_rolls_back_whole_batch() {
+    let (vlite, pool) = setup_vlite();
+
+    let err = vlite
+        .insert_batch(batch_with_failure(), BatchOptions::default())
+        .expect_err("batch should fail");
+
+    assert!(matches!(err, VecXError::SqlError(_)));
+    assert!(payload_ids(&pool).is_empty());
+    assert!(searchable_ids(&vlite).is_empty());
+}
+
+#[test]
+fn best_effort_commits_successful_points() {
+    let (vlite, pool) = setup_vlite();
+
+    let result = vlite
+        .insert_batch(
+            batch_with_failure(),
+            BatchOptions::default().with_stop_on_error(false),
+        )
+        .expect("best-effort batch should succeed");
+
+    assert_eq!(result.succeeded, 4);
+    assert_eq!(result.failed.len(), 1);
+    assert_eq!(result.failed[0].0, 2);
+    assert!(matches!(result.failed[0].1, VecXError::SqlError(_)));
+    assert_eq!(payload_ids(&pool), vec![1, 2, 4, 5]);
+    assert_eq!(searchable_ids(&vlite), vec![1, 2, 4, 5]);
+}
+
+#[test]
+fn best_effort_reports_planning_failures_in_batch_order() {
+    let (vlite, pool) = setup_vlite();
+    let empty_vector = InsertPoint::builder()
+        .collection_name("items")
+        .id(2)
+        .vector(vec![])
+        .build()
+        .unwrap();
+
+    let result = vlite
+        .insert_batch(
+            vec![point(1), empty_vector, failing_point(3), point(4)],
+            BatchOptions::default().with_stop_on_error(false),
+        )
+        .expect("best-effort batch should succeed");
+
+    assert_eq!(result.succeeded, 2);
+    let failed_positions: Vec<usize> = result.failed.iter().map(|(index, _)| *index).collect();
+    assert_eq!(failed_positions, vec![1, 2]);
+    assert!(matches!(
+        result.failed[0].1,
+        VecXError::InvalidQueryError(_)
+    ));
+    assert_eq!(payload_ids(&pool), vec![1, 4]);
+}
+
+#[test]
+fn duplicate_id_in_batch_is_rejected_by_default() {
+    let (vlite, pool) = setup_vlite();
+
+    for options in [
+        BatchOptions::default(),
+        BatchOptions::default().with_stop_on_error(false),
+    ] {
+        let err = vlite
+            .insert_batch(vec![point(1), point(2), point(1)], options)
+            .expect_err("batch with a duplicate id should be rejected");
+
+        assert!(matches!(err, VecXError::InvalidQueryError(_)), "{:?}", err);
+        assert!(err.to_string().contains("id 1"), "{}", err);
+    }
+    assert!(payload_ids(&pool).is_empty());
+    assert!(searchable_ids(&vlite).is_empty());
+}
+
+#[test]
+fn dedupe_ids_keeps_last_occurrence() {
+    let (vlite, pool) = setup_vlite();
+
+    let result = vlite
+        .insert_batch(
+            vec![
+                named_point(1, "first"),
+                point(2),
+                failing_point(1),
+                named_point(1, "last"),
+            ],
+            BatchOptions::default().with_dedupe_ids(true),
+        )
+        .expect("deduplicated batch should succeed");
+
+    assert_eq!(result.succeeded, 2);
+    assert!(result.failed.is_empty());
+    assert_eq!(payload_ids(&pool), vec![1, 2]);
+    assert_eq!(searchable_ids(&vlite), vec![1, 2]);
+    let name: String = pool
+        .get()
+        .unwrap()
+        .query_row("SELECT name FROM items WHERE rowid = 1", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(name, "last");
+}
+
+#[test]
+fn dedupe_ids_reports_failures_at_original_positions() {
+    let (vlite, pool) = setup_vlite();
+
+    let result = vlite
+        .insert_batch(
+            vec![point(1), point(1), point(2), failing_point(3)],
+            BatchOptions::default()
+                .with_dedupe_ids(true)
+                .with_stop_on_error(false),
+        )
+        .expect("best-effort batch should succeed");
+
+    assert_eq!(result.succeeded, 2);
+    assert_eq!(result.failed.len(), 1);
+    assert_eq!(result.failed[0].0, 3);
+    assert_eq!(payload_ids(&pool), vec![1, 2]);
+}
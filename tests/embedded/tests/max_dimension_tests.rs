@@ -0,0 +1,85 @@
+//! Tests for the max_allowed_dimension setting of VectorXLiteConfig
+//!
+//! These tests verify:
+//! - Collections above the default maximum of 4096 are rejected without side effects
+//! - Collections at or below the maximum are created
+//! - Raising or lowering the maximum through the config moves the guard
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+fn setup_vlite(config: VectorXLiteConfig) -> VectorXLite {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    VectorXLite::with_config(pool, config).expect("create VectorXLite")
+}
+
+fn embeddings_config(dimension: u16) -> CollectionConfig {
+    CollectionConfigBuilder::default()
+        .collection_name("embeddings")
+        .vector_dimension(dimension)
+        .max_elements(10)
+        .payload_table_schema("create table embeddings (rowid integer primary key, name text)")
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn dimension_above_default_maximum_is_rejected() {
+    let vlite = setup_vlite(VectorXLiteConfig::default());
+
+    let err = vlite
+        .create_collection(embeddings_config(4097))
+        .expect_err("dimension above 4096 should be rejected");
+
+    assert!(matches!(err, VecXError::InvalidQueryError(_)), "{:?}", err);
+    assert!(err.to_string().contains("4096"), "{}", err);
+    assert!(!vlite.collection_exists("embeddings").unwrap());
+}
+
+#[test]
+fn dimension_at_default_maximum_is_accepted() {
+    let vlite = setup_vlite(VectorXLiteConfig::default());
+
+    vlite
+        .create_collection(embeddings_config(4096))
+        .expect("collection should be created");
+
+    assert!(vlite.collection_exists("embeddings").unwrap());
+}
+
+#[test]
+fn raised_maximum_allows_larger_dimension() {
+    let vlite = setup_vlite(VectorXLiteConfig::default().with_max_allowed_dimension(8192));
+
+    vlite
+        .create_collection(embeddings_config(5000))
+        .expect("collection should be created");
+
+    assert!(vlite.collection_exists("embeddings").unwrap());
+}
+
+#[test]
+fn lowered_maximum_is_enforced_by_validate_and_create() {
+    let vlite = setup_vlite(VectorXLiteConfig::default().with_max_allowed_dimension(64));
+
+    assert!(matches!(
+        vlite.validate_collection(&embeddings_config(128)),
+        Err(VecXError::InvalidQueryError(_))
+    ));
+    assert!(matches!(
+        vlite.create_collection(embeddings_config(128)),
+        Err(VecXError::InvalidQueryError(_))
+    ));
+    vlite
+        .create_collection(embeddings_config(64))
+        .expect("collection should be created");
+}
@@ -0,0 +1,122 @@
+//! Tests for limiting the complexity of payload search queries
+//!
+//! These tests verify:
+//! - A payload query nested deeper than max_sql_complexity is rejected
+//! - A normal payload query passes under the same limit
+//! - The connection's expression depth limit is restored after the check
+//! - A non-positive limit is rejected by the builder
+
+use vector_xlite::{error::VecXError, types::*, VectorXLite};
+
+fn setup_vlite() -> VectorXLite {
+    let vlite = VectorXLite::builder()
+        .memory()
+        .build()
+        .expect("create VectorXLite");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("docs")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema("create table docs (rowid integer primary key, score integer)")
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for id in 1..=5 {
+        let point = InsertPoint::builder()
+            .collection_name("docs")
+            .id(id)
+            .vector(vec![id as f32, 0.0])
+            .payload_insert_query(format!(
+                "insert into docs(rowid, score) values (?1, {})",
+                id
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+    vlite
+}
+
+/// A filter whose expression tree is `depth` levels deep: `score + 1 + 1 + ...`.
+fn nested_query(depth: usize) -> String {
+    format!(
+        "select rowid, score from docs where score{} > 0",
+        " + 1".repeat(depth)
+    )
+}
+
+fn search(payload_search_query: &str, max_depth: i32) -> SearchPoint {
+    SearchPoint::builder()
+        .collection_name("docs")
+        .vector(vec![0.0, 0.0])
+        .top_k(3)
+        .payload_search_query(payload_search_query)
+        .max_sql_complexity(max_depth)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn deeply_nested_payload_query_is_rejected() {
+    let vlite = setup_vlite();
+
+    let err = vlite
+        .search(search(&nested_query(200), 50))
+        .expect_err("search should be rejected");
+
+    assert!(matches!(err, VecXError::InvalidQueryError(_)), "{}", err);
+    assert!(err.to_string().contains("max_sql_complexity"), "{}", err);
+}
+
+#[test]
+fn normal_payload_query_passes() {
+    let vlite = setup_vlite();
+
+    let results = vlite
+        .search(search("select rowid, score from docs where score > 1", 50))
+        .expect("search should succeed");
+    assert_eq!(results.len(), 3);
+
+    let results = vlite
+        .search(search(&nested_query(20), 50))
+        .expect("search within the limit should succeed");
+    assert_eq!(results.len(), 3);
+}
+
+#[test]
+fn limit_is_restored_after_the_check() {
+    let vlite = setup_vlite();
+
+    vlite
+        .search(search(&nested_query(200), 50))
+        .expect_err("search should be rejected");
+
+    let unlimited = SearchPoint::builder()
+        .collection_name("docs")
+        .vector(vec![0.0, 0.0])
+        .top_k(3)
+        .payload_search_query(nested_query(200))
+        .build()
+        .unwrap();
+    let results = vlite
+        .search(unlimited)
+        .expect("search without a limit should succeed");
+    assert_eq!(results.len(), 3);
+}
+
+#[test]
+fn non_positive_limit_is_rejected() {
+    let result = SearchPoint::builder()
+        .collection_name("docs")
+        .vector(vec![0.0, 0.0])
+        .max_sql_complexity(0)
+        .build();
+
+    assert_eq!(
+        result.unwrap_err(),
+        "max_sql_complexity must be greater than 0."
+    );
+}
@@ -0,0 +1,157 @@
+//! Tests for the min_similarity option of SearchPoint
+//!
+//! These tests verify:
+//! - Only vectors within the similarity threshold are returned
+//! - The threshold applies with every payload filter strategy
+//! - Non-cosine collections and out-of-range similarities are rejected
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::collections::HashMap;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+/// Angles in degrees from the x axis, keyed by id. cos(20°) ≈ 0.94 and
+/// cos(30°) ≈ 0.87, so a 0.9 threshold keeps ids 1..=3.
+const ANGLES: [(u64, f32); 6] = [
+    (1, 0.0),
+    (2, 10.0),
+    (3, 20.0),
+    (4, 30.0),
+    (5, 60.0),
+    (6, 90.0),
+];
+
+fn setup_vlite(distance: DistanceFunction) -> VectorXLite {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool).expect("create VectorXLite");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("arrows")
+        .distance(distance)
+        .vector_dimension(2)
+        .payload_table_schema("create table arrows (rowid integer primary key, label text)")
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for (id, degrees) in ANGLES {
+        let radians = degrees.to_radians();
+        let point = InsertPoint::builder()
+            .collection_name("arrows")
+            .id(id)
+            .vector(vec![radians.cos(), radians.sin()])
+            .payload_insert_query(format!(
+                "insert into arrows(rowid, label) values (?1, 'deg{}')",
+                degrees
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+    vlite
+}
+
+fn rowids(results: &[HashMap<String, String>]) -> Vec<i64> {
+    results
+        .iter()
+        .map(|row| row["rowid"].parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn min_similarity_keeps_near_parallel_vectors() {
+    let vlite = setup_vlite(DistanceFunction::Cosine);
+
+    let search_point = SearchPoint::builder()
+        .collection_name("arrows")
+        .vector(vec![1.0, 0.0])
+        .top_k(6)
+        .min_similarity(0.9)
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).unwrap();
+
+    assert_eq!(rowids(&results), vec![1, 2, 3]);
+    for row in &results {
+        assert!(row["distance"].parse::<f32>().unwrap() <= 0.1 + f32::EPSILON);
+    }
+}
+
+#[test]
+fn min_similarity_applies_with_every_filter_strategy() {
+    let vlite = setup_vlite(DistanceFunction::Cosine);
+
+    for strategy in [
+        FilterStrategy::Auto,
+        FilterStrategy::Pushdown,
+        FilterStrategy::PostFilter,
+    ] {
+        let search_point = SearchPoint::builder()
+            .collection_name("arrows")
+            .vector(vec![1.0, 0.0])
+            .top_k(6)
+            .payload_search_query("select rowid, label from arrows where rowid != 2")
+            .filter_strategy(strategy)
+            .min_similarity(0.9)
+            .build()
+            .unwrap();
+        let results = vlite.search(search_point).unwrap();
+
+        assert_eq!(rowids(&results), vec![1, 3], "{:?}", strategy);
+        assert_eq!(results[1]["label"], "deg20");
+    }
+}
+
+#[test]
+fn top_k_still_caps_results_above_threshold() {
+    let vlite = setup_vlite(DistanceFunction::Cosine);
+
+    let search_point = SearchPoint::builder()
+        .collection_name("arrows")
+        .vector(vec![1.0, 0.0])
+        .top_k(2)
+        .min_similarity(0.0)
+        .build()
+        .unwrap();
+
+    assert_eq!(rowids(&vlite.search(search_point).unwrap()), vec![1, 2]);
+}
+
+#[test]
+fn min_similarity_on_non_cosine_collection_fails() {
+    for distance in [DistanceFunction::L2, DistanceFunction::IP] {
+        let vlite = setup_vlite(distance);
+
+        let search_point = SearchPoint::builder()
+            .collection_name("arrows")
+            .vector(vec![1.0, 0.0])
+            .min_similarity(0.9)
+            .build()
+            .unwrap();
+
+        let err = vlite
+            .search(search_point)
+            .expect_err("non-cosine collection should be rejected");
+        assert!(matches!(err, VecXError::InvalidQueryError(_)));
+    }
+}
+
+#[test]
+fn out_of_range_similarity_is_rejected() {
+    let result = SearchPoint::builder()
+        .collection_name("arrows")
+        .vector(vec![1.0, 0.0])
+        .min_similarity(1.5)
+        .build();
+
+    assert!(result.is_err());
+}
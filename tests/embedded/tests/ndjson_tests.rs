@@ -0,0 +1,112 @@
+//! Tests for search_to_ndjson method in VectorXLite
+//!
+//! These tests verify:
+//! - Each result is written as one JSON object per line, in search order
+//! - Values keep their SQL types and blobs are base64-encoded
+//! - The returned count matches the number of lines written
+
+use serde_json::{json, Map, Value};
+use vector_xlite::{types::*, VectorXLite};
+
+fn setup_vlite() -> VectorXLite {
+    let vlite = VectorXLite::builder()
+        .memory()
+        .build()
+        .expect("build in-memory instance");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("docs")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema(
+            "create table docs (rowid integer primary key, title text, score real, thumb blob)",
+        )
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for id in 1..=4u64 {
+        let point = InsertPoint::builder()
+            .collection_name("docs")
+            .id(id)
+            .vector(vec![id as f32, 0.0])
+            .payload_insert_query(format!(
+                "insert into docs(rowid, title, score, thumb) values (?1, 'doc \"{}\"', {}.5, x'0102ff')",
+                id, id
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+    vlite
+}
+
+fn parse_lines(buffer: &[u8]) -> Vec<Map<String, Value>> {
+    String::from_utf8(buffer.to_vec())
+        .unwrap()
+        .lines()
+        .map(
+            |line| match serde_json::from_str(line).expect("line should be JSON") {
+                Value::Object(map) => map,
+                other => panic!("expected a JSON object, got {}", other),
+            },
+        )
+        .collect()
+}
+
+#[test]
+fn search_to_ndjson_writes_one_typed_object_per_result() {
+    let vlite = setup_vlite();
+    let search_point = SearchPoint::builder()
+        .collection_name("docs")
+        .vector(vec![4.0, 0.0])
+        .top_k(3)
+        .payload_search_query("select rowid, title, score, thumb from docs")
+        .build()
+        .unwrap();
+
+    let mut buffer = Vec::new();
+    let written = vlite
+        .search_to_ndjson(search_point, &mut buffer)
+        .expect("search_to_ndjson should succeed");
+    let rows = parse_lines(&buffer);
+
+    assert_eq!(written, 3);
+    assert_eq!(rows.len(), 3);
+    let rowids: Vec<i64> = rows
+        .iter()
+        .map(|row| row["rowid"].as_i64().unwrap())
+        .collect();
+    assert_eq!(rowids, vec![4, 3, 2]);
+
+    let first = &rows[0];
+    assert_eq!(first["title"], json!("doc \"4\""));
+    assert_eq!(first["score"], json!(4.5));
+    assert_eq!(first["thumb"], json!("AQL/"));
+    assert_eq!(first["distance"].as_f64(), Some(0.0));
+    assert!(rows[1]["distance"].as_f64().unwrap() > 0.0);
+}
+
+#[test]
+fn search_to_ndjson_matches_search_results() {
+    let vlite = setup_vlite();
+    let search_point = || {
+        SearchPoint::builder()
+            .collection_name("docs")
+            .vector(vec![1.0, 0.0])
+            .top_k(4)
+            .build()
+            .unwrap()
+    };
+
+    let mut buffer = Vec::new();
+    let written = vlite.search_to_ndjson(search_point(), &mut buffer).unwrap();
+    let rows = parse_lines(&buffer);
+    let expected = vlite.search(search_point()).unwrap();
+
+    assert_eq!(written, expected.len());
+    for (row, expected) in rows.iter().zip(&expected) {
+        assert_eq!(row["rowid"].to_string(), expected["rowid"]);
+    }
+}
@@ -0,0 +1,174 @@
+//! Tests for collections created with no_payload
+//!
+//! These tests verify:
+//! - A no-payload collection has a vector table but no payload table
+//! - Vectors are inserted, upserted, deleted and searched without a payload table
+//! - Inserts with a payload query and searches with a payload query are rejected
+//! - Operations that need the payload table fail with a clear error
+//! - No-payload collections can be deleted
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+fn setup_vlite() -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("embeddings")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .no_payload(true)
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for id in 1..=5u64 {
+        let point = InsertPoint::builder()
+            .collection_name("embeddings")
+            .id(id)
+            .vector(vec![id as f32, 0.0])
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+    (vlite, pool)
+}
+
+fn search(vlite: &VectorXLite, x: f32, top_k: i64) -> Vec<String> {
+    let search_point = SearchPoint::builder()
+        .collection_name("embeddings")
+        .vector(vec![x, 0.0])
+        .top_k(top_k)
+        .build()
+        .unwrap();
+    vlite
+        .search(search_point)
+        .expect("search should succeed")
+        .iter()
+        .map(|row| row["rowid"].clone())
+        .collect()
+}
+
+fn assert_no_payload_error<T: std::fmt::Debug>(result: Result<T, VecXError>) {
+    match result {
+        Err(VecXError::InvalidQueryError(message)) => {
+            assert!(message.contains("no payload table"), "{}", message)
+        }
+        other => panic!("expected a no-payload error, got {:?}", other),
+    }
+}
+
+#[test]
+fn no_payload_collection_has_no_payload_table() {
+    let (vlite, pool) = setup_vlite();
+
+    let tables: Vec<String> = pool
+        .get()
+        .unwrap()
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name LIKE '%embeddings'")
+        .unwrap()
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(tables, vec!["vt_vector_embeddings"]);
+    assert!(vlite.collection_exists("embeddings").unwrap());
+}
+
+#[test]
+fn vector_search_works_without_payload_table() {
+    let (vlite, _pool) = setup_vlite();
+
+    assert_eq!(search(&vlite, 5.0, 3), vec!["5", "4", "3"]);
+}
+
+#[test]
+fn upsert_and_delete_work_without_payload_table() {
+    let (vlite, _pool) = setup_vlite();
+
+    let point = InsertPoint::builder()
+        .collection_name("embeddings")
+        .id(1)
+        .vector(vec![10.0, 0.0])
+        .build()
+        .unwrap();
+    vlite
+        .upsert_batch(vec![point])
+        .expect("upsert should succeed");
+    assert_eq!(search(&vlite, 10.0, 1), vec!["1"]);
+
+    let delete_point = DeletePoint::builder()
+        .collection_name("embeddings")
+        .id(1)
+        .build()
+        .unwrap();
+    vlite.delete(delete_point).expect("delete should succeed");
+    assert_eq!(search(&vlite, 10.0, 1), vec!["5"]);
+}
+
+#[test]
+fn insert_with_payload_query_fails() {
+    let (vlite, _pool) = setup_vlite();
+
+    let point = InsertPoint::builder()
+        .collection_name("embeddings")
+        .id(6)
+        .vector(vec![6.0, 0.0])
+        .payload_insert_query("insert into embeddings(rowid) values (?1)")
+        .build()
+        .unwrap();
+
+    assert_no_payload_error(vlite.insert(point));
+    assert_eq!(search(&vlite, 6.0, 1), vec!["5"]);
+}
+
+#[test]
+fn search_with_payload_query_fails() {
+    let (vlite, _pool) = setup_vlite();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("embeddings")
+        .vector(vec![1.0, 0.0])
+        .payload_search_query("select rowid from embeddings")
+        .build()
+        .unwrap();
+
+    assert_no_payload_error(vlite.search(search_point));
+}
+
+#[test]
+fn operations_needing_payload_table_fail() {
+    let (vlite, _pool) = setup_vlite();
+
+    assert_no_payload_error(vlite.count_where("embeddings", "1 = 1"));
+    assert_no_payload_error(vlite.has_any("embeddings"));
+    assert_no_payload_error(vlite.rename_collection("embeddings", "vectors"));
+    assert_no_payload_error(vlite.update_where("embeddings", "label = 1", "1 = 1"));
+}
+
+#[test]
+fn no_payload_collection_can_be_deleted() {
+    let (vlite, _pool) = setup_vlite();
+
+    vlite
+        .delete_collection(
+            DeleteCollection::builder()
+                .collection_name("embeddings")
+                .build()
+                .unwrap(),
+        )
+        .expect("delete collection should succeed");
+    assert!(!vlite.collection_exists("embeddings").unwrap());
+}
@@ -0,0 +1,142 @@
+//! Tests for ordering search results by a payload column
+//!
+//! These tests verify:
+//! - The nearest top_k neighbours are re-sorted by the payload column
+//! - Ordering never changes which neighbours are returned
+//! - Invalid column names are rejected by the builder
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+fn setup_vlite() -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(5)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    (vlite, pool)
+}
+
+/// Point `i` sits at distance `i` from the origin and was created at `(i * 7) % 20`,
+/// so distance order and recency order disagree.
+fn create_events_collection(vlite: &VectorXLite) {
+    let config = CollectionConfigBuilder::default()
+        .collection_name("events")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema(
+            "create table events (rowid integer primary key, created_at integer)",
+        )
+        .build()
+        .unwrap();
+
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for i in 1..=20u64 {
+        let point = InsertPoint::builder()
+            .collection_name("events")
+            .id(i)
+            .vector(vec![i as f32, 0.0])
+            .payload_insert_query(format!(
+                "insert into events(rowid, created_at) values (?1, {})",
+                (i * 7) % 20
+            ))
+            .build()
+            .expect("Builder should create insert point.");
+
+        vlite.insert(point).expect("insert should be successful.");
+    }
+}
+
+fn column(results: &[std::collections::HashMap<String, String>], name: &str) -> Vec<i64> {
+    results
+        .iter()
+        .map(|row| row[name].parse::<i64>().unwrap())
+        .collect()
+}
+
+#[test]
+fn order_by_timestamp_desc_resorts_nearest_neighbours() {
+    let (vlite, _) = setup_vlite();
+    create_events_collection(&vlite);
+
+    let search_point = SearchPoint::builder()
+        .collection_name("events")
+        .vector(vec![0.0, 0.0])
+        .top_k(5)
+        .payload_search_query("select rowid, created_at from events")
+        .order_by("created_at", Direction::Desc)
+        .build()
+        .unwrap();
+
+    let results = vlite.search(search_point).unwrap();
+
+    let mut rowids = column(&results, "rowid");
+    rowids.sort();
+    assert_eq!(rowids, vec![1, 2, 3, 4, 5]);
+
+    let timestamps = column(&results, "created_at");
+    assert_eq!(timestamps, vec![15, 14, 8, 7, 1]);
+}
+
+#[test]
+fn order_by_asc_matches_unordered_result_set() {
+    let (vlite, _) = setup_vlite();
+    create_events_collection(&vlite);
+
+    let builder = || {
+        SearchPoint::builder()
+            .collection_name("events")
+            .vector(vec![10.0, 0.0])
+            .top_k(4)
+            .payload_search_query("select rowid, created_at from events")
+    };
+
+    let unordered = vlite.search(builder().build().unwrap()).unwrap();
+    let ordered = vlite
+        .search(builder().order_by("created_at", Direction::Asc).build().unwrap())
+        .unwrap();
+
+    let mut expected = column(&unordered, "created_at");
+    expected.sort();
+    assert_eq!(column(&ordered, "created_at"), expected);
+    assert!(ordered.iter().all(|row| row.contains_key("distance")));
+}
+
+#[test]
+fn order_by_without_payload_query_sorts_by_rowid() {
+    let (vlite, _) = setup_vlite();
+    create_events_collection(&vlite);
+
+    let search_point = SearchPoint::builder()
+        .collection_name("events")
+        .vector(vec![20.0, 0.0])
+        .top_k(3)
+        .order_by("rowid", Direction::Asc)
+        .build()
+        .unwrap();
+
+    let results = vlite.search(search_point).unwrap();
+
+    assert_eq!(column(&results, "rowid"), vec![18, 19, 20]);
+}
+
+#[test]
+fn order_by_rejects_non_identifier_column() {
+    let result = SearchPoint::builder()
+        .collection_name("events")
+        .vector(vec![0.0, 0.0])
+        .order_by("created_at; DROP TABLE events", Direction::Desc)
+        .build();
+
+    assert_eq!(
+        result.unwrap_err(),
+        "order_by column must be a plain column name."
+    );
+}
@@ -0,0 +1,261 @@
+//! Tests for create_payload_index, list_payload_indexes and drop_payload_index methods
+//! in VectorXLite
+//!
+//! These tests verify:
+//! - Filtered searches use a created payload index
+//! - Existing indexes are kept or rejected according to `OnConflict`
+//! - Created indexes are listed with their columns
+//! - Dropping the index returns searches to a table scan
+//! - Payload indexes survive reopening a file database
+//! - Invalid columns and unknown collections are rejected
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::fs;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+fn create_vlite(manager: SqliteConnectionManager) -> VectorXLite {
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    VectorXLite::new(pool).expect("create VectorXLite")
+}
+
+fn create_products_collection(vlite: &VectorXLite) {
+    let config = CollectionConfigBuilder::default()
+        .collection_name("products")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema(
+            "create table products (rowid integer primary key, category text, price real)",
+        )
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for id in 1..=6u64 {
+        let category = if id % 2 == 0 { "books" } else { "games" };
+        let point = InsertPoint::builder()
+            .collection_name("products")
+            .id(id)
+            .vector(vec![id as f32, 0.0])
+            .payload_insert_query(format!(
+                "insert into products(rowid, category, price) values (?1, '{}', {})",
+                category, id
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+}
+
+fn books_search() -> SearchPoint {
+    SearchPoint::builder()
+        .collection_name("products")
+        .vector(vec![0.0, 0.0])
+        .top_k(6)
+        .payload_search_query("select rowid, category from products where category = 'books'")
+        .build()
+        .unwrap()
+}
+
+fn uses_category_index(vlite: &VectorXLite) -> bool {
+    vlite
+        .explain_query_plan(&books_search())
+        .unwrap()
+        .iter()
+        .any(|line| line.contains("INDEX vx_idx_products_category"))
+}
+
+#[test]
+fn filtered_search_uses_payload_index() {
+    let vlite = create_vlite(SqliteConnectionManager::memory());
+    create_products_collection(&vlite);
+    assert!(!uses_category_index(&vlite));
+
+    vlite
+        .create_payload_index("products", &["category"])
+        .expect("index should be created");
+
+    assert!(uses_category_index(&vlite));
+    assert_eq!(vlite.search(books_search()).unwrap().len(), 3);
+}
+
+#[test]
+fn create_payload_index_twice_is_a_no_op() {
+    let vlite = create_vlite(SqliteConnectionManager::memory());
+    create_products_collection(&vlite);
+
+    vlite
+        .create_payload_index("products", &["category"])
+        .unwrap();
+    vlite
+        .create_payload_index("products", &["category"])
+        .expect("existing index should be kept");
+
+    assert!(uses_category_index(&vlite));
+}
+
+#[test]
+fn create_payload_index_with_error_on_conflict_rejects_existing_index() {
+    let vlite = create_vlite(SqliteConnectionManager::memory());
+    create_products_collection(&vlite);
+
+    vlite
+        .create_payload_index_with("products", &["category"], OnConflict::Error)
+        .expect("new index should be created");
+    vlite
+        .create_payload_index_with("products", &["category"], OnConflict::Ignore)
+        .expect("existing index should be kept");
+
+    let err = vlite
+        .create_payload_index_with("products", &["category"], OnConflict::Error)
+        .expect_err("existing index should be rejected");
+
+    assert!(matches!(err, VecXError::InvalidQueryError(_)));
+    assert!(err.to_string().contains("vx_idx_products_category"), "{}", err);
+    assert!(uses_category_index(&vlite));
+}
+
+#[test]
+fn list_payload_indexes_returns_created_indexes() {
+    let vlite = create_vlite(SqliteConnectionManager::memory());
+    create_products_collection(&vlite);
+    assert!(vlite.list_payload_indexes("products").unwrap().is_empty());
+
+    vlite
+        .create_payload_index("products", &["price"])
+        .unwrap();
+    vlite
+        .create_payload_index("products", &["category", "price"])
+        .unwrap();
+
+    let indexes = vlite.list_payload_indexes("products").unwrap();
+    assert_eq!(
+        indexes,
+        vec![
+            PayloadIndex {
+                name: "vx_idx_products_category_price".to_string(),
+                columns: vec!["category".to_string(), "price".to_string()],
+            },
+            PayloadIndex {
+                name: "vx_idx_products_price".to_string(),
+                columns: vec!["price".to_string()],
+            },
+        ]
+    );
+
+    let columns: Vec<&str> = indexes[1].columns.iter().map(String::as_str).collect();
+    vlite.drop_payload_index("products", &columns).unwrap();
+    assert_eq!(vlite.list_payload_indexes("products").unwrap().len(), 1);
+}
+
+#[test]
+fn drop_payload_index_removes_index() {
+    let vlite = create_vlite(SqliteConnectionManager::memory());
+    create_products_collection(&vlite);
+    vlite
+        .create_payload_index("products", &["category"])
+        .unwrap();
+
+    vlite
+        .drop_payload_index("products", &["category"])
+        .expect("index should be dropped");
+
+    assert!(!uses_category_index(&vlite));
+    vlite
+        .drop_payload_index("products", &["category"])
+        .expect("dropping a missing index should succeed");
+}
+
+#[test]
+fn multi_column_index_is_used() {
+    let vlite = create_vlite(SqliteConnectionManager::memory());
+    create_products_collection(&vlite);
+
+    vlite
+        .create_payload_index("products", &["category", "price"])
+        .unwrap();
+
+    let plan = vlite
+        .explain_query_plan(&books_search())
+        .unwrap()
+        .join("\n");
+    assert!(
+        plan.contains("INDEX vx_idx_products_category_price"),
+        "{}",
+        plan
+    );
+}
+
+#[test]
+fn payload_index_survives_file_db_reopen() {
+    let db_path = "/tmp/vxlite_test_payload_index.db";
+    let idx_path = "/tmp/vxlite_test_payload_index.idx";
+    let cleanup = || {
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = fs::remove_file(format!("{}{}", db_path, suffix));
+        }
+        let _ = fs::remove_file(idx_path);
+    };
+    cleanup();
+
+    {
+        let vlite = create_vlite(SqliteConnectionManager::file(db_path));
+        let config = CollectionConfigBuilder::default()
+            .collection_name("products")
+            .distance(DistanceFunction::L2)
+            .vector_dimension(2)
+            .index_file_path(idx_path)
+            .payload_table_schema(
+                "create table products (rowid integer primary key, category text, price real)",
+            )
+            .build()
+            .unwrap();
+        vlite.create_collection(config).unwrap();
+        vlite
+            .create_payload_index("products", &["category"])
+            .unwrap();
+    }
+
+    let reopened = create_vlite(SqliteConnectionManager::file(db_path));
+    assert!(uses_category_index(&reopened));
+
+    drop(reopened);
+    cleanup();
+}
+
+#[test]
+fn invalid_column_name_is_rejected() {
+    let vlite = create_vlite(SqliteConnectionManager::memory());
+    create_products_collection(&vlite);
+
+    let err = vlite
+        .create_payload_index("products", &["category); drop table products; --"])
+        .expect_err("column should be rejected");
+
+    assert!(matches!(err, VecXError::InvalidQueryError(_)));
+    assert!(vlite.create_payload_index("products", &[]).is_err());
+}
+
+#[test]
+fn unknown_collection_is_rejected() {
+    let vlite = create_vlite(SqliteConnectionManager::memory());
+
+    let err = vlite
+        .create_payload_index("missing", &["category"])
+        .expect_err("collection should be rejected");
+
+    assert!(matches!(err, VecXError::InvalidQueryError(_)));
+    assert!(matches!(
+        vlite.list_payload_indexes("missing"),
+        Err(VecXError::InvalidQueryError(_))
+    ));
+}
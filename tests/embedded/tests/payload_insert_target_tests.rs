@@ -0,0 +1,130 @@
+//! Tests for validating the target table of payload insert queries
+//!
+//! These tests verify:
+//! - Payload queries writing to another table are rejected before anything is written
+//! - Queries targeting the collection's payload table succeed, quoted or not
+//! - Prefixed payload tables must be targeted by their prefixed name
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+fn setup_vlite(config: VectorXLiteConfig) -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::with_config(pool.clone(), config).expect("create VectorXLite");
+    vlite
+        .create_collection(
+            CollectionConfigBuilder::default()
+                .collection_name("person")
+                .distance(DistanceFunction::L2)
+                .vector_dimension(2)
+                .payload_table_schema("create table person (rowid integer primary key, name text)")
+                .build()
+                .unwrap(),
+        )
+        .expect("collection should be created");
+
+    pool.get()
+        .unwrap()
+        .execute_batch("create table audit_log (rowid integer primary key, name text)")
+        .unwrap();
+    (vlite, pool)
+}
+
+fn person_point(id: u64, payload_insert_query: &str) -> InsertPoint {
+    InsertPoint::builder()
+        .collection_name("person")
+        .id(id)
+        .vector(vec![id as f32, 0.0])
+        .payload_insert_query(payload_insert_query)
+        .build()
+        .unwrap()
+}
+
+fn row_count(pool: &Pool<SqliteConnectionManager>, table: &str) -> i64 {
+    pool.get()
+        .unwrap()
+        .query_row(&format!("select count(*) from {}", table), [], |row| {
+            row.get(0)
+        })
+        .unwrap()
+}
+
+#[test]
+fn payload_query_targeting_foreign_table_is_rejected() {
+    let (vlite, pool) = setup_vlite(VectorXLiteConfig::default());
+
+    let err = vlite
+        .insert(person_point(
+            1,
+            "insert into audit_log(rowid, name) values (?1, 'Mallory')",
+        ))
+        .expect_err("foreign table should be rejected");
+
+    assert!(matches!(err, VecXError::InvalidQueryError(_)));
+    assert!(err.to_string().contains("'audit_log'"), "{}", err);
+    assert_eq!(row_count(&pool, "audit_log"), 0);
+    assert_eq!(vlite.count_where("person", "1 = 1").unwrap(), 0);
+}
+
+#[test]
+fn payload_query_targeting_collection_table_succeeds() {
+    let (vlite, pool) = setup_vlite(VectorXLiteConfig::default());
+
+    vlite
+        .insert(person_point(
+            1,
+            "insert into person(rowid, name) values (?1, 'Alice')",
+        ))
+        .expect("insert should be successful.");
+    vlite
+        .insert(person_point(
+            2,
+            "INSERT INTO \"Person\" (rowid, name) VALUES (?1, 'Bob')",
+        ))
+        .expect("quoted and differently cased target should be accepted");
+
+    assert_eq!(row_count(&pool, "person"), 2);
+}
+
+#[test]
+fn non_insert_payload_query_is_rejected() {
+    let (vlite, pool) = setup_vlite(VectorXLiteConfig::default());
+
+    let err = vlite
+        .insert(person_point(1, "delete from audit_log"))
+        .expect_err("non-insert query should be rejected");
+
+    assert!(matches!(err, VecXError::InvalidQueryError(_)));
+    assert_eq!(row_count(&pool, "person"), 0);
+}
+
+#[test]
+fn prefixed_payload_table_must_be_targeted_by_prefixed_name() {
+    let (vlite, pool) =
+        setup_vlite(VectorXLiteConfig::default().with_prefixed_payload_tables(true));
+
+    let err = vlite
+        .insert(person_point(
+            1,
+            "insert into person(rowid, name) values (?1, 'Alice')",
+        ))
+        .expect_err("unprefixed table should be rejected");
+    assert!(matches!(err, VecXError::InvalidQueryError(_)));
+
+    vlite
+        .insert(person_point(
+            1,
+            "insert into pt_person(rowid, name) values (?1, 'Alice')",
+        ))
+        .expect("insert should be successful.");
+    assert_eq!(row_count(&pool, "pt_person"), 1);
+}
@@ -0,0 +1,205 @@
+//! Tests for payload-only collections
+//!
+//! These tests verify:
+//! - A payload-only collection has a payload table but no vector table
+//! - Points are inserted, deleted and searched by payload filters alone
+//! - Inserting or searching with a vector fails with a clear error
+//! - Payload-only collections can be renamed and deleted
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+fn setup_vlite() -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("events")
+        .payload_table_schema(
+            "create table events (rowid integer primary key, kind text, severity integer)",
+        )
+        .payload_only(true)
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for (id, kind, severity) in [
+        (1, "login", 1),
+        (2, "error", 5),
+        (3, "error", 3),
+        (4, "logout", 1),
+    ] {
+        let point = InsertPoint::builder()
+            .collection_name("events")
+            .id(id)
+            .vector(vec![])
+            .payload_insert_query(format!(
+                "insert into events(rowid, kind, severity) values (?1, '{}', {})",
+                kind, severity
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+    (vlite, pool)
+}
+
+fn filter_only_search(payload_search_query: &str) -> SearchPointBuilder {
+    SearchPoint::builder()
+        .collection_name("events")
+        .vector(vec![])
+        .payload_search_query(payload_search_query)
+}
+
+fn rowids(results: &[std::collections::HashMap<String, String>]) -> Vec<&str> {
+    results.iter().map(|row| row["rowid"].as_str()).collect()
+}
+
+#[test]
+fn payload_only_collection_has_no_vector_table() {
+    let (vlite, pool) = setup_vlite();
+
+    let vector_tables: i64 = pool
+        .get()
+        .unwrap()
+        .query_row(
+            "SELECT count(*) FROM sqlite_master WHERE name LIKE 'vt_%events'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+
+    assert_eq!(vector_tables, 0);
+    assert!(vlite.collection_exists("events").unwrap());
+    assert_eq!(vlite.count_where("events", "1 = 1").unwrap(), 4);
+}
+
+#[test]
+fn filter_only_search_runs_payload_query() {
+    let (vlite, _pool) = setup_vlite();
+
+    let search_point = filter_only_search("select rowid, kind from events where kind = 'error'")
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).expect("search should succeed");
+
+    assert_eq!(rowids(&results), vec!["2", "3"]);
+    assert!(results.iter().all(|row| row["kind"] == "error"));
+    assert!(results.iter().all(|row| !row.contains_key("distance")));
+}
+
+#[test]
+fn filter_only_search_honors_top_k_and_order_by() {
+    let (vlite, _pool) = setup_vlite();
+
+    let search_point = filter_only_search("select rowid, severity from events")
+        .order_by("severity", Direction::Desc)
+        .top_k(3)
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).expect("search should succeed");
+
+    assert_eq!(rowids(&results), vec!["2", "3", "1"]);
+}
+
+#[test]
+fn filter_only_search_without_payload_query_scans_collection() {
+    let (vlite, _pool) = setup_vlite();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("events")
+        .vector(vec![])
+        .restrict_to_ids(vec![1, 4])
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).expect("search should succeed");
+
+    assert_eq!(rowids(&results), vec!["1", "4"]);
+}
+
+#[test]
+fn vector_search_of_payload_only_collection_fails() {
+    let (vlite, _pool) = setup_vlite();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("events")
+        .vector(vec![1.0, 0.0, 0.0])
+        .build()
+        .unwrap();
+    let err = vlite
+        .search(search_point)
+        .expect_err("vector search should fail");
+
+    assert!(matches!(err, VecXError::InvalidQueryError(_)));
+    assert!(err.to_string().contains("payload-only"), "{}", err);
+}
+
+#[test]
+fn insert_with_vector_into_payload_only_collection_fails() {
+    let (vlite, _pool) = setup_vlite();
+
+    let point = InsertPoint::builder()
+        .collection_name("events")
+        .id(5)
+        .vector(vec![1.0, 0.0, 0.0])
+        .build()
+        .unwrap();
+    let err = vlite
+        .insert(point)
+        .expect_err("insert with a vector should fail");
+
+    assert!(matches!(err, VecXError::InvalidQueryError(_)));
+    assert_eq!(vlite.count_where("events", "1 = 1").unwrap(), 4);
+}
+
+#[test]
+fn delete_removes_payload_row() {
+    let (vlite, _pool) = setup_vlite();
+
+    let delete_point = DeletePoint::builder()
+        .collection_name("events")
+        .id(2)
+        .build()
+        .unwrap();
+    vlite.delete(delete_point).expect("delete should succeed");
+
+    assert_eq!(vlite.count_where("events", "1 = 1").unwrap(), 3);
+}
+
+#[test]
+fn payload_only_collection_can_be_renamed_and_deleted() {
+    let (vlite, _pool) = setup_vlite();
+
+    vlite
+        .rename_collection("events", "audit_events")
+        .expect("rename should succeed");
+    assert!(!vlite.collection_exists("events").unwrap());
+
+    let search_point = SearchPoint::builder()
+        .collection_name("audit_events")
+        .vector(vec![])
+        .payload_search_query("select rowid from audit_events")
+        .build()
+        .unwrap();
+    assert_eq!(vlite.search(search_point).unwrap().len(), 4);
+
+    vlite
+        .delete_collection(
+            DeleteCollection::builder()
+                .collection_name("audit_events")
+                .build()
+                .unwrap(),
+        )
+        .expect("delete collection should succeed");
+    assert!(!vlite.collection_exists("audit_events").unwrap());
+}
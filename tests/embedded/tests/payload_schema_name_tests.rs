@@ -0,0 +1,132 @@
+//! Tests for validating the table created by the payload table schema
+//!
+//! These tests verify:
+//! - A schema creating a table other than the collection is rejected before anything is created
+//! - Schemas naming the collection succeed, quoted or in a different case
+//! - With a prefix configured, the schema may name the collection or the prefixed table
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+fn setup_vlite(config: VectorXLiteConfig) -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::with_config(pool.clone(), config).expect("create VectorXLite");
+    (vlite, pool)
+}
+
+fn person_config(payload_table_schema: &str) -> CollectionConfig {
+    CollectionConfigBuilder::default()
+        .collection_name("person")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema(payload_table_schema)
+        .build()
+        .unwrap()
+}
+
+fn table_count(pool: &Pool<SqliteConnectionManager>) -> i64 {
+    pool.get()
+        .unwrap()
+        .query_row("select count(*) from sqlite_master", [], |row| row.get(0))
+        .unwrap()
+}
+
+#[test]
+fn schema_creating_another_table_is_rejected() {
+    let (vlite, pool) = setup_vlite(VectorXLiteConfig::default());
+    let tables_before = table_count(&pool);
+
+    let err = vlite
+        .create_collection(person_config(
+            "create table people (rowid integer primary key, name text)",
+        ))
+        .expect_err("mismatched table name should be rejected");
+
+    assert!(matches!(err, VecXError::InvalidQueryError(_)));
+    assert!(err.to_string().contains("'people'"), "{}", err);
+    assert!(err.to_string().contains("'person'"), "{}", err);
+    assert_eq!(table_count(&pool), tables_before);
+    assert!(!vlite.collection_exists("person").unwrap());
+}
+
+#[test]
+fn schema_that_is_not_create_table_is_rejected() {
+    let (vlite, _pool) = setup_vlite(VectorXLiteConfig::default());
+
+    let err = vlite
+        .create_collection(person_config("create view person as select 1"))
+        .expect_err("non CREATE TABLE schema should be rejected");
+
+    assert!(matches!(err, VecXError::InvalidQueryError(_)));
+}
+
+#[test]
+fn schema_naming_collection_succeeds() {
+    let (vlite, _pool) = setup_vlite(VectorXLiteConfig::default());
+
+    for (name, schema) in [
+        (
+            "person",
+            "create table person (rowid integer primary key, name text)",
+        ),
+        (
+            "quoted",
+            "CREATE TABLE IF NOT EXISTS \"quoted\" (rowid integer primary key)",
+        ),
+        ("Mixed", "create table mixed (rowid integer primary key)"),
+    ] {
+        let config = CollectionConfigBuilder::default()
+            .collection_name(name)
+            .vector_dimension(2)
+            .payload_table_schema(schema)
+            .build()
+            .unwrap();
+        vlite
+            .create_collection(config)
+            .unwrap_or_else(|err| panic!("{} should be created: {}", name, err));
+    }
+}
+
+#[test]
+fn prefixed_collection_accepts_either_table_name() {
+    let (vlite, pool) = setup_vlite(VectorXLiteConfig {
+        prefix_payload_tables: true,
+        ..Default::default()
+    });
+
+    vlite
+        .create_collection(person_config(
+            "create table person (rowid integer primary key, name text)",
+        ))
+        .expect("schema naming the collection should be created");
+
+    let config = CollectionConfigBuilder::default()
+        .collection_name("animal")
+        .vector_dimension(2)
+        .payload_table_schema("create table pt_animal (rowid integer primary key)")
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("schema naming the prefixed table should be created");
+
+    let payload_tables: i64 = pool
+        .get()
+        .unwrap()
+        .query_row(
+            "select count(*) from sqlite_master where name in ('pt_person', 'pt_animal')",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(payload_tables, 2);
+}
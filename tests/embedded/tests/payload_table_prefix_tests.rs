@@ -0,0 +1,152 @@
+//! Tests for prefixed payload table naming
+//!
+//! These tests verify:
+//! - A collection named like an existing unrelated table does not collide with it
+//! - Inserts, searches, counts and deletes use the `pt_` payload table
+//! - Without the prefix the same collection name collides
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+fn setup_pool() -> Pool<SqliteConnectionManager> {
+    let manager = SqliteConnectionManager::memory();
+    Pool::builder()
+        .max_size(5)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool")
+}
+
+fn setup_prefixed_vlite(pool: &Pool<SqliteConnectionManager>) -> VectorXLite {
+    VectorXLite::with_config(
+        pool.clone(),
+        VectorXLiteConfig::default().with_prefixed_payload_tables(true),
+    )
+    .expect("create VectorXLite")
+}
+
+/// Creates an application table named `docs` that has nothing to do with vectors.
+fn create_unrelated_docs_table(pool: &Pool<SqliteConnectionManager>) {
+    pool.get()
+        .unwrap()
+        .execute_batch(
+            "CREATE TABLE docs (id INTEGER PRIMARY KEY, body TEXT);
+             INSERT INTO docs (id, body) VALUES (1, 'unrelated'), (2, 'also unrelated');",
+        )
+        .expect("create unrelated table");
+}
+
+fn docs_collection_config() -> CollectionConfig {
+    CollectionConfigBuilder::default()
+        .collection_name("docs")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema("create table docs (rowid integer primary key, title text)")
+        .build()
+        .unwrap()
+}
+
+fn unrelated_row_count(pool: &Pool<SqliteConnectionManager>) -> i64 {
+    pool.get()
+        .unwrap()
+        .query_row("SELECT count(body) FROM docs", [], |row| row.get(0))
+        .expect("unrelated table should be intact")
+}
+
+#[test]
+fn prefixed_collection_does_not_collide_with_existing_table() {
+    let pool = setup_pool();
+    create_unrelated_docs_table(&pool);
+    let vlite = setup_prefixed_vlite(&pool);
+
+    vlite
+        .create_collection(docs_collection_config())
+        .expect("collection should be created next to the unrelated table");
+
+    for i in 1..=3u64 {
+        let point = InsertPoint::builder()
+            .collection_name("docs")
+            .id(i)
+            .vector(vec![i as f32, 0.0])
+            .payload_insert_query(format!(
+                "insert into pt_docs(rowid, title) values (?1, 'title {}')",
+                i
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+
+    let search_point = SearchPoint::builder()
+        .collection_name("docs")
+        .vector(vec![0.0, 0.0])
+        .top_k(2)
+        .payload_search_query("select rowid, title from pt_docs")
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["title"], "title 1");
+    assert!(vlite.collection_exists("docs").unwrap());
+    assert_eq!(vlite.count_where("docs", "title LIKE 'title%'").unwrap(), 3);
+    assert_eq!(unrelated_row_count(&pool), 2);
+}
+
+#[test]
+fn prefixed_collection_uses_default_payload_table() {
+    let pool = setup_pool();
+    let vlite = setup_prefixed_vlite(&pool);
+
+    let config = CollectionConfigBuilder::default()
+        .collection_name("plain")
+        .vector_dimension(2)
+        .build()
+        .unwrap();
+    vlite.create_collection(config).unwrap();
+
+    let point = InsertPoint::builder()
+        .collection_name("plain")
+        .id(1)
+        .vector(vec![1.0, 0.0])
+        .build()
+        .unwrap();
+    vlite.insert(point).expect("insert should be successful.");
+
+    let count: i64 = pool
+        .get()
+        .unwrap()
+        .query_row("SELECT count(*) FROM pt_plain", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn deleting_prefixed_collection_keeps_unrelated_table() {
+    let pool = setup_pool();
+    create_unrelated_docs_table(&pool);
+    let vlite = setup_prefixed_vlite(&pool);
+    vlite.create_collection(docs_collection_config()).unwrap();
+
+    vlite
+        .delete_collection(
+            DeleteCollection::builder()
+                .collection_name("docs")
+                .build()
+                .unwrap(),
+        )
+        .expect("delete collection");
+
+    assert!(!vlite.collection_exists("docs").unwrap());
+    assert_eq!(unrelated_row_count(&pool), 2);
+}
+
+#[test]
+fn unprefixed_collection_collides_with_existing_table() {
+    let pool = setup_pool();
+    create_unrelated_docs_table(&pool);
+    let vlite = VectorXLite::new(pool.clone()).unwrap();
+
+    assert!(vlite.create_collection(docs_collection_config()).is_err());
+}
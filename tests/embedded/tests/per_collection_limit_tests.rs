@@ -0,0 +1,157 @@
+//! Tests for VectorXLite::with_per_collection_limit
+//!
+//! These tests verify:
+//! - Operations over a collection's limit wait and fail after the acquisition timeout
+//! - Operations on other collections are not blocked by a collection at its limit
+//! - A collection hammered from many threads does not starve another collection
+
+use std::fs;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use vector_xlite::{error::VecXError, types::*, VectorXLite};
+
+struct TestDir(String);
+
+impl TestDir {
+    fn new(name: &str) -> Self {
+        let dir = format!("/tmp/vxlite_test_per_collection_limit_{}", name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        TestDir(dir)
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn setup_vlite(dir: &TestDir, limit: usize, connection_timeout: Duration) -> VectorXLite {
+    let vlite = VectorXLite::builder()
+        .file(format!("{}/limit.db", dir.0))
+        .max_size(4)
+        .config(VectorXLiteConfig::default().with_connection_timeout(connection_timeout))
+        .build()
+        .expect("build file-backed instance")
+        .with_per_collection_limit(limit);
+
+    for name in ["heavy", "light"] {
+        let config = CollectionConfigBuilder::default()
+            .collection_name(name)
+            .payload_table_schema(format!(
+                "create table {} (rowid integer primary key, body text)",
+                name
+            ))
+            .payload_only(true)
+            .build()
+            .unwrap();
+        vlite
+            .create_collection(config)
+            .expect("collection should be created");
+
+        for id in 1..=200 {
+            let point = InsertPoint::builder()
+                .collection_name(name)
+                .id(id)
+                .vector(vec![])
+                .payload_insert_query(format!(
+                    "insert into {}(rowid, body) values (?1, 'row {}')",
+                    name, id
+                ))
+                .build()
+                .unwrap();
+            vlite.insert(point).expect("insert should be successful.");
+        }
+    }
+    vlite
+}
+
+fn scan(collection_name: &str, payload_search_query: &str) -> SearchPoint {
+    SearchPoint::builder()
+        .collection_name(collection_name)
+        .vector(vec![])
+        .top_k(10)
+        .payload_search_query(payload_search_query)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn operation_over_limit_times_out_without_blocking_other_collections() {
+    let dir = TestDir::new("timeout");
+    let vlite = setup_vlite(&dir, 1, Duration::from_millis(200));
+
+    let stream = vlite
+        .search_stream(scan("heavy", "select rowid from heavy"))
+        .expect("stream should start");
+
+    let started = Instant::now();
+    let err = vlite
+        .count_where("heavy", "1 = 1")
+        .expect_err("second operation on heavy should time out");
+    assert!(matches!(err, VecXError::Other(_)));
+    assert!(err.to_string().contains("per-collection limit"), "{}", err);
+    assert!(started.elapsed() >= Duration::from_millis(200));
+
+    assert_eq!(vlite.count_where("light", "1 = 1").unwrap(), 200);
+
+    drop(stream);
+    assert_eq!(vlite.count_where("heavy", "1 = 1").unwrap(), 200);
+}
+
+#[test]
+fn hammered_collection_does_not_starve_others() {
+    let dir = TestDir::new("fairness");
+    let vlite = Arc::new(setup_vlite(&dir, 2, Duration::from_secs(10)));
+    let stop = Arc::new(AtomicBool::new(false));
+    let heavy_searches = Arc::new(AtomicUsize::new(0));
+
+    let hammers: Vec<_> = (0..8)
+        .map(|_| {
+            let (vlite, stop, heavy_searches) = (
+                Arc::clone(&vlite),
+                Arc::clone(&stop),
+                Arc::clone(&heavy_searches),
+            );
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let search_point = scan(
+                        "heavy",
+                        "select a.rowid, count(*) as n from heavy a, heavy b \
+                         where a.body < b.body group by a.rowid",
+                    );
+                    vlite
+                        .search(search_point)
+                        .expect("heavy search should succeed");
+                    heavy_searches.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+        })
+        .collect();
+
+    thread::sleep(Duration::from_millis(100));
+    let mut slowest = Duration::ZERO;
+    for _ in 0..20 {
+        let started = Instant::now();
+        let results = vlite
+            .search(scan("light", "select rowid from light where rowid <= 5"))
+            .expect("light search should succeed");
+        slowest = slowest.max(started.elapsed());
+        assert_eq!(results.len(), 5);
+    }
+
+    stop.store(true, Ordering::Relaxed);
+    for hammer in hammers {
+        hammer.join().unwrap();
+    }
+
+    assert!(heavy_searches.load(Ordering::Relaxed) > 0);
+    assert!(
+        slowest < Duration::from_secs(2),
+        "slowest light search took {:?}",
+        slowest
+    );
+}
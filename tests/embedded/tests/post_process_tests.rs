@@ -0,0 +1,109 @@
+//! Tests for SearchPoint post_process hooks
+//!
+//! These tests verify:
+//! - The hook runs once on the materialized results and can reorder them
+//! - Results are cut back to top_k after the hook
+//! - The hook runs for search_many and search_with_meta
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use vector_xlite::{types::*, VectorXLite};
+
+fn setup_vlite() -> VectorXLite {
+    let vlite = VectorXLite::builder()
+        .memory()
+        .build()
+        .expect("build in-memory instance");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("points")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema("create table points (rowid integer primary key)")
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for id in 1..=10 {
+        let point = InsertPoint::builder()
+            .collection_name("points")
+            .id(id)
+            .vector(vec![id as f32, 0.0])
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+    vlite
+}
+
+fn reversing_search(calls: &Arc<AtomicUsize>) -> SearchPoint {
+    let calls = Arc::clone(calls);
+    SearchPoint::builder()
+        .collection_name("points")
+        .vector(vec![10.0, 0.0])
+        .top_k(3)
+        .post_process(move |results| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            results.reverse();
+        })
+        .build()
+        .unwrap()
+}
+
+fn rowids(results: &[SearchResult]) -> Vec<&str> {
+    results.iter().map(|row| row["rowid"].as_str()).collect()
+}
+
+#[test]
+fn post_process_reorders_results() {
+    let vlite = setup_vlite();
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let results = vlite
+        .search(reversing_search(&calls))
+        .expect("search should succeed");
+
+    assert_eq!(rowids(&results), vec!["8", "9", "10"]);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn post_process_results_are_cut_back_to_top_k() {
+    let vlite = setup_vlite();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("points")
+        .vector(vec![10.0, 0.0])
+        .top_k(3)
+        .post_process(|results| {
+            let mut boosted = SearchResult::new();
+            boosted.insert("rowid".to_string(), "42".to_string());
+            results.insert(0, boosted);
+        })
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).expect("search should succeed");
+
+    assert_eq!(rowids(&results), vec!["42", "10", "9"]);
+}
+
+#[test]
+fn post_process_runs_for_search_many_and_search_with_meta() {
+    let vlite = setup_vlite();
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let results = vlite
+        .search_many(vec![reversing_search(&calls), reversing_search(&calls)])
+        .expect("search_many should succeed");
+    assert!(results
+        .iter()
+        .all(|results| rowids(results) == vec!["8", "9", "10"]));
+
+    let response = vlite
+        .search_with_meta(reversing_search(&calls))
+        .expect("search_with_meta should succeed");
+    assert_eq!(rowids(&response.results), vec!["8", "9", "10"]);
+    assert!(response.truncated);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
@@ -0,0 +1,105 @@
+//! Tests for measure_recall method in VectorXLite
+//!
+//! These tests verify:
+//! - Recall is 1.0 when the index returns the exact neighbours
+//! - Recall stays within 0.0..=1.0 on a larger random collection
+//! - An empty query set is rejected
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+fn setup_vlite() -> VectorXLite {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    VectorXLite::new(pool).expect("create VectorXLite")
+}
+
+fn create_collection(vlite: &VectorXLite, name: &str, dimension: u16, vectors: &[Vec<f32>]) {
+    let config = CollectionConfigBuilder::default()
+        .collection_name(name)
+        .distance(DistanceFunction::L2)
+        .vector_dimension(dimension)
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for (i, vector) in vectors.iter().enumerate() {
+        let point = InsertPoint::builder()
+            .collection_name(name)
+            .id(i as u64 + 1)
+            .vector(vector.clone())
+            .build()
+            .expect("Builder should create insert point.");
+        vlite.insert(point).expect("insert should be successful.");
+    }
+}
+
+/// Deterministic pseudo-random vectors so the test does not need a rand dependency.
+fn random_vectors(count: usize, dimension: usize, seed: u64) -> Vec<Vec<f32>> {
+    let mut state = seed;
+    let mut next = || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 40) as f32 / (1u64 << 24) as f32
+    };
+    (0..count)
+        .map(|_| (0..dimension).map(|_| next()).collect())
+        .collect()
+}
+
+#[test]
+fn measure_recall_is_perfect_on_small_collection() {
+    let vlite = setup_vlite();
+    let vectors: Vec<Vec<f32>> = (1..=10).map(|i| vec![i as f32, 0.0]).collect();
+    create_collection(&vlite, "small", 2, &vectors);
+
+    let recall = vlite
+        .measure_recall("small", &[vec![0.0, 0.0], vec![5.2, 0.0]], 3)
+        .expect("recall should be measured");
+
+    assert_eq!(recall, 1.0);
+}
+
+#[test]
+fn measure_recall_is_a_fraction_on_random_collection() {
+    let vlite = setup_vlite();
+    create_collection(&vlite, "random", 8, &random_vectors(300, 8, 42));
+
+    let recall = vlite
+        .measure_recall("random", &random_vectors(5, 8, 7), 10)
+        .expect("recall should be measured");
+
+    assert!(recall > 0.0 && recall <= 1.0, "recall {}", recall);
+}
+
+#[test]
+fn measure_recall_rejects_empty_queries() {
+    let vlite = setup_vlite();
+    create_collection(&vlite, "small", 2, &[vec![1.0, 0.0]]);
+
+    let err = vlite
+        .measure_recall("small", &[], 3)
+        .expect_err("empty queries should be rejected");
+
+    assert!(matches!(err, VecXError::InvalidQueryError(_)));
+}
+
+#[test]
+fn measure_recall_on_missing_collection_is_error() {
+    let vlite = setup_vlite();
+
+    assert!(vlite
+        .measure_recall("missing", &[vec![0.0, 0.0]], 3)
+        .is_err());
+}
@@ -0,0 +1,156 @@
+//! Tests for refresh_connections method in VectorXLite
+//!
+//! These tests verify:
+//! - Pooled connections keep serving data that was replaced on disk
+//! - After a refresh, searches return the data restored out-of-band
+//! - In-memory databases are left untouched
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::collections::HashMap;
+use std::fs;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer,
+    snapshot::{SnapshotChunk, SnapshotConfig, SnapshotExporter},
+    types::*,
+    VectorXLite,
+};
+
+fn test_paths(name: &str) -> (String, String) {
+    (
+        format!("/tmp/vxlite_test_refresh_{}.db", name),
+        format!("/tmp/vxlite_test_refresh_{}.idx", name),
+    )
+}
+
+fn cleanup(db_path: &str, idx_path: &str) {
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = fs::remove_file(format!("{}{}", db_path, suffix));
+    }
+    let _ = fs::remove_file(idx_path);
+}
+
+fn create_pool(db_path: &str) -> Pool<SqliteConnectionManager> {
+    Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(SqliteConnectionManager::file(db_path))
+        .expect("create pool")
+}
+
+fn insert_doc(vlite: &VectorXLite, id: u64) {
+    let point = InsertPoint::builder()
+        .collection_name("docs")
+        .id(id)
+        .vector(vec![id as f32, 0.0])
+        .payload_insert_query(format!(
+            "insert into docs(rowid, title) values (?1, 'doc {}')",
+            id
+        ))
+        .build()
+        .unwrap();
+    vlite.insert(point).expect("insert should be successful.");
+}
+
+fn searchable_ids(vlite: &VectorXLite) -> Vec<i64> {
+    let search_point = SearchPoint::builder()
+        .collection_name("docs")
+        .vector(vec![0.0, 0.0])
+        .top_k(10)
+        .build()
+        .unwrap();
+
+    let mut ids: Vec<i64> = vlite
+        .search(search_point)
+        .expect("search should succeed")
+        .iter()
+        .map(|row| row["rowid"].parse().unwrap())
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn refresh_sees_snapshot_restored_out_of_band() {
+    let (db_path, idx_path) = test_paths("restore");
+    cleanup(&db_path, &idx_path);
+
+    let pool = create_pool(&db_path);
+    let vlite = VectorXLite::new(pool.clone()).unwrap();
+    let config = CollectionConfigBuilder::default()
+        .collection_name("docs")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .index_file_path(&idx_path)
+        .payload_table_schema("create table docs (rowid integer primary key, title text)")
+        .build()
+        .unwrap();
+    vlite.create_collection(config).unwrap();
+    for id in 1..=3 {
+        insert_doc(&vlite, id);
+    }
+
+    let chunks: Vec<SnapshotChunk> = SnapshotExporter::new(
+        pool.clone(),
+        SnapshotConfig::default().with_consistent_export(true),
+    )
+    .export_to_memory()
+    .expect("Export should succeed");
+
+    for id in 4..=6 {
+        insert_doc(&vlite, id);
+    }
+
+    // Replace the files in place, as an external restore tool would
+    let mut restored: HashMap<String, Vec<u8>> = HashMap::new();
+    for chunk in chunks.iter().filter_map(|c| c.file_chunk.as_ref()) {
+        let dest = match chunk.file_name.as_str() {
+            "database.db" => db_path.clone(),
+            _ => idx_path.clone(),
+        };
+        restored.entry(dest).or_default().extend(&chunk.data);
+    }
+    for (dest, data) in restored {
+        let tmp = format!("{}.restore", dest);
+        fs::write(&tmp, data).unwrap();
+        fs::rename(&tmp, dest).unwrap();
+    }
+    for suffix in ["-wal", "-shm"] {
+        let _ = fs::remove_file(format!("{}{}", db_path, suffix));
+    }
+
+    // The pooled connection still holds the replaced database and index
+    assert_eq!(searchable_ids(&vlite), vec![1, 2, 3, 4, 5, 6]);
+
+    vlite.refresh_connections().expect("refresh should succeed");
+
+    assert_eq!(searchable_ids(&vlite), vec![1, 2, 3]);
+    assert_eq!(vlite.count_where("docs", "1 = 1").unwrap(), 3);
+
+    drop(vlite);
+    drop(pool);
+    cleanup(&db_path, &idx_path);
+}
+
+#[test]
+fn refresh_in_memory_database_keeps_data() {
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(SqliteConnectionManager::memory())
+        .expect("create pool");
+    let vlite = VectorXLite::new(pool).unwrap();
+    let config = CollectionConfigBuilder::default()
+        .collection_name("docs")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema("create table docs (rowid integer primary key, title text)")
+        .build()
+        .unwrap();
+    vlite.create_collection(config).unwrap();
+    insert_doc(&vlite, 1);
+
+    vlite.refresh_connections().expect("refresh should succeed");
+
+    assert_eq!(searchable_ids(&vlite), vec![1]);
+}
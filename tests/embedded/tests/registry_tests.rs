@@ -0,0 +1,203 @@
+//! Tests for VectorXLiteRegistry
+//!
+//! These tests verify:
+//! - Each tenant gets its own isolated database
+//! - Tenants are reused while open and reopened from disk after eviction
+//! - Idle tenants are evicted unless still referenced
+//! - Explicit eviction keeps tenants that are still referenced
+//! - Invalid tenant keys are rejected
+//! - A shared customizer applies to every tenant
+
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLiteRegistry};
+
+fn setup_registry(name: &str, idle_timeout: Duration) -> (VectorXLiteRegistry, String) {
+    let base_dir = format!("/tmp/vxlite_test_registry_{}", name);
+    let _ = fs::remove_dir_all(&base_dir);
+
+    let registry = VectorXLiteRegistry::builder()
+        .base_dir(&base_dir)
+        .pool_size(1)
+        .idle_timeout(idle_timeout)
+        .build()
+        .expect("create registry");
+    (registry, base_dir)
+}
+
+fn create_with_points(registry: &VectorXLiteRegistry, tenant: &str, ids: &[u64]) {
+    let vlite = registry.get(tenant).expect("open tenant");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("docs")
+        .vector_dimension(2)
+        .build()
+        .unwrap();
+    vlite.create_collection(config).expect("create collection");
+
+    for id in ids {
+        let point = InsertPoint::builder()
+            .collection_name("docs")
+            .id(*id)
+            .vector(vec![*id as f32, 1.0])
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert");
+    }
+}
+
+fn search_ids(registry: &VectorXLiteRegistry, tenant: &str) -> Vec<String> {
+    let search = SearchPoint::builder()
+        .collection_name("docs")
+        .vector(vec![1.0, 1.0])
+        .top_k(100)
+        .build()
+        .unwrap();
+
+    let mut ids: Vec<String> = registry
+        .get(tenant)
+        .unwrap()
+        .search(search)
+        .expect("search")
+        .iter()
+        .map(|row| row["rowid"].clone())
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn tenants_are_isolated() {
+    let (registry, base_dir) = setup_registry("isolation", Duration::from_secs(600));
+
+    create_with_points(&registry, "tenant_a", &[1, 2, 3]);
+    create_with_points(&registry, "tenant_b", &[100, 200]);
+
+    assert_eq!(search_ids(&registry, "tenant_a"), vec!["1", "2", "3"]);
+    assert_eq!(search_ids(&registry, "tenant_b"), vec!["100", "200"]);
+    assert!(!registry.get("tenant_c").unwrap().collection_exists("docs").unwrap());
+
+    let _ = fs::remove_dir_all(base_dir);
+}
+
+#[test]
+fn open_tenant_is_reused() {
+    let (registry, base_dir) = setup_registry("reuse", Duration::from_secs(600));
+
+    let first = registry.get("tenant_a").unwrap();
+    let second = registry.get("tenant_a").unwrap();
+
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(registry.open_tenant_count(), 1);
+
+    drop((first, second));
+    let _ = fs::remove_dir_all(base_dir);
+}
+
+#[test]
+fn idle_tenants_are_evicted_and_reopened_from_disk() {
+    let (registry, base_dir) = setup_registry("eviction", Duration::ZERO);
+
+    create_with_points(&registry, "tenant_a", &[1, 2]);
+    std::thread::sleep(Duration::from_millis(10));
+
+    // Opening another tenant evicts the idle one
+    let held = registry.get("tenant_b").unwrap();
+    assert_eq!(registry.open_tenant_count(), 1);
+
+    std::thread::sleep(Duration::from_millis(10));
+    assert_eq!(registry.evict_idle(), 0, "referenced tenants stay open");
+
+    drop(held);
+    assert_eq!(registry.evict_idle(), 1);
+    assert_eq!(registry.open_tenant_count(), 0);
+
+    let reopened = registry.get("tenant_a").unwrap();
+    assert!(reopened.collection_exists("docs").unwrap());
+
+    drop(reopened);
+    let _ = fs::remove_dir_all(base_dir);
+}
+
+#[test]
+fn evict_keeps_referenced_tenant_open() {
+    let (registry, base_dir) = setup_registry("evict", Duration::from_secs(600));
+
+    let held = registry.get("tenant_a").unwrap();
+    assert!(!registry.evict("tenant_a"), "referenced tenant stays open");
+    assert!(Arc::ptr_eq(&held, &registry.get("tenant_a").unwrap()));
+
+    drop(held);
+    assert!(registry.evict("tenant_a"));
+    assert!(!registry.evict("tenant_a"));
+    assert_eq!(registry.open_tenant_count(), 0);
+
+    let _ = fs::remove_dir_all(base_dir);
+}
+
+#[test]
+fn invalid_tenant_keys_are_rejected() {
+    let (registry, base_dir) = setup_registry("invalid", Duration::from_secs(600));
+
+    assert!(registry.get("").is_err());
+    assert!(registry.get("../escape").is_err());
+    assert_eq!(registry.open_tenant_count(), 0);
+
+    let _ = fs::remove_dir_all(base_dir);
+}
+
+#[test]
+fn customizer_applies_to_every_tenant() {
+    let base_dir = "/tmp/vxlite_test_registry_customizer";
+    let _ = fs::remove_dir_all(base_dir);
+    let registry = VectorXLiteRegistry::builder()
+        .base_dir(base_dir)
+        .pool_size(1)
+        .customizer(|| {
+            SqliteConnectionCustomizer::new()
+                .with_scalar_function("double", 1, |ctx| Ok(ctx.get::<i64>(0)? * 2))
+        })
+        .build()
+        .expect("create registry");
+
+    for tenant in ["tenant_a", "tenant_b"] {
+        let vlite = registry.get(tenant).unwrap();
+        let config = CollectionConfigBuilder::default()
+            .collection_name("docs")
+            .vector_dimension(2)
+            .payload_table_schema("create table docs (rowid integer primary key, n integer)")
+            .build()
+            .unwrap();
+        vlite.create_collection(config).unwrap();
+        let point = InsertPoint::builder()
+            .collection_name("docs")
+            .id(1)
+            .vector(vec![1.0, 1.0])
+            .payload_insert_query("insert into docs(rowid, n) values (?1, 21)")
+            .build()
+            .unwrap();
+        vlite.insert(point).unwrap();
+
+        assert_eq!(vlite.count_where("docs", "double(n) = 42").unwrap(), 1);
+    }
+
+    let _ = fs::remove_dir_all(base_dir);
+}
+
+#[test]
+fn builder_rejects_customizer_with_busy_timeout() {
+    let result = VectorXLiteRegistry::builder()
+        .base_dir("/tmp/vxlite_test_registry_unused")
+        .busy_timeout_ms(1000)
+        .customizer(SqliteConnectionCustomizer::new)
+        .build();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn builder_requires_base_dir() {
+    let result = VectorXLiteRegistry::builder().build();
+
+    assert_eq!(result.err(), Some("Base directory must be provided.".to_string()));
+}
@@ -0,0 +1,232 @@
+//! Tests for rename_collection method in VectorXLite
+//!
+//! These tests verify:
+//! - A populated collection is searchable under its new name only
+//! - Renaming onto an existing collection or from a missing one fails
+//! - Renaming to a name that is not a plain SQL identifier fails
+//! - File-backed collections get their index file renamed
+//! - Payload columns that need quoting are copied
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::fs;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+fn setup_vlite(manager: SqliteConnectionManager) -> VectorXLite {
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    VectorXLite::new(pool).expect("create VectorXLite")
+}
+
+fn create_populated(vlite: &VectorXLite, name: &str, index_file_path: Option<&str>) {
+    let mut builder = CollectionConfigBuilder::default()
+        .collection_name(name)
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema(format!(
+            "create table {} (rowid integer primary key, label text)",
+            name
+        ));
+    if let Some(path) = index_file_path {
+        builder = builder.index_file_path(path);
+    }
+    vlite
+        .create_collection(builder.build().unwrap())
+        .expect("create collection");
+
+    for i in 1..=10u64 {
+        let point = InsertPoint::builder()
+            .collection_name(name)
+            .id(i)
+            .vector(vec![i as f32, 0.0])
+            .payload_insert_query(format!(
+                "insert into {}(rowid, label) values (?1, 'item {}')",
+                name, i
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert");
+    }
+}
+
+fn search(
+    vlite: &VectorXLite,
+    name: &str,
+) -> Result<Vec<std::collections::HashMap<String, String>>, vector_xlite::error::VecXError> {
+    let search_point = SearchPoint::builder()
+        .collection_name(name)
+        .vector(vec![0.0, 0.0])
+        .top_k(3)
+        .payload_search_query(format!("select rowid, label from {}", name))
+        .build()
+        .unwrap();
+    vlite.search(search_point)
+}
+
+#[test]
+fn renamed_collection_is_searchable_under_new_name_only() {
+    let vlite = setup_vlite(SqliteConnectionManager::memory());
+    create_populated(&vlite, "drafts", None);
+
+    vlite
+        .rename_collection("drafts", "published")
+        .expect("rename should succeed");
+
+    let results = search(&vlite, "published").expect("search under new name");
+    let labels: Vec<&str> = results.iter().map(|r| r["label"].as_str()).collect();
+    assert_eq!(labels, vec!["item 1", "item 2", "item 3"]);
+
+    assert!(search(&vlite, "drafts").is_err());
+    assert!(!vlite.collection_exists("drafts").unwrap());
+    assert!(vlite.collection_exists("published").unwrap());
+}
+
+#[test]
+fn rename_leaves_other_collections_intact() {
+    let vlite = setup_vlite(SqliteConnectionManager::memory());
+    create_populated(&vlite, "first", None);
+    create_populated(&vlite, "second", None);
+
+    vlite.rename_collection("first", "renamed").unwrap();
+
+    assert_eq!(search(&vlite, "second").unwrap().len(), 3);
+    assert_eq!(search(&vlite, "renamed").unwrap().len(), 3);
+}
+
+#[test]
+fn rename_keeps_payload_indexes() {
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(SqliteConnectionManager::memory())
+        .expect("create pool");
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    create_populated(&vlite, "drafts", None);
+    pool.get()
+        .unwrap()
+        .execute("create index drafts_label on drafts(label)", [])
+        .unwrap();
+
+    vlite.rename_collection("drafts", "published").unwrap();
+
+    let index_table: String = pool
+        .get()
+        .unwrap()
+        .query_row(
+            "select tbl_name from sqlite_master where type = 'index' and name = 'drafts_label'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("index should be recreated");
+    assert_eq!(index_table, "published");
+    assert_eq!(search(&vlite, "published").unwrap().len(), 3);
+}
+
+#[test]
+fn rename_onto_existing_collection_fails() {
+    let vlite = setup_vlite(SqliteConnectionManager::memory());
+    create_populated(&vlite, "first", None);
+    create_populated(&vlite, "second", None);
+
+    assert!(vlite.rename_collection("first", "second").is_err());
+    assert_eq!(search(&vlite, "first").unwrap().len(), 3);
+}
+
+#[test]
+fn rename_missing_collection_fails() {
+    let vlite = setup_vlite(SqliteConnectionManager::memory());
+
+    assert!(vlite.rename_collection("missing", "other").is_err());
+}
+
+#[test]
+fn rename_to_name_with_sql_characters_fails() {
+    let vlite = setup_vlite(SqliteConnectionManager::memory());
+    create_populated(&vlite, "first", None);
+
+    let result = vlite.rename_collection("first", "second; DROP TABLE first");
+
+    assert!(matches!(
+        result,
+        Err(vector_xlite::error::VecXError::InvalidQueryError(_))
+    ));
+    assert_eq!(search(&vlite, "first").unwrap().len(), 3);
+}
+
+#[test]
+fn rename_moves_index_file_of_file_backed_collection() {
+    let db_path = "/tmp/vxlite_test_rename.db";
+    let old_idx = "/tmp/vxlite_test_rename_drafts.idx";
+    let new_idx = "/tmp/vxlite_test_rename_published.idx";
+    for path in [db_path, old_idx, new_idx] {
+        let _ = fs::remove_file(path);
+    }
+
+    {
+        let vlite = setup_vlite(SqliteConnectionManager::file(db_path));
+        create_populated(&vlite, "drafts", Some(old_idx));
+        vlite.flush("drafts").unwrap();
+
+        vlite.rename_collection("drafts", "published").unwrap();
+
+        assert!(
+            fs::metadata(new_idx).is_ok(),
+            "index file should be renamed"
+        );
+        assert!(
+            fs::metadata(old_idx).is_err(),
+            "old index file should be gone"
+        );
+    }
+
+    {
+        let vlite = setup_vlite(SqliteConnectionManager::file(db_path));
+        assert_eq!(search(&vlite, "published").unwrap().len(), 3);
+    }
+
+    for path in [db_path, old_idx, new_idx] {
+        let _ = fs::remove_file(path);
+    }
+}
+
+#[test]
+fn rename_copies_columns_that_need_quoting() {
+    let vlite = setup_vlite(SqliteConnectionManager::memory());
+    let config = CollectionConfigBuilder::default()
+        .collection_name("orders")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema(
+            "create table orders (rowid integer primary key, \"order\" text, \"my col\" text)",
+        )
+        .build()
+        .unwrap();
+    vlite.create_collection(config).unwrap();
+    let point = InsertPoint::builder()
+        .collection_name("orders")
+        .id(1)
+        .vector(vec![1.0, 0.0])
+        .payload_insert_query(
+            "insert into orders(rowid, \"order\", \"my col\") values (?1, 'first', 'value')",
+        )
+        .build()
+        .unwrap();
+    vlite.insert(point).unwrap();
+
+    vlite.rename_collection("orders", "archive").unwrap();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("archive")
+        .vector(vec![0.0, 0.0])
+        .top_k(1)
+        .payload_search_query("select rowid, \"order\", \"my col\" from archive")
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).unwrap();
+    assert_eq!(results[0]["order"], "first");
+    assert_eq!(results[0]["my col"], "value");
+}
@@ -0,0 +1,142 @@
+//! Tests for the rerank_with option of SearchPoint
+//!
+//! These tests verify:
+//! - Results carry both the index distance and a rerank_score
+//! - Reranking with the collection's own metric reproduces the index distance
+//! - The score is added with and without a payload filter, and keeps the result order
+//! - Searches without rerank_with have no rerank_score
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::collections::HashMap;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+const QUERY: [f32; 2] = [1.0, 0.0];
+
+/// Id 2 points the same way as the query but lies far away, id 3 is close but at 45°.
+fn setup_vlite() -> VectorXLite {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool).expect("create VectorXLite");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("points")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema("create table points (rowid integer primary key, label text)")
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for (id, vector) in [(1, [1.0, 0.0]), (2, [10.0, 0.5]), (3, [0.9, 0.9])] {
+        let point = InsertPoint::builder()
+            .collection_name("points")
+            .id(id)
+            .vector(vector.to_vec())
+            .payload_insert_query(format!(
+                "insert into points(rowid, label) values (?1, 'p{}')",
+                id
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+    vlite
+}
+
+fn float(row: &HashMap<String, String>, column: &str) -> f32 {
+    row[column].parse().unwrap()
+}
+
+fn rowids(results: &[HashMap<String, String>]) -> Vec<&str> {
+    results.iter().map(|row| row["rowid"].as_str()).collect()
+}
+
+#[test]
+fn every_hit_carries_index_distance_and_rerank_score() {
+    let vlite = setup_vlite();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("points")
+        .vector(QUERY.to_vec())
+        .top_k(3)
+        .rerank_with(DistanceFunction::Cosine)
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).unwrap();
+
+    assert_eq!(rowids(&results), vec!["1", "3", "2"]);
+    for row in &results {
+        assert!(row.contains_key("distance"));
+        assert!(row.contains_key("rerank_score"));
+    }
+    assert!(float(&results[2], "distance") > 80.0);
+    assert!(float(&results[2], "rerank_score") < 0.01);
+    assert!((float(&results[1], "rerank_score") - (1.0 - 45f32.to_radians().cos())).abs() < 1e-4);
+}
+
+#[test]
+fn rerank_with_collection_metric_matches_index_distance() {
+    let vlite = setup_vlite();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("points")
+        .vector(QUERY.to_vec())
+        .top_k(3)
+        .rerank_with(DistanceFunction::L2)
+        .build()
+        .unwrap();
+
+    for row in vlite.search(search_point).unwrap() {
+        let distance = float(&row, "distance");
+        assert!(
+            (float(&row, "rerank_score") - distance).abs() <= 1e-4 * distance.max(1.0),
+            "{:?}",
+            row
+        );
+    }
+}
+
+#[test]
+fn rerank_score_is_added_with_payload_filter() {
+    let vlite = setup_vlite();
+
+    for strategy in [FilterStrategy::Pushdown, FilterStrategy::PostFilter] {
+        let search_point = SearchPoint::builder()
+            .collection_name("points")
+            .vector(QUERY.to_vec())
+            .top_k(3)
+            .payload_search_query("select rowid, label from points where rowid != 1")
+            .filter_strategy(strategy)
+            .rerank_with(DistanceFunction::Cosine)
+            .build()
+            .unwrap();
+        let results = vlite.search(search_point).unwrap();
+
+        assert_eq!(rowids(&results), vec!["3", "2"], "{:?}", strategy);
+        assert_eq!(results[1]["label"], "p2");
+        assert!(float(&results[1], "rerank_score") < float(&results[0], "rerank_score"));
+    }
+}
+
+#[test]
+fn search_without_rerank_has_no_score() {
+    let vlite = setup_vlite();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("points")
+        .vector(QUERY.to_vec())
+        .top_k(3)
+        .build()
+        .unwrap();
+
+    for row in vlite.search(search_point).unwrap() {
+        assert!(!row.contains_key("rerank_score"));
+    }
+}
@@ -0,0 +1,124 @@
+//! Tests for rerank method in VectorXLite
+//!
+//! These tests verify:
+//! - Candidates are reordered by the requested distance function
+//! - Reranking under the collection's own metric matches its search order
+//! - Unknown ids are left out and invalid input is rejected
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+const QUERY: [f32; 2] = [1.0, 0.0];
+
+/// Id 2 points the same way as the query but lies far away, id 3 is close but at 45°.
+fn setup_vlite() -> VectorXLite {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool).expect("create VectorXLite");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("points")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for (id, vector) in [(1, [1.0, 0.0]), (2, [10.0, 0.5]), (3, [0.9, 0.9])] {
+        let point = InsertPoint::builder()
+            .collection_name("points")
+            .id(id)
+            .vector(vector.to_vec())
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+    vlite
+}
+
+fn ids(ranked: &[(i64, f32)]) -> Vec<i64> {
+    ranked.iter().map(|(id, _)| *id).collect()
+}
+
+#[test]
+fn cosine_and_l2_order_candidates_differently() {
+    let vlite = setup_vlite();
+
+    let l2 = vlite
+        .rerank("points", &[1, 2, 3], &QUERY, DistanceFunction::L2)
+        .unwrap();
+    let cosine = vlite
+        .rerank("points", &[1, 2, 3], &QUERY, DistanceFunction::Cosine)
+        .unwrap();
+
+    assert_eq!(ids(&l2), vec![1, 3, 2]);
+    assert_eq!(ids(&cosine), vec![1, 2, 3]);
+    assert!(cosine[1].1 < 0.01, "{:?}", cosine);
+    assert!((cosine[2].1 - (1.0 - 45f32.to_radians().cos())).abs() < 1e-4);
+}
+
+#[test]
+fn rerank_under_collection_metric_matches_search() {
+    let vlite = setup_vlite();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("points")
+        .vector(QUERY.to_vec())
+        .top_k(3)
+        .build()
+        .unwrap();
+    let searched: Vec<(i64, f32)> = vlite
+        .search(search_point)
+        .unwrap()
+        .iter()
+        .map(|row| {
+            (
+                row["rowid"].parse().unwrap(),
+                row["distance"].parse().unwrap(),
+            )
+        })
+        .collect();
+
+    let reranked = vlite
+        .rerank("points", &[3, 2, 1], &QUERY, DistanceFunction::L2)
+        .unwrap();
+
+    assert_eq!(reranked, searched);
+}
+
+#[test]
+fn unknown_candidate_ids_are_left_out() {
+    let vlite = setup_vlite();
+
+    let ranked = vlite
+        .rerank("points", &[2, 99], &QUERY, DistanceFunction::Cosine)
+        .unwrap();
+
+    assert_eq!(ids(&ranked), vec![2]);
+}
+
+#[test]
+fn invalid_rerank_input_is_rejected() {
+    let vlite = setup_vlite();
+
+    let empty = vlite.rerank("points", &[], &QUERY, DistanceFunction::L2);
+    assert!(matches!(empty, Err(VecXError::InvalidQueryError(_))));
+
+    let wrong_dimension = vlite.rerank("points", &[1], &[1.0, 0.0, 0.0], DistanceFunction::L2);
+    assert!(matches!(
+        wrong_dimension,
+        Err(VecXError::InvalidQueryError(_))
+    ));
+
+    let missing = vlite.rerank("missing", &[1], &QUERY, DistanceFunction::L2);
+    assert!(matches!(missing, Err(VecXError::InvalidQueryError(_))));
+}
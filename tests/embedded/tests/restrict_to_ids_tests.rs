@@ -0,0 +1,130 @@
+//! Tests for restricting a search to an explicit id allowlist
+//!
+//! These tests verify:
+//! - Results are confined to the allowlisted ids
+//! - The allowlist combines with payload filters
+//! - Empty allowlists are rejected by the builder
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+fn setup_vlite() -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(5)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    (vlite, pool)
+}
+
+fn create_collection_with_points(vlite: &VectorXLite, payload_schema: Option<&str>) {
+    let mut builder = CollectionConfigBuilder::default()
+        .collection_name("items")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2);
+    if let Some(schema) = payload_schema {
+        builder = builder.payload_table_schema(schema);
+    }
+    vlite
+        .create_collection(builder.build().unwrap())
+        .expect("collection should be created");
+
+    for i in 1..=20u64 {
+        let mut point = InsertPoint::builder()
+            .collection_name("items")
+            .id(i)
+            .vector(vec![i as f32, 0.0]);
+        if payload_schema.is_some() {
+            let parity = if i % 2 == 0 { "even" } else { "odd" };
+            point = point.payload_insert_query(format!(
+                "insert into items(rowid, parity) values (?1, '{}')",
+                parity
+            ));
+        }
+        vlite
+            .insert(point.build().unwrap())
+            .expect("insert should be successful.");
+    }
+}
+
+fn rowids(results: &[std::collections::HashMap<String, String>]) -> Vec<i64> {
+    results
+        .iter()
+        .map(|row| row["rowid"].parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn search_results_are_confined_to_allowlist() {
+    let (vlite, _) = setup_vlite();
+    create_collection_with_points(&vlite, None);
+
+    let allowlist = vec![3, 11, 17];
+    let search_point = SearchPoint::builder()
+        .collection_name("items")
+        .vector(vec![1.0, 0.0])
+        .top_k(10)
+        .restrict_to_ids(allowlist.clone())
+        .build()
+        .unwrap();
+
+    let results = vlite.search(search_point).unwrap();
+
+    assert_eq!(rowids(&results), allowlist, "Nearest allowlisted ids in distance order");
+}
+
+#[test]
+fn allowlist_returns_nearest_allowed_neighbours_within_top_k() {
+    let (vlite, _) = setup_vlite();
+    create_collection_with_points(&vlite, None);
+
+    let search_point = SearchPoint::builder()
+        .collection_name("items")
+        .vector(vec![20.0, 0.0])
+        .top_k(2)
+        .restrict_to_ids(vec![2, 4, 6, 8, 10])
+        .build()
+        .unwrap();
+
+    let results = vlite.search(search_point).unwrap();
+
+    assert_eq!(rowids(&results), vec![10, 8]);
+}
+
+#[test]
+fn allowlist_combines_with_payload_filter() {
+    let (vlite, _) = setup_vlite();
+    create_collection_with_points(
+        &vlite,
+        Some("create table items (rowid integer primary key, parity text)"),
+    );
+
+    let search_point = SearchPoint::builder()
+        .collection_name("items")
+        .vector(vec![1.0, 0.0])
+        .top_k(10)
+        .payload_search_query("select rowid, parity from items where parity = 'even'")
+        .restrict_to_ids(vec![1, 2, 3, 4, 5])
+        .build()
+        .unwrap();
+
+    let results = vlite.search(search_point).unwrap();
+
+    assert_eq!(rowids(&results), vec![2, 4]);
+    assert!(results.iter().all(|row| row["parity"] == "even"));
+}
+
+#[test]
+fn empty_allowlist_is_rejected() {
+    let result = SearchPoint::builder()
+        .collection_name("items")
+        .vector(vec![1.0, 0.0])
+        .restrict_to_ids(vec![])
+        .build();
+
+    assert_eq!(result.unwrap_err(), "restrict_to_ids must not be empty.");
+}
@@ -0,0 +1,156 @@
+//! Tests for collection rowid strategies
+//!
+//! These tests verify:
+//! - Offset and Hash strategies store points under the mapped rowid
+//! - restrict_to_ids, upserts and deletes take the original ids under each strategy
+//! - The strategy survives a rename
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+fn setup_vlite(strategy: RowidStrategy) -> VectorXLite {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool).expect("create VectorXLite");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("synced")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema(
+            "create table synced (rowid integer primary key, external_id integer)",
+        )
+        .rowid_strategy(strategy)
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for id in 1..=3 {
+        insert(&vlite, "synced", id);
+    }
+    vlite
+}
+
+fn insert(vlite: &VectorXLite, collection_name: &str, id: u64) {
+    let point = InsertPoint::builder()
+        .collection_name(collection_name)
+        .id(id)
+        .vector(vec![id as f32, 0.0])
+        .payload_insert_query(format!(
+            "insert into {}(rowid, external_id) values (?1, {})",
+            collection_name, id
+        ))
+        .build()
+        .unwrap();
+    vlite.insert(point).expect("insert should be successful.");
+}
+
+fn search(vlite: &VectorXLite, restrict_to_ids: Option<Vec<i64>>) -> Vec<(u64, String)> {
+    let builder = SearchPoint::builder()
+        .collection_name("synced")
+        .vector(vec![0.0, 0.0])
+        .top_k(10)
+        .payload_search_query("select rowid, external_id from synced");
+    let builder = match restrict_to_ids {
+        Some(ids) => builder.restrict_to_ids(ids),
+        None => builder,
+    };
+
+    vlite
+        .search(builder.build().unwrap())
+        .expect("search should succeed")
+        .iter()
+        .map(|row| (row["rowid"].parse().unwrap(), row["external_id"].clone()))
+        .collect()
+}
+
+fn delete(vlite: &VectorXLite, id: u64) {
+    let delete_point = DeletePoint::builder()
+        .collection_name("synced")
+        .id(id)
+        .build()
+        .unwrap();
+    vlite.delete(delete_point).expect("delete should succeed");
+}
+
+#[test]
+fn offset_strategy_shifts_rowids() {
+    let vlite = setup_vlite(RowidStrategy::Offset(1000));
+
+    assert_eq!(
+        search(&vlite, None),
+        vec![
+            (1001, "1".to_string()),
+            (1002, "2".to_string()),
+            (1003, "3".to_string())
+        ]
+    );
+    assert_eq!(search(&vlite, Some(vec![2])), vec![(1002, "2".to_string())]);
+
+    delete(&vlite, 2);
+    assert_eq!(vlite.count_where("synced", "1 = 1").unwrap(), 2);
+    assert!(search(&vlite, Some(vec![2])).is_empty());
+}
+
+#[test]
+fn hash_strategy_stores_hashed_rowids() {
+    let vlite = setup_vlite(RowidStrategy::Hash);
+
+    let results = search(&vlite, None);
+    let expected: Vec<(u64, String)> = (1..=3)
+        .map(|id| (RowidStrategy::Hash.rowid(id).unwrap(), id.to_string()))
+        .collect();
+    assert_eq!(results, expected);
+    assert!(results.iter().all(|(rowid, _)| *rowid > 3));
+
+    assert_eq!(search(&vlite, Some(vec![3])), vec![expected[2].clone()]);
+
+    delete(&vlite, 1);
+    let remaining: Vec<String> = search(&vlite, None)
+        .into_iter()
+        .map(|(_, external_id)| external_id)
+        .collect();
+    assert_eq!(remaining, vec!["2", "3"]);
+}
+
+#[test]
+fn upsert_replaces_point_under_mapped_rowid() {
+    let vlite = setup_vlite(RowidStrategy::Offset(1000));
+
+    let point = InsertPoint::builder()
+        .collection_name("synced")
+        .id(1)
+        .vector(vec![5.0, 0.0])
+        .payload_insert_query("insert into synced(rowid, external_id) values (?1, 1)")
+        .build()
+        .unwrap();
+    vlite
+        .upsert_batch(vec![point])
+        .expect("upsert should succeed");
+
+    assert_eq!(vlite.count_where("synced", "1 = 1").unwrap(), 3);
+    let rowids: Vec<u64> = search(&vlite, None)
+        .into_iter()
+        .map(|(rowid, _)| rowid)
+        .collect();
+    assert_eq!(rowids, vec![1002, 1003, 1001]);
+}
+
+#[test]
+fn strategy_survives_rename() {
+    let vlite = setup_vlite(RowidStrategy::Offset(1000));
+
+    vlite
+        .rename_collection("synced", "renamed")
+        .expect("rename should succeed");
+    insert(&vlite, "renamed", 4);
+
+    assert_eq!(vlite.count_where("renamed", "rowid = 1004").unwrap(), 1);
+}
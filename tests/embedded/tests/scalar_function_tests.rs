@@ -0,0 +1,77 @@
+//! Tests for SqliteConnectionCustomizer::with_scalar_function
+//!
+//! These tests verify:
+//! - A registered function can be called from a payload search query
+//! - The function is available on every pooled connection
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+fn customizer() -> Box<SqliteConnectionCustomizer> {
+    SqliteConnectionCustomizer::new()
+        .with_scalar_function("double", 1, |ctx| Ok(ctx.get::<i64>(0)? * 2))
+}
+
+#[test]
+fn scalar_function_is_usable_in_payload_search_query() {
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(customizer())
+        .build(SqliteConnectionManager::memory())
+        .expect("create pool");
+    let vlite = VectorXLite::new(pool).expect("create VectorXLite");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("items")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema("create table items (rowid integer primary key, price integer)")
+        .build()
+        .unwrap();
+    vlite.create_collection(config).unwrap();
+    for (id, price) in [(1, 10), (2, 25), (3, 40)] {
+        let point = InsertPoint::builder()
+            .collection_name("items")
+            .id(id)
+            .vector(vec![id as f32, 0.0])
+            .payload_insert_query(format!(
+                "insert into items(rowid, price) values (?1, {})",
+                price
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+
+    let search_point = SearchPoint::builder()
+        .collection_name("items")
+        .vector(vec![0.0, 0.0])
+        .top_k(3)
+        .payload_search_query(
+            "select rowid, double(price) as doubled from items where double(price) > 30",
+        )
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).expect("search should succeed");
+
+    let doubled: Vec<&str> = results.iter().map(|row| row["doubled"].as_str()).collect();
+    assert_eq!(doubled, vec!["50", "80"]);
+}
+
+#[test]
+fn scalar_function_is_registered_on_every_pooled_connection() {
+    let pool = Pool::builder()
+        .max_size(3)
+        .connection_customizer(customizer())
+        .build(SqliteConnectionManager::memory())
+        .expect("create pool");
+
+    // Holding the connections forces the pool to hand out distinct ones
+    let connections: Vec<_> = (0..3).map(|_| pool.get().unwrap()).collect();
+    for (i, conn) in connections.iter().enumerate() {
+        let doubled: i64 = conn
+            .query_row("SELECT double(?1)", [i as i64 + 1], |row| row.get(0))
+            .expect("double() should be registered");
+        assert_eq!(doubled, 2 * (i as i64 + 1));
+    }
+}
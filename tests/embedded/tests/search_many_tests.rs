@@ -0,0 +1,91 @@
+//! Tests for search_many method in VectorXLite
+//!
+//! These tests verify:
+//! - Each query gets the same results `search` would return, in query order
+//! - An invalid query fails the whole call
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+fn setup_vlite() -> VectorXLite {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool).expect("create VectorXLite");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("points")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema("create table points (rowid integer primary key)")
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for id in 1..=10 {
+        let point = InsertPoint::builder()
+            .collection_name("points")
+            .id(id)
+            .vector(vec![id as f32, 0.0])
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+    vlite
+}
+
+fn query(x: f32) -> SearchPoint {
+    SearchPoint::builder()
+        .collection_name("points")
+        .vector(vec![x, 0.0])
+        .top_k(3)
+        .build()
+        .unwrap()
+}
+
+fn rowids(results: &[std::collections::HashMap<String, String>]) -> Vec<String> {
+    results.iter().map(|row| row["rowid"].clone()).collect()
+}
+
+#[test]
+fn search_many_returns_results_in_query_order() {
+    let vlite = setup_vlite();
+
+    let results = vlite
+        .search_many(vec![query(1.0), query(10.0), query(5.0)])
+        .expect("search_many should succeed");
+
+    assert_eq!(results.len(), 3);
+    for (results, x) in results.iter().zip([1.0, 10.0, 5.0]) {
+        let expected = vlite.search(query(x)).unwrap();
+        assert_eq!(rowids(results), rowids(&expected));
+    }
+    assert_eq!(rowids(&results[1]), vec!["10", "9", "8"]);
+}
+
+#[test]
+fn search_many_with_invalid_query_fails() {
+    let vlite = setup_vlite();
+    let missing = SearchPoint::builder()
+        .collection_name("missing")
+        .vector(vec![0.0, 0.0])
+        .top_k(3)
+        .build()
+        .unwrap();
+
+    let result = vlite.search_many(vec![query(1.0), missing]);
+
+    assert!(
+        matches!(result, Err(VecXError::SqlError(_))),
+        "{:?}",
+        result.map(|results| results.len())
+    );
+}
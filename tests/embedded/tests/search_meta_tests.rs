@@ -0,0 +1,253 @@
+//! Tests for search_with_meta method in VectorXLite
+//!
+//! These tests verify:
+//! - `truncated` is false when fewer than top_k points match
+//! - `truncated` is true when the matches reach top_k
+//! - `total_candidates` counts the payload rows matching the filter
+//! - `plan` and `estimated_candidates` report the plan chosen by filter selectivity
+//! - `approximate` is false for the exact search fallback and true for HNSW searches
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+fn setup_vlite() -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(5)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    (vlite, pool)
+}
+
+fn create_scored_collection(vlite: &VectorXLite, count: u64) {
+    let config = CollectionConfigBuilder::default()
+        .collection_name("scored")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema("create table scored (rowid integer primary key, score integer)")
+        .build()
+        .unwrap();
+
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for i in 1..=count {
+        let point = InsertPoint::builder()
+            .collection_name("scored")
+            .id(i)
+            .vector(vec![i as f32, 0.0])
+            .payload_insert_query(format!(
+                "insert into scored(rowid, score) values (?1, {})",
+                i
+            ))
+            .build()
+            .expect("Builder should create insert point.");
+
+        vlite.insert(point).expect("insert should be successful.");
+    }
+}
+
+#[test]
+fn fewer_matches_than_top_k_is_not_truncated() {
+    let (vlite, _) = setup_vlite();
+    create_scored_collection(&vlite, 5);
+
+    let search_point = SearchPoint::builder()
+        .collection_name("scored")
+        .vector(vec![0.0, 0.0])
+        .top_k(10)
+        .build()
+        .unwrap();
+
+    let response = vlite.search_with_meta(search_point).unwrap();
+
+    assert_eq!(response.results.len(), 5);
+    assert!(!response.truncated);
+    assert_eq!(response.total_candidates, None);
+}
+
+#[test]
+fn matches_reaching_top_k_are_truncated() {
+    let (vlite, _) = setup_vlite();
+    create_scored_collection(&vlite, 20);
+
+    let search_point = SearchPoint::builder()
+        .collection_name("scored")
+        .vector(vec![0.0, 0.0])
+        .top_k(5)
+        .build()
+        .unwrap();
+
+    let response = vlite.search_with_meta(search_point).unwrap();
+
+    assert_eq!(response.results.len(), 5);
+    assert!(response.truncated);
+}
+
+#[test]
+fn payload_filter_reports_total_candidates() {
+    let (vlite, _) = setup_vlite();
+    create_scored_collection(&vlite, 20);
+
+    let builder = || {
+        SearchPoint::builder()
+            .collection_name("scored")
+            .vector(vec![0.0, 0.0])
+            .payload_search_query("select rowid, score from scored where score > 12")
+    };
+
+    let truncated = vlite.search_with_meta(builder().top_k(3).build().unwrap()).unwrap();
+    assert_eq!(truncated.results.len(), 3);
+    assert!(truncated.truncated);
+    assert_eq!(truncated.total_candidates, Some(8));
+
+    let complete = vlite.search_with_meta(builder().top_k(10).build().unwrap()).unwrap();
+    assert_eq!(complete.results.len(), 8);
+    assert!(!complete.truncated);
+    assert_eq!(complete.total_candidates, Some(8));
+}
+
+#[test]
+fn total_candidates_honors_restrict_to_ids() {
+    let (vlite, _) = setup_vlite();
+    create_scored_collection(&vlite, 20);
+
+    let search_point = SearchPoint::builder()
+        .collection_name("scored")
+        .vector(vec![0.0, 0.0])
+        .top_k(10)
+        .payload_search_query("select rowid, score from scored where score > 12")
+        .restrict_to_ids(vec![1, 13, 14, 99])
+        .build()
+        .unwrap();
+
+    let response = vlite.search_with_meta(search_point).unwrap();
+
+    assert_eq!(response.total_candidates, Some(2));
+    assert_eq!(response.results.len(), 2);
+    assert!(!response.truncated);
+}
+
+#[test]
+fn search_without_filter_reports_vector_only_plan() {
+    let (vlite, _) = setup_vlite();
+    create_scored_collection(&vlite, 5);
+
+    let search_point = SearchPoint::builder()
+        .collection_name("scored")
+        .vector(vec![0.0, 0.0])
+        .top_k(3)
+        .build()
+        .unwrap();
+    let response = vlite.search_with_meta(search_point).unwrap();
+
+    assert_eq!(response.plan, PlanKind::VectorOnly);
+    assert_eq!(response.estimated_candidates, 0);
+}
+
+#[test]
+fn selective_filter_reports_metadata_first_plan() {
+    let (vlite, _) = setup_vlite();
+    create_scored_collection(&vlite, 20);
+
+    let search_point = SearchPoint::builder()
+        .collection_name("scored")
+        .vector(vec![0.0, 0.0])
+        .top_k(3)
+        .payload_search_query("select rowid, score from scored where score > 12")
+        .build()
+        .unwrap();
+    let response = vlite.search_with_meta(search_point).unwrap();
+
+    assert_eq!(response.plan, PlanKind::MetadataFirst);
+    assert_eq!(response.estimated_candidates, 8);
+    assert_eq!(response.results.len(), 3);
+}
+
+#[test]
+fn unselective_filter_reports_vector_first_plan() {
+    let (vlite, pool) = setup_vlite();
+    create_scored_collection(&vlite, 20);
+    // Payload rows without vectors are enough to make the filter unselective.
+    pool.get()
+        .unwrap()
+        .execute_batch(
+            "with recursive ids(id) as (select 21 union all select id + 1 from ids where id < 12000)
+             insert into scored(rowid, score) select id, id from ids",
+        )
+        .unwrap();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("scored")
+        .vector(vec![0.0, 0.0])
+        .top_k(3)
+        .payload_search_query("select rowid, score from scored where score > 12")
+        .build()
+        .unwrap();
+    let response = vlite.search_with_meta(search_point).unwrap();
+
+    assert_eq!(response.plan, PlanKind::VectorFirst);
+    assert_eq!(response.estimated_candidates, 11_988);
+    let rowids: Vec<&str> = response
+        .results
+        .iter()
+        .map(|row| row["rowid"].as_str())
+        .collect();
+    assert_eq!(rowids, vec!["13", "14", "15"]);
+}
+
+const SCORE_FILTER: &str = "select rowid, score from scored where score > 2";
+
+fn top_3_search(payload_search_query: Option<&str>) -> SearchPoint {
+    let builder = SearchPoint::builder()
+        .collection_name("scored")
+        .vector(vec![0.0, 0.0])
+        .top_k(3);
+    match payload_search_query {
+        Some(query) => builder.payload_search_query(query),
+        None => builder,
+    }
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn hnsw_search_is_approximate() {
+    let (vlite, _) = setup_vlite();
+    create_scored_collection(&vlite, 5);
+
+    for payload_search_query in [None, Some(SCORE_FILTER)] {
+        let response = vlite
+            .search_with_meta(top_3_search(payload_search_query))
+            .unwrap();
+
+        assert!(response.approximate, "{:?}", payload_search_query);
+    }
+}
+
+#[test]
+fn exact_search_fallback_is_not_approximate() {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+    let config = VectorXLiteConfig::default().with_exact_below(100);
+    let vlite = VectorXLite::with_config(pool, config).expect("create VectorXLite");
+    create_scored_collection(&vlite, 5);
+
+    for payload_search_query in [None, Some(SCORE_FILTER)] {
+        let response = vlite
+            .search_with_meta(top_3_search(payload_search_query))
+            .unwrap();
+
+        assert!(!response.approximate, "{:?}", payload_search_query);
+        assert_eq!(response.results.len(), 3);
+    }
+}
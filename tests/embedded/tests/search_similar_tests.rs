@@ -0,0 +1,140 @@
+//! Tests for search_similar method in VectorXLite
+//!
+//! These tests verify:
+//! - The nearest neighbors of a stored point are the other points of its cluster
+//! - exclude_self leaves the point itself out and still returns top_k points
+//! - Without exclude_self the point ranks first at distance 0
+//! - Points mapped through a rowid strategy are found by their id
+//! - A missing id or a payload-only collection fails with InvalidQueryError
+
+use vector_xlite::{error::VecXError, types::*, VectorXLite};
+
+fn setup_vlite(rowid_strategy: RowidStrategy) -> VectorXLite {
+    let vlite = VectorXLite::builder()
+        .memory()
+        .build()
+        .expect("build in-memory instance");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("points")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .rowid_strategy(rowid_strategy)
+        .payload_table_schema("create table points (rowid integer primary key, cluster text)")
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    // Three clusters of three points around (0, 0), (100, 0) and (0, 100)
+    let centers = [("a", 0.0, 0.0), ("b", 100.0, 0.0), ("c", 0.0, 100.0)];
+    for (cluster_index, (cluster, x, y)) in centers.iter().enumerate() {
+        for offset in 0..3u64 {
+            let id = cluster_index as u64 * 3 + offset + 1;
+            let point = InsertPoint::builder()
+                .collection_name("points")
+                .id(id)
+                .vector(vec![x + offset as f32, *y])
+                .payload_insert_query(format!(
+                    "insert into points(rowid, cluster) values (?1, '{}')",
+                    cluster
+                ))
+                .build()
+                .unwrap();
+            vlite.insert(point).expect("insert should be successful.");
+        }
+    }
+    vlite
+}
+
+fn rowids(results: &[SearchResult]) -> Vec<&str> {
+    results.iter().map(|row| row["rowid"].as_str()).collect()
+}
+
+#[test]
+fn search_similar_ranks_cluster_mates_highest() {
+    let vlite = setup_vlite(RowidStrategy::Direct);
+
+    let results = vlite
+        .search_similar("points", 5, 2, true)
+        .expect("search_similar should succeed");
+
+    let mut neighbors = rowids(&results);
+    neighbors.sort();
+    assert_eq!(neighbors, vec!["4", "6"]);
+}
+
+#[test]
+fn search_similar_without_exclude_self_returns_the_point_first() {
+    let vlite = setup_vlite(RowidStrategy::Direct);
+
+    let results = vlite
+        .search_similar("points", 7, 3, false)
+        .expect("search_similar should succeed");
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0]["rowid"], "7");
+    assert_eq!(results[0]["distance"].parse::<f32>().unwrap(), 0.0);
+    assert!(rowids(&results)
+        .iter()
+        .all(|id| ["7", "8", "9"].contains(id)));
+}
+
+#[test]
+fn search_similar_excludes_self_across_clusters() {
+    let vlite = setup_vlite(RowidStrategy::Direct);
+
+    let results = vlite
+        .search_similar("points", 1, 5, true)
+        .expect("search_similar should succeed");
+
+    assert_eq!(results.len(), 5);
+    assert!(!rowids(&results).contains(&"1"));
+    assert_eq!(rowids(&results)[..2], ["2", "3"]);
+}
+
+#[test]
+fn search_similar_maps_ids_through_rowid_strategy() {
+    let vlite = setup_vlite(RowidStrategy::Offset(1000));
+
+    let results = vlite
+        .search_similar("points", 2, 2, true)
+        .expect("search_similar should succeed");
+
+    let mut neighbors = rowids(&results);
+    neighbors.sort();
+    assert_eq!(neighbors, vec!["1001", "1003"]);
+}
+
+#[test]
+fn search_similar_of_missing_id_fails() {
+    let vlite = setup_vlite(RowidStrategy::Direct);
+
+    let result = vlite.search_similar("points", 42, 3, true);
+
+    assert!(
+        matches!(result, Err(VecXError::InvalidQueryError(_))),
+        "{:?}",
+        result
+    );
+}
+
+#[test]
+fn search_similar_of_payload_only_collection_fails() {
+    let vlite = setup_vlite(RowidStrategy::Direct);
+    let config = CollectionConfigBuilder::default()
+        .collection_name("events")
+        .payload_table_schema("create table events (rowid integer primary key)")
+        .payload_only(true)
+        .build()
+        .unwrap();
+    vlite.create_collection(config).unwrap();
+
+    let result = vlite.search_similar("events", 1, 3, true);
+
+    assert!(
+        matches!(result, Err(VecXError::InvalidQueryError(_))),
+        "{:?}",
+        result
+    );
+}
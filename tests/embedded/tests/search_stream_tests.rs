@@ -0,0 +1,150 @@
+//! Tests for search_stream method in VectorXLite
+//!
+//! These tests verify:
+//! - A fully consumed stream yields the same rows as `search`
+//! - The pooled connection is held while the stream is alive
+//! - Dropping a partially consumed stream returns the connection to the pool
+//! - Results longer than one page are streamed without gaps or repeats
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+fn setup_vlite() -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    vlite
+        .create_collection(
+            CollectionConfigBuilder::default()
+                .collection_name("items")
+                .distance(DistanceFunction::L2)
+                .vector_dimension(2)
+                .payload_table_schema("create table items (rowid integer primary key, name text)")
+                .build()
+                .unwrap(),
+        )
+        .expect("collection should be created");
+
+    for id in 1..=20u64 {
+        let point = InsertPoint::builder()
+            .collection_name("items")
+            .id(id)
+            .vector(vec![id as f32, 0.0])
+            .payload_insert_query(format!(
+                "insert into items(rowid, name) values (?1, 'item {}')",
+                id
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+
+    (vlite, pool)
+}
+
+fn items_search() -> SearchPoint {
+    SearchPoint::builder()
+        .collection_name("items")
+        .vector(vec![0.0, 0.0])
+        .top_k(20)
+        .payload_search_query("select rowid, name from items")
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn stream_yields_same_rows_as_search() {
+    let (vlite, _pool) = setup_vlite();
+
+    let expected = vlite.search(items_search()).unwrap();
+    let streamed: Vec<SearchResult> = vlite
+        .search_stream(items_search())
+        .expect("stream should start")
+        .collect::<Result<_, _>>()
+        .expect("every row should be read");
+
+    assert_eq!(streamed.len(), 20);
+    assert_eq!(streamed, expected);
+}
+
+#[test]
+fn stream_spans_several_pages() {
+    let (vlite, _pool) = setup_vlite();
+    let points = (21..=2500u64)
+        .map(|id| {
+            InsertPoint::builder()
+                .collection_name("items")
+                .id(id)
+                .vector(vec![id as f32, 0.0])
+                .payload_insert_query(format!(
+                    "insert into items(rowid, name) values (?1, 'item {}')",
+                    id
+                ))
+                .build()
+                .unwrap()
+        })
+        .collect();
+    vlite
+        .insert_batch(points, BatchOptions::default())
+        .expect("batch insert should be successful.");
+
+    let search_point = || {
+        SearchPoint::builder()
+            .collection_name("items")
+            .vector(vec![0.0, 0.0])
+            .top_k(2500)
+            .payload_search_query("select rowid, name from items")
+            .build()
+            .unwrap()
+    };
+    let expected = vlite.search(search_point()).unwrap();
+    let streamed: Vec<SearchResult> = vlite
+        .search_stream(search_point())
+        .expect("stream should start")
+        .collect::<Result<_, _>>()
+        .expect("every row should be read");
+
+    assert_eq!(streamed.len(), 2500);
+    assert_eq!(streamed, expected);
+}
+
+#[test]
+fn dropping_partially_consumed_stream_releases_connection() {
+    let (vlite, pool) = setup_vlite();
+
+    let mut stream = vlite
+        .search_stream(items_search())
+        .expect("stream should start");
+    let first_three: Vec<SearchResult> = stream.by_ref().take(3).map(Result::unwrap).collect();
+
+    assert_eq!(first_three[0]["rowid"], "1");
+    assert_eq!(first_three[2]["rowid"], "3");
+    assert_eq!(pool.state().idle_connections, 0);
+    assert!(pool.try_get().is_none());
+
+    drop(stream);
+
+    assert_eq!(pool.state().idle_connections, 1);
+    assert_eq!(vlite.search(items_search()).unwrap().len(), 20);
+}
+
+#[test]
+fn stream_of_missing_collection_fails_before_iterating() {
+    let (vlite, pool) = setup_vlite();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("missing")
+        .vector(vec![0.0, 0.0])
+        .top_k(5)
+        .build()
+        .unwrap();
+
+    assert!(vlite.search_stream(search_point).is_err());
+    assert_eq!(pool.state().idle_connections, 1);
+}
@@ -0,0 +1,120 @@
+//! Tests for shutdown method in VectorXLite
+//!
+//! These tests verify:
+//! - Shutdown leaves a file database without `-wal`/`-shm` files, even while the pool lives
+//! - Payload rows and vectors are present after reopening
+//! - In-memory and single-connection instances shut down cleanly
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::Connection;
+use std::fs;
+use std::path::Path;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+const DB_PATH: &str = "/tmp/vxlite_test_shutdown.db";
+const IDX_PATH: &str = "/tmp/vxlite_test_shutdown.idx";
+
+fn cleanup() {
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = fs::remove_file(format!("{}{}", DB_PATH, suffix));
+    }
+    let _ = fs::remove_file(IDX_PATH);
+}
+
+fn create_pool(manager: SqliteConnectionManager, max_size: u32) -> Pool<SqliteConnectionManager> {
+    Pool::builder()
+        .max_size(max_size)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool")
+}
+
+fn create_notes(vlite: &VectorXLite, index_file_path: Option<&str>) {
+    let mut builder = CollectionConfigBuilder::default()
+        .collection_name("notes")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema("create table notes (rowid integer primary key, body text)");
+    if let Some(path) = index_file_path {
+        builder = builder.index_file_path(path);
+    }
+    vlite
+        .create_collection(builder.build().unwrap())
+        .expect("collection should be created");
+
+    for id in 1..=5u64 {
+        let point = InsertPoint::builder()
+            .collection_name("notes")
+            .id(id)
+            .vector(vec![id as f32, 0.0])
+            .payload_insert_query(format!(
+                "insert into notes(rowid, body) values (?1, 'note {}')",
+                id
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+}
+
+fn search_bodies(vlite: &VectorXLite) -> Vec<String> {
+    let search_point = SearchPoint::builder()
+        .collection_name("notes")
+        .vector(vec![0.0, 0.0])
+        .top_k(10)
+        .payload_search_query("select rowid, body from notes")
+        .build()
+        .unwrap();
+    vlite
+        .search(search_point)
+        .unwrap()
+        .into_iter()
+        .map(|mut row| row.remove("body").unwrap())
+        .collect()
+}
+
+#[test]
+fn shutdown_leaves_clean_file_database() {
+    cleanup();
+
+    let pool = create_pool(SqliteConnectionManager::file(DB_PATH), 2);
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    create_notes(&vlite, Some(IDX_PATH));
+
+    vlite.shutdown().expect("shutdown should be successful.");
+
+    // The pool is still alive, so only shutdown can have closed the database
+    assert!(!Path::new(&format!("{}-wal", DB_PATH)).exists());
+    assert!(!Path::new(&format!("{}-shm", DB_PATH)).exists());
+    assert!(Path::new(IDX_PATH).exists());
+    drop(pool);
+
+    let reopened = VectorXLite::new(create_pool(SqliteConnectionManager::file(DB_PATH), 1))
+        .expect("create VectorXLite");
+    assert_eq!(
+        search_bodies(&reopened),
+        vec!["note 1", "note 2", "note 3", "note 4", "note 5"]
+    );
+
+    drop(reopened);
+    cleanup();
+}
+
+#[test]
+fn shutdown_in_memory_database_succeeds() {
+    let vlite = VectorXLite::new(create_pool(SqliteConnectionManager::memory(), 1))
+        .expect("create VectorXLite");
+    create_notes(&vlite, None);
+
+    vlite.shutdown().expect("shutdown should be successful.");
+}
+
+#[test]
+fn shutdown_single_connection_instance_succeeds() {
+    let vlite = VectorXLite::from_connection(Connection::open_in_memory().unwrap())
+        .expect("create VectorXLite");
+    create_notes(&vlite, None);
+
+    vlite.shutdown().expect("shutdown should be successful.");
+}
@@ -4,9 +4,14 @@
 //! - Export snapshot from in-memory DB
 //! - Export snapshot from file-backed DB with index files
 //! - Import snapshot and restore
+//! - Import progress reporting
 //! - Large collection snapshots
 //! - Follower recovery scenarios
 //! - Atomic restore correctness
+//! - Read-only views of a past snapshot
+//! - Metadata-only exports and imports checked against expected metadata
+//! - Low-disk imports staging files next to their destinations
+//! - Encrypted snapshots restored with the right key and rejected with a wrong one
 
 mod common;
 
@@ -247,6 +252,41 @@ fn test_import_with_data() {
     assert!(result.files_restored > 0, "Should have restored files");
 }
 
+#[test]
+fn test_import_with_progress_reports_each_chunk() {
+    let src_ctx = TestContext::memory();
+
+    let coll = src_ctx.collection("progress_test").dimension(64).create();
+    for i in 1..=200 {
+        let vector: Vec<f32> = (0..64).map(|j| (i * j) as f32 / 1000.0).collect();
+        coll.insert_vector(i, vector);
+    }
+
+    let config = SnapshotConfig::default().with_chunk_size(1024);
+    let exporter = SnapshotExporter::new(src_ctx.pool.clone(), config);
+    let chunks: Vec<SnapshotChunk> = exporter.export().expect("Export should succeed").collect();
+    let file_chunks = chunks.iter().filter(|c| c.file_chunk.is_some()).count();
+    assert!(file_chunks > 1, "Snapshot should span multiple chunks");
+
+    let dest_ctx = TestContext::memory();
+    let importer = SnapshotImporter::with_defaults(dest_ctx.pool.clone());
+    let mut progress = Vec::new();
+    let result = importer
+        .import_with_progress(chunks, |p| progress.push(p))
+        .expect("Import should succeed");
+
+    assert_eq!(progress.len(), file_chunks, "Callback should run per file chunk");
+    for pair in progress.windows(2) {
+        assert!(pair[1].bytes_restored > pair[0].bytes_restored);
+        assert!(pair[1].files_restored >= pair[0].files_restored);
+    }
+    assert_eq!(progress[0].current_file, "database.db");
+
+    let last = progress.last().unwrap();
+    assert_eq!(last.bytes_restored, result.bytes_restored);
+    assert_eq!(last.files_restored, result.files_restored);
+}
+
 #[test]
 fn test_import_preserves_data() {
     let src_ctx = TestContext::memory();
@@ -435,6 +475,74 @@ fn test_snapshot_checksum_validation() {
     );
 }
 
+fn file_infos(metadata: &SnapshotMetadata) -> Vec<(String, u64, String)> {
+    let mut files: Vec<(String, u64, String)> = metadata
+        .files
+        .iter()
+        .map(|file| (file.file_name.clone(), file.file_size, file.checksum.clone()))
+        .collect();
+    files.sort();
+    files
+}
+
+#[test]
+fn test_export_metadata_matches_full_export() {
+    let ctx = TestContext::file();
+
+    let coll = ctx.collection("catalog_test").dimension(3).create();
+    coll.insert_vector(1, vec![1.0, 0.0, 0.0]);
+    coll.insert_vector(2, vec![0.0, 1.0, 0.0]);
+
+    let exporter = SnapshotExporter::with_defaults(ctx.pool.clone());
+    let metadata = exporter
+        .export_metadata()
+        .expect("Metadata export should succeed");
+    let chunks: Vec<SnapshotChunk> = exporter.export().expect("Export should succeed").collect();
+    let embedded = chunks[0].metadata.as_ref().unwrap();
+
+    assert!(!metadata.files.is_empty(), "Metadata should list files");
+    assert_eq!(file_infos(&metadata), file_infos(embedded));
+    assert_eq!(metadata.total_size, embedded.total_size);
+    assert_eq!(metadata.version, embedded.version);
+    assert_eq!(metadata.checksum, embedded.checksum);
+}
+
+#[test]
+fn test_import_with_expected_metadata() {
+    let src_ctx = TestContext::memory();
+
+    let coll = src_ctx.collection("expected_test").dimension(3).create();
+    coll.insert_vector(1, vec![1.0, 0.0, 0.0]);
+
+    let exporter = SnapshotExporter::with_defaults(src_ctx.pool.clone());
+    let metadata = exporter
+        .export_metadata()
+        .expect("Metadata export should succeed");
+
+    // Matching snapshot imports
+    let chunks: Vec<SnapshotChunk> = exporter.export().expect("Export should succeed").collect();
+    let dest_ctx = TestContext::memory();
+    let result = SnapshotImporter::with_defaults(dest_ctx.pool.clone())
+        .with_expected_metadata(metadata.clone())
+        .import(chunks.into_iter())
+        .expect("Import should succeed");
+    assert!(result.success, "Import should be successful");
+
+    // Snapshot of a changed database is rejected
+    coll.insert_vector(2, vec![0.0, 1.0, 0.0]);
+    let chunks: Vec<SnapshotChunk> = exporter.export().expect("Export should succeed").collect();
+    let dest_ctx = TestContext::memory();
+    let err = SnapshotImporter::with_defaults(dest_ctx.pool.clone())
+        .with_expected_metadata(metadata)
+        .import(chunks.into_iter())
+        .expect_err("Import of a different snapshot should fail");
+    assert!(
+        err.to_string().contains("expected metadata"),
+        "Unexpected error: {}",
+        err
+    );
+}
+
 // ============================================================================
 // Configuration Tests
 // ============================================================================
@@ -562,3 +670,681 @@ fn test_multiple_collections_export() {
         "Should have data from all collections"
     );
 }
+
+// ============================================================================
+// Encrypted Snapshot Tests
+// ============================================================================
+
+mod encrypted_snapshot {
+    use super::*;
+    use vector_xlite::error::VecXError;
+
+    const KEY: [u8; 32] = [7; 32];
+
+    fn export_notes(config: SnapshotConfig) -> Vec<SnapshotChunk> {
+        let ctx = TestContext::memory();
+        let coll = ctx
+            .collection("enc_notes")
+            .dimension(3)
+            .with_payload("name TEXT")
+            .create();
+        coll.insert(1)
+            .vector(vec![1.0, 0.0, 0.0])
+            .payload("INSERT INTO enc_notes (rowid, name) VALUES (?1, 'Alice')")
+            .execute_ok();
+        coll.insert(2)
+            .vector(vec![0.0, 1.0, 0.0])
+            .payload("INSERT INTO enc_notes (rowid, name) VALUES (?1, 'Bob')")
+            .execute_ok();
+
+        SnapshotExporter::new(ctx.pool.clone(), config)
+            .export()
+            .expect("Export should succeed")
+            .collect()
+    }
+
+    fn import(
+        chunks: Vec<SnapshotChunk>,
+        config: SnapshotConfig,
+    ) -> (TestContext, Result<(), VecXError>) {
+        let dest_ctx = TestContext::memory();
+        let result = SnapshotImporter::new(dest_ctx.pool.clone(), config)
+            .import(chunks)
+            .map(|_| ());
+        (dest_ctx, result)
+    }
+
+    fn assert_data_parsing_error(result: Result<(), VecXError>) {
+        assert!(
+            matches!(result, Err(VecXError::DataParsingError(_))),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn encrypted_snapshot_round_trips_with_the_right_key() {
+        let chunks = export_notes(SnapshotConfig::default().with_encryption(KEY));
+
+        let metadata = chunks[0].metadata.as_ref().unwrap();
+        assert!(metadata.encrypted);
+        let database_chunk = chunks
+            .iter()
+            .filter_map(|chunk| chunk.file_chunk.as_ref())
+            .find(|file_chunk| file_chunk.file_name == "database.db" && file_chunk.offset == 0)
+            .unwrap();
+        assert!(
+            !database_chunk.data.starts_with(b"SQLite format 3"),
+            "chunk data should not be plaintext"
+        );
+
+        let (dest_ctx, result) = import(chunks, SnapshotConfig::default().with_encryption(KEY));
+        result.expect("Import with the right key should succeed");
+
+        let names = dest_ctx
+            .query_sql("SELECT name FROM enc_notes ORDER BY rowid", |row| {
+                row.get::<_, String>(0)
+            })
+            .unwrap();
+        assert_eq!(names, vec!["Alice", "Bob"]);
+    }
+
+    #[test]
+    fn encrypted_snapshot_with_wrong_key_fails_cleanly() {
+        let chunks = export_notes(SnapshotConfig::default().with_encryption(KEY));
+
+        let (dest_ctx, result) = import(chunks, SnapshotConfig::default().with_encryption([8; 32]));
+
+        assert_data_parsing_error(result);
+        assert!(!dest_ctx.vlite.collection_exists("enc_notes").unwrap());
+    }
+
+    #[test]
+    fn tampered_encrypted_chunk_fails() {
+        let mut chunks = export_notes(SnapshotConfig::default().with_encryption(KEY));
+        let file_chunk = chunks
+            .iter_mut()
+            .find_map(|chunk| chunk.file_chunk.as_mut())
+            .unwrap();
+        let last = file_chunk.data.len() - 1;
+        file_chunk.data[last] ^= 1;
+
+        let (_dest_ctx, result) = import(chunks, SnapshotConfig::default().with_encryption(KEY));
+
+        assert_data_parsing_error(result);
+    }
+
+    #[test]
+    fn importer_key_must_match_snapshot_encryption() {
+        let encrypted = export_notes(SnapshotConfig::default().with_encryption(KEY));
+        let (_dest_ctx, result) = import(encrypted, SnapshotConfig::default());
+        assert_data_parsing_error(result);
+
+        let plaintext = export_notes(SnapshotConfig::default());
+        assert!(!plaintext[0].metadata.as_ref().unwrap().encrypted);
+        let (_dest_ctx, result) = import(plaintext, SnapshotConfig::default().with_encryption(KEY));
+        assert_data_parsing_error(result);
+    }
+}
+
+// ============================================================================
+// Consistent Export Tests
+// ============================================================================
+
+mod consistent_export {
+    use super::*;
+    use r2d2::Pool;
+    use r2d2_sqlite::SqliteConnectionManager;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+    const DB_PATH: &str = "/tmp/vxlite_test_consistent_export.db";
+    const IDX_PATH: &str = "/tmp/vxlite_test_consistent_export.idx";
+    const RESTORE_DIR: &str = "/tmp/vxlite_test_consistent_export_restore";
+
+    fn cleanup() {
+        let _ = fs::remove_file(DB_PATH);
+        let _ = fs::remove_file(IDX_PATH);
+        let _ = fs::remove_dir_all(RESTORE_DIR);
+    }
+
+    fn insert_point(vlite: &VectorXLite, id: u64) {
+        let point = InsertPoint::builder()
+            .collection_name("live")
+            .id(id)
+            .vector(vec![id as f32, 0.0])
+            .payload_insert_query(format!("insert into live(rowid, n) values (?1, {})", id))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+
+    /// Writes the snapshot files into `dir` so they can be inspected directly.
+    fn write_snapshot_files(chunks: &[SnapshotChunk], dir: &str) {
+        let _ = fs::remove_dir_all(dir);
+        fs::create_dir_all(dir).unwrap();
+        let mut contents: HashMap<String, Vec<u8>> = HashMap::new();
+        for file_chunk in chunks.iter().filter_map(|c| c.file_chunk.as_ref()) {
+            contents
+                .entry(file_chunk.file_name.clone())
+                .or_default()
+                .extend_from_slice(&file_chunk.data);
+        }
+        for (name, data) in contents {
+            fs::write(PathBuf::from(dir).join(name), data).unwrap();
+        }
+    }
+
+    /// Asserts every payload row of the exported database has a vector in the exported
+    /// index and the index holds no vector without a payload row.
+    ///
+    /// Each snapshot gets its own directory: the pool checking it may close its
+    /// connection, saving the loaded index back to `index_0.idx`, only after returning.
+    fn assert_snapshot_consistent(chunks: &[SnapshotChunk], max_id: u64, snapshot: usize) {
+        let dir = format!("{}/{}", RESTORE_DIR, snapshot);
+        write_snapshot_files(chunks, &dir);
+
+        let pool = Pool::builder()
+            .max_size(1)
+            .connection_customizer(SqliteConnectionCustomizer::new())
+            .build(SqliteConnectionManager::memory())
+            .unwrap();
+        let conn = pool.get().unwrap();
+        conn.execute_batch(&format!(
+            "ATTACH DATABASE '{dir}/database.db' AS snap;
+             CREATE VIRTUAL TABLE exported USING vectorlite(vector_embedding float32[2] l2, hnsw(max_elements=100000), {dir}/index_0.idx);"
+        ))
+        .unwrap();
+
+        let payload_rows: i64 = conn
+            .query_row("SELECT count(*) FROM snap.live", [], |row| row.get(0))
+            .unwrap();
+        let indexed_payload_rows: i64 = conn
+            .query_row(
+                "SELECT count(*) FROM exported WHERE rowid IN (SELECT rowid FROM snap.live)",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        let indexed_rows: i64 = conn
+            .query_row(
+                "SELECT count(*) FROM exported WHERE rowid IN (
+                     WITH RECURSIVE ids(id) AS (SELECT 1 UNION ALL SELECT id + 1 FROM ids WHERE id < ?1)
+                     SELECT id FROM ids)",
+                [max_id as i64],
+                |row| row.get(0),
+            )
+            .unwrap();
+
+        assert!(payload_rows >= 20, "snapshot should hold the initial rows");
+        assert_eq!(indexed_payload_rows, payload_rows, "payload row without a vector");
+        assert_eq!(indexed_rows, payload_rows, "vector without a payload row");
+    }
+
+    #[test]
+    fn consistent_export_during_concurrent_inserts() {
+        cleanup();
+
+        // A single connection, since every connection holds its own in-memory index
+        let pool = Pool::builder()
+            .max_size(1)
+            .connection_customizer(SqliteConnectionCustomizer::new())
+            .build(SqliteConnectionManager::file(DB_PATH))
+            .expect("create pool");
+        let vlite = VectorXLite::new(pool.clone()).unwrap();
+
+        let config = CollectionConfigBuilder::default()
+            .collection_name("live")
+            .distance(DistanceFunction::L2)
+            .vector_dimension(2)
+            .payload_table_schema("create table live (rowid integer primary key, n integer)")
+            .index_file_path(IDX_PATH)
+            .build()
+            .unwrap();
+        vlite.create_collection(config).unwrap();
+        for id in 1..=20 {
+            insert_point(&vlite, id);
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let writer = {
+            let pool = pool.clone();
+            let stop = stop.clone();
+            thread::spawn(move || {
+                let writer = VectorXLite::new(pool).unwrap();
+                let mut id = 21;
+                while !stop.load(Ordering::Relaxed) {
+                    insert_point(&writer, id);
+                    id += 1;
+                }
+                id
+            })
+        };
+
+        let exporter = SnapshotExporter::new(
+            pool.clone(),
+            SnapshotConfig::default().with_consistent_export(true),
+        );
+        let snapshots: Vec<Vec<SnapshotChunk>> = (0..3)
+            .map(|_| {
+                thread::sleep(std::time::Duration::from_millis(20));
+                exporter.export_to_memory().expect("Export should succeed")
+            })
+            .collect();
+
+        stop.store(true, Ordering::Relaxed);
+        let next_id = writer.join().unwrap();
+        assert!(next_id > 21, "writer should have inserted during the export");
+
+        for (snapshot, chunks) in snapshots.iter().enumerate() {
+            assert_snapshot_consistent(chunks, next_id, snapshot);
+        }
+
+        cleanup();
+    }
+}
+
+// ============================================================================
+// Online Export Tests
+// ============================================================================
+
+mod online_export {
+    use super::*;
+    use r2d2::Pool;
+    use r2d2_sqlite::SqliteConnectionManager;
+    use std::thread;
+    use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+    const DB_PATH: &str = "/tmp/vxlite_test_online_export.db";
+    const INITIAL_ROWS: u64 = 200;
+    const CONCURRENT_ROWS: u64 = 50;
+
+    fn cleanup() {
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = fs::remove_file(format!("{}{}", DB_PATH, suffix));
+        }
+    }
+
+    fn insert_note(vlite: &VectorXLite, id: u64) {
+        let point = InsertPoint::builder()
+            .collection_name("notes")
+            .id(id)
+            .vector(vec![id as f32, 0.0])
+            .payload_insert_query(format!(
+                "insert into notes(rowid, body) values (?1, '{}')",
+                "x".repeat(2048)
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+
+    #[test]
+    fn online_export_runs_alongside_concurrent_inserts() {
+        cleanup();
+
+        // A single connection, so a backup holding it would stall the writer
+        let pool = Pool::builder()
+            .max_size(1)
+            .connection_customizer(SqliteConnectionCustomizer::new())
+            .build(SqliteConnectionManager::file(DB_PATH))
+            .expect("create pool");
+        let vlite = VectorXLite::new(pool.clone()).unwrap();
+
+        let config = CollectionConfigBuilder::default()
+            .collection_name("notes")
+            .distance(DistanceFunction::L2)
+            .vector_dimension(2)
+            .payload_table_schema("create table notes (rowid integer primary key, body text)")
+            .build()
+            .unwrap();
+        vlite.create_collection(config).unwrap();
+        for id in 1..=INITIAL_ROWS {
+            insert_note(&vlite, id);
+        }
+
+        let writer = {
+            let pool = pool.clone();
+            thread::spawn(move || {
+                let writer = VectorXLite::new(pool).unwrap();
+                for id in INITIAL_ROWS + 1..=INITIAL_ROWS + CONCURRENT_ROWS {
+                    insert_note(&writer, id);
+                }
+            })
+        };
+
+        let exporter =
+            SnapshotExporter::new(pool.clone(), SnapshotConfig::default().with_online(true));
+        let chunks = exporter
+            .export_to_memory()
+            .expect("Online export should succeed");
+
+        writer.join().expect("writer should finish");
+        assert_eq!(
+            vlite.count_where("notes", "1 = 1").unwrap(),
+            INITIAL_ROWS + CONCURRENT_ROWS
+        );
+
+        let snapshot_path = PathBuf::from("/tmp/vxlite_test_online_export_snapshot.db");
+        let data: Vec<u8> = chunks
+            .iter()
+            .filter_map(|c| c.file_chunk.as_ref())
+            .filter(|c| c.file_name == "database.db")
+            .flat_map(|c| c.data.iter().copied())
+            .collect();
+        fs::write(&snapshot_path, data).unwrap();
+
+        let snapshot = rusqlite::Connection::open(&snapshot_path).unwrap();
+        let integrity: String = snapshot
+            .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+            .unwrap();
+        let rows: u64 = snapshot
+            .query_row("SELECT count(*) FROM notes", [], |row| row.get(0))
+            .unwrap();
+
+        assert_eq!(integrity, "ok");
+        assert!(
+            (INITIAL_ROWS..=INITIAL_ROWS + CONCURRENT_ROWS).contains(&rows),
+            "snapshot has {} rows",
+            rows
+        );
+
+        drop(snapshot);
+        let _ = fs::remove_file(&snapshot_path);
+        cleanup();
+    }
+}
+
+// ============================================================================
+// Read-Only Snapshot View Tests
+// ============================================================================
+
+mod read_only_view {
+    use super::*;
+    use r2d2::Pool;
+    use r2d2_sqlite::SqliteConnectionManager;
+    use vector_xlite::error::VecXError;
+    use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+    const DB_PATH: &str = "/tmp/vxlite_test_read_only_view.db";
+    const IDX_PATH: &str = "/tmp/vxlite_test_read_only_view.idx";
+    const TEMP_DIR: &str = "/tmp/vxlite_test_read_only_view_tmp";
+
+    fn cleanup() {
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = fs::remove_file(format!("{}{}", DB_PATH, suffix));
+        }
+        let _ = fs::remove_file(IDX_PATH);
+        let _ = fs::remove_dir_all(TEMP_DIR);
+    }
+
+    fn insert_doc(vlite: &VectorXLite, id: u64, title: &str) {
+        let point = InsertPoint::builder()
+            .collection_name("docs")
+            .id(id)
+            .vector(vec![id as f32, 0.0])
+            .payload_insert_query(format!(
+                "insert into docs(rowid, title) values (?1, '{}')",
+                title
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+
+    fn titles(vlite: &VectorXLite) -> Vec<String> {
+        let search_point = SearchPoint::builder()
+            .collection_name("docs")
+            .vector(vec![0.0, 0.0])
+            .top_k(10)
+            .payload_search_query("select rowid, title from docs")
+            .build()
+            .unwrap();
+        vlite
+            .search(search_point)
+            .unwrap()
+            .into_iter()
+            .map(|mut row| row.remove("title").unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn open_read_only_searches_past_snapshot() {
+        cleanup();
+        fs::create_dir_all(TEMP_DIR).unwrap();
+
+        // A single connection, since every connection holds its own in-memory index
+        let pool = Pool::builder()
+            .max_size(1)
+            .connection_customizer(SqliteConnectionCustomizer::new())
+            .build(SqliteConnectionManager::file(DB_PATH))
+            .expect("create pool");
+        let vlite = VectorXLite::new(pool.clone()).unwrap();
+        let config = CollectionConfigBuilder::default()
+            .collection_name("docs")
+            .distance(DistanceFunction::L2)
+            .vector_dimension(2)
+            .payload_table_schema("create table docs (rowid integer primary key, title text)")
+            .index_file_path(IDX_PATH)
+            .build()
+            .unwrap();
+        vlite.create_collection(config).unwrap();
+        insert_doc(&vlite, 1, "old 1");
+        insert_doc(&vlite, 2, "old 2");
+
+        let snapshot_config = SnapshotConfig::default()
+            .with_consistent_export(true)
+            .with_temp_dir(PathBuf::from(TEMP_DIR));
+        let chunks = SnapshotExporter::new(pool.clone(), snapshot_config.clone())
+            .export_to_memory()
+            .expect("Export should succeed");
+
+        // The live database moves on after the snapshot
+        insert_doc(&vlite, 3, "new 3");
+        vlite
+            .delete(
+                DeletePoint::builder()
+                    .collection_name("docs")
+                    .id(1)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+        vlite.flush("docs").unwrap();
+
+        let live_index = fs::read(IDX_PATH).unwrap();
+        let importer = SnapshotImporter::new(pool.clone(), snapshot_config);
+        let view = importer
+            .open_read_only(chunks)
+            .expect("snapshot should open read-only");
+
+        assert_eq!(titles(&view), vec!["old 1", "old 2"]);
+        assert_eq!(view.count_where("docs", "1 = 1").unwrap(), 2);
+        assert_eq!(titles(&vlite), vec!["old 2", "new 3"]);
+
+        let write = view.update_where("docs", "title = 'changed'", "1 = 1");
+        assert!(matches!(write, Err(VecXError::SqlError(_))), "{:?}", write);
+
+        drop(view);
+        assert_eq!(
+            fs::read_dir(TEMP_DIR).unwrap().count(),
+            0,
+            "temporary snapshot files should be removed"
+        );
+        assert_eq!(
+            fs::read(IDX_PATH).unwrap(),
+            live_index,
+            "live index file should be untouched"
+        );
+        assert_eq!(titles(&vlite), vec!["old 2", "new 3"]);
+
+        drop(vlite);
+        drop(pool);
+        cleanup();
+    }
+}
+
+// ============================================================================
+// Low-Disk Import Tests
+// ============================================================================
+
+mod low_disk_import {
+    use super::*;
+    use r2d2::Pool;
+    use r2d2_sqlite::SqliteConnectionManager;
+    use std::path::Path;
+    use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+    const DATA_DIR: &str = "/tmp/vxlite_test_low_disk_import";
+    const DB_PATH: &str = "/tmp/vxlite_test_low_disk_import/node.db";
+    const IDX_PATH: &str = "/tmp/vxlite_test_low_disk_import/node.idx";
+    const TEMP_DIR: &str = "/tmp/vxlite_test_low_disk_import_tmp";
+    const POINTS: u64 = 2000;
+    const DIMENSION: usize = 32;
+
+    fn cleanup() {
+        let _ = fs::remove_dir_all(DATA_DIR);
+        let _ = fs::remove_dir_all(TEMP_DIR);
+    }
+
+    fn file_pool() -> Pool<SqliteConnectionManager> {
+        // A single connection, since every connection holds its own in-memory index
+        Pool::builder()
+            .max_size(1)
+            .connection_customizer(SqliteConnectionCustomizer::new())
+            .build(SqliteConnectionManager::file(DB_PATH))
+            .expect("create pool")
+    }
+
+    fn vector(id: u64) -> Vec<f32> {
+        (0..DIMENSION).map(|j| ((id as usize * 7 + j) % 97) as f32).collect()
+    }
+
+    /// Total size of the files under `dir`, in bytes.
+    fn dir_size(dir: &Path) -> u64 {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return 0;
+        };
+        entries
+            .filter_map(Result::ok)
+            .map(|entry| match entry.metadata() {
+                Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+                Ok(metadata) => metadata.len(),
+                Err(_) => 0,
+            })
+            .sum()
+    }
+
+    /// Exports a file-backed collection, then removes its files as a fresh node would
+    /// have none.
+    fn export_snapshot() -> Vec<SnapshotChunk> {
+        let pool = file_pool();
+        let vlite = VectorXLite::new(pool.clone()).unwrap();
+        let config = CollectionConfigBuilder::default()
+            .collection_name("docs")
+            .distance(DistanceFunction::L2)
+            .vector_dimension(DIMENSION as u16)
+            .payload_table_schema("create table docs (rowid integer primary key, title text)")
+            .index_file_path(IDX_PATH)
+            .build()
+            .unwrap();
+        vlite.create_collection(config).unwrap();
+        let points = (1..=POINTS)
+            .map(|id| {
+                InsertPoint::builder()
+                    .collection_name("docs")
+                    .id(id)
+                    .vector(vector(id))
+                    .payload_insert_query(format!(
+                        "insert into docs(rowid, title) values (?1, 'doc {}')",
+                        id
+                    ))
+                    .build()
+                    .unwrap()
+            })
+            .collect();
+        let result = vlite.insert_batch(points, BatchOptions::default()).unwrap();
+        assert!(result.failed.is_empty());
+
+        let snapshot_config = SnapshotConfig::default()
+            .with_consistent_export(true)
+            .with_chunk_size(16 * 1024)
+            .with_temp_dir(PathBuf::from(TEMP_DIR));
+        let chunks = SnapshotExporter::new(pool.clone(), snapshot_config)
+            .export_to_memory()
+            .expect("Export should succeed");
+
+        drop(vlite);
+        drop(pool);
+        cleanup();
+        chunks
+    }
+
+    #[test]
+    fn low_disk_import_stages_files_beside_destination() {
+        cleanup();
+        fs::create_dir_all(DATA_DIR).unwrap();
+        fs::create_dir_all(TEMP_DIR).unwrap();
+
+        let chunks = export_snapshot();
+        let total_size = chunks[0].metadata.as_ref().unwrap().total_size;
+        let file_chunks = chunks.iter().filter(|c| c.file_chunk.is_some()).count();
+        assert!(file_chunks > 10, "Snapshot should span many chunks");
+
+        fs::create_dir_all(DATA_DIR).unwrap();
+        fs::create_dir_all(TEMP_DIR).unwrap();
+        let pool = file_pool();
+        pool.get().unwrap();
+        let baseline = dir_size(Path::new(DATA_DIR));
+
+        let config = SnapshotConfig::default()
+            .with_temp_dir(PathBuf::from(TEMP_DIR))
+            .with_low_disk(true);
+        let importer = SnapshotImporter::new(pool.clone(), config)
+            .with_index_paths(vec![IDX_PATH.to_string()]);
+        let mut peak_extra = 0;
+        let mut peak_temp = 0;
+        let result = importer
+            .import_with_progress(chunks, |_| {
+                peak_extra = peak_extra.max(dir_size(Path::new(DATA_DIR)) - baseline);
+                peak_temp = peak_temp.max(dir_size(Path::new(TEMP_DIR)));
+            })
+            .expect("Import should succeed");
+
+        assert!(result.success);
+        assert_eq!(peak_temp, 0, "nothing should be written to the temp directory");
+        assert!(
+            peak_extra <= total_size,
+            "staged {} bytes for a {} byte snapshot",
+            peak_extra,
+            total_size
+        );
+        let leftovers: Vec<_> = fs::read_dir(DATA_DIR)
+            .unwrap()
+            .filter_map(Result::ok)
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .filter(|name| name.ends_with(".import"))
+            .collect();
+        assert!(leftovers.is_empty(), "staged files left behind: {:?}", leftovers);
+
+        let vlite = VectorXLite::new(pool.clone()).unwrap();
+        assert_eq!(vlite.count_where("docs", "1 = 1").unwrap(), POINTS);
+        for id in [1, POINTS / 2, POINTS] {
+            let search_point = SearchPoint::builder()
+                .collection_name("docs")
+                .vector(vector(id))
+                .top_k(1)
+                .payload_search_query("select rowid, title from docs")
+                .build()
+                .unwrap();
+            let results = vlite.search(search_point).unwrap();
+            assert_eq!(results[0]["distance"].parse::<f32>().unwrap(), 0.0);
+        }
+
+        drop(vlite);
+        drop(pool);
+        cleanup();
+    }
+}
@@ -0,0 +1,137 @@
+//! Tests for sparse vectors in InsertPoint and SearchPoint
+//!
+//! These tests verify:
+//! - Points inserted and searched in sparse form match the dense form
+//! - Out-of-range indices and conflicting vectors are rejected by the builders
+
+use std::collections::HashMap;
+use vector_xlite::{types::*, VectorXLite};
+
+const DIMENSION: usize = 16;
+
+fn setup_vlite() -> VectorXLite {
+    let vlite = VectorXLite::builder()
+        .memory()
+        .build()
+        .expect("create VectorXLite");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("terms")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(DIMENSION as u16)
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+    vlite
+}
+
+/// Two non-zero entries per point, at positions derived from its id.
+fn sparse(id: u64) -> HashMap<usize, f32> {
+    HashMap::from([
+        ((id as usize) % DIMENSION, 1.0 + id as f32 / 10.0),
+        ((id as usize * 7) % DIMENSION, -(id as f32) / 20.0),
+    ])
+}
+
+fn dense(entries: &HashMap<usize, f32>) -> Vec<f32> {
+    let mut vector = vec![0.0; DIMENSION];
+    for (&index, &value) in entries {
+        vector[index] = value;
+    }
+    vector
+}
+
+fn insert_all(vlite: &VectorXLite, use_sparse: bool) {
+    for id in 1..=20 {
+        let builder = InsertPoint::builder().collection_name("terms").id(id);
+        let builder = if use_sparse {
+            builder.sparse_vector(sparse(id), DIMENSION)
+        } else {
+            builder.vector(dense(&sparse(id)))
+        };
+        vlite
+            .insert(builder.build().unwrap())
+            .expect("insert should be successful.");
+    }
+}
+
+fn search(vlite: &VectorXLite, query: SearchPointBuilder) -> Vec<(String, String)> {
+    let search_point = query.collection_name("terms").top_k(5).build().unwrap();
+    vlite
+        .search(search_point)
+        .expect("search should succeed")
+        .into_iter()
+        .map(|row| (row["rowid"].clone(), row["distance"].clone()))
+        .collect()
+}
+
+#[test]
+fn sparse_insert_and_search_match_dense_form() {
+    let dense_vlite = setup_vlite();
+    insert_all(&dense_vlite, false);
+    let sparse_vlite = setup_vlite();
+    insert_all(&sparse_vlite, true);
+
+    let query = HashMap::from([(3, 1.2), (5, -0.4)]);
+    let expected = search(&dense_vlite, SearchPoint::builder().vector(dense(&query)));
+
+    assert_eq!(expected.len(), 5);
+    assert_eq!(
+        search(
+            &sparse_vlite,
+            SearchPoint::builder().sparse_vector(query.clone(), DIMENSION)
+        ),
+        expected
+    );
+    assert_eq!(
+        search(
+            &dense_vlite,
+            SearchPoint::builder().sparse_vector(query, DIMENSION)
+        ),
+        expected
+    );
+}
+
+#[test]
+fn out_of_range_index_is_rejected() {
+    let entries = HashMap::from([(2, 1.0), (DIMENSION, 1.0)]);
+
+    let insert = InsertPoint::builder()
+        .collection_name("terms")
+        .id(1)
+        .sparse_vector(entries.clone(), DIMENSION)
+        .build();
+    let search = SearchPoint::builder()
+        .collection_name("terms")
+        .sparse_vector(entries, DIMENSION)
+        .build();
+
+    let message = "sparse_vector index 16 is out of range for dimension 16.";
+    assert_eq!(insert.unwrap_err(), message);
+    assert_eq!(search.unwrap_err(), message);
+}
+
+#[test]
+fn sparse_and_dense_vector_together_are_rejected() {
+    let insert = InsertPoint::builder()
+        .collection_name("terms")
+        .id(1)
+        .vector(vec![0.0; DIMENSION])
+        .sparse_vector(HashMap::from([(0, 1.0)]), DIMENSION)
+        .build();
+    let search = SearchPoint::builder()
+        .collection_name("terms")
+        .vector(vec![0.0; DIMENSION])
+        .sparse_vector(HashMap::from([(0, 1.0)]), DIMENSION)
+        .build();
+
+    assert_eq!(
+        insert.unwrap_err(),
+        "Only one of vector, vector_bytes or sparse_vector may be provided."
+    );
+    assert_eq!(
+        search.unwrap_err(),
+        "Only one of vector or sparse_vector may be provided."
+    );
+}
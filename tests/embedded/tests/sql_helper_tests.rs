@@ -307,6 +307,57 @@ mod select_transformation {
             "Expected at most 5 results above average"
         );
     }
+
+    #[test]
+    fn search_with_multi_line_cte_query() {
+        let (vlite, _) = setup_vlite();
+
+        let config = CollectionConfigBuilder::default()
+            .collection_name("cte_test")
+            .vector_dimension(3)
+            .payload_table_schema("CREATE TABLE cte_test (rowid INTEGER PRIMARY KEY, score REAL)")
+            .build()
+            .unwrap();
+        vlite.create_collection(config).expect("create collection");
+
+        for i in 1..=10 {
+            let point = InsertPoint::builder()
+                .collection_name("cte_test")
+                .id(i)
+                .vector(vec![i as f32, 0.0, 0.0])
+                .payload_insert_query(format!(
+                    "INSERT INTO cte_test(rowid, score) VALUES (?1, {})",
+                    i as f32 * 10.0
+                ))
+                .build()
+                .unwrap();
+            vlite.insert(point).expect("insert");
+        }
+
+        let search = SearchPoint::builder()
+            .collection_name("cte_test")
+            .vector(vec![5.0, 0.0, 0.0])
+            .top_k(10)
+            .payload_search_query(
+                "-- only high scores\n\
+                 WITH high AS (\n\
+                     SELECT rowid, score FROM cte_test WHERE score >= 70\n\
+                 )\n\
+                 SELECT rowid,\n\
+                        score\n\
+                 FROM high",
+            )
+            .build()
+            .unwrap();
+
+        let response = vlite.search_with_meta(search).expect("search with CTE");
+        assert_eq!(response.results.len(), 4);
+        assert_eq!(response.total_candidates, Some(4));
+        for result in &response.results {
+            let score: f64 = result.get("score").unwrap().parse().unwrap();
+            assert!(score >= 70.0);
+        }
+    }
 }
 
 // ============================================================================
@@ -596,4 +647,57 @@ mod row_parsing {
         assert_eq!(null_row.get("col").unwrap(), "NULL");
         assert_eq!(empty_row.get("col").unwrap(), "");
     }
+
+    #[test]
+    fn typed_values_convert_bool_and_date_columns() {
+        let (vlite, _) = setup_vlite();
+
+        let config = CollectionConfigBuilder::default()
+            .collection_name("typed")
+            .vector_dimension(3)
+            .payload_table_schema(
+                "CREATE TABLE typed (rowid INTEGER PRIMARY KEY, is_active BOOLEAN, created_on DATE, score REAL)",
+            )
+            .build()
+            .unwrap();
+        vlite.create_collection(config).expect("create collection");
+
+        for (id, active) in [(1, "TRUE"), (2, "0")] {
+            let point = InsertPoint::builder()
+                .collection_name("typed")
+                .id(id)
+                .vector(vec![id as f32, 0.0, 0.0])
+                .payload_insert_query(format!(
+                    "INSERT INTO typed(rowid, is_active, created_on, score) VALUES (?1, {}, '2024-01-1{}', 7.0)",
+                    active, id
+                ))
+                .build()
+                .unwrap();
+            vlite.insert(point).expect("insert");
+        }
+
+        let search = SearchPoint::builder()
+            .collection_name("typed")
+            .vector(vec![1.0, 0.0, 0.0])
+            .top_k(2)
+            .payload_search_query("SELECT rowid, is_active, created_on, score FROM typed")
+            .build()
+            .unwrap();
+
+        let results = vlite.search_typed(search).expect("typed search");
+        assert_eq!(results.len(), 2);
+
+        let first = &results[0];
+        assert_eq!(first["rowid"].as_i64(), Some(1));
+        assert_eq!(first["is_active"], SqlValue::Integer(1));
+        assert_eq!(first["is_active"].as_bool(), Some(true));
+        assert_eq!(first["created_on"].as_str(), Some("2024-01-11"));
+        assert_eq!(first["score"].as_f64(), Some(7.0));
+        assert_eq!(first["score"].as_i64(), Some(7));
+        assert!(first["distance"].as_f64().is_some());
+
+        let second = &results[1];
+        assert_eq!(second["is_active"].as_bool(), Some(false));
+        assert_eq!(second["created_on"].as_str(), Some("2024-01-12"));
+    }
 }
@@ -0,0 +1,127 @@
+//! Tests for strict_ip collections
+//!
+//! These tests verify:
+//! - Vectors of similar magnitude are accepted
+//! - A vector far off the mean norm is rejected without writing anything
+//! - strict_ip is rejected for non-IP collections and is off by default
+//! - The norm bookkeeping follows renames
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+fn setup_vlite(strict_ip: bool) -> VectorXLite {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool).expect("create VectorXLite");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("embeddings")
+        .distance(DistanceFunction::IP)
+        .vector_dimension(2)
+        .strict_ip(strict_ip)
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+    vlite
+}
+
+fn point(collection_name: &str, id: u64, vector: Vec<f32>) -> InsertPoint {
+    InsertPoint::builder()
+        .collection_name(collection_name)
+        .id(id)
+        .vector(vector)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn similar_magnitudes_are_accepted() {
+    let vlite = setup_vlite(true);
+
+    vlite
+        .insert(point("embeddings", 1, vec![1.0, 0.0]))
+        .unwrap();
+    vlite
+        .insert(point("embeddings", 2, vec![0.0, 2.0]))
+        .unwrap();
+    vlite
+        .insert(point("embeddings", 3, vec![0.3, 0.4]))
+        .unwrap();
+
+    assert_eq!(vlite.count_where("embeddings", "1 = 1").unwrap(), 3);
+}
+
+#[test]
+fn mixed_magnitudes_are_rejected() {
+    let vlite = setup_vlite(true);
+    vlite
+        .insert(point("embeddings", 1, vec![1.0, 0.0]))
+        .unwrap();
+    vlite
+        .insert(point("embeddings", 2, vec![0.6, 0.8]))
+        .unwrap();
+
+    let err = vlite
+        .insert(point("embeddings", 3, vec![300.0, 400.0]))
+        .expect_err("a vector 500x the mean norm should be rejected");
+    assert!(matches!(err, VecXError::InvalidQueryError(_)));
+    assert!(err.to_string().contains("strict_ip"), "{}", err);
+
+    let err = vlite
+        .insert(point("embeddings", 4, vec![0.001, 0.0]))
+        .expect_err("a vector far below the mean norm should be rejected");
+    assert!(matches!(err, VecXError::InvalidQueryError(_)));
+
+    assert_eq!(vlite.count_where("embeddings", "1 = 1").unwrap(), 2);
+}
+
+#[test]
+fn mixed_magnitudes_are_accepted_without_strict_ip() {
+    let vlite = setup_vlite(false);
+
+    vlite
+        .insert(point("embeddings", 1, vec![1.0, 0.0]))
+        .unwrap();
+    vlite
+        .insert(point("embeddings", 2, vec![300.0, 400.0]))
+        .unwrap();
+
+    assert_eq!(vlite.count_where("embeddings", "1 = 1").unwrap(), 2);
+}
+
+#[test]
+fn strict_ip_requires_ip_distance() {
+    let result = CollectionConfigBuilder::default()
+        .collection_name("embeddings")
+        .distance(DistanceFunction::Cosine)
+        .strict_ip(true)
+        .build();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn strict_ip_survives_rename() {
+    let vlite = setup_vlite(true);
+    vlite
+        .insert(point("embeddings", 1, vec![1.0, 0.0]))
+        .unwrap();
+
+    vlite
+        .rename_collection("embeddings", "renamed")
+        .expect("rename should succeed");
+
+    let err = vlite
+        .insert(point("renamed", 2, vec![300.0, 400.0]))
+        .expect_err("the renamed collection should still be strict");
+    assert!(matches!(err, VecXError::InvalidQueryError(_)));
+}
@@ -0,0 +1,106 @@
+//! Tests for SqliteConnectionCustomizer temp storage settings
+//!
+//! These tests verify:
+//! - `with_temp_store` sets `PRAGMA temp_store` on pooled connections
+//! - `set_temp_dir` sets SQLite's temp directory
+//! - A large `ORDER BY` search completes with either setting
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::fs;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+const ROWS: i64 = 20_000;
+
+fn setup_vlite(
+    customizer: Box<SqliteConnectionCustomizer>,
+) -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(customizer)
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("events")
+        .payload_table_schema("create table events (rowid integer primary key, body text)")
+        .payload_only(true)
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    pool.get()
+        .unwrap()
+        .execute(
+            "insert into events(rowid, body)
+             with recursive n(i) as (select 1 union all select i + 1 from n where i < ?1)
+             select i, hex(randomblob(64)) from n",
+            [ROWS],
+        )
+        .expect("load rows");
+    (vlite, pool)
+}
+
+fn sort_all_rows(vlite: &VectorXLite) {
+    let search_point = SearchPoint::builder()
+        .collection_name("events")
+        .vector(vec![])
+        .payload_search_query("select rowid, body from events")
+        .order_by("body", Direction::Desc)
+        .top_k(ROWS)
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).expect("search should succeed");
+
+    assert_eq!(results.len(), ROWS as usize);
+    assert!(results
+        .windows(2)
+        .all(|pair| pair[0]["body"] >= pair[1]["body"]));
+}
+
+#[test]
+fn large_order_by_with_memory_temp_store() {
+    let customizer = SqliteConnectionCustomizer::new().with_temp_store(TempStore::Memory);
+    let (vlite, pool) = setup_vlite(customizer);
+
+    let temp_store: i64 = pool
+        .get()
+        .unwrap()
+        .query_row("PRAGMA temp_store", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(temp_store, 2);
+
+    sort_all_rows(&vlite);
+}
+
+#[test]
+fn large_order_by_with_custom_temp_dir() {
+    let dir = "/tmp/vxlite_test_temp_dir";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+
+    SqliteConnectionCustomizer::set_temp_dir(dir).expect("temp dir should be set");
+    let customizer = SqliteConnectionCustomizer::new().with_temp_store(TempStore::File);
+    let (vlite, pool) = setup_vlite(customizer);
+
+    let temp_dir: String = pool
+        .get()
+        .unwrap()
+        .query_row("PRAGMA temp_store_directory", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(temp_dir, dir);
+
+    sort_all_rows(&vlite);
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn missing_temp_dir_is_rejected() {
+    let result = SqliteConnectionCustomizer::set_temp_dir("/tmp/vxlite_test_missing_temp_dir/x");
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,80 @@
+//! Tests for clamping top_k to a collection's max_elements
+//!
+//! These tests verify:
+//! - A top_k above max_elements returns every stored point instead of failing
+//! - Post-filter searches, which widen k, are clamped as well
+//! - A top_k below max_elements is still honored
+
+use vector_xlite::{types::*, VectorXLite};
+
+fn setup_vlite() -> VectorXLite {
+    let vlite = VectorXLite::builder()
+        .memory()
+        .build()
+        .expect("create VectorXLite");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("points")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .max_elements(10)
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for id in 1..=6 {
+        let point = InsertPoint::builder()
+            .collection_name("points")
+            .id(id)
+            .vector(vec![id as f32, 0.0])
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+    vlite
+}
+
+fn search(top_k: i64) -> SearchPointBuilder {
+    SearchPoint::builder()
+        .collection_name("points")
+        .vector(vec![0.0, 0.0])
+        .top_k(top_k)
+}
+
+#[test]
+fn top_k_above_max_elements_returns_all_points() {
+    let vlite = setup_vlite();
+
+    for top_k in [11, 1_000_000, i64::MAX] {
+        let results = vlite
+            .search(search(top_k).build().unwrap())
+            .expect("search should succeed");
+
+        assert_eq!(results.len(), 6, "top_k {}", top_k);
+    }
+}
+
+#[test]
+fn post_filter_search_with_huge_top_k_is_clamped() {
+    let vlite = setup_vlite();
+
+    let search_point = search(100_000_000_000)
+        .payload_search_query("select rowid from points where rowid > 2")
+        .filter_strategy(FilterStrategy::PostFilter)
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).expect("search should succeed");
+
+    assert_eq!(results.len(), 4);
+}
+
+#[test]
+fn top_k_below_max_elements_is_honored() {
+    let vlite = setup_vlite();
+
+    let results = vlite.search(search(3).build().unwrap()).unwrap();
+
+    let rowids: Vec<&str> = results.iter().map(|row| row["rowid"].as_str()).collect();
+    assert_eq!(rowids, vec!["1", "2", "3"]);
+}
@@ -0,0 +1,157 @@
+//! Tests for update_where method in VectorXLite
+//!
+//! These tests verify:
+//! - Payload rows matching a predicate are updated and counted
+//! - Vectors stay searchable after the update
+//! - Vector tables and rowid assignments are rejected
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+fn setup_vlite() -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(5)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    (vlite, pool)
+}
+
+fn create_labeled_collection(vlite: &VectorXLite) {
+    let config = CollectionConfigBuilder::default()
+        .collection_name("labeled")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema(
+            "create table labeled (rowid integer primary key, rating integer, category text)",
+        )
+        .build()
+        .unwrap();
+
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for i in 1..=10u64 {
+        let point = InsertPoint::builder()
+            .collection_name("labeled")
+            .id(i)
+            .vector(vec![i as f32, 0.0])
+            .payload_insert_query(format!(
+                "insert into labeled(rowid, rating, category) values (?1, {}, 'new')",
+                i
+            ))
+            .build()
+            .expect("Builder should create insert point.");
+
+        vlite.insert(point).expect("insert should be successful.");
+    }
+}
+
+fn search_category(vlite: &VectorXLite, category: &str) -> Vec<i64> {
+    let search_point = SearchPoint::builder()
+        .collection_name("labeled")
+        .vector(vec![0.0, 0.0])
+        .top_k(10)
+        .payload_search_query(format!(
+            "select rowid, category from labeled where category = '{}'",
+            category
+        ))
+        .build()
+        .unwrap();
+
+    vlite
+        .search(search_point)
+        .expect("search should succeed")
+        .iter()
+        .map(|row| row["rowid"].parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn update_where_relabels_matching_rows() {
+    let (vlite, _) = setup_vlite();
+    create_labeled_collection(&vlite);
+
+    let updated = vlite
+        .update_where("labeled", "category = 'archived'", "rating <= 3")
+        .expect("update should succeed");
+
+    assert_eq!(updated, 3);
+    assert_eq!(search_category(&vlite, "archived"), vec![1, 2, 3]);
+    assert_eq!(search_category(&vlite, "new"), vec![4, 5, 6, 7, 8, 9, 10]);
+}
+
+#[test]
+fn update_where_keeps_vectors_searchable() {
+    let (vlite, _) = setup_vlite();
+    create_labeled_collection(&vlite);
+
+    vlite
+        .update_where("labeled", "rating = rating * 10", "1 = 1")
+        .unwrap();
+
+    let search_point = SearchPoint::builder()
+        .collection_name("labeled")
+        .vector(vec![0.0, 0.0])
+        .top_k(2)
+        .payload_search_query("select rowid, rating from labeled")
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["rowid"], "1");
+    assert_eq!(results[0]["rating"], "10");
+}
+
+#[test]
+fn update_where_without_matches_updates_nothing() {
+    let (vlite, _) = setup_vlite();
+    create_labeled_collection(&vlite);
+
+    assert_eq!(
+        vlite
+            .update_where("labeled", "category = 'x'", "rating > 100")
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn update_where_rejects_vector_table() {
+    let (vlite, _) = setup_vlite();
+    create_labeled_collection(&vlite);
+
+    let err = vlite
+        .update_where("vt_vector_labeled", "vector_embedding = NULL", "1 = 1")
+        .expect_err("vector table should be rejected");
+
+    assert!(matches!(err, VecXError::InvalidQueryError(_)));
+}
+
+#[test]
+fn update_where_rejects_rowid_assignment() {
+    let (vlite, _) = setup_vlite();
+    create_labeled_collection(&vlite);
+
+    let err = vlite
+        .update_where("labeled", "category = 'x', rowid = rowid + 100", "1 = 1")
+        .expect_err("rowid assignment should be rejected");
+
+    assert!(matches!(err, VecXError::InvalidQueryError(_)));
+    assert_eq!(search_category(&vlite, "new").len(), 10);
+}
+
+#[test]
+fn update_where_on_missing_collection_is_error() {
+    let (vlite, _) = setup_vlite();
+
+    assert!(vlite.update_where("missing", "a = 1", "1 = 1").is_err());
+}
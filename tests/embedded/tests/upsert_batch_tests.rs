@@ -0,0 +1,175 @@
+//! Tests for upsert_batch method in VectorXLite
+//!
+//! These tests verify:
+//! - Upserting over existing ids replaces both the vector and the payload
+//! - New ids in the same batch are inserted
+//! - Replaced vectors leave no stale HNSW entries, also after reopening a file database
+//! - A failing payload row rolls back the whole batch, including earlier vectors
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::collections::HashMap;
+use std::fs;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, types::*, VectorXLite};
+
+fn create_vlite(manager: SqliteConnectionManager) -> VectorXLite {
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    VectorXLite::new(pool).expect("create VectorXLite")
+}
+
+fn create_docs_collection(vlite: &VectorXLite, index_file_path: Option<&str>) {
+    let mut builder = CollectionConfigBuilder::default()
+        .collection_name("docs")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema("create table docs (rowid integer primary key, title text)");
+    if let Some(path) = index_file_path {
+        builder = builder.index_file_path(path);
+    }
+
+    vlite
+        .create_collection(builder.build().unwrap())
+        .expect("collection should be created");
+}
+
+fn doc(id: u64, x: f32, title: &str) -> InsertPoint {
+    InsertPoint::builder()
+        .collection_name("docs")
+        .id(id)
+        .vector(vec![x, 0.0])
+        .payload_insert_query(format!(
+            "insert into docs(rowid, title) values (?1, '{}')",
+            title
+        ))
+        .build()
+        .unwrap()
+}
+
+fn insert_originals(vlite: &VectorXLite) {
+    for id in 1..=4u64 {
+        vlite
+            .insert(doc(id, id as f32, &format!("v1-{}", id)))
+            .expect("insert should be successful.");
+    }
+}
+
+fn search_near(vlite: &VectorXLite, x: f32) -> Vec<HashMap<String, String>> {
+    let search_point = SearchPoint::builder()
+        .collection_name("docs")
+        .vector(vec![x, 0.0])
+        .top_k(10)
+        .payload_search_query("select rowid, title from docs")
+        .build()
+        .unwrap();
+    vlite.search(search_point).unwrap()
+}
+
+fn rowids(results: &[HashMap<String, String>]) -> Vec<i64> {
+    results
+        .iter()
+        .map(|row| row["rowid"].parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn upsert_batch_replaces_existing_points() {
+    let vlite = create_vlite(SqliteConnectionManager::memory());
+    create_docs_collection(&vlite, None);
+    insert_originals(&vlite);
+
+    vlite
+        .upsert_batch(vec![
+            doc(1, 101.0, "v2-1"),
+            doc(2, 102.0, "v2-2"),
+            doc(5, 5.0, "v1-5"),
+        ])
+        .expect("upsert should be successful.");
+
+    let results = search_near(&vlite, 0.0);
+    assert_eq!(rowids(&results), vec![3, 4, 5, 1, 2]);
+    assert_eq!(results[3]["title"], "v2-1");
+    assert_eq!(
+        results[3]["distance"].parse::<f32>().unwrap(),
+        101.0 * 101.0
+    );
+    assert_eq!(vlite.count_where("docs", "1 = 1").unwrap(), 5);
+}
+
+#[test]
+fn replaced_vectors_leave_no_stale_entries() {
+    let vlite = create_vlite(SqliteConnectionManager::memory());
+    create_docs_collection(&vlite, None);
+    insert_originals(&vlite);
+
+    vlite
+        .upsert_batch(vec![doc(1, 101.0, "v2-1"), doc(1, 201.0, "v3-1")])
+        .unwrap();
+
+    // A search at the old and intermediate positions only finds the latest vector once
+    for x in [1.0, 101.0] {
+        let results = search_near(&vlite, x);
+        let ones: Vec<_> = results.iter().filter(|row| row["rowid"] == "1").collect();
+        assert_eq!(ones.len(), 1);
+        assert_eq!(ones[0]["title"], "v3-1");
+        assert_eq!(results.len(), 4);
+    }
+    assert_eq!(rowids(&search_near(&vlite, 201.0))[0], 1);
+}
+
+#[test]
+fn upserted_vectors_survive_file_db_reopen() {
+    let db_path = "/tmp/vxlite_test_upsert_batch.db";
+    let idx_path = "/tmp/vxlite_test_upsert_batch.idx";
+    let cleanup = || {
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = fs::remove_file(format!("{}{}", db_path, suffix));
+        }
+        let _ = fs::remove_file(idx_path);
+    };
+    cleanup();
+
+    {
+        let vlite = create_vlite(SqliteConnectionManager::file(db_path));
+        create_docs_collection(&vlite, Some(idx_path));
+        insert_originals(&vlite);
+        vlite
+            .upsert_batch(vec![doc(3, 103.0, "v2-3"), doc(4, 104.0, "v2-4")])
+            .unwrap();
+    }
+
+    let reopened = create_vlite(SqliteConnectionManager::file(db_path));
+    let results = search_near(&reopened, 0.0);
+    assert_eq!(rowids(&results), vec![1, 2, 3, 4]);
+    assert_eq!(results[2]["title"], "v2-3");
+
+    drop(reopened);
+    cleanup();
+}
+
+#[test]
+fn failing_point_rolls_back_whole_batch() {
+    let vlite = create_vlite(SqliteConnectionManager::memory());
+    create_docs_collection(&vlite, None);
+    insert_originals(&vlite);
+
+    let bad = InsertPoint::builder()
+        .collection_name("docs")
+        .id(2)
+        .vector(vec![102.0, 0.0])
+        .payload_insert_query("insert into docs(rowid, missing_column) values (?1, 1)")
+        .build()
+        .unwrap();
+
+    assert!(vlite
+        .upsert_batch(vec![doc(1, 101.0, "v2-1"), bad])
+        .is_err());
+
+    let results = search_near(&vlite, 0.0);
+    assert_eq!(rowids(&results), vec![1, 2, 3, 4]);
+    assert_eq!(results[0]["title"], "v1-1");
+}
@@ -0,0 +1,185 @@
+//! Tests for vacuum method in VectorXLite
+//!
+//! These tests verify:
+//! - Vacuuming after heavy deletes shrinks the database file
+//! - Remaining points keep their payload rows
+//! - Payload tables without an INTEGER PRIMARY KEY are refused, since their rowids may change
+//! - In-memory databases can be vacuumed
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::fs;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+/// Helper to create unique test file paths
+fn test_paths(name: &str) -> (String, String) {
+    let db_path = format!("/tmp/vxlite_test_vacuum_{}.db", name);
+    let idx_path = format!("/tmp/vxlite_test_vacuum_{}.idx", name);
+    (db_path, idx_path)
+}
+
+fn cleanup(db_path: &str, idx_path: &str) {
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = fs::remove_file(format!("{}{}", db_path, suffix));
+    }
+    let _ = fs::remove_file(idx_path);
+}
+
+fn setup_vlite(
+    manager: SqliteConnectionManager,
+    index_file_path: Option<&str>,
+    payload_table_schema: &str,
+) -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let pool = Pool::builder()
+        .max_size(2)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    let mut builder = CollectionConfigBuilder::default()
+        .collection_name("logs")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema(payload_table_schema);
+    if let Some(path) = index_file_path {
+        builder = builder.index_file_path(path);
+    }
+    vlite
+        .create_collection(builder.build().unwrap())
+        .expect("collection should be created");
+    (vlite, pool)
+}
+
+const LOGS_SCHEMA: &str = "create table logs (rowid integer primary key, body text)";
+
+fn insert_logs(vlite: &VectorXLite, count: u64) {
+    let points = (1..=count)
+        .map(|id| {
+            InsertPoint::builder()
+                .collection_name("logs")
+                .id(id)
+                .vector(vec![id as f32, 0.0])
+                .payload_insert_query(format!(
+                    "insert into logs(body) values ('{}-{}')",
+                    id,
+                    "x".repeat(2000)
+                ))
+                .build()
+                .unwrap()
+        })
+        .collect();
+    vlite
+        .insert_batch(points, BatchOptions::default())
+        .expect("insert should be successful.");
+}
+
+fn delete_logs(vlite: &VectorXLite, ids: impl Iterator<Item = u64>) {
+    let batch_delete = BatchDelete::builder()
+        .collection_name("logs")
+        .ids(ids.collect())
+        .build()
+        .unwrap();
+    vlite.batch_delete(batch_delete).unwrap();
+}
+
+fn checkpointed_file_size(pool: &Pool<SqliteConnectionManager>, db_path: &str) -> u64 {
+    pool.get()
+        .unwrap()
+        .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+        .unwrap();
+    fs::metadata(db_path).unwrap().len()
+}
+
+#[test]
+fn vacuum_shrinks_file_after_deletes() {
+    let (db_path, idx_path) = test_paths("shrink");
+    cleanup(&db_path, &idx_path);
+    let (vlite, pool) = setup_vlite(
+        SqliteConnectionManager::file(&db_path),
+        Some(&idx_path),
+        LOGS_SCHEMA,
+    );
+    insert_logs(&vlite, 500);
+    delete_logs(&vlite, 51..=500);
+
+    let size_before = checkpointed_file_size(&pool, &db_path);
+    vlite.vacuum().expect("vacuum should be successful.");
+    let size_after = fs::metadata(&db_path).unwrap().len();
+
+    assert!(
+        size_after * 4 < size_before,
+        "expected {} to shrink well below {}",
+        size_after,
+        size_before
+    );
+
+    drop(vlite);
+    drop(pool);
+    cleanup(&db_path, &idx_path);
+}
+
+#[test]
+fn vacuum_keeps_payload_rows_of_remaining_points() {
+    let (db_path, idx_path) = test_paths("payload_rows");
+    cleanup(&db_path, &idx_path);
+    let (vlite, pool) = setup_vlite(
+        SqliteConnectionManager::file(&db_path),
+        Some(&idx_path),
+        LOGS_SCHEMA,
+    );
+    insert_logs(&vlite, 200);
+    delete_logs(&vlite, (1..=200).filter(|id| id % 10 != 0));
+
+    vlite.vacuum().expect("vacuum should be successful.");
+
+    let search_point = SearchPoint::builder()
+        .collection_name("logs")
+        .vector(vec![0.0, 0.0])
+        .top_k(50)
+        .payload_search_query("select rowid, body from logs")
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).unwrap();
+
+    assert_eq!(results.len(), 20);
+    for row in &results {
+        assert!(
+            row["body"].starts_with(&format!("{}-", row["rowid"])),
+            "{}",
+            row["rowid"]
+        );
+    }
+
+    drop(vlite);
+    drop(pool);
+    cleanup(&db_path, &idx_path);
+}
+
+#[test]
+fn vacuum_in_memory_database_succeeds() {
+    let (vlite, _pool) = setup_vlite(SqliteConnectionManager::memory(), None, LOGS_SCHEMA);
+    insert_logs(&vlite, 20);
+    delete_logs(&vlite, 1..=10);
+
+    vlite.vacuum().expect("vacuum should be successful.");
+    assert_eq!(vlite.count_where("logs", "1 = 1").unwrap(), 10);
+}
+
+#[test]
+fn payload_table_without_integer_primary_key_is_refused() {
+    let (vlite, _pool) = setup_vlite(
+        SqliteConnectionManager::memory(),
+        None,
+        "create table logs (body text)",
+    );
+    insert_logs(&vlite, 20);
+    delete_logs(&vlite, 1..=10);
+
+    let err = vlite.vacuum().expect_err("vacuum should be refused");
+
+    assert!(matches!(err, VecXError::InvalidQueryError(_)));
+    assert!(err.to_string().contains("'logs'"), "{}", err);
+}
@@ -0,0 +1,123 @@
+//! Tests for validate_collection method in VectorXLite
+//!
+//! These tests verify:
+//! - A valid config passes without creating any table
+//! - SQL errors in the payload schema are reported without side effects
+//! - Invalid configs, existing collections and index files are handled
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::path::Path;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+fn setup_vlite() -> (VectorXLite, Pool<SqliteConnectionManager>) {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    let vlite = VectorXLite::new(pool.clone()).expect("create VectorXLite");
+    (vlite, pool)
+}
+
+fn table_count(pool: &Pool<SqliteConnectionManager>) -> i64 {
+    pool.get()
+        .unwrap()
+        .query_row("select count(*) from sqlite_master", [], |row| row.get(0))
+        .unwrap()
+}
+
+fn books_config(payload_table_schema: &str) -> CollectionConfig {
+    CollectionConfigBuilder::default()
+        .collection_name("books")
+        .distance(DistanceFunction::Cosine)
+        .vector_dimension(4)
+        .payload_table_schema(payload_table_schema)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn valid_config_passes_without_creating_tables() {
+    let (vlite, pool) = setup_vlite();
+    let tables_before = table_count(&pool);
+
+    vlite
+        .validate_collection(&books_config(
+            "create table books (rowid integer primary key, title text not null)",
+        ))
+        .expect("config should be valid");
+
+    assert_eq!(table_count(&pool), tables_before);
+    assert!(!vlite.collection_exists("books").unwrap());
+}
+
+#[test]
+fn validated_config_can_still_be_created() {
+    let (vlite, _pool) = setup_vlite();
+    let config = books_config("create table books (rowid integer primary key, title text)");
+
+    vlite.validate_collection(&config).unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    assert!(vlite.collection_exists("books").unwrap());
+}
+
+#[test]
+fn bad_payload_schema_is_reported_without_side_effects() {
+    let (vlite, pool) = setup_vlite();
+    let tables_before = table_count(&pool);
+
+    let err = vlite
+        .validate_collection(&books_config(
+            "create table books (rowid integer primary key, title text,)",
+        ))
+        .expect_err("schema with a syntax error should be rejected");
+
+    assert!(matches!(err, VecXError::SqlError(_)), "{:?}", err);
+    assert_eq!(table_count(&pool), tables_before);
+}
+
+#[test]
+fn invalid_config_and_existing_collection_are_rejected() {
+    let (vlite, _pool) = setup_vlite();
+
+    let unnamed = CollectionConfig {
+        collection_name: " ".to_string(),
+        ..Default::default()
+    };
+    assert!(matches!(
+        vlite.validate_collection(&unnamed),
+        Err(VecXError::InvalidQueryError(_))
+    ));
+
+    let config = books_config("create table books (rowid integer primary key, title text)");
+    vlite.create_collection(config.clone()).unwrap();
+    assert!(matches!(
+        vlite.validate_collection(&config),
+        Err(VecXError::InvalidQueryError(_))
+    ));
+}
+
+#[test]
+fn index_file_is_not_written() {
+    let (vlite, _pool) = setup_vlite();
+    let idx_path = "/tmp/vxlite_test_validate_collection.idx";
+    let _ = std::fs::remove_file(idx_path);
+
+    let config = CollectionConfigBuilder::default()
+        .collection_name("books")
+        .vector_dimension(4)
+        .index_file_path(idx_path)
+        .build()
+        .unwrap();
+    vlite.validate_collection(&config).unwrap();
+
+    assert!(!Path::new(idx_path).exists());
+}
@@ -0,0 +1,145 @@
+//! Tests for inserting pre-serialized vector bytes
+//!
+//! These tests verify:
+//! - Vectors inserted as little-endian f32 bytes are searchable like `Vec<f32>` ones
+//! - Byte lengths that are not a multiple of 4 are rejected by the builder
+//! - Byte lengths that do not match the collection dimension are rejected on insert
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::collections::HashMap;
+use vector_xlite::{
+    customizer::SqliteConnectionCustomizer, error::VecXError, types::*, VectorXLite,
+};
+
+fn setup_vlite() -> VectorXLite {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    VectorXLite::new(pool).expect("create VectorXLite")
+}
+
+fn create_collection(vlite: &VectorXLite, name: &str) {
+    let config = CollectionConfigBuilder::default()
+        .collection_name(name)
+        .distance(DistanceFunction::L2)
+        .vector_dimension(3)
+        .build()
+        .unwrap();
+
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+}
+
+fn to_bytes(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn sample_vectors() -> Vec<Vec<f32>> {
+    vec![
+        vec![1.0, 0.0, 0.0],
+        vec![0.0, 2.0, 0.0],
+        vec![0.5, 0.5, 3.0],
+        vec![-1.0, 1.5, 0.25],
+    ]
+}
+
+fn search(vlite: &VectorXLite, name: &str) -> Vec<HashMap<String, String>> {
+    let search_point = SearchPoint::builder()
+        .collection_name(name)
+        .vector(vec![0.9, 0.1, 0.0])
+        .top_k(4)
+        .build()
+        .unwrap();
+
+    vlite.search(search_point).expect("search should succeed")
+}
+
+#[test]
+fn bytes_and_vec_inserts_search_identically() {
+    let vlite = setup_vlite();
+    create_collection(&vlite, "from_vec");
+    create_collection(&vlite, "from_bytes");
+
+    for (i, vector) in sample_vectors().into_iter().enumerate() {
+        let id = i as u64 + 1;
+        let bytes = to_bytes(&vector);
+
+        let from_vec = InsertPoint::builder()
+            .collection_name("from_vec")
+            .id(id)
+            .vector(vector)
+            .build()
+            .unwrap();
+        vlite
+            .insert(from_vec)
+            .expect("insert should be successful.");
+
+        let from_bytes = InsertPoint::builder()
+            .collection_name("from_bytes")
+            .id(id)
+            .vector_bytes(&bytes)
+            .build()
+            .unwrap();
+        vlite
+            .insert(from_bytes)
+            .expect("insert should be successful.");
+    }
+
+    let expected = search(&vlite, "from_vec");
+    let actual = search(&vlite, "from_bytes");
+
+    assert_eq!(actual.len(), 4);
+    assert_eq!(actual, expected);
+    assert_eq!(actual[0]["rowid"], "1");
+}
+
+#[test]
+fn vector_bytes_not_multiple_of_four_is_rejected() {
+    let result = InsertPoint::builder()
+        .collection_name("docs")
+        .id(1)
+        .vector_bytes(&[0u8; 7])
+        .build();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn vector_and_vector_bytes_together_are_rejected() {
+    let result = InsertPoint::builder()
+        .collection_name("docs")
+        .id(1)
+        .vector(vec![1.0])
+        .vector_bytes(&to_bytes(&[1.0]))
+        .build();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn vector_bytes_with_wrong_dimension_is_rejected() {
+    let vlite = setup_vlite();
+    create_collection(&vlite, "docs");
+
+    let point = InsertPoint::builder()
+        .collection_name("docs")
+        .id(1)
+        .vector_bytes(&to_bytes(&[1.0, 2.0]))
+        .build()
+        .unwrap();
+    let err = vlite
+        .insert(point)
+        .expect_err("two floats should not fit a 3-dimensional collection");
+
+    match err {
+        VecXError::InvalidQueryError(msg) => assert!(msg.contains("expects 12"), "{}", msg),
+        other => panic!("expected InvalidQueryError, got {:?}", other),
+    }
+    assert!(search(&vlite, "docs").is_empty());
+}
@@ -0,0 +1,105 @@
+//! Tests for the vector dimension guard on insert and search
+//!
+//! These tests verify:
+//! - Inserting a vector longer or shorter than the collection's dimension fails with
+//!   an InvalidQueryError naming both dimensions, and stores nothing
+//! - Searching with a vector longer or shorter than the collection's dimension fails
+//!   the same way
+//! - Vectors of the right dimension still insert and search normally
+
+use vector_xlite::{error::VecXError, types::*, VectorXLite};
+
+fn setup_vlite() -> VectorXLite {
+    let vlite = VectorXLite::builder()
+        .memory()
+        .build()
+        .expect("build in-memory instance");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("points")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(3)
+        .payload_table_schema("create table points (rowid integer primary key)")
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    let point = InsertPoint::builder()
+        .collection_name("points")
+        .id(1)
+        .vector(vec![1.0, 2.0, 3.0])
+        .build()
+        .unwrap();
+    vlite.insert(point).expect("insert should be successful.");
+    vlite
+}
+
+fn insert(vlite: &VectorXLite, vector: Vec<f32>) -> Result<(), VecXError> {
+    let point = InsertPoint::builder()
+        .collection_name("points")
+        .id(2)
+        .vector(vector)
+        .build()
+        .unwrap();
+    vlite.insert(point)
+}
+
+fn search(vlite: &VectorXLite, vector: Vec<f32>) -> Result<usize, VecXError> {
+    let search_point = SearchPoint::builder()
+        .collection_name("points")
+        .vector(vector)
+        .top_k(5)
+        .build()
+        .unwrap();
+    vlite.search(search_point).map(|results| results.len())
+}
+
+fn assert_dimension_error<T: std::fmt::Debug>(result: Result<T, VecXError>, got: usize) {
+    match result {
+        Err(VecXError::InvalidQueryError(message)) => assert!(
+            message.contains(&format!("expected dim 3, got {}", got)),
+            "{}",
+            message
+        ),
+        other => panic!("expected a dimension error, got {:?}", other),
+    }
+}
+
+#[test]
+fn insert_with_too_long_vector_fails() {
+    let vlite = setup_vlite();
+
+    assert_dimension_error(insert(&vlite, vec![1.0, 2.0, 3.0, 4.0, 5.0]), 5);
+    assert_eq!(vlite.count_where("points", "1 = 1").unwrap(), 1);
+}
+
+#[test]
+fn insert_with_too_short_vector_fails() {
+    let vlite = setup_vlite();
+
+    assert_dimension_error(insert(&vlite, vec![1.0, 2.0]), 2);
+    assert_eq!(vlite.count_where("points", "1 = 1").unwrap(), 1);
+}
+
+#[test]
+fn search_with_too_long_vector_fails() {
+    let vlite = setup_vlite();
+
+    assert_dimension_error(search(&vlite, vec![1.0, 2.0, 3.0, 4.0]), 4);
+}
+
+#[test]
+fn search_with_too_short_vector_fails() {
+    let vlite = setup_vlite();
+
+    assert_dimension_error(search(&vlite, vec![1.0]), 1);
+}
+
+#[test]
+fn vectors_of_the_right_dimension_still_work() {
+    let vlite = setup_vlite();
+
+    insert(&vlite, vec![3.0, 2.0, 1.0]).expect("insert should succeed");
+    assert_eq!(search(&vlite, vec![1.0, 2.0, 3.0]).unwrap(), 2);
+}
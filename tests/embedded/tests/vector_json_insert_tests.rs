@@ -0,0 +1,94 @@
+//! Tests for inserting vectors given as JSON array strings
+//!
+//! These tests verify:
+//! - Vectors inserted as JSON are searchable like `Vec<f32>` ones
+//! - JSON that is not an array of finite numbers is rejected by the builder
+//! - Arrays that do not match the collection dimension are rejected on insert
+
+use vector_xlite::{error::VecXError, types::*, VectorXLite};
+
+fn setup_vlite() -> VectorXLite {
+    let vlite = VectorXLite::builder()
+        .memory()
+        .build()
+        .expect("build in-memory instance");
+    let config = CollectionConfigBuilder::default()
+        .collection_name("points")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(3)
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+    vlite
+}
+
+fn json_point(id: u64, json: &str) -> Result<InsertPoint, String> {
+    InsertPoint::builder()
+        .collection_name("points")
+        .id(id)
+        .vector_json(json)
+        .build()
+}
+
+#[test]
+fn json_vectors_are_searchable() {
+    let vlite = setup_vlite();
+    for (id, json) in [
+        (1, "[1, 0, 0]"),
+        (2, " [0.0,2.5,0.0] "),
+        (3, "[-1e0, 1.5, 0.25]"),
+    ] {
+        vlite
+            .insert(json_point(id, json).unwrap())
+            .expect("insert should be successful.");
+    }
+
+    let search_point = SearchPoint::builder()
+        .collection_name("points")
+        .vector(vec![0.0, 2.4, 0.0])
+        .top_k(3)
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).expect("search should succeed");
+
+    let rowids: Vec<&str> = results.iter().map(|row| row["rowid"].as_str()).collect();
+    assert_eq!(rowids, vec!["2", "3", "1"]);
+    let nearest: f32 = results[0]["distance"].parse().unwrap();
+    assert!(nearest < 0.02, "distance {}", nearest);
+}
+
+#[test]
+fn builder_rejects_invalid_json_vectors() {
+    for json in ["[1, \"two\", 3]", "1, 2, 3", "[]", "[1, NaN, 3]", "[1,,3]"] {
+        let err = json_point(1, json).expect_err(json);
+        assert!(err.contains("vector_json"), "{}: {}", json, err);
+    }
+
+    let err = InsertPoint::builder()
+        .collection_name("points")
+        .id(1)
+        .vector(vec![1.0, 0.0, 0.0])
+        .vector_json("[1, 0, 0]")
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        "Only one of vector, vector_bytes, vector_json or sparse_vector may be provided."
+    );
+}
+
+#[test]
+fn insert_rejects_json_vector_of_wrong_length() {
+    let vlite = setup_vlite();
+
+    for json in ["[1, 0]", "[1, 0, 0, 0]"] {
+        let err = vlite
+            .insert(json_point(1, json).unwrap())
+            .expect_err("insert should fail");
+        assert!(matches!(err, VecXError::InvalidQueryError(_)), "{}", err);
+        assert!(err.to_string().contains("expects 3"), "{}", err);
+    }
+    assert_eq!(vlite.count_where("points", "1 = 1").unwrap(), 0);
+}
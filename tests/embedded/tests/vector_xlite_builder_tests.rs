@@ -0,0 +1,92 @@
+//! Tests for VectorXLite::builder
+//!
+//! These tests verify:
+//! - In-memory and file-backed instances built in one step support create/insert/search
+//! - Invalid builder settings are rejected
+
+use std::fs;
+use vector_xlite::{error::VecXError, types::*, VectorXLite};
+
+fn create_insert_and_search(vlite: &VectorXLite) {
+    let config = CollectionConfigBuilder::default()
+        .collection_name("docs")
+        .distance(DistanceFunction::L2)
+        .vector_dimension(2)
+        .payload_table_schema("create table docs (rowid integer primary key, title text)")
+        .build()
+        .unwrap();
+    vlite
+        .create_collection(config)
+        .expect("collection should be created");
+
+    for id in 1..=3u64 {
+        let point = InsertPoint::builder()
+            .collection_name("docs")
+            .id(id)
+            .vector(vec![id as f32, 0.0])
+            .payload_insert_query(format!(
+                "insert into docs(rowid, title) values (?1, 'doc {}')",
+                id
+            ))
+            .build()
+            .unwrap();
+        vlite.insert(point).expect("insert should be successful.");
+    }
+
+    let search_point = SearchPoint::builder()
+        .collection_name("docs")
+        .vector(vec![3.0, 0.0])
+        .top_k(2)
+        .payload_search_query("select rowid, title from docs")
+        .build()
+        .unwrap();
+    let results = vlite.search(search_point).expect("search should succeed");
+
+    let titles: Vec<&str> = results.iter().map(|row| row["title"].as_str()).collect();
+    assert_eq!(titles, vec!["doc 3", "doc 2"]);
+}
+
+#[test]
+fn builder_creates_in_memory_instance() {
+    let vlite = VectorXLite::builder()
+        .memory()
+        .busy_timeout(1_000)
+        .build()
+        .expect("build in-memory instance");
+
+    create_insert_and_search(&vlite);
+}
+
+#[test]
+fn builder_creates_file_backed_instance() {
+    let dir = "/tmp/vxlite_test_builder_file";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let db_path = format!("{}/builder.db", dir);
+
+    let vlite = VectorXLite::builder()
+        .file(&db_path)
+        .max_size(1)
+        .build()
+        .expect("build file-backed instance");
+
+    create_insert_and_search(&vlite);
+    assert!(fs::metadata(&db_path).is_ok(), "database file should exist");
+
+    drop(vlite);
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn builder_without_location_fails() {
+    let result = VectorXLite::builder().max_size(1).build();
+
+    assert!(matches!(result, Err(VecXError::Other(_))));
+}
+
+#[test]
+fn builder_with_zero_max_size_fails() {
+    let result = VectorXLite::builder().memory().max_size(0).build();
+
+    assert!(matches!(result, Err(VecXError::Other(_))));
+}
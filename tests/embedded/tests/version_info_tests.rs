@@ -0,0 +1,44 @@
+//! Tests for version_info method in VectorXLite
+//!
+//! These tests verify:
+//! - The loaded vectorlite extension reports a version
+//! - Build flags are parsed into the structured fields
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use vector_xlite::{customizer::SqliteConnectionCustomizer, VectorXLite};
+
+fn setup_vlite() -> VectorXLite {
+    let manager = SqliteConnectionManager::memory();
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(SqliteConnectionCustomizer::new())
+        .build(manager)
+        .expect("create pool");
+
+    VectorXLite::new(pool).expect("create VectorXLite")
+}
+
+#[test]
+fn version_info_reports_extension_version() {
+    let vlite = setup_vlite();
+
+    let info = vlite.version_info().expect("version info should be available");
+
+    assert!(!info.raw.is_empty());
+    assert!(!info.version.is_empty());
+    assert!(info.raw.contains(&info.version));
+}
+
+#[test]
+fn version_info_populates_simd_flags() {
+    let vlite = setup_vlite();
+
+    let info = vlite.version_info().unwrap();
+
+    assert!(info.version.starts_with(|c: char| c.is_ascii_digit()));
+    assert_eq!(info.simd_supported(), info.simd.is_some());
+    if let Some(simd) = &info.simd {
+        assert!(info.raw.contains(simd.as_str()));
+    }
+}